@@ -0,0 +1,157 @@
+use cappie::{Formatter, JsonFormatter, Key, Level, Logger, Output, PrettyFormatter, Timestamp};
+use criterion::{criterion_group, criterion_main, Criterion};
+use serde_json::{json, Map, Value};
+use smallvec::SmallVec;
+use std::cell::RefCell;
+use std::hint::black_box;
+
+/// Swallows records so the benchmarks measure formatting, not terminal I/O.
+struct NullOutput;
+
+impl Output for NullOutput {
+    fn write(&self, message: &str) {
+        black_box(message);
+    }
+}
+
+fn sample_fields() -> Map<String, Value> {
+    let mut fields = Map::new();
+    fields.insert("user_id".to_string(), json!(42));
+    fields.insert("method".to_string(), json!("GET"));
+    fields.insert("path".to_string(), json!("/api/users"));
+    fields.insert("cached".to_string(), json!(true));
+    fields
+}
+
+fn formatters(c: &mut Criterion) {
+    let fields = sample_fields();
+    let now = Timestamp::now();
+    let json = JsonFormatter;
+    let pretty = PrettyFormatter::new();
+
+    let mut group = c.benchmark_group("format");
+    group.bench_function("json/format", |b| {
+        b.iter(|| black_box(json.format(Level::Info, "request handled", &fields, now, "api")))
+    });
+    group.bench_function("json/format_into", |b| {
+        let mut buf = Vec::with_capacity(512);
+        b.iter(|| {
+            buf.clear();
+            json.format_into(&mut buf, Level::Info, "request handled", &fields, now, "api");
+            black_box(&buf);
+        })
+    });
+    group.bench_function("pretty/format", |b| {
+        b.iter(|| black_box(pretty.format(Level::Info, "request handled", &fields, now, "api")))
+    });
+    group.bench_function("pretty/format_into", |b| {
+        let mut buf = Vec::with_capacity(512);
+        b.iter(|| {
+            buf.clear();
+            pretty.format_into(&mut buf, Level::Info, "request handled", &fields, now, "api");
+            black_box(&buf);
+        })
+    });
+    group.finish();
+}
+
+/// A fresh `Vec` per record against one reused per thread, as the logger does.
+fn buffers(c: &mut Criterion) {
+    thread_local! {
+        static BUFFER: RefCell<Vec<u8>> = RefCell::new(Vec::with_capacity(512));
+    }
+    let fields = sample_fields();
+    let now = Timestamp::now();
+
+    let mut group = c.benchmark_group("buffer");
+    group.bench_function("fresh_vec", |b| {
+        b.iter(|| {
+            let mut buf = Vec::new();
+            JsonFormatter.format_into(&mut buf, Level::Info, "request handled", &fields, now, "api");
+            black_box(buf);
+        })
+    });
+    group.bench_function("thread_local", |b| {
+        b.iter(|| {
+            BUFFER.with(|cell| {
+                let mut buf = cell.borrow_mut();
+                buf.clear();
+                JsonFormatter.format_into(&mut buf, Level::Info, "request handled", &fields, now, "api");
+                black_box(&buf);
+            })
+        })
+    });
+    group.finish();
+}
+
+const KEYS: [&str; 8] = ["user_id", "method", "path", "status", "bytes", "cached", "region", "trace"];
+
+/// Eight fields collected into a `serde_json::Map` against the inline pairs a
+/// `LogBuilder` keeps.
+fn small_fields(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fields");
+    group.bench_function("map/8", |b| {
+        b.iter(|| {
+            let mut fields = Map::new();
+            for (i, key) in KEYS.iter().enumerate() {
+                fields.insert(key.to_string(), Value::from(i));
+            }
+            black_box(fields);
+        })
+    });
+    group.bench_function("small_vec/8", |b| {
+        b.iter(|| {
+            let mut fields: SmallVec<[(Key, Value); 8]> = SmallVec::new();
+            for (i, &key) in KEYS.iter().enumerate() {
+                fields.push((Key::from(key), Value::from(i)));
+            }
+            black_box(fields);
+        })
+    });
+    group.finish();
+}
+
+fn logger(c: &mut Criterion) {
+    let log = Logger::new("api")
+        .with_output(Box::new(NullOutput))
+        .with_field("service", "bench");
+
+    c.bench_function("logger/filtered_out", |b| {
+        b.iter(|| log.debug(black_box("not emitted at the default Info level")))
+    });
+    c.bench_function("logger/filtered_out_with", |b| {
+        b.iter(|| {
+            log.debug_with("not emitted", |l| {
+                l.number("user_id", 42);
+            })
+        })
+    });
+    c.bench_function("logger/info_with", |b| {
+        b.iter(|| {
+            log.info_with("request handled", |l| {
+                l.number("user_id", 42).string("method", "GET").bool("cached", true);
+            })
+        })
+    });
+    c.bench_function("logger/info_with_8", |b| {
+        b.iter(|| {
+            log.info_with("request handled", |l| {
+                for (i, key) in KEYS.iter().enumerate() {
+                    l.number(key, i as u64);
+                }
+            })
+        })
+    });
+    c.bench_function("logger/info_with_8_static", |b| {
+        b.iter(|| {
+            log.info_with("request handled", |l| {
+                for (i, &key) in KEYS.iter().enumerate() {
+                    l.number_static(key, i as u64);
+                }
+            })
+        })
+    });
+}
+
+criterion_group!(benches, formatters, buffers, small_fields, logger);
+criterion_main!(benches);
@@ -0,0 +1,50 @@
+use cappie::output::AsyncOutput;
+use cappie::{FileOutput, Logger, Output};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::hint::black_box;
+
+/// Records written per iteration of the async benchmark, well below the queue capacity.
+const BATCH: u64 = 1_000;
+
+const LINE: &str = r#"{"level":30,"msg":"request handled","name":"api","time":"2025-06-21T12:34:56Z","user_id":42}"#;
+
+fn file_output(c: &mut Criterion) {
+    let path = std::env::temp_dir().join(format!("cappie-bench-{}.log", std::process::id()));
+    let output = FileOutput::new(&path);
+
+    let mut group = c.benchmark_group("output");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("file/write", |b| b.iter(|| output.write(black_box(LINE))));
+
+    let log = Logger::new("api").with_output(Box::new(FileOutput::new(&path)));
+    group.bench_function("file/logger_info", |b| b.iter(|| log.info(black_box("request handled"))));
+    group.finish();
+
+    let _ = std::fs::remove_file(&path);
+}
+
+/// Records queued and drained by the worker: each iteration writes a batch and waits for
+/// it to reach the file, so the figure is end‑to‑end throughput, not just enqueueing.
+fn async_output(c: &mut Criterion) {
+    let path = std::env::temp_dir().join(format!("cappie-bench-async-{}.log", std::process::id()));
+    let output = AsyncOutput::new(Box::new(FileOutput::new(&path)));
+
+    let mut group = c.benchmark_group("output");
+    group.throughput(Throughput::Elements(BATCH));
+    group.bench_function("async_file/write", |b| {
+        b.iter(|| {
+            for _ in 0..BATCH {
+                output.write(black_box(LINE));
+            }
+            output.flush();
+        })
+    });
+    group.finish();
+    assert_eq!(output.stats().dropped, 0);
+
+    drop(output);
+    let _ = std::fs::remove_file(&path);
+}
+
+criterion_group!(benches, file_output, async_output);
+criterion_main!(benches);
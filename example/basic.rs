@@ -1,4 +1,4 @@
-use cappie::{Logger, Level, PrettyFormatter, JsonFormatter};
+use cappie::{Logger, Level, PrettyFormatter};
 
 fn main() {
     // Basic JSON logging
@@ -1,5 +1,5 @@
-use cappie::{Logger, Level};
-use cappie::formatter::{FlexibleFormatter, ComponentType, ComponentPosition};
+use cappie::Logger;
+use cappie::formatter::{FlexibleFormatter, ComponentPosition};
 
 fn main() {
     println!("=== FLEXIBLE FORMATTER EXAMPLES ===\n");
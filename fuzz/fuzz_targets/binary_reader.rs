@@ -0,0 +1,15 @@
+//! Arbitrary bytes fed to the binary log reader must produce records or errors, never a
+//! panic or an unbounded allocation.
+
+#![no_main]
+
+use cappie::binary::RecordReader;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    for record in RecordReader::new(data) {
+        if record.is_err() {
+            break;
+        }
+    }
+});
@@ -0,0 +1,25 @@
+//! Any message, logger name and string fields must format to a single line of valid JSON
+//! that parses back to the same values.
+
+#![no_main]
+
+use cappie::{Formatter, JsonFormatter, Level, Timestamp};
+use libfuzzer_sys::fuzz_target;
+use serde_json::{Map, Value};
+
+fuzz_target!(|input: (String, String, Vec<(String, String)>)| {
+    let (msg, name, pairs) = input;
+    let fields: Map<String, Value> = pairs.into_iter().map(|(k, v)| (k, Value::String(v))).collect();
+    let timestamp = Timestamp::from_unix_nanos(1_704_067_200_000_000_000).unwrap();
+
+    let line = JsonFormatter.format(Level::Info, &msg, &fields, timestamp, &name);
+    assert!(!line.contains('\n') && !line.contains('\r'), "record spans lines: {:?}", line);
+
+    let parsed: Value = serde_json::from_str(&line).expect("record is not valid JSON");
+    // Fields named like an envelope key replace it.
+    assert_eq!(&parsed["msg"], fields.get("msg").unwrap_or(&Value::from(msg)));
+    assert_eq!(&parsed["name"], fields.get("name").unwrap_or(&Value::from(name)));
+    for (key, value) in &fields {
+        assert_eq!(&parsed[key], value);
+    }
+});
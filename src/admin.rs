@@ -0,0 +1,318 @@
+use crate::diagnostics::{self, OutputHealth};
+use crate::http_head;
+use crate::level::{global_level, set_global_level, Level, LevelHandle};
+use crate::logger::Logger;
+use crate::sampling::SamplingHandle;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::io::{self, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Set of loggers exposed through the admin API, keyed by logger name.  Cheap to clone;
+/// all clones share the same entries.
+///
+/// ```
+/// use cappie::Logger;
+/// use cappie::admin::AdminRegistry;
+///
+/// let api = Logger::new("api");
+/// let registry = AdminRegistry::new();
+/// registry.register(&api);
+///
+/// let res = registry.handle("PUT", "/loggers/api/level", "debug");
+/// assert_eq!(res.status, 200);
+/// ```
+#[derive(Clone, Default)]
+pub struct AdminRegistry {
+    loggers: Arc<Mutex<BTreeMap<String, Entry>>>,
+}
+
+/// What the admin API can change of one logger.
+#[derive(Clone)]
+struct Entry {
+    level: LevelHandle,
+    sampling: Option<SamplingHandle>,
+}
+
+/// Response produced by [`AdminRegistry::handle`], ready to be mapped onto whatever HTTP
+/// framework hosts the endpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdminResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl AdminResponse {
+    fn json(status: u16, body: Value) -> Self {
+        Self { status, body: body.to_string() }
+    }
+
+    fn error(status: u16, msg: &str) -> Self {
+        Self::json(status, json!({ "error": msg }))
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        "application/json"
+    }
+}
+
+impl AdminRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expose `logger` under its name.  Registering another logger with the same name
+    /// replaces the previous entry.
+    pub fn register(&self, logger: &Logger) {
+        let entry = Entry { level: logger.level_handle(), sampling: Some(logger.sampling_handle()) };
+        self.lock().insert(logger.name().to_string(), entry);
+    }
+
+    /// Expose a level alone under `name`; its sampling rate cannot be changed.
+    pub fn register_handle(&self, name: &str, handle: LevelHandle) {
+        self.lock().insert(name.to_string(), Entry { level: handle, sampling: None });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, Entry>> {
+        self.loggers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Framework‑agnostic request handler.  Supported routes:
+    ///
+    /// | Method | Path                       | Body                         |
+    /// |--------|----------------------------|------------------------------|
+    /// | `GET`  | `/loggers`                 |                              |
+    /// | `GET`  | `/loggers/{name}`          |                              |
+    /// | `PUT`  | `/loggers/{name}/level`    | `debug` or `{"level":"debug"}` |
+    /// | `PUT`  | `/loggers/{name}/sampling` | `0.1` or `{"rate":0.1}`      |
+    /// | `GET`  | `/level`                   |                              |
+    /// | `PUT`  | `/level`                   | a level, or empty / `null` to clear the override |
+    /// | `GET`  | `/outputs`                 |                              |
+    ///
+    /// `/level` is the process‑wide override from [`set_global_level`]; `/outputs` lists
+    /// the [health](diagnostics::output_health) of outputs that have had problems.
+    pub fn handle(&self, method: &str, path: &str, body: &str) -> AdminResponse {
+        let path = path.split('?').next().unwrap_or_default().trim_end_matches('/');
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        match (method, segments.as_slice()) {
+            ("GET", ["loggers"]) => {
+                let loggers: Vec<Value> = self.lock()
+                    .iter()
+                    .map(|(name, entry)| logger_json(name, entry))
+                    .collect();
+                AdminResponse::json(200, json!({
+                    "global_level": global_level().map(|l| l.as_str()),
+                    "loggers": loggers,
+                }))
+            }
+            ("GET", ["loggers", name]) => match self.lock().get(*name) {
+                Some(entry) => AdminResponse::json(200, logger_json(name, entry)),
+                None => AdminResponse::error(404, "unknown logger"),
+            },
+            ("PUT", ["loggers", name, "level"]) => {
+                let level = match parse_level(body) {
+                    Some(Some(level)) => level,
+                    _ => return AdminResponse::error(400, "invalid level"),
+                };
+                match self.lock().get(*name) {
+                    Some(entry) => {
+                        entry.level.set(level);
+                        AdminResponse::json(200, logger_json(name, entry))
+                    }
+                    None => AdminResponse::error(404, "unknown logger"),
+                }
+            }
+            ("PUT", ["loggers", name, "sampling"]) => {
+                let rate = match parse_rate(body) {
+                    Some(rate) => rate,
+                    None => return AdminResponse::error(400, "invalid sampling rate"),
+                };
+                match self.lock().get(*name) {
+                    Some(entry @ Entry { sampling: Some(sampling), .. }) => {
+                        sampling.set(rate);
+                        AdminResponse::json(200, logger_json(name, entry))
+                    }
+                    Some(_) => AdminResponse::error(404, "logger has no sampling rate"),
+                    None => AdminResponse::error(404, "unknown logger"),
+                }
+            }
+            ("GET", ["level"]) => AdminResponse::json(200, json!({
+                "global_level": global_level().map(|l| l.as_str()),
+            })),
+            ("PUT", ["level"]) => match parse_level(body) {
+                Some(level) => {
+                    set_global_level(level);
+                    AdminResponse::json(200, json!({ "global_level": level.map(|l| l.as_str()) }))
+                }
+                None => AdminResponse::error(400, "invalid level"),
+            },
+            ("GET", ["outputs"]) => {
+                let outputs: Vec<Value> = diagnostics::output_health().iter().map(health_json).collect();
+                AdminResponse::json(200, json!({ "outputs": outputs }))
+            }
+            (_, ["loggers", ..]) | (_, ["level"]) | (_, ["outputs"]) => AdminResponse::error(405, "method not allowed"),
+            _ => AdminResponse::error(404, "not found"),
+        }
+    }
+}
+
+fn logger_json(name: &str, entry: &Entry) -> Value {
+    json!({
+        "name": name,
+        "level": entry.level.get().as_str(),
+        "sampling": entry.sampling.as_ref().map(|s| s.get()),
+    })
+}
+
+fn health_json(health: &OutputHealth) -> Value {
+    json!({
+        "name": health.name(),
+        "write_failures": health.write_failures(),
+        "dropped": health.dropped(),
+        "panics": health.panics(),
+        "last_error": health.last_error(),
+        "last_failure": health.last_failure().map(|t| t.to_string()),
+    })
+}
+
+/// A rate in `0.0..=1.0`, bare or as `{"rate": …}`.
+fn parse_rate(body: &str) -> Option<f64> {
+    let rate = match serde_json::from_str::<Value>(body.trim()).ok()? {
+        Value::Object(obj) => obj.get("rate")?.as_f64()?,
+        value => value.as_f64()?,
+    };
+    (0.0..=1.0).contains(&rate).then_some(rate)
+}
+
+/// `Some(None)` means "clear", `None` means the body could not be understood.
+fn parse_level(body: &str) -> Option<Option<Level>> {
+    let body = body.trim();
+    if body.is_empty() || body == "null" {
+        return Some(None);
+    }
+    let raw = match serde_json::from_str::<Value>(body) {
+        Ok(Value::Object(obj)) => match obj.get("level") {
+            Some(Value::Null) => return Some(None),
+            Some(Value::String(s)) => s.clone(),
+            _ => return None,
+        },
+        Ok(Value::String(s)) => s,
+        _ => body.to_string(),
+    };
+    Level::from_str(&raw).map(Some)
+}
+
+/// Minimal blocking HTTP/1.1 server around an [`AdminRegistry`], for processes that don't
+/// already run a web framework.  One connection is served at a time and closed after the
+/// response, which is all an admin endpoint needs.
+pub struct AdminServer {
+    addr: std::net::SocketAddr,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl AdminServer {
+    pub fn local_addr(&self) -> std::net::SocketAddr {
+        self.addr
+    }
+
+    /// Stop accepting connections and join the server thread.
+    pub fn shutdown(mut self) {
+        self.stop_thread();
+    }
+
+    fn stop_thread(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // Unblock `accept` with a throwaway connection.
+        let _ = TcpStream::connect(self.addr);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for AdminServer {
+    fn drop(&mut self) {
+        self.stop_thread();
+    }
+}
+
+/// Serve `registry` on `addr` from a background thread.  Requests with more than 8 KiB of
+/// request line and headers, or more than 64 headers, are answered with 431.
+///
+/// ```no_run
+/// use cappie::admin::{serve, AdminRegistry};
+///
+/// let registry = AdminRegistry::new();
+/// let server = serve("127.0.0.1:9091", registry.clone()).unwrap();
+/// // curl -X PUT -d debug http://127.0.0.1:9091/loggers/api/level
+/// # server.shutdown();
+/// ```
+pub fn serve<A: ToSocketAddrs>(addr: A, registry: AdminRegistry) -> io::Result<AdminServer> {
+    let listener = TcpListener::bind(addr)?;
+    let addr = listener.local_addr()?;
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_flag = stop.clone();
+
+    let thread = thread::Builder::new()
+        .name("cappie-admin".to_string())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                if stop_flag.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    let _ = serve_connection(stream, &registry);
+                }
+            }
+        })?;
+
+    Ok(AdminServer { addr, stop, thread: Some(thread) })
+}
+
+fn serve_connection(stream: TcpStream, registry: &AdminRegistry) -> io::Result<()> {
+    // A client that connects and then goes quiet must not hold up the only server thread.
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let Some(head) = http_head::read_head(&mut reader)? else {
+        respond(&stream, &AdminResponse::error(431, "request headers too large"))?;
+        return http_head::close_unread(&stream, reader);
+    };
+
+    let mut body = vec![0u8; head.content_length.min(64 * 1024)];
+    reader.read_exact(&mut body)?;
+    respond(&stream, &registry.handle(&head.method, &head.target, &String::from_utf8_lossy(&body)))
+}
+
+fn respond(mut stream: &TcpStream, response: &AdminResponse) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason(response.status),
+        response.content_type(),
+        response.body.len(),
+        response.body,
+    )?;
+    stream.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        431 => "Request Header Fields Too Large",
+        _ => "",
+    }
+}
@@ -0,0 +1,156 @@
+use crate::diagnostics;
+use crate::output::{line, Output, Record};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// `chattr +a` / `chattr +i` bits returned by `FS_IOC_GETFLAGS`.
+#[cfg(target_os = "linux")]
+const FS_APPEND_FL: libc::c_long = 0x20;
+#[cfg(target_os = "linux")]
+const FS_IMMUTABLE_FL: libc::c_long = 0x10;
+
+/// Write‑once file output for audit and compliance logs.
+///
+/// * the file is opened once with `O_APPEND` and never truncated – existing contents are
+///   kept and every record lands at the current end of file;
+/// * before each record the path is checked against the opened file.  If the file was
+///   replaced (rotated away, deleted, swapped for another inode) or shrank below what this
+///   output has written, the record is refused rather than silently written into a file
+///   nobody is watching: [`try_write_record`](Output::try_write_record) returns the error,
+///   other writes report it as a [diagnostic](crate::diagnostics), and
+///   [`panic_on_tamper`](Self::panic_on_tamper) turns it into a panic;
+/// * on Linux, [`require_append_attr`](Self::require_append_attr) additionally insists on
+///   the file system's append‑only attribute (`chattr +a`).
+///
+/// ```no_run
+/// use cappie::{FileOutput, Logger};
+///
+/// let audit = FileOutput::append_only("/var/log/app/audit.log").unwrap();
+/// let log = Logger::new("audit").with_output(Box::new(audit));
+/// log.info("user role changed");
+/// ```
+pub struct AppendOnlyFileOutput {
+    path: PathBuf,
+    state: Mutex<State>,
+    panic_on_tamper: bool,
+}
+
+struct State {
+    file: File,
+    /// Lowest length the file may have: everything written so far must still be there.
+    min_len: u64,
+}
+
+impl AppendOnlyFileOutput {
+    /// Open (or create) `path` for appending.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let min_len = file.metadata()?.len();
+        Ok(Self {
+            path,
+            state: Mutex::new(State { file, min_len }),
+            panic_on_tamper: false,
+        })
+    }
+
+    /// Panic instead of returning an error when the file was replaced or truncated, for
+    /// deployments that would rather stop than log into the void.  The panic unwinds into
+    /// the logging call, or stops the worker of an
+    /// [`AsyncOutput`](crate::output::AsyncOutput), which then drops and counts every later
+    /// record.
+    pub fn panic_on_tamper(mut self) -> Self {
+        self.panic_on_tamper = true;
+        self
+    }
+
+    /// Fail unless the file carries the append‑only (`chattr +a`) or immutable attribute, so
+    /// that not even the process owner can rewrite what has been logged.  Setting the
+    /// attribute requires `CAP_LINUX_IMMUTABLE`, typically done once by the deployment.
+    #[cfg(target_os = "linux")]
+    pub fn require_append_attr(self) -> io::Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let flags = {
+            let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let mut flags: libc::c_long = 0;
+            // SAFETY: FS_IOC_GETFLAGS writes at most a `c_long` through the pointer, which
+            // points at a live local; the descriptor is owned by `state.file`.
+            let rc = unsafe { libc::ioctl(state.file.as_raw_fd(), libc::FS_IOC_GETFLAGS, &mut flags) };
+            if rc != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            flags
+        };
+        if flags & (FS_APPEND_FL | FS_IMMUTABLE_FL) == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("{} is not append-only (chattr +a)", self.path.display()),
+            ));
+        }
+        Ok(self)
+    }
+
+    fn append(&self, bytes: &[u8]) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(reason) = self.verify(&state) {
+            let message = format!("append-only log {}: {}", self.path.display(), reason);
+            if self.panic_on_tamper {
+                panic!("{message}");
+            }
+            return Err(io::Error::other(message));
+        }
+        state.file.write_all(bytes)?;
+        state.min_len += bytes.len() as u64;
+        Ok(())
+    }
+
+    /// Compare the file at `path` with the one we hold open.
+    fn verify(&self, state: &State) -> Result<(), &'static str> {
+        let on_disk = std::fs::metadata(&self.path).map_err(|_| "file was removed")?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let opened = state.file.metadata().map_err(|_| "file handle is unusable")?;
+            if (on_disk.dev(), on_disk.ino()) != (opened.dev(), opened.ino()) {
+                return Err("file was replaced");
+            }
+        }
+
+        if on_disk.len() < state.min_len {
+            return Err("file was truncated");
+        }
+        Ok(())
+    }
+}
+
+impl Output for AppendOnlyFileOutput {
+    fn write(&self, message: &str) {
+        diagnostics::check_write("AppendOnlyFileOutput", self.append(&line(message)));
+    }
+
+    fn write_bytes(&self, bytes: &[u8]) {
+        diagnostics::check_write("AppendOnlyFileOutput", self.append(bytes));
+    }
+
+    fn try_write_record(&self, record: &Record<'_>) -> io::Result<()> {
+        self.append(&record.bytes())
+    }
+
+    /// Sync the written records to disk (`fdatasync`).
+    fn flush(&self) {
+        diagnostics::check_write("AppendOnlyFileOutput", self.try_flush());
+    }
+
+    fn try_flush(&self) -> io::Result<()> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.file.sync_data()
+    }
+
+    fn needs_fields(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,405 @@
+use crate::diagnostics::{self, Diagnostic};
+use crate::error::BuildError;
+use crate::flush::{self, Flush};
+use crate::level::Level;
+use crate::logger::panic_message;
+use crate::output::{Output, Record};
+use crate::timestamp::Timestamp;
+use serde_json::{Map, Value};
+use std::collections::VecDeque;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+const DEFAULT_CAPACITY: usize = 10_000;
+const WORKER_NAME: &str = "cappie-async";
+
+/// How long crash‑time flushing waits for the worker to finish writing the batch it has
+/// already taken off the queue.
+const BATCH_PATIENCE: Duration = Duration::from_secs(1);
+
+/// Hands records to a background thread that writes them to the wrapped output, so slow
+/// sinks (network collectors, remote file systems) do not hold up the logging thread.
+///
+/// * the queue holds at most [`with_capacity`](Self::with_capacity) records (default
+///   10 000); when it is full new records are dropped and counted;
+/// * with [`with_max_age`](Self::with_max_age) records that waited longer than that are
+///   dropped and counted instead of being delivered late;
+/// * [`Output::flush`] waits until the queue is empty; dropping the output does the same
+///   and stops the thread; [`flush_all`](crate::flush_all) drains it on crashes;
+/// * if the wrapped output panics, the panic is reported as a
+///   [diagnostic](crate::diagnostics) and the worker stops: what was still queued and
+///   every later record is dropped and counted, and flushing no longer waits.
+///
+/// Delivery happens later, on the worker, so [`Output::try_write_record`] can only report
+/// whether the record was queued.  Records the wrapped output then fails to write are
+/// reported as [diagnostics](crate::diagnostics) and counted in [`AsyncStats::failed`].
+///
+/// ```
+/// use cappie::{Logger, StdoutOutput};
+/// use cappie::output::AsyncOutput;
+/// use std::time::Duration;
+///
+/// let output = AsyncOutput::new(Box::new(StdoutOutput)).with_max_age(Duration::from_secs(300));
+/// let log = Logger::new("metrics").with_output(Box::new(output));
+/// log.debug("queue depth sampled");
+/// ```
+pub struct AsyncOutput {
+    shared: Arc<Shared>,
+    worker: Option<JoinHandle<()>>,
+}
+
+/// Counters of an [`AsyncOutput`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AsyncStats {
+    /// Records waiting to be written.
+    pub queued: usize,
+    /// Records dropped because the queue was full or the wrapped output had panicked.
+    pub dropped: u64,
+    /// Records dropped because they were older than the maximum age.
+    pub expired: u64,
+    /// Records the wrapped output reported it could not write.
+    pub failed: u64,
+}
+
+/// Live view of an [`AsyncOutput`]'s [counters](AsyncStats) that stays valid after the
+/// output has been moved into a logger, e.g. for a [`Governor`](crate::Governor).
+#[derive(Clone)]
+pub struct AsyncStatsHandle(Arc<Counters>);
+
+impl AsyncStatsHandle {
+    pub fn get(&self) -> AsyncStats {
+        self.0.get()
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    queued: AtomicUsize,
+    dropped: AtomicU64,
+    expired: AtomicU64,
+    failed: AtomicU64,
+}
+
+impl Counters {
+    fn get(&self) -> AsyncStats {
+        AsyncStats {
+            queued: self.queued.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            expired: self.expired.load(Ordering::Relaxed),
+            failed: self.failed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+struct Shared {
+    inner: Box<dyn Output>,
+    state: Mutex<State>,
+    /// Signalled when records arrive or the output shuts down.
+    available: Condvar,
+    /// Signalled when the worker has written a batch.
+    idle: Condvar,
+    counters: Arc<Counters>,
+}
+
+struct State {
+    queue: VecDeque<Queued>,
+    capacity: usize,
+    max_age: Option<Duration>,
+    busy: bool,
+    shutdown: bool,
+    /// The worker stopped because the wrapped output panicked.
+    dead: bool,
+}
+
+struct Queued {
+    at: Instant,
+    payload: Payload,
+}
+
+enum Payload {
+    Text(String),
+    Bytes(Vec<u8>),
+    Record(OwnedRecord),
+}
+
+struct OwnedRecord {
+    level: Level,
+    timestamp: Timestamp,
+    name: String,
+    msg: String,
+    tags: Vec<String>,
+    fields: Map<String, Value>,
+    formatted: Vec<u8>,
+    binary: bool,
+}
+
+impl AsyncOutput {
+    pub fn new(inner: Box<dyn Output>) -> Self {
+        let shared = Arc::new(Shared {
+            inner,
+            state: Mutex::new(State {
+                queue: VecDeque::new(),
+                capacity: DEFAULT_CAPACITY,
+                max_age: None,
+                busy: false,
+                shutdown: false,
+                dead: false,
+            }),
+            available: Condvar::new(),
+            idle: Condvar::new(),
+            counters: Arc::default(),
+        });
+        let worker = {
+            let shared = shared.clone();
+            thread::Builder::new()
+                .name(WORKER_NAME.to_string())
+                .spawn(move || shared.run())
+                .ok()
+        };
+        let registered: Arc<dyn Flush> = shared.clone();
+        flush::register(&registered);
+        Self { shared, worker }
+    }
+
+    /// Most records waiting at once (at least 1).
+    pub fn with_capacity(self, capacity: usize) -> Self {
+        self.shared.lock().capacity = capacity.max(1);
+        self
+    }
+
+    /// Drop records that waited in the queue for longer than `max_age`.
+    pub fn with_max_age(self, max_age: Duration) -> Self {
+        self.shared.lock().max_age = Some(max_age);
+        self
+    }
+
+    pub fn stats(&self) -> AsyncStats {
+        self.shared.counters.get()
+    }
+
+    /// Handle to the counters that can be kept after the output is handed to a logger.
+    pub fn stats_handle(&self) -> AsyncStatsHandle {
+        AsyncStatsHandle(self.shared.counters.clone())
+    }
+
+    /// Queue `payload`; the error says why it was dropped instead.
+    fn push(&self, payload: Payload) -> io::Result<()> {
+        // Without a worker (thread creation failed) write on the caller's thread.
+        if self.worker.is_none() {
+            return self.shared.deliver(&payload);
+        }
+        let mut state = self.shared.lock();
+        let refused = if state.dead {
+            "worker panicked"
+        } else if state.queue.len() >= state.capacity {
+            "queue full"
+        } else {
+            state.queue.push_back(Queued { at: Instant::now(), payload });
+            self.shared.counters.queued.store(state.queue.len(), Ordering::Relaxed);
+            drop(state);
+            self.shared.available.notify_one();
+            return Ok(());
+        };
+        drop(state);
+        self.shared.drop_records(1, refused);
+        Err(io::Error::other(format!("AsyncOutput dropped the record: {}", refused)))
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn run(&self) {
+        loop {
+            let (mut batch, max_age) = {
+                let mut state = self.lock();
+                while state.queue.is_empty() && !state.shutdown {
+                    state = self.available.wait(state).unwrap_or_else(|e| e.into_inner());
+                }
+                if state.queue.is_empty() {
+                    return;
+                }
+                state.busy = true;
+                self.counters.queued.store(0, Ordering::Relaxed);
+                (std::mem::take(&mut state.queue), state.max_age)
+            };
+            let written = panic::catch_unwind(AssertUnwindSafe(|| self.write_batch(&mut batch, max_age)));
+
+            let mut state = self.lock();
+            state.busy = false;
+            if let Err(payload) = written {
+                // The output may panic again on every record; stop handing it any.
+                state.dead = true;
+                let lost = batch.len() + std::mem::take(&mut state.queue).len();
+                self.counters.queued.store(0, Ordering::Relaxed);
+                drop(state);
+                self.idle.notify_all();
+                diagnostics::report(Diagnostic::Panicked { component: "AsyncOutput", message: panic_message(payload.as_ref()) });
+                self.drop_records(lost, "worker panicked");
+                return;
+            }
+            drop(state);
+            self.idle.notify_all();
+        }
+    }
+
+    /// Count `n` records as dropped for `reason`.
+    fn drop_records(&self, n: usize, reason: &'static str) {
+        for _ in 0..n {
+            let total = self.counters.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            diagnostics::dropped("AsyncOutput", reason, total);
+        }
+    }
+
+    /// Write `batch` front to back; after a panic it holds the records not yet written.
+    fn write_batch(&self, batch: &mut VecDeque<Queued>, max_age: Option<Duration>) {
+        while let Some(queued) = batch.pop_front() {
+            if max_age.is_some_and(|age| queued.at.elapsed() > age) {
+                let total = self.counters.expired.fetch_add(1, Ordering::Relaxed) + 1;
+                diagnostics::dropped("AsyncOutput", "older than the maximum age", total);
+                continue;
+            }
+            if let Err(error) = self.deliver(&queued.payload) {
+                self.counters.failed.fetch_add(1, Ordering::Relaxed);
+                diagnostics::check_write("AsyncOutput", Err(error));
+            }
+        }
+    }
+
+    /// Only records report failures; text and bytes are written blindly.
+    fn deliver(&self, payload: &Payload) -> io::Result<()> {
+        match payload {
+            Payload::Text(text) => {
+                self.inner.write(text);
+                Ok(())
+            }
+            Payload::Bytes(bytes) => {
+                self.inner.write_bytes(bytes);
+                Ok(())
+            }
+            Payload::Record(record) => {
+                let tags: Vec<&str> = record.tags.iter().map(String::as_str).collect();
+                self.inner.try_write_record(&Record {
+                    level: record.level,
+                    timestamp: record.timestamp,
+                    name: &record.name,
+                    msg: &record.msg,
+                    tags: &tags,
+                    fields: &record.fields,
+                    formatted: &record.formatted,
+                    binary: record.binary,
+                })
+            }
+        }
+    }
+
+    /// Wait until the worker has written everything queued so far, or has died.
+    fn wait_idle(&self) {
+        let mut state = self.lock();
+        while (!state.queue.is_empty() || state.busy) && !state.dead {
+            state = self.idle.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+}
+
+impl Flush for Shared {
+    /// Write what is still queued on the calling thread; the worker may not get to run
+    /// again while the process is going down.  A batch the worker is writing is given a
+    /// moment to finish first, unless the worker itself is the thread going down.
+    fn flush_now(&self) {
+        let Some(mut state) = flush::lock_briefly(&self.state) else {
+            return;
+        };
+        if thread::current().name() != Some(WORKER_NAME) {
+            let deadline = Instant::now() + BATCH_PATIENCE;
+            while state.busy {
+                let left = deadline.saturating_duration_since(Instant::now());
+                if left.is_zero() {
+                    break;
+                }
+                state = match self.idle.wait_timeout(state, left) {
+                    Ok((state, _)) => state,
+                    Err(e) => e.into_inner().0,
+                };
+            }
+        }
+        self.counters.queued.store(0, Ordering::Relaxed);
+        let (mut batch, max_age) = (std::mem::take(&mut state.queue), state.max_age);
+        drop(state);
+        self.write_batch(&mut batch, max_age);
+        self.inner.flush();
+    }
+}
+
+impl Output for AsyncOutput {
+    fn write(&self, message: &str) {
+        let _ = self.push(Payload::Text(message.to_string()));
+    }
+
+    fn write_bytes(&self, bytes: &[u8]) {
+        let _ = self.push(Payload::Bytes(bytes.to_vec()));
+    }
+
+    fn write_record(&self, record: &Record<'_>) {
+        let _ = self.try_write_record(record);
+    }
+
+    /// Reports whether the record was queued, not whether it was written.
+    fn try_write_record(&self, record: &Record<'_>) -> io::Result<()> {
+        self.push(Payload::Record(OwnedRecord {
+            level: record.level,
+            timestamp: record.timestamp,
+            name: record.name.to_string(),
+            msg: record.msg.to_string(),
+            tags: record.tags.iter().map(|t| t.to_string()).collect(),
+            fields: record.fields.clone(),
+            formatted: record.formatted.to_vec(),
+            binary: record.binary,
+        }))
+    }
+
+    fn flush(&self) {
+        if self.worker.is_some() {
+            self.shared.wait_idle();
+        }
+        self.shared.inner.flush();
+    }
+
+    /// Waits for the queue like [`flush`](Output::flush) and reports the wrapped output's
+    /// flush error; write failures on the way are only counted.
+    fn try_flush(&self) -> io::Result<()> {
+        if self.worker.is_some() {
+            self.shared.wait_idle();
+        }
+        self.shared.inner.try_flush()
+    }
+
+    fn validate(&self) -> Result<(), BuildError> {
+        self.shared.inner.validate()
+    }
+
+    fn needs_fields(&self) -> bool {
+        self.shared.inner.needs_fields()
+    }
+
+    fn describe(&self) -> String {
+        format!("AsyncOutput({})", self.shared.inner.describe())
+    }
+}
+
+impl Drop for AsyncOutput {
+    fn drop(&mut self) {
+        self.shared.lock().shutdown = true;
+        self.shared.available.notify_all();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+        self.shared.inner.flush();
+    }
+}
@@ -0,0 +1,172 @@
+use crate::checksum::crc32;
+use crate::formatter::{Formatter, JsonFormatter};
+use crate::id::next_ulid;
+use crate::level::Level;
+use crate::logger::LogBuilder;
+use crate::output::{AppendOnlyFileOutput, Output, Record};
+use crate::timestamp::Timestamp;
+use serde_json::{Map, Value};
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Dedicated channel for audit events, kept apart from application logs.
+///
+/// * every event is written – there is no level and no filtering;
+/// * each event carries the required `actor` and `action` fields and a unique `id`;
+/// * every output in the chain is [flushed](Output::try_flush) after each event, which for
+///   [`AppendOnlyFileOutput`] means the record is synced to disk before the call returns;
+/// * [`event`](Self::event) returns the first write or sync error of any output, so a lost
+///   record never goes unnoticed.
+///
+/// Records are written at level `Info` with `audit: true`, using [`JsonFormatter`] unless
+/// [told otherwise](Self::with_formatter).
+///
+/// ```no_run
+/// use cappie::AuditLogger;
+///
+/// let audit = AuditLogger::open("billing", "/var/log/app/audit.log").unwrap();
+/// audit.event("alice", "invoice.refund", |b| {
+///     b.string("invoice", "INV-1042").number("amount_cents", 1999);
+/// }).expect("audit record not stored");
+/// ```
+#[derive(Clone)]
+pub struct AuditLogger {
+    name: Arc<str>,
+    formatter: Arc<dyn Formatter>,
+    outputs: Vec<Arc<dyn Output>>,
+    base_fields: Map<String, Value>,
+    checksums: bool,
+}
+
+impl AuditLogger {
+    /// Audit channel writing to `output`.
+    pub fn new(name: &str, output: Box<dyn Output>) -> Self {
+        Self {
+            name: Arc::from(name),
+            formatter: Arc::new(JsonFormatter),
+            outputs: vec![Arc::from(output)],
+            base_fields: Map::new(),
+            checksums: false,
+        }
+    }
+
+    /// Audit channel writing to the [append‑only](AppendOnlyFileOutput) file at `path`.
+    pub fn open<P: AsRef<Path>>(name: &str, path: P) -> io::Result<Self> {
+        Ok(Self::new(name, Box::new(AppendOnlyFileOutput::open(path)?)))
+    }
+
+    /// Also write every event to `output`, e.g. a remote collector next to the local file.
+    pub fn with_output(mut self, output: Box<dyn Output>) -> Self {
+        self.outputs.push(Arc::from(output));
+        self
+    }
+
+    pub fn with_formatter(mut self, formatter: Box<dyn Formatter>) -> Self {
+        self.formatter = Arc::from(formatter);
+        self
+    }
+
+    pub fn with_field<T: Into<Value>>(mut self, key: &str, value: T) -> Self {
+        self.base_fields.insert(key.to_string(), value.into());
+        self
+    }
+
+    /// End every text record with a CRC‑32 of the record, so a reader can use
+    /// [`verify_line`](Self::verify_line) to tell records torn by a crash from good ones.
+    /// JSON records get it as a last `"crc32"` member and stay valid JSON, other text
+    /// as a trailing ` crc32=…`.  Binary records are left alone: use
+    /// [`ChecksummedBinaryFormatter`](crate::binary::ChecksummedBinaryFormatter) for them.
+    ///
+    /// ```
+    /// use cappie::{AuditLogger, CaptureOutput};
+    ///
+    /// let capture = CaptureOutput::new();
+    /// let audit = AuditLogger::new("billing", Box::new(capture.clone())).with_checksums();
+    /// audit.record("alice", "invoice.refund").unwrap();
+    ///
+    /// let line = capture.lines().remove(0);
+    /// assert_eq!(AuditLogger::verify_line(&line), Some(true));
+    /// assert_eq!(AuditLogger::verify_line(&line.replace("alice", "mallory")), Some(false));
+    /// ```
+    pub fn with_checksums(mut self) -> Self {
+        self.checksums = true;
+        self
+    }
+
+    /// Whether the checksum a [`with_checksums`](Self::with_checksums) channel put on
+    /// `line` matches it, or `None` if the line carries none.
+    pub fn verify_line(line: &str) -> Option<bool> {
+        let line = line.trim_end_matches(['\n', '\r']);
+        let (body, hex) = match line.strip_suffix("\"}").and_then(|rest| rest.rsplit_once(",\"crc32\":\"")) {
+            Some((body, hex)) => (format!("{body}}}"), hex),
+            None => line.rsplit_once(" crc32=").map(|(body, hex)| (body.to_string(), hex))?,
+        };
+        if hex.len() != 8 {
+            return None;
+        }
+        let stored = u32::from_str_radix(hex, 16).ok()?;
+        Some(crc32(body.as_bytes()) == stored)
+    }
+
+    /// Record that `actor` performed `action`, with further details added by `f`.  The
+    /// contract fields (`actor`, `action`, `id`, `audit`) cannot be overridden by `f`.
+    ///
+    /// Every output gets the event even if an earlier one fails; the first write or sync
+    /// error is returned.
+    pub fn event<F>(&self, actor: &str, action: &str, f: F) -> io::Result<()>
+    where
+        F: FnOnce(&mut LogBuilder),
+    {
+        let mut builder = LogBuilder::new();
+        f(&mut builder);
+
+        let mut fields = self.base_fields.clone();
+        fields.extend(builder.into_fields());
+        fields.insert("actor".to_string(), Value::from(actor));
+        fields.insert("action".to_string(), Value::from(action));
+        fields.insert("id".to_string(), Value::String(next_ulid()));
+        fields.insert("audit".to_string(), Value::Bool(true));
+
+        let timestamp = Timestamp::now();
+        let mut buf = Vec::new();
+        self.formatter.format_into(&mut buf, Level::Info, action, &fields, timestamp, &self.name);
+        if self.checksums && !self.formatter.is_binary() {
+            add_checksum(&mut buf);
+        }
+        let record = Record {
+            level: Level::Info,
+            timestamp,
+            name: &self.name,
+            msg: action,
+            tags: &[],
+            fields: &fields,
+            formatted: &buf,
+            binary: self.formatter.is_binary(),
+        };
+        let mut result = Ok(());
+        for output in &self.outputs {
+            let delivered = output.try_write_record(&record).and_then(|()| output.try_flush());
+            if result.is_ok() {
+                result = delivered;
+            }
+        }
+        result
+    }
+
+    /// [`event`](Self::event) without further details.
+    pub fn record(&self, actor: &str, action: &str) -> io::Result<()> {
+        self.event(actor, action, |_| {})
+    }
+}
+
+/// Append the CRC‑32 of `record`, as a last JSON member for JSON objects.
+fn add_checksum(record: &mut Vec<u8>) {
+    let crc = crc32(record);
+    if record.last() == Some(&b'}') {
+        record.pop();
+        record.extend_from_slice(format!(",\"crc32\":\"{crc:08x}\"}}").as_bytes());
+    } else {
+        record.extend_from_slice(format!(" crc32={crc:08x}").as_bytes());
+    }
+}
@@ -0,0 +1,46 @@
+//! `cappie` command line tool.
+//!
+//! ```text
+//! cappie to-json [FILE]    convert a binary log (or stdin) to ND-JSON on stdout
+//! ```
+
+use cappie::binary::RecordReader;
+use cappie::JsonFormatter;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: cappie to-json [FILE]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["to-json"] => to_json(io::stdin().lock()),
+        ["to-json", path] => File::open(path).and_then(|f| to_json(BufReader::new(f))),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("cappie: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn to_json<R: Read>(input: R) -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut records = RecordReader::new(input);
+    for record in records.by_ref() {
+        writeln!(out, "{}", record?.format(&JsonFormatter))?;
+    }
+    if records.skipped() > 0 {
+        eprintln!("cappie: skipped {} corrupt records", records.skipped());
+    }
+    out.flush()
+}
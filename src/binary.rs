@@ -0,0 +1,282 @@
+//! Compact binary log format (feature `binary`).
+//!
+//! Every record is one frame: a little‑endian `u32` payload length followed by a
+//! MessagePack array `[level, time_us, name, msg, fields]`, where `time_us` is microseconds
+//! since the Unix epoch and `fields` is a map.  Frames are self‑delimiting, so files can be
+//! appended to by any output that supports [`Output::write_bytes`](crate::Output::write_bytes)
+//! and read back with [`RecordReader`].
+//!
+//! Frames written by [`ChecksummedBinaryFormatter`] have the top bit of the length set and
+//! end in a little‑endian CRC‑32 of the length and payload, so a reader can tell a record
+//! torn by a crash from a good one and skip it.  The `cappie` binary (`cargo install cappie
+//! --features binary`) converts such files back to ND‑JSON: `cappie to-json app.clog`.
+
+use crate::checksum::crc32;
+use crate::formatter::{Formatter, JsonFormatter};
+use crate::level::Level;
+use crate::timestamp::Timestamp;
+use serde_json::{Map, Value};
+use std::io::{self, Read};
+
+/// Frames larger than this are treated as corruption instead of being allocated.
+const MAX_FRAME: usize = 64 * 1024 * 1024;
+
+/// Length bit marking a frame followed by a CRC‑32.
+const CHECKSUM_FLAG: u32 = 1 << 31;
+
+/// Writes records as length‑prefixed MessagePack frames.  Pair it with an output that
+/// writes bytes verbatim, such as [`FileOutput`](crate::FileOutput).
+///
+/// ```no_run
+/// use cappie::{FileOutput, Logger};
+/// use cappie::binary::BinaryFormatter;
+///
+/// let log = Logger::new("ingest")
+///     .with_formatter(Box::new(BinaryFormatter))
+///     .with_output(Box::new(FileOutput::new("ingest.clog")));
+/// log.info("batch stored");
+/// ```
+pub struct BinaryFormatter;
+
+impl Formatter for BinaryFormatter {
+    /// A `String` cannot carry a binary frame, so this returns the record as JSON; the
+    /// [`Logger`](crate::Logger) always goes through [`format_into`](Formatter::format_into).
+    fn format(&self, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) -> String {
+        JsonFormatter.format(level, msg, fields, timestamp, name)
+    }
+
+    fn format_into(&self, buf: &mut Vec<u8>, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) {
+        encode(buf, level, msg, fields, timestamp, name, false);
+    }
+
+    fn is_binary(&self) -> bool {
+        true
+    }
+}
+
+/// [`BinaryFormatter`] with a CRC‑32 after every frame, for files that must survive
+/// crashes: [`RecordReader`] skips frames whose checksum does not match, and finds the
+/// next good frame after them, instead of giving up on the rest of the file.
+///
+/// ```no_run
+/// use cappie::{FileOutput, Logger};
+/// use cappie::binary::ChecksummedBinaryFormatter;
+///
+/// let log = Logger::new("ingest")
+///     .with_formatter(Box::new(ChecksummedBinaryFormatter))
+///     .with_output(Box::new(FileOutput::new("ingest.clog")));
+/// ```
+pub struct ChecksummedBinaryFormatter;
+
+impl Formatter for ChecksummedBinaryFormatter {
+    /// The record as JSON, like [`BinaryFormatter::format`].
+    fn format(&self, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) -> String {
+        JsonFormatter.format(level, msg, fields, timestamp, name)
+    }
+
+    fn format_into(&self, buf: &mut Vec<u8>, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) {
+        encode(buf, level, msg, fields, timestamp, name, true);
+    }
+
+    fn is_binary(&self) -> bool {
+        true
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn encode(buf: &mut Vec<u8>, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str, checksum: bool) {
+    let start = buf.len();
+    buf.extend_from_slice(&[0; 4]);
+    let record = (level.value(), timestamp.unix_nanos().div_euclid(1000) as i64, name, msg, fields);
+    if rmp_serde::encode::write(buf, &record).is_err() || buf.len() - start - 4 > MAX_FRAME {
+        buf.truncate(start);
+        return;
+    }
+    let len = (buf.len() - start - 4) as u32;
+    let prefix = if checksum { len | CHECKSUM_FLAG } else { len };
+    buf[start..start + 4].copy_from_slice(&prefix.to_le_bytes());
+    if checksum {
+        let crc = crc32(&buf[start..]);
+        buf.extend_from_slice(&crc.to_le_bytes());
+    }
+}
+
+/// A record decoded by [`RecordReader`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinaryRecord {
+    pub level: Level,
+    pub timestamp: Timestamp,
+    pub name: String,
+    pub msg: String,
+    pub fields: Map<String, Value>,
+}
+
+impl BinaryRecord {
+    /// Render the record with any formatter, e.g. [`JsonFormatter`] to get ND‑JSON back.
+    pub fn format(&self, formatter: &dyn Formatter) -> String {
+        formatter.format(self.level, &self.msg, &self.fields, self.timestamp, &self.name)
+    }
+}
+
+/// Iterates over the frames written by [`BinaryFormatter`] and
+/// [`ChecksummedBinaryFormatter`].
+///
+/// Iteration ends at a clean end of input, or at a zero length prefix (the zero padding a
+/// pre‑allocated file such as `MmapFileOutput` leaves after a crash).  A truncated or
+/// undecodable frame yields an `Err`; the reader cannot resynchronise after that, so
+/// iteration stops there too.
+///
+/// Checksummed frames are different: once the reader has seen one, a frame whose checksum
+/// does not match, or that is cut short at the end of the input, is skipped and counted in
+/// [`skipped`](Self::skipped), and reading resumes at the next checksummed frame that is
+/// intact.
+///
+/// ```no_run
+/// use std::fs::File;
+/// use std::io::BufReader;
+/// use cappie::binary::RecordReader;
+///
+/// let file = BufReader::new(File::open("ingest.clog").unwrap());
+/// for record in RecordReader::new(file) {
+///     let record = record.unwrap();
+///     println!("{} {}", record.level.as_str(), record.msg);
+/// }
+/// ```
+pub struct RecordReader<R> {
+    reader: R,
+    /// Bytes read but not yet consumed, starting at a frame (or what may be one).
+    buf: Vec<u8>,
+    done: bool,
+    /// Whether a checksummed frame was seen, so corruption can be skipped.
+    checksummed: bool,
+    skipped: u64,
+}
+
+impl<R: Read> RecordReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader, buf: Vec::new(), done: false, checksummed: false, skipped: 0 }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Corrupt or torn checksummed frames skipped so far.
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    fn read_frame(&mut self) -> io::Result<Option<BinaryRecord>> {
+        loop {
+            if !self.fill(4)? {
+                return self.torn(self.buf.is_empty());
+            }
+            let prefix = u32::from_le_bytes([self.buf[0], self.buf[1], self.buf[2], self.buf[3]]);
+            if prefix == 0 {
+                return Ok(None);
+            }
+            let len = (prefix & !CHECKSUM_FLAG) as usize;
+            let checksum = prefix & CHECKSUM_FLAG != 0;
+            self.checksummed |= checksum;
+            if len > MAX_FRAME || len == 0 {
+                if self.checksummed {
+                    self.skip()?;
+                    continue;
+                }
+                return Err(invalid("frame length exceeds limit"));
+            }
+
+            let frame_len = 4 + len + if checksum { 4 } else { 0 };
+            // A frame cut short may also be one with a damaged length.
+            let complete = self.fill(frame_len)?;
+            if self.checksummed && !(complete && (!checksum || intact(&self.buf, len))) {
+                self.skip()?;
+                continue;
+            }
+            if !complete {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+            let record = decode(&self.buf[4..4 + len]);
+            self.buf.drain(..frame_len);
+            return record.map(Some);
+        }
+    }
+
+    /// Make `buf` hold at least `n` bytes; `false` if the input ends first.
+    fn fill(&mut self, n: usize) -> io::Result<bool> {
+        if let Some(missing) = n.checked_sub(self.buf.len()).filter(|&missing| missing > 0) {
+            let read = (&mut self.reader).take(missing as u64).read_to_end(&mut self.buf)?;
+            return Ok(read == missing);
+        }
+        Ok(true)
+    }
+
+    /// The input ended inside a length prefix: expected after a crash when frames are
+    /// checksummed.
+    fn torn(&mut self, clean: bool) -> io::Result<Option<BinaryRecord>> {
+        if clean {
+            Ok(None)
+        } else if self.checksummed {
+            self.skipped += 1;
+            self.buf.clear();
+            Ok(None)
+        } else {
+            Err(io::ErrorKind::UnexpectedEof.into())
+        }
+    }
+
+    /// Count the frame at the start of `buf` as skipped and move on to the next intact
+    /// checksummed frame, or to the end of the input.
+    fn skip(&mut self) -> io::Result<()> {
+        self.skipped += 1;
+        loop {
+            self.buf.drain(..1);
+            if !self.fill(4)? {
+                self.buf.clear();
+                return Ok(());
+            }
+            let prefix = u32::from_le_bytes([self.buf[0], self.buf[1], self.buf[2], self.buf[3]]);
+            let len = (prefix & !CHECKSUM_FLAG) as usize;
+            if prefix & CHECKSUM_FLAG == 0 || len == 0 || len > MAX_FRAME {
+                continue;
+            }
+            if self.fill(8 + len)? && intact(&self.buf, len) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Whether the checksummed frame of payload length `len` at the start of `buf` matches
+/// its CRC.
+fn intact(buf: &[u8], len: usize) -> bool {
+    let stored = u32::from_le_bytes([buf[4 + len], buf[5 + len], buf[6 + len], buf[7 + len]]);
+    crc32(&buf[..4 + len]) == stored
+}
+
+fn decode(payload: &[u8]) -> io::Result<BinaryRecord> {
+    let (level, time_us, name, msg, fields): (u8, i64, String, String, Map<String, Value>) =
+        rmp_serde::from_slice(payload).map_err(|e| invalid(&e.to_string()))?;
+    let level = Level::from_value(level).ok_or_else(|| invalid("unknown level"))?;
+    let timestamp = Timestamp::from_unix_nanos(time_us as i128 * 1000).ok_or_else(|| invalid("timestamp out of range"))?;
+    Ok(BinaryRecord { level, timestamp, name, msg, fields })
+}
+
+impl<R: Read> Iterator for RecordReader<R> {
+    type Item = io::Result<BinaryRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let item = self.read_frame().transpose();
+        if !matches!(item, Some(Ok(_))) {
+            self.done = true;
+        }
+        item
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
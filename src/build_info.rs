@@ -0,0 +1,112 @@
+use crate::timestamp::{self, Precision, Timestamp};
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+/// Variables [`BuildInfo::emit`] sets for the compiler and [`build_info!`](crate::build_info)
+/// reads back.
+const GIT_SHA: &str = "CAPPIE_GIT_SHA";
+const BUILD_TIME: &str = "CAPPIE_BUILD_TIME";
+const PROFILE: &str = "CAPPIE_PROFILE";
+
+/// Where a binary came from – `git_sha`, `build_time` and `profile` – for attaching to
+/// every record with [`Logger::with_build_info`](crate::Logger::with_build_info), so logs
+/// can be matched to the deploy that wrote them.
+///
+/// The values are baked in at compile time: a build script calls [`emit`](Self::emit)
+/// (with cappie as a build dependency) and the program reads them back with
+/// [`build_info!`](crate::build_info):
+///
+/// ```no_run
+/// // in build.rs's main
+/// cappie::BuildInfo::emit();
+/// ```
+///
+/// ```
+/// // main.rs
+/// let log = cappie::Logger::new("api").with_build_info(cappie::build_info!());
+/// ```
+///
+/// Values that are not available (no build script, not a git checkout) are left out of
+/// the records.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BuildInfo {
+    /// Commit the binary was built from.
+    pub git_sha: Option<String>,
+    /// RFC 3339 time the build script last ran.
+    pub build_time: Option<String>,
+    /// Cargo profile, e.g. `debug` or `release`.
+    pub profile: Option<String>,
+}
+
+impl BuildInfo {
+    /// Pass the build's git commit, time and profile to the crate being compiled, as
+    /// `cargo:rustc-env` instructions.  Call from a build script.
+    ///
+    /// Variables already set in the build environment win, so CI can provide
+    /// `CAPPIE_GIT_SHA` when there is no `.git` directory.  `build_time` honours
+    /// `SOURCE_DATE_EPOCH` for reproducible builds.  The build script is rerun when `HEAD`
+    /// moves; otherwise `build_time` keeps the time of its last run.  As with any
+    /// `cargo:rerun-if` instruction, the build script is then no longer rerun on every
+    /// change to the package – emit your own for the files it reads.
+    pub fn emit() {
+        for var in [GIT_SHA, BUILD_TIME, "SOURCE_DATE_EPOCH"] {
+            println!("cargo:rerun-if-env-changed={var}");
+        }
+        let info = Self::collect();
+        for (var, value) in [(GIT_SHA, &info.git_sha), (BUILD_TIME, &info.build_time), (PROFILE, &info.profile)] {
+            if let Some(value) = value {
+                println!("cargo:rustc-env={var}={value}");
+            }
+        }
+    }
+
+    /// The values [`emit`](Self::emit) would pass on, from the build script's environment.
+    fn collect() -> Self {
+        let git_sha = env::var(GIT_SHA).ok().or_else(git_sha);
+        let build_time = env::var(BUILD_TIME).ok().or_else(|| {
+            let time = match env::var("SOURCE_DATE_EPOCH").ok().and_then(|s| s.parse().ok()) {
+                Some(secs) => timestamp::from_unix(secs)?,
+                None => Timestamp::now(),
+            };
+            Some(timestamp::rfc3339_z(&time, Precision::Secs))
+        });
+        Self {
+            git_sha,
+            build_time,
+            profile: env::var("PROFILE").ok(),
+        }
+    }
+
+    /// Attached fields, skipping missing values.
+    pub(crate) fn fields(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        [("git_sha", &self.git_sha), ("build_time", &self.build_time), ("profile", &self.profile)]
+            .into_iter()
+            .filter_map(|(key, value)| Some((key, value.as_deref()?)))
+    }
+}
+
+/// `HEAD` of the git checkout the build runs in, asking cargo to rerun the build script
+/// when it changes.
+fn git_sha() -> Option<String> {
+    let git = |args: &[&str]| -> Option<String> {
+        let output = Command::new("git").args(args).output().ok()?;
+        let text = String::from_utf8(output.stdout).ok()?;
+        output.status.success().then(|| text.trim().to_string())
+    };
+
+    let sha = git(&["rev-parse", "HEAD"])?;
+    if let Some(git_dir) = git(&["rev-parse", "--git-dir"]) {
+        let git_dir = Path::new(&git_dir);
+        println!("cargo:rerun-if-changed={}", git_dir.join("HEAD").display());
+        if let Some(reference) = git(&["symbolic-ref", "-q", "HEAD"]) {
+            let reference = git_dir.join(reference);
+            // A packed ref has no file of its own; naming a missing path would rerun
+            // the build script on every build.
+            if reference.exists() {
+                println!("cargo:rerun-if-changed={}", reference.display());
+            }
+        }
+    }
+    Some(sha)
+}
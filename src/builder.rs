@@ -0,0 +1,133 @@
+use crate::error::BuildError;
+use crate::formatter::{Formatter, JsonFormatter};
+use crate::level::Level;
+use crate::logger::{LevelRange, Logger};
+use crate::output::{MultiOutput, Output, StdoutOutput};
+use serde_json::{Map, Value};
+use std::ops::RangeBounds;
+
+/// Fallible counterpart of the `Logger::with_*` chain: the formatter and outputs are checked
+/// when [`build`](Self::build) is called, so a typo'd time format or an unwritable log path
+/// is reported at start‑up rather than silently producing garbage (or nothing) later.
+///
+/// ```
+/// use cappie::{BuildError, FileOutput, Level, LoggerBuilder};
+///
+/// let err = LoggerBuilder::new("api")
+///     .level(Level::Debug)
+///     .output(Box::new(FileOutput::new("/nonexistent/dir/api.log")))
+///     .build()
+///     .err()
+///     .unwrap();
+/// assert!(matches!(err, BuildError::UnwritablePath { .. }));
+/// ```
+///
+/// Validation is done by [`Formatter::validate`] and [`Output::validate`], so custom
+/// formatters and outputs can take part in it.
+pub struct LoggerBuilder {
+    name: String,
+    level: Level,
+    formatter: Option<Box<dyn Formatter>>,
+    outputs: Vec<Box<dyn Output>>,
+    routes: Vec<(LevelRange, Box<dyn Output>)>,
+    fields: Map<String, Value>,
+    record_ids: bool,
+    echo_errors: bool,
+    isolate_panics: bool,
+}
+
+impl LoggerBuilder {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            level: Level::Info,
+            formatter: None,
+            outputs: Vec::new(),
+            routes: Vec::new(),
+            fields: Map::new(),
+            record_ids: false,
+            echo_errors: false,
+            isolate_panics: false,
+        }
+    }
+    
+    pub fn level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+    
+    /// Defaults to [`JsonFormatter`].
+    pub fn formatter(mut self, formatter: Box<dyn Formatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+    
+    /// Add an output.  Several outputs are combined into a [`MultiOutput`]; without any,
+    /// records go to stdout.
+    pub fn output(mut self, output: Box<dyn Output>) -> Self {
+        self.outputs.push(output);
+        self
+    }
+    
+    /// See [`Logger::route`].
+    pub fn route<R: RangeBounds<Level>>(mut self, levels: R, output: Box<dyn Output>) -> Self {
+        let levels = (levels.start_bound().cloned(), levels.end_bound().cloned());
+        self.routes.push((levels, output));
+        self
+    }
+    
+    pub fn field<T: Into<Value>>(mut self, key: &str, value: T) -> Self {
+        self.fields.insert(key.to_string(), value.into());
+        self
+    }
+    
+    /// See [`Logger::with_record_ids`].
+    pub fn record_ids(mut self, enabled: bool) -> Self {
+        self.record_ids = enabled;
+        self
+    }
+    
+    /// See [`Logger::with_error_echo`].
+    pub fn echo_errors_to_stderr(mut self, enabled: bool) -> Self {
+        self.echo_errors = enabled;
+        self
+    }
+    
+    /// See [`Logger::with_panic_isolation`].
+    pub fn catch_panics(mut self, enabled: bool) -> Self {
+        self.isolate_panics = enabled;
+        self
+    }
+    
+    /// Validate the configuration and create the logger.  The first problem found is
+    /// returned.
+    pub fn build(self) -> Result<Logger, BuildError> {
+        let formatter = self.formatter.unwrap_or_else(|| Box::new(JsonFormatter));
+        formatter.validate()?;
+        for output in self.outputs.iter().chain(self.routes.iter().map(|(_, output)| output)) {
+            output.validate()?;
+        }
+        
+        let mut outputs = self.outputs;
+        let output: Box<dyn Output> = match outputs.len() {
+            0 => Box::new(StdoutOutput),
+            1 => outputs.remove(0),
+            _ => Box::new(outputs.into_iter().fold(MultiOutput::new(), MultiOutput::add_output)),
+        };
+        
+        let mut logger = Logger::new(&self.name)
+            .with_level(self.level)
+            .with_formatter(formatter)
+            .with_output(output)
+            .with_fields(self.fields)
+            .with_error_echo(self.echo_errors)
+            .with_panic_isolation(self.isolate_panics);
+        for (levels, output) in self.routes {
+            logger = logger.route(levels, output);
+        }
+        if self.record_ids {
+            logger = logger.with_record_ids();
+        }
+        Ok(logger)
+    }
+}
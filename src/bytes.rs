@@ -0,0 +1,62 @@
+use serde_json::Value;
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// How [`LogBuilder::bytes_raw`](crate::LogBuilder::bytes_raw) renders binary data.  Set
+/// per logger with [`Logger::with_bytes_encoding`](crate::Logger::with_bytes_encoding).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BytesEncoding {
+    /// Standard base64 with padding: `"3q2+7w=="`.
+    Base64,
+    /// Lower‑case hex: `"deadbeef"`.
+    Hex,
+    /// Only the number of bytes, as a number: `4`.
+    Length,
+    /// Hex of at most this many leading bytes plus the total length:
+    /// `"deadbe… (4 bytes)"`, or just the hex if nothing was cut.
+    Preview(usize),
+}
+
+impl Default for BytesEncoding {
+    /// `Preview(32)`: enough to recognise a payload without blowing up the record.
+    fn default() -> Self {
+        BytesEncoding::Preview(32)
+    }
+}
+
+impl BytesEncoding {
+    pub fn render(&self, bytes: &[u8]) -> Value {
+        match *self {
+            BytesEncoding::Base64 => Value::String(base64(bytes)),
+            BytesEncoding::Hex => Value::String(hex(bytes)),
+            BytesEncoding::Length => Value::from(bytes.len()),
+            BytesEncoding::Preview(max) if bytes.len() <= max => Value::String(hex(bytes)),
+            BytesEncoding::Preview(max) => Value::String(format!("{}… ({} bytes)", hex(&bytes[..max]), bytes.len())),
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for &b in bytes {
+        out.push(HEX_DIGITS[usize::from(b >> 4)] as char);
+        out.push(HEX_DIGITS[usize::from(b & 0x0f)] as char);
+    }
+    out
+}
+
+fn base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,122 @@
+use crate::level::{self, Level};
+use std::collections::HashMap;
+use std::panic::Location;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+/// Counter of each call site of the `*_once` / `*_every` methods.  Call sites are fixed
+/// by the program text, so the counters are leaked rather than ever removed.
+static COUNTS: RwLock<Option<HashMap<&'static Location<'static>, &'static AtomicU64>>> = RwLock::new(None);
+
+/// Count one more call from `site` and return the new total (starting at 1).  Only the
+/// first call from a site takes the write lock; later ones share the read lock and add
+/// to the site's own atomic, so threads in the same loop are not serialised.
+pub(crate) fn hit(site: &'static Location<'static>) -> u64 {
+    let known = COUNTS.read().unwrap_or_else(|e| e.into_inner()).as_ref().and_then(|counts| counts.get(site).copied());
+    let counter = match known {
+        Some(counter) => counter,
+        None => *COUNTS
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_or_insert_with(HashMap::new)
+            .entry(site)
+            .or_insert_with(|| Box::leak(Box::new(AtomicU64::new(0)))),
+    };
+    counter.fetch_add(1, Ordering::Relaxed) + 1
+}
+
+/// Static metadata of one logging macro invocation.  Each [`info!`](crate::info) and
+/// friends expands to a `static Callsite`, so nothing is built per call and the disabled
+/// path costs a single atomic load.
+#[doc(hidden)]
+pub struct Callsite {
+    level: Level,
+    module_path: &'static str,
+    file: &'static str,
+    line: u32,
+    /// Calls counted by [`log_once!`](crate::log_once) and [`log_every!`](crate::log_every).
+    hits: AtomicU64,
+}
+
+impl Callsite {
+    pub const fn new(level: Level, module_path: &'static str, file: &'static str, line: u32) -> Self {
+        Self { level, module_path, file, line, hits: AtomicU64::new(0) }
+    }
+
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    pub fn module_path(&self) -> &'static str {
+        self.module_path
+    }
+
+    pub fn file(&self) -> &'static str {
+        self.file
+    }
+
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// `false` if no logger in the process accepts this level, so the call can be skipped
+    /// without looking at the logger.
+    #[inline]
+    pub fn interested(&self) -> bool {
+        level::may_be_enabled(self.level)
+    }
+
+    /// Count one more call and return the new total (starting at 1).
+    pub(crate) fn hit(&self) -> u64 {
+        self.hits.fetch_add(1, Ordering::Relaxed) + 1
+    }
+}
+
+/// How file paths of call sites are shortened, see
+/// [`Logger::with_source_path_prefix`](crate::Logger::with_source_path_prefix) and
+/// [`Logger::with_source_crate_root`](crate::Logger::with_source_crate_root).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SourcePaths {
+    /// Longest first.
+    prefixes: Vec<String>,
+    crate_root: bool,
+}
+
+impl SourcePaths {
+    pub(crate) fn add_prefix(&mut self, prefix: &str) {
+        let prefix = prefix.trim_end_matches(['/', '\\']).to_string();
+        if !prefix.is_empty() && !self.prefixes.contains(&prefix) {
+            self.prefixes.push(prefix);
+            self.prefixes.sort_by_key(|p| std::cmp::Reverse(p.len()));
+        }
+    }
+
+    pub(crate) fn set_crate_root(&mut self) {
+        self.crate_root = true;
+    }
+
+    pub(crate) fn trim<'a>(&self, path: &'a str) -> &'a str {
+        let stripped = self.prefixes.iter().find_map(|prefix| {
+            let rest = path.strip_prefix(prefix.as_str())?;
+            rest.starts_with(['/', '\\']).then(|| rest.trim_start_matches(['/', '\\']))
+        });
+        let path = stripped.unwrap_or(path);
+        if self.crate_root {
+            return crate_relative(path);
+        }
+        path
+    }
+}
+
+/// `path` from its last `src` directory on, e.g. `src/db/pool.rs` for
+/// `/home/ci/build/crates/db/src/db/pool.rs`; unchanged without one.
+fn crate_relative(path: &str) -> &str {
+    let bytes = path.as_bytes();
+    path.rmatch_indices("src")
+        .map(|(at, _)| at)
+        .find(|&at| {
+            let separator = |b: u8| b == b'/' || b == b'\\';
+            (at == 0 || separator(bytes[at - 1])) && bytes.get(at + 3).is_some_and(|&b| separator(b))
+        })
+        .map_or(path, |at| &path[at..])
+}
@@ -0,0 +1,145 @@
+use crate::builder::LoggerBuilder;
+use crate::error::BuildError;
+use crate::formatter::{Formatter, JsonFormatter, PrettyFormatter};
+use crate::level::Level;
+use crate::logfmt::LogfmtFormatter;
+use crate::logger::Logger;
+use crate::output::{FileOutput, StderrOutput};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{Arg, ArgAction, ArgMatches, Args, Command, FromArgMatches};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+const LEVELS: [&str; 6] = ["trace", "debug", "info", "warn", "error", "fatal"];
+const FORMATS: [&str; 3] = ["pretty", "json", "logfmt"];
+
+/// Logging flags for command‑line tools, implementing clap's [`Args`] so they can be
+/// flattened into any clap parser.  Feature `clap`.
+///
+/// | Flag                  | Effect                                               |
+/// |-----------------------|------------------------------------------------------|
+/// | `--log-level <LEVEL>` | minimum level (default `info`)                       |
+/// | `--log-format <FMT>`  | `pretty`, `json` or `logfmt`                         |
+/// | `--log-file <PATH>`   | append to a file instead of stderr                   |
+/// | `--no-color`          | plain pretty output                                  |
+///
+/// Without `--log-format`, records are pretty when they go to a terminal and JSON
+/// otherwise, like [`Logger::auto`].  Records go to stderr, leaving stdout to the program.
+///
+/// ```ignore
+/// #[derive(clap::Parser)]
+/// struct Cli {
+///     #[command(flatten)]
+///     log: cappie::CappieArgs,
+/// }
+///
+/// let cli = Cli::parse();
+/// let log = cli.log.build_logger("tool")?;
+/// ```
+///
+/// Without the derive macros:
+///
+/// ```
+/// use cappie::{CappieArgs, Level};
+/// use clap::{Args, Command, FromArgMatches};
+///
+/// let matches = CappieArgs::augment_args(Command::new("tool"))
+///     .get_matches_from(["tool", "--log-level", "debug", "--log-format", "json"]);
+/// let args = CappieArgs::from_arg_matches(&matches).unwrap();
+/// assert_eq!(args.log_level, Some(Level::Debug));
+///
+/// let log = args.build_logger("tool").unwrap();
+/// assert_eq!(log.level(), Level::Debug);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CappieArgs {
+    pub log_level: Option<Level>,
+    /// `pretty`, `json` or `logfmt`.
+    pub log_format: Option<String>,
+    pub log_file: Option<PathBuf>,
+    pub no_color: bool,
+}
+
+impl CappieArgs {
+    /// Create the logger the flags describe.  Fails if the log file cannot be written.
+    pub fn build_logger(&self, name: &str) -> Result<Logger, BuildError> {
+        let format = match self.log_format.as_deref() {
+            Some(format) => format,
+            None if self.log_file.is_none() && std::io::stderr().is_terminal() => "pretty",
+            None => "json",
+        };
+        let formatter: Box<dyn Formatter> = match format {
+            "pretty" if self.no_color || self.log_file.is_some() => Box::new(PrettyFormatter::new().with_no_colors()),
+            "pretty" => Box::new(PrettyFormatter::new()),
+            "logfmt" => Box::new(LogfmtFormatter::new()),
+            _ => Box::new(JsonFormatter),
+        };
+        let builder = LoggerBuilder::new(name)
+            .level(self.log_level.unwrap_or(Level::Info))
+            .formatter(formatter);
+        let builder = match &self.log_file {
+            Some(path) => builder.output(Box::new(FileOutput::new(path))),
+            None => builder.output(Box::new(StderrOutput)),
+        };
+        builder.build()
+    }
+}
+
+impl Args for CappieArgs {
+    fn augment_args(cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("log_level")
+                .long("log-level")
+                .value_name("LEVEL")
+                .value_parser(PossibleValuesParser::new(LEVELS).map(|s: String| Level::from_str(&s).unwrap_or(Level::Info)))
+                .ignore_case(true)
+                .help("Minimum level of records to log [default: info]"),
+        )
+        .arg(
+            Arg::new("log_format")
+                .long("log-format")
+                .value_name("FORMAT")
+                .value_parser(PossibleValuesParser::new(FORMATS))
+                .help("Record format [default: pretty on a terminal, json otherwise]"),
+        )
+        .arg(
+            Arg::new("log_file")
+                .long("log-file")
+                .value_name("PATH")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Append records to this file instead of stderr"),
+        )
+        .arg(
+            Arg::new("no_color")
+                .long("no-color")
+                .action(ArgAction::SetTrue)
+                .help("Disable colored log output"),
+        )
+    }
+
+    fn augment_args_for_update(cmd: Command) -> Command {
+        Self::augment_args(cmd)
+    }
+}
+
+impl FromArgMatches for CappieArgs {
+    fn from_arg_matches(matches: &ArgMatches) -> Result<Self, clap::Error> {
+        let mut args = Self::default();
+        args.update_from_arg_matches(matches)?;
+        Ok(args)
+    }
+
+    fn update_from_arg_matches(&mut self, matches: &ArgMatches) -> Result<(), clap::Error> {
+        if let Some(level) = matches.get_one::<Level>("log_level") {
+            self.log_level = Some(*level);
+        }
+        if let Some(format) = matches.get_one::<String>("log_format") {
+            self.log_format = Some(format.clone());
+        }
+        if let Some(path) = matches.get_one::<PathBuf>("log_file") {
+            self.log_file = Some(path.clone());
+        }
+        self.no_color |= matches.get_flag("no_color");
+        Ok(())
+    }
+}
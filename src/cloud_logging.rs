@@ -0,0 +1,114 @@
+use crate::formatter::Formatter;
+use crate::level::Level;
+use crate::timestamp::{self, Precision, Timestamp};
+use serde_json::{Map, Value};
+
+const LABELS: &str = "logging.googleapis.com/labels";
+const TRACE: &str = "logging.googleapis.com/trace";
+const SPAN_ID: &str = "logging.googleapis.com/spanId";
+
+/// Google Cloud Logging's structured‑JSON contract for stdout, as read by the logging
+/// agents of GKE, Cloud Run, App Engine and Cloud Functions.
+///
+/// * the level becomes `severity` (`DEBUG`, `INFO`, `WARNING`, `ERROR`, `CRITICAL`);
+/// * the message becomes `message` and the timestamp `time`;
+/// * a `trace` field (a trace id) becomes `logging.googleapis.com/trace`, prefixed with
+///   `projects/<id>/traces/` when a [project](Self::with_project) is set, and `span_id`
+///   becomes `logging.googleapis.com/spanId`, so entries are correlated with Cloud Trace;
+/// * a `labels` object field, merged over the [static labels](Self::with_label), becomes
+///   `logging.googleapis.com/labels`;
+/// * the logger name is kept as `logger`; other fields stay in the JSON payload.
+///
+/// ```
+/// use cappie::{CloudLoggingFormatter, Logger};
+///
+/// let log = Logger::new("checkout").with_formatter(Box::new(
+///     CloudLoggingFormatter::new()
+///         .with_project("my-project")
+///         .with_label("service", "checkout"),
+/// ));
+/// log.warn_with("payment retried", |b| {
+///     b.string("trace", "4bf92f3577b34da6a3ce929d0e0e4736");
+/// });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CloudLoggingFormatter {
+    project: Option<String>,
+    labels: Map<String, Value>,
+}
+
+impl CloudLoggingFormatter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Project id used to turn bare trace ids into `projects/<id>/traces/<trace>`.
+    pub fn with_project(mut self, project: &str) -> Self {
+        self.project = Some(project.to_string());
+        self
+    }
+
+    /// Label attached to every entry.
+    pub fn with_label(mut self, key: &str, value: &str) -> Self {
+        self.labels.insert(key.to_string(), Value::from(value));
+        self
+    }
+
+    /// Cloud Logging severity for `level`.
+    pub fn severity(level: Level) -> &'static str {
+        match level {
+            Level::Trace | Level::Debug => "DEBUG",
+            Level::Info => "INFO",
+            Level::Warn => "WARNING",
+            Level::Error => "ERROR",
+            Level::Fatal => "CRITICAL",
+        }
+    }
+
+    fn trace_path(&self, trace: &str) -> String {
+        match &self.project {
+            Some(project) if !trace.starts_with("projects/") => format!("projects/{}/traces/{}", project, trace),
+            _ => trace.to_string(),
+        }
+    }
+}
+
+impl Formatter for CloudLoggingFormatter {
+    fn format(&self, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) -> String {
+        let mut entry = Map::new();
+        entry.insert("severity".to_string(), Value::from(Self::severity(level)));
+        entry.insert("message".to_string(), Value::from(msg));
+        entry.insert("time".to_string(), Value::from(timestamp::rfc3339_z(&timestamp, Precision::Nanos)));
+        entry.insert("logger".to_string(), Value::from(name));
+
+        let mut labels = self.labels.clone();
+        for (key, value) in fields {
+            match (key.as_str(), value) {
+                ("trace", Value::String(trace)) => {
+                    entry.insert(TRACE.to_string(), Value::from(self.trace_path(trace)));
+                }
+                ("span_id", Value::String(span)) => {
+                    entry.insert(SPAN_ID.to_string(), Value::from(span.as_str()));
+                }
+                ("labels", Value::Object(extra)) => {
+                    // Label values must be strings.
+                    for (k, v) in extra {
+                        let v = match v {
+                            Value::String(s) => s.clone(),
+                            other => other.to_string(),
+                        };
+                        labels.insert(k.clone(), Value::from(v));
+                    }
+                }
+                _ => {
+                    entry.insert(key.clone(), value.clone());
+                }
+            }
+        }
+        if !labels.is_empty() {
+            entry.insert(LABELS.to_string(), Value::Object(labels));
+        }
+
+        serde_json::to_string(&entry).unwrap_or_default()
+    }
+}
@@ -0,0 +1,228 @@
+//! Logger configuration as a JSON document, for setups that live in deployment tooling
+//! rather than in code:
+//!
+//! ```
+//! use cappie::config::Config;
+//!
+//! let log = Config::from_json(r#"{
+//!     "name": "api",
+//!     "level": "debug",
+//!     "format": "pretty",
+//!     "outputs": ["stderr", {"file": "api.log"}],
+//!     "fields": {"service": "api"}
+//! }"#)?
+//! .build()?;
+//! # std::fs::remove_file("api.log").ok();
+//! # Ok::<(), cappie::BuildError>(())
+//! ```
+//!
+//! Every key is optional; [`default_config`] shows them all with their defaults, and
+//! [`schema`] describes the format as a JSON Schema for validating configs before they
+//! are deployed.  `routes` holds rules in the [router language](crate::output::RouterOutput),
+//! as one string.
+
+use crate::builder::LoggerBuilder;
+use crate::error::BuildError;
+use crate::formatter::{FlexibleFormatter, Formatter, JsonFormatter, PrettyFormatter};
+use crate::level::Level;
+use crate::logfmt::LogfmtFormatter;
+use crate::logger::Logger;
+use crate::output::{FileOutput, Output, RouterOutput, StderrOutput, StdoutOutput};
+use crate::theme::Theme;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+
+const DEFAULT_CONFIG: &str = include_str!("default_config.json");
+
+/// A logger configuration, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub name: String,
+    /// Level name, case‑insensitive.
+    pub level: String,
+    pub format: Format,
+    /// [`FlexibleFormatter`] template, for the `template` format.
+    pub template: Option<String>,
+    /// strftime pattern of the `pretty` and `template` formats.
+    pub time_format: Option<String>,
+    /// [`Theme`] name of the `pretty` and `template` formats.
+    pub theme: Option<String>,
+    pub outputs: Vec<Sink>,
+    /// [Router](crate::output::RouterOutput) rules, written in addition to `outputs`.
+    pub routes: Option<String>,
+    pub fields: Map<String, Value>,
+    pub record_ids: bool,
+    pub source_location: bool,
+    /// See [`Logger::with_panic_isolation`].
+    pub catch_panics: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    #[default]
+    Json,
+    Pretty,
+    Logfmt,
+    Template,
+}
+
+/// Where records go: `"stdout"`, `"stderr"` or `{"file": "path"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Sink {
+    Stdout,
+    Stderr,
+    File(PathBuf),
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            name: "app".to_string(),
+            level: "info".to_string(),
+            format: Format::Json,
+            template: None,
+            time_format: None,
+            theme: None,
+            outputs: vec![Sink::Stdout],
+            routes: None,
+            fields: Map::new(),
+            record_ids: false,
+            source_location: false,
+            catch_panics: false,
+        }
+    }
+}
+
+impl Config {
+    pub fn from_json(json: &str) -> Result<Self, BuildError> {
+        serde_json::from_str(json).map_err(|e| BuildError::Custom(format!("invalid logger config: {}", e)))
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, BuildError> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|e| BuildError::Custom(format!("cannot read {}: {}", path.display(), e)))?;
+        Self::from_json(&json)
+    }
+
+    /// The configured logger, validated like [`LoggerBuilder::build`].
+    pub fn build(&self) -> Result<Logger, BuildError> {
+        let level = Level::from_str(&self.level)
+            .ok_or_else(|| BuildError::Custom(format!("unknown level `{}`", self.level)))?;
+        let mut builder = LoggerBuilder::new(&self.name)
+            .level(level)
+            .formatter(self.formatter()?)
+            .record_ids(self.record_ids)
+            .catch_panics(self.catch_panics);
+        for sink in &self.outputs {
+            let output: Box<dyn Output> = match sink {
+                Sink::Stdout => Box::new(StdoutOutput),
+                Sink::Stderr => Box::new(StderrOutput),
+                Sink::File(path) => Box::new(FileOutput::new(path)),
+            };
+            builder = builder.output(output);
+        }
+        if let Some(routes) = &self.routes {
+            builder = builder.output(Box::new(RouterOutput::parse(routes)?));
+        }
+        for (key, value) in &self.fields {
+            builder = builder.field(key, value.clone());
+        }
+        let logger = builder.build()?;
+        Ok(if self.source_location { logger.with_source_location() } else { logger })
+    }
+
+    fn formatter(&self) -> Result<Box<dyn Formatter>, BuildError> {
+        let theme = match &self.theme {
+            Some(name) => Some(Theme::from_str(name).ok_or_else(|| BuildError::Custom(format!("unknown theme `{}`", name)))?),
+            None => None,
+        };
+        Ok(match self.format {
+            Format::Json => Box::new(JsonFormatter),
+            Format::Logfmt => Box::new(LogfmtFormatter::new()),
+            Format::Pretty => {
+                let mut formatter = PrettyFormatter::new();
+                if let Some(theme) = theme {
+                    formatter = formatter.with_theme(theme);
+                }
+                if let Some(pattern) = &self.time_format {
+                    formatter = formatter.try_with_time_format(pattern.as_str())?;
+                }
+                Box::new(formatter)
+            }
+            Format::Template => {
+                let template = self.template.as_deref().ok_or_else(|| {
+                    BuildError::Custom("the `template` format needs a `template`".to_string())
+                })?;
+                let mut formatter = FlexibleFormatter::from_template(template)?;
+                if let Some(theme) = theme {
+                    formatter = formatter.with_theme(theme);
+                }
+                if let Some(pattern) = &self.time_format {
+                    formatter = formatter.try_with_time_format(pattern.as_str())?;
+                }
+                Box::new(formatter)
+            }
+        })
+    }
+}
+
+/// A config with every key at its default, as a JSON document to start from.  The same
+/// text is in the crate as `src/default_config.json`.
+///
+/// ```
+/// use cappie::config::{default_config, Config};
+///
+/// assert_eq!(Config::from_json(default_config())?, Config::default());
+/// # Ok::<(), cappie::BuildError>(())
+/// ```
+pub fn default_config() -> &'static str {
+    DEFAULT_CONFIG
+}
+
+/// JSON Schema (draft 2020‑12) of the config format.
+pub fn schema() -> Value {
+    let nullable_string = |description: &str| json!({ "type": ["string", "null"], "description": description });
+    let levels: Vec<String> = Level::ALL.iter().map(|level| level.as_str().to_lowercase()).collect();
+    let themes: Vec<&str> = Theme::ALL.iter().map(Theme::as_str).collect();
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Cappie logger configuration",
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "name": { "type": "string", "description": "Logger name.", "default": "app" },
+            "level": { "enum": levels, "description": "Minimum level written.", "default": "info" },
+            "format": { "enum": ["json", "pretty", "logfmt", "template"], "default": "json" },
+            "template": nullable_string("FlexibleFormatter template, required by the `template` format, e.g. `[{time}] {level}: {msg} {fields}`."),
+            "time_format": nullable_string("strftime pattern of the `pretty` and `template` formats."),
+            "theme": {
+                "oneOf": [{ "enum": themes }, { "type": "null" }],
+                "description": "Color theme of the `pretty` and `template` formats.",
+            },
+            "outputs": {
+                "type": "array",
+                "items": {
+                    "oneOf": [
+                        { "enum": ["stdout", "stderr"] },
+                        {
+                            "type": "object",
+                            "properties": { "file": { "type": "string" } },
+                            "required": ["file"],
+                            "additionalProperties": false,
+                        },
+                    ],
+                },
+                "default": ["stdout"],
+            },
+            "routes": nullable_string("Router rules, one per line, written in addition to `outputs`."),
+            "fields": { "type": "object", "description": "Fields added to every record.", "default": {} },
+            "record_ids": { "type": "boolean", "default": false },
+            "source_location": { "type": "boolean", "default": false },
+            "catch_panics": { "type": "boolean", "default": false },
+        },
+    })
+}
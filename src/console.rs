@@ -0,0 +1,407 @@
+//! Terminal capabilities of the standard streams.
+//!
+//! The pretty formatters emit ANSI escape sequences.  Unix terminals understand them
+//! natively; Windows consoles only do once virtual‑terminal processing is switched on for
+//! the handle, which [`StdoutOutput`](crate::StdoutOutput) and
+//! [`StderrOutput`](crate::StderrOutput) do automatically on first use.  Where that is not
+//! possible (consoles older than Windows 10, handles redirected to a file or pipe) they
+//! strip the escapes instead of printing garbage.
+//!
+//! Colors deeper than the terminal can show – 24‑bit colors of the themes on an 8‑color
+//! terminal, say – are likewise replaced by the nearest color it has, see
+//! [`color_depth`].
+
+use std::borrow::Cow;
+use std::env;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+/// A standard output stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// Whether ANSI escapes written to `stream` are interpreted.  On Windows the first call
+/// tries to enable virtual‑terminal processing; the answer is cached for the process.
+/// Always `true` on other platforms.
+#[cfg(windows)]
+pub fn ansi_enabled(stream: Stream) -> bool {
+    static STDOUT: OnceLock<bool> = OnceLock::new();
+    static STDERR: OnceLock<bool> = OnceLock::new();
+
+    let cell = match stream {
+        Stream::Stdout => &STDOUT,
+        Stream::Stderr => &STDERR,
+    };
+    *cell.get_or_init(|| enable_virtual_terminal(stream))
+}
+
+/// Whether ANSI escapes written to `stream` are interpreted.  On Windows the first call
+/// tries to enable virtual‑terminal processing; the answer is cached for the process.
+/// Always `true` on other platforms.
+#[cfg(not(windows))]
+pub fn ansi_enabled(_stream: Stream) -> bool {
+    true
+}
+
+/// `message` as it should be written to `stream`: with its colors reduced to the
+/// terminal's [`color_depth`] where escapes are understood, stripped of them otherwise.
+pub(crate) fn for_stream(stream: Stream, message: &str) -> Cow<'_, str> {
+    if ansi_enabled(stream) {
+        downgrade_colors(message, color_depth())
+    } else {
+        strip_ansi(message)
+    }
+}
+
+/// How many colors a terminal can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorDepth {
+    /// The 8 ANSI colors and their bright variants.
+    Basic,
+    /// The xterm 256‑color palette.
+    Ansi256,
+    /// 24‑bit RGB.
+    TrueColor,
+}
+
+/// [`set_color_depth`]'s override: 0 to detect, otherwise the depth's discriminant + 1.
+static DEPTH_OVERRIDE: AtomicU8 = AtomicU8::new(0);
+
+/// Colors the terminal on the standard streams can show: the [override](set_color_depth)
+/// if one is set, otherwise detected once per process from `COLORTERM` (`truecolor` or
+/// `24bit`), the `colors` capability of `TERM`'s terminfo entry and the `TERM` name
+/// itself.  Windows consoles with virtual‑terminal processing show 24‑bit colors.
+pub fn color_depth() -> ColorDepth {
+    match DEPTH_OVERRIDE.load(Ordering::Relaxed) {
+        1 => ColorDepth::Basic,
+        2 => ColorDepth::Ansi256,
+        3 => ColorDepth::TrueColor,
+        _ => {
+            static DETECTED: OnceLock<ColorDepth> = OnceLock::new();
+            *DETECTED.get_or_init(detect_color_depth)
+        }
+    }
+}
+
+/// Use `depth` instead of detecting it, e.g. from a `--color-depth` option; `None` goes
+/// back to detection.
+pub fn set_color_depth(depth: Option<ColorDepth>) {
+    DEPTH_OVERRIDE.store(depth.map_or(0, |depth| depth as u8 + 1), Ordering::Relaxed);
+}
+
+fn detect_color_depth() -> ColorDepth {
+    let colorterm = env::var("COLORTERM").unwrap_or_default().to_ascii_lowercase();
+    let term = env::var("TERM").unwrap_or_default();
+    if colorterm == "truecolor" || colorterm == "24bit" || term.ends_with("-direct") {
+        return ColorDepth::TrueColor;
+    }
+    if cfg!(windows) && term.is_empty() {
+        return ColorDepth::TrueColor;
+    }
+    match terminfo_colors(&term) {
+        Some(colors) if colors >= 1 << 24 => ColorDepth::TrueColor,
+        Some(colors) if colors >= 256 => ColorDepth::Ansi256,
+        Some(_) => ColorDepth::Basic,
+        None if term.contains("256color") => ColorDepth::Ansi256,
+        None => ColorDepth::Basic,
+    }
+}
+
+/// The `colors` capability of the compiled terminfo entry for `term`, looked up where
+/// ncurses looks.
+#[cfg(unix)]
+fn terminfo_colors(term: &str) -> Option<i32> {
+    use std::path::{Path, PathBuf};
+
+    let first = term.chars().next()?;
+    if term.contains('/') || term.contains("..") {
+        return None;
+    }
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    dirs.extend(env::var_os("TERMINFO").map(PathBuf::from));
+    dirs.extend(env::var_os("HOME").map(|home| Path::new(&home).join(".terminfo")));
+    if let Some(list) = env::var_os("TERMINFO_DIRS") {
+        dirs.extend(env::split_paths(&list).filter(|dir| !dir.as_os_str().is_empty()));
+    }
+    dirs.extend(["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo", "/usr/lib/terminfo"].map(PathBuf::from));
+
+    // Entries live under their first letter, or its hex code on case‑insensitive
+    // file systems.
+    let entry = dirs
+        .iter()
+        .flat_map(|dir| [dir.join(first.to_string()), dir.join(format!("{:x}", first as u32))])
+        .find_map(|dir| std::fs::read(dir.join(term)).ok())?;
+    terminfo_number(&entry, COLORS)
+}
+
+#[cfg(not(unix))]
+fn terminfo_colors(_term: &str) -> Option<i32> {
+    None
+}
+
+/// Index of `colors` among the numeric capabilities.
+#[cfg(unix)]
+const COLORS: usize = 13;
+
+/// Numeric capability `index` of a compiled terminfo entry (see term(5)), in the legacy
+/// 16‑bit or the 32‑bit format.
+#[cfg(unix)]
+fn terminfo_number(entry: &[u8], index: usize) -> Option<i32> {
+    let short = |at: usize| Some(i16::from_le_bytes([*entry.get(at)?, *entry.get(at + 1)?]));
+    let size = |at: usize| usize::try_from(short(at)?).ok();
+    let width = match short(0)? {
+        0o432 => 2,
+        0o1036 => 4,
+        _ => return None,
+    };
+    let (names, booleans, numbers) = (size(2)?, size(4)?, size(6)?);
+    if index >= numbers {
+        return None;
+    }
+    // Numbers start on an even offset after the header, names and booleans.
+    let start = (12 + names + booleans).next_multiple_of(2) + index * width;
+    let value = match width {
+        2 => i32::from(short(start)?),
+        _ => i32::from_le_bytes(entry.get(start..start + 4)?.try_into().ok()?),
+    };
+    (value >= 0).then_some(value)
+}
+
+/// `text` with its 24‑bit and 256‑palette colors (SGR `38`/`48` sequences) replaced by
+/// the nearest color available at `depth`.  Borrows when there is nothing to change.
+pub fn downgrade_colors(text: &str, depth: ColorDepth) -> Cow<'_, str> {
+    if depth == ColorDepth::TrueColor || !(text.contains("38;") || text.contains("48;")) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("\x1b[") {
+        out.push_str(&rest[..start + 2]);
+        rest = &rest[start + 2..];
+        let end = rest.find(|c: char| !c.is_ascii_digit() && c != ';').unwrap_or(rest.len());
+        if rest[end..].starts_with('m') {
+            out.push_str(&downgrade_sgr(&rest[..end], depth));
+            rest = &rest[end..];
+        }
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+/// The parameters of one SGR sequence, with deeper colors replaced.
+fn downgrade_sgr(params: &str, depth: ColorDepth) -> String {
+    let params: Vec<&str> = params.split(';').collect();
+    let mut out = Vec::with_capacity(params.len());
+    let mut i = 0;
+    while i < params.len() {
+        match downgrade_color(&params[i..], depth) {
+            Some((color, used)) => {
+                out.push(color);
+                i += used;
+            }
+            None => {
+                out.push(params[i].to_string());
+                i += 1;
+            }
+        }
+    }
+    out.join(";")
+}
+
+/// The extended color at the start of `params` at `depth`, if it needs replacing, and
+/// the number of parameters it spans.
+fn downgrade_color(params: &[&str], depth: ColorDepth) -> Option<(String, usize)> {
+    let background = match params[0] {
+        "38" => false,
+        "48" => true,
+        _ => return None,
+    };
+    let number = |i: usize| params.get(i)?.parse::<u8>().ok();
+    match *params.get(1)? {
+        "2" => {
+            let rgb = (number(2)?, number(3)?, number(4)?);
+            let color = match depth {
+                ColorDepth::Ansi256 => format!("{};5;{}", params[0], rgb_to_palette(rgb)),
+                _ => basic_sgr(nearest_basic(rgb), background),
+            };
+            Some((color, 5))
+        }
+        "5" if depth == ColorDepth::Basic => Some((basic_sgr(nearest_basic(palette_rgb(number(2)?)), background), 3)),
+        _ => None,
+    }
+}
+
+type Rgb = (u8, u8, u8);
+
+/// xterm's default values of the 16 basic colors.
+const BASIC: [Rgb; 16] = [
+    (0, 0, 0), (205, 0, 0), (0, 205, 0), (205, 205, 0),
+    (0, 0, 238), (205, 0, 205), (0, 205, 205), (229, 229, 229),
+    (127, 127, 127), (255, 0, 0), (0, 255, 0), (255, 255, 0),
+    (92, 92, 255), (255, 0, 255), (0, 255, 255), (255, 255, 255),
+];
+
+/// Channel values of the 6×6×6 color cube of the 256 palette.
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance(a: Rgb, b: Rgb) -> u32 {
+    let d = |x: u8, y: u8| (i32::from(x) - i32::from(y)).unsigned_abs();
+    d(a.0, b.0).pow(2) + d(a.1, b.1).pow(2) + d(a.2, b.2).pow(2)
+}
+
+fn nearest_basic(rgb: Rgb) -> u8 {
+    (0..16u8).min_by_key(|&i| distance(rgb, BASIC[usize::from(i)])).unwrap_or(7)
+}
+
+/// SGR parameter for basic color `index`: `30`–`37` and `90`–`97`, `40`–`47` and
+/// `100`–`107` as background.
+fn basic_sgr(index: u8, background: bool) -> String {
+    let base = if index < 8 { 30 + index } else { 90 + index - 8 };
+    (base + if background { 10 } else { 0 }).to_string()
+}
+
+fn palette_rgb(index: u8) -> Rgb {
+    match index {
+        0..=15 => BASIC[usize::from(index)],
+        16..=231 => {
+            let i = usize::from(index - 16);
+            (CUBE[i / 36], CUBE[i / 6 % 6], CUBE[i % 6])
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
+
+/// The closest entry of the cube or the gray ramp of the 256 palette.
+fn rgb_to_palette(rgb: Rgb) -> u8 {
+    let step = |c: u8| match c {
+        0..=47 => 0,
+        48..=114 => 1,
+        _ => (c - 35) / 40,
+    };
+    let cube = 16 + 36 * step(rgb.0) + 6 * step(rgb.1) + step(rgb.2);
+    let average = ((u16::from(rgb.0) + u16::from(rgb.1) + u16::from(rgb.2)) / 3) as u8;
+    let gray = 232 + (average.saturating_sub(3) / 10).min(23);
+    if distance(rgb, palette_rgb(gray)) < distance(rgb, palette_rgb(cube)) {
+        gray
+    } else {
+        cube
+    }
+}
+
+#[cfg(windows)]
+fn enable_virtual_terminal(stream: Stream) -> bool {
+    use windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE;
+    use windows_sys::Win32::System::Console::{
+        GetConsoleMode, GetStdHandle, SetConsoleMode, ENABLE_VIRTUAL_TERMINAL_PROCESSING,
+        STD_ERROR_HANDLE, STD_OUTPUT_HANDLE,
+    };
+
+    let id = match stream {
+        Stream::Stdout => STD_OUTPUT_HANDLE,
+        Stream::Stderr => STD_ERROR_HANDLE,
+    };
+    // SAFETY: plain Win32 calls on the process' own standard handle; `mode` outlives the
+    // call that writes it.  GetConsoleMode fails for handles that are not a console.
+    unsafe {
+        let handle = GetStdHandle(id);
+        if handle.is_null() || handle == INVALID_HANDLE_VALUE {
+            return false;
+        }
+        let mut mode = 0;
+        if GetConsoleMode(handle, &mut mode) == 0 {
+            return false;
+        }
+        mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0
+            || SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0
+    }
+}
+
+/// Width in columns of the terminal `stream` is attached to, or `None` when it is not a
+/// terminal (redirected to a file or pipe) or the size cannot be determined.
+pub fn terminal_width(stream: Stream) -> Option<usize> {
+    query_width(stream).filter(|&width| width > 0)
+}
+
+#[cfg(unix)]
+fn query_width(stream: Stream) -> Option<usize> {
+    let fd = match stream {
+        Stream::Stdout => libc::STDOUT_FILENO,
+        Stream::Stderr => libc::STDERR_FILENO,
+    };
+    // SAFETY: TIOCGWINSZ fills a `winsize`, which `size` is; it fails with ENOTTY for
+    // descriptors that are not terminals.
+    unsafe {
+        let mut size: libc::winsize = std::mem::zeroed();
+        if libc::ioctl(fd, libc::TIOCGWINSZ, &mut size) != 0 {
+            return None;
+        }
+        Some(size.ws_col as usize)
+    }
+}
+
+#[cfg(windows)]
+fn query_width(stream: Stream) -> Option<usize> {
+    use windows_sys::Win32::System::Console::{
+        GetConsoleScreenBufferInfo, GetStdHandle, CONSOLE_SCREEN_BUFFER_INFO, STD_ERROR_HANDLE,
+        STD_OUTPUT_HANDLE,
+    };
+
+    let id = match stream {
+        Stream::Stdout => STD_OUTPUT_HANDLE,
+        Stream::Stderr => STD_ERROR_HANDLE,
+    };
+    // SAFETY: `info` is a plain C struct the call fills in; the call fails for handles that
+    // are not a console screen buffer.
+    unsafe {
+        let mut info: CONSOLE_SCREEN_BUFFER_INFO = std::mem::zeroed();
+        if GetConsoleScreenBufferInfo(GetStdHandle(id), &mut info) == 0 {
+            return None;
+        }
+        Some((info.srWindow.Right - info.srWindow.Left + 1).max(0) as usize)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn query_width(_stream: Stream) -> Option<usize> {
+    None
+}
+
+/// Number of terminal columns `text` occupies, ignoring ANSI escapes.  Every character
+/// counts as one column.
+pub(crate) fn visible_width(text: &str) -> usize {
+    strip_ansi(text).chars().count()
+}
+
+/// Remove ANSI escape sequences (CSI sequences such as colors, and two‑byte escapes) from
+/// `text`.  Borrows when there is nothing to remove.
+pub fn strip_ansi(text: &str) -> Cow<'_, str> {
+    if !text.contains('\x1b') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            out.push(c);
+            continue;
+        }
+        // CSI: parameters and intermediates up to a final byte in `@`..=`~`.  Any other
+        // escape is two characters long.
+        if chars.next() == Some('[') {
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+    Cow::Owned(out)
+}
@@ -0,0 +1,247 @@
+//! Thread‑local context fields (a request id, a tenant…) added to every record logged on
+//! the thread, whichever logger writes it, and helpers to carry them into other threads.
+//!
+//! ```
+//! use cappie::{context, CaptureOutput, Logger};
+//!
+//! let capture = CaptureOutput::new();
+//! let log = Logger::new("api").with_output(Box::new(capture.clone()));
+//!
+//! let _request = context::push("request_id", "r-42");
+//! let worker = log.clone();
+//! context::spawn_with_context(move || worker.info("resizing image")).join().unwrap();
+//!
+//! assert!(capture.contents().contains(r#""request_id":"r-42""#));
+//! ```
+//!
+//! Context fields override base fields and are overridden by scope and per‑call fields.
+//! They belong to the thread: they do not follow async tasks across `.await` points, and
+//! a guard held over an `.await` may restore the context on the wrong thread.
+
+#[cfg(feature = "rayon")]
+pub use parallel::{ParallelIteratorExt, WithLogContext};
+
+use serde_json::{Map, Value};
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+thread_local! {
+    static CURRENT: RefCell<Context> = RefCell::new(Context::default());
+}
+
+/// A snapshot of context fields, cheap to clone and to send to another thread.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Context {
+    fields: Arc<Map<String, Value>>,
+}
+
+impl Context {
+    /// The context of the current thread.
+    pub fn current() -> Self {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    pub fn fields(&self) -> &Map<String, Value> {
+        &self.fields
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// This context plus `key`.
+    pub fn with<T: Into<Value>>(mut self, key: &str, value: T) -> Self {
+        Arc::make_mut(&mut self.fields).insert(key.to_string(), value.into());
+        self
+    }
+
+    /// Make this the current thread's context until the guard is dropped.
+    pub fn attach(self) -> ContextGuard {
+        let previous = CURRENT.with(|current| current.replace(self));
+        ContextGuard { previous: Some(previous), _not_send: PhantomData }
+    }
+
+    /// Run `f` with this context attached.
+    pub fn run<R>(self, f: impl FnOnce() -> R) -> R {
+        let _guard = self.attach();
+        f()
+    }
+}
+
+/// Restores the previous context when dropped, see [`Context::attach`] and [`push`].
+#[must_use = "the context is detached again when the guard is dropped"]
+pub struct ContextGuard {
+    previous: Option<Context>,
+    /// Guards restore the context of the thread they were created on.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            CURRENT.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+}
+
+/// Add `key` to the current thread's context until the guard is dropped.
+pub fn push<T: Into<Value>>(key: &str, value: T) -> ContextGuard {
+    Context::current().with(key, value).attach()
+}
+
+/// Wrap `f` so that it runs with the current thread's context, wherever it is called.
+/// Use it for closures handed to thread pools:
+///
+/// ```ignore
+/// rayon::spawn(cappie::context::propagate(move || log.info("chunk done")));
+/// ```
+pub fn propagate<F, R>(f: F) -> impl FnOnce() -> R + Send
+where
+    F: FnOnce() -> R + Send,
+{
+    let context = Context::current();
+    move || context.run(f)
+}
+
+/// [`std::thread::spawn`] with the current thread's context carried over.
+pub fn spawn_with_context<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    thread::spawn(propagate(f))
+}
+
+/// The current thread's context fields, for the logger.
+pub(crate) fn current_fields() -> Option<Arc<Map<String, Value>>> {
+    CURRENT
+        .try_with(|current| {
+            let current = current.borrow();
+            (!current.is_empty()).then(|| current.fields.clone())
+        })
+        .ok()
+        .flatten()
+}
+
+#[cfg(feature = "rayon")]
+mod parallel {
+    use super::Context;
+    use rayon::iter::plumbing::{Consumer, Folder, UnindexedConsumer};
+    use rayon::iter::ParallelIterator;
+
+    /// Context for rayon's parallel iterators.  Feature `rayon`.
+    pub trait ParallelIteratorExt: ParallelIterator {
+        /// Attach `context` on the worker threads while they process this iterator's
+        /// items, so records logged from the closures of the following adapters carry
+        /// it.  Pass [`Context::current`] to keep the caller's context.
+        ///
+        /// ```
+        /// use cappie::context::{self, Context, ParallelIteratorExt};
+        /// use cappie::{CaptureOutput, Logger};
+        /// use rayon::prelude::*;
+        ///
+        /// let capture = CaptureOutput::new();
+        /// let log = Logger::new("batch").with_output(Box::new(capture.clone()));
+        /// let _job = context::push("job_id", 7);
+        ///
+        /// (0..100).into_par_iter()
+        ///     .with_log_context(Context::current())
+        ///     .for_each(|i| log.info(&format!("item {i}")));
+        ///
+        /// assert!(capture.lines().iter().all(|line| line.contains(r#""job_id":7"#)));
+        /// ```
+        fn with_log_context(self, context: Context) -> WithLogContext<Self> {
+            WithLogContext { base: self, context }
+        }
+    }
+
+    impl<I: ParallelIterator> ParallelIteratorExt for I {}
+
+    /// Parallel iterator returned by [`ParallelIteratorExt::with_log_context`].
+    pub struct WithLogContext<I> {
+        base: I,
+        context: Context,
+    }
+
+    impl<I: ParallelIterator> ParallelIterator for WithLogContext<I> {
+        type Item = I::Item;
+
+        fn drive_unindexed<C>(self, consumer: C) -> C::Result
+        where
+            C: UnindexedConsumer<Self::Item>,
+        {
+            self.base.drive_unindexed(ContextConsumer { base: consumer, context: self.context })
+        }
+
+        fn opt_len(&self) -> Option<usize> {
+            self.base.opt_len()
+        }
+    }
+
+    struct ContextConsumer<C> {
+        base: C,
+        context: Context,
+    }
+
+    impl<T, C: Consumer<T>> Consumer<T> for ContextConsumer<C> {
+        type Folder = ContextFolder<C::Folder>;
+        type Reducer = C::Reducer;
+        type Result = C::Result;
+
+        fn split_at(self, index: usize) -> (Self, Self, Self::Reducer) {
+            let (left, right, reducer) = self.base.split_at(index);
+            let left = ContextConsumer { base: left, context: self.context.clone() };
+            (left, ContextConsumer { base: right, context: self.context }, reducer)
+        }
+
+        fn into_folder(self) -> Self::Folder {
+            ContextFolder { base: self.base.into_folder(), context: self.context }
+        }
+
+        fn full(&self) -> bool {
+            self.base.full()
+        }
+    }
+
+    impl<T, C: UnindexedConsumer<T>> UnindexedConsumer<T> for ContextConsumer<C> {
+        fn split_off_left(&self) -> Self {
+            ContextConsumer { base: self.base.split_off_left(), context: self.context.clone() }
+        }
+
+        fn to_reducer(&self) -> Self::Reducer {
+            self.base.to_reducer()
+        }
+    }
+
+    struct ContextFolder<F> {
+        base: F,
+        context: Context,
+    }
+
+    impl<T, F: Folder<T>> Folder<T> for ContextFolder<F> {
+        type Result = F::Result;
+
+        fn consume(self, item: T) -> Self {
+            let base = self.context.clone().run(|| self.base.consume(item));
+            ContextFolder { base, context: self.context }
+        }
+
+        fn consume_iter<I>(self, iter: I) -> Self
+        where
+            I: IntoIterator<Item = T>,
+        {
+            let base = self.context.clone().run(|| self.base.consume_iter(iter));
+            ContextFolder { base, context: self.context }
+        }
+
+        fn complete(self) -> Self::Result {
+            self.context.run(|| self.base.complete())
+        }
+
+        fn full(&self) -> bool {
+            self.base.full()
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::error::BuildError;
+use crate::output::{FileOutput, Output, Record};
+use crate::timestamp::{self, Timestamp};
+use serde_json::{Map, Value};
+use std::fmt::Write;
+use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const DEFAULT_PATH: &str = "dead-letter.jsonl";
+
+/// Wraps an output and keeps what it fails to deliver.
+///
+/// Each record goes to the primary output through
+/// [`try_write_record`](Output::try_write_record).  When that reports an error, a JSON
+/// line describing the failure is written to the dead‑letter output instead (by default
+/// the file `dead-letter.jsonl` in the working directory):
+///
+/// ```json
+/// {"failed_at":"…","error":"Broken pipe (os error 32)","level":50,"name":"api","msg":"…","record":"<formatted record>"}
+/// ```
+///
+/// Binary records are kept as `record_hex`, so nothing is lost and the records can be
+/// re‑sent once the primary sink is healthy again.
+///
+/// Composite outputs ([`MultiOutput`](crate::MultiOutput), [`RouterOutput`](crate::output::RouterOutput))
+/// report the first failure of the outputs they wrap.  An
+/// [`AsyncOutput`](crate::output::AsyncOutput) can only report records it could not
+/// queue; failures of its worker are counted in its [stats](crate::output::AsyncStats).
+///
+/// ```no_run
+/// use cappie::{FileOutput, Logger};
+/// use cappie::output::DeadLetterOutput;
+///
+/// let output = DeadLetterOutput::new(Box::new(FileOutput::new("/mnt/nfs/app.log")))
+///     .with_dead_letter(Box::new(FileOutput::new("/var/tmp/app-dead-letter.jsonl")));
+/// let log = Logger::new("api").with_output(Box::new(output));
+/// ```
+pub struct DeadLetterOutput {
+    primary: Box<dyn Output>,
+    dead_letter: Box<dyn Output>,
+    failures: AtomicU64,
+}
+
+impl DeadLetterOutput {
+    pub fn new(primary: Box<dyn Output>) -> Self {
+        Self {
+            primary,
+            dead_letter: Box::new(FileOutput::new(DEFAULT_PATH)),
+            failures: AtomicU64::new(0),
+        }
+    }
+
+    /// Where failed records are written.
+    pub fn with_dead_letter(mut self, output: Box<dyn Output>) -> Self {
+        self.dead_letter = output;
+        self
+    }
+
+    /// Records the primary output failed to deliver so far.
+    pub fn failures(&self) -> u64 {
+        self.failures.load(Ordering::Relaxed)
+    }
+
+    fn bury(&self, record: &Record<'_>, error: &io::Error) {
+        self.failures.fetch_add(1, Ordering::Relaxed);
+
+        let mut entry = Map::new();
+        entry.insert("failed_at".to_string(), Value::from(timestamp::rfc3339(&Timestamp::now())));
+        entry.insert("error".to_string(), Value::from(error.to_string()));
+        entry.insert("level".to_string(), Value::from(record.level.value()));
+        entry.insert("name".to_string(), Value::from(record.name));
+        entry.insert("msg".to_string(), Value::from(record.msg));
+        if record.binary {
+            let mut hex = String::with_capacity(record.formatted.len() * 2);
+            for byte in record.formatted {
+                let _ = write!(hex, "{:02x}", byte);
+            }
+            entry.insert("record_hex".to_string(), Value::from(hex));
+        } else {
+            entry.insert("record".to_string(), Value::from(record.text()));
+        }
+        if let Ok(line) = serde_json::to_string(&entry) {
+            self.dead_letter.write(&line);
+        }
+    }
+}
+
+impl Output for DeadLetterOutput {
+    /// Text without metadata is passed through; failures cannot be detected for it.
+    fn write(&self, message: &str) {
+        self.primary.write(message);
+    }
+
+    fn write_bytes(&self, bytes: &[u8]) {
+        self.primary.write_bytes(bytes);
+    }
+
+    fn write_record(&self, record: &Record<'_>) {
+        if let Err(error) = self.primary.try_write_record(record) {
+            self.bury(record, &error);
+        }
+    }
+
+    /// Reports the primary's error, after burying the record.
+    fn try_write_record(&self, record: &Record<'_>) -> io::Result<()> {
+        self.primary.try_write_record(record).inspect_err(|error| self.bury(record, error))
+    }
+
+    fn flush(&self) {
+        self.primary.flush();
+        self.dead_letter.flush();
+    }
+
+    /// Reports the primary's error.
+    fn try_flush(&self) -> io::Result<()> {
+        let result = self.primary.try_flush();
+        self.dead_letter.flush();
+        result
+    }
+
+    fn validate(&self) -> Result<(), BuildError> {
+        self.primary.validate()?;
+        self.dead_letter.validate()
+    }
+
+    fn needs_fields(&self) -> bool {
+        self.primary.needs_fields() || self.dead_letter.needs_fields()
+    }
+
+    fn describe(&self) -> String {
+        format!("DeadLetterOutput({}, {})", self.primary.describe(), self.dead_letter.describe())
+    }
+}
@@ -0,0 +1,220 @@
+//! Reports about Cappie's own failures – outputs that cannot write, records dropped under
+//! load, panicking formatters – which are otherwise swallowed so that logging never takes
+//! the application down.
+//!
+//! Diagnostics are off by default.  Enable them process‑wide with a callback or an output:
+//!
+//! ```
+//! use cappie::diagnostics;
+//! use cappie::StderrOutput;
+//!
+//! diagnostics::set_handler(|d| eprintln!("cappie: {}", d));
+//! // or: one JSON line per problem
+//! diagnostics::set_output(Box::new(StderrOutput));
+//! # diagnostics::disable();
+//! ```
+//!
+//! The handler runs on the thread that hit the problem, often while an output holds a
+//! lock, so it should be quick and must not log through the output that failed.  Problems
+//! raised while a diagnostic is being handled are not reported again.
+
+use crate::output::Output;
+use crate::timestamp::{self, Timestamp};
+use serde_json::{Map, Value};
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+type Handler = Arc<dyn Fn(&Diagnostic<'_>) + Send + Sync>;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static HANDLER: RwLock<Option<Handler>> = RwLock::new(None);
+static HEALTH: Mutex<BTreeMap<&'static str, OutputHealth>> = Mutex::new(BTreeMap::new());
+
+thread_local! {
+    static REPORTING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// One problem inside Cappie.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Diagnostic<'a> {
+    /// An output failed to write a record, which is lost (or went to a fallback).
+    WriteFailed { output: &'static str, error: &'a io::Error },
+    /// An output dropped records.  Reported when `total`, the number dropped so far, is a
+    /// power of two, so a persistent overload does not flood the handler.
+    Dropped { output: &'static str, reason: &'static str, total: u64 },
+    /// A formatter or output panicked; the record was not written.
+    Panicked { component: &'static str, message: &'a str },
+}
+
+impl Diagnostic<'_> {
+    /// Short machine‑readable name: `write_failed`, `dropped` or `panicked`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Diagnostic::WriteFailed { .. } => "write_failed",
+            Diagnostic::Dropped { .. } => "dropped",
+            Diagnostic::Panicked { .. } => "panicked",
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::WriteFailed { output, error } => write!(f, "{} failed to write a record: {}", output, error),
+            Diagnostic::Dropped { output, reason, total } => write!(f, "{} dropped a record ({}), {} so far", output, reason, total),
+            Diagnostic::Panicked { component, message } => write!(f, "{} panicked: {}", component, message),
+        }
+    }
+}
+
+/// Send diagnostics to `handler`, replacing any previous handler or output.
+pub fn set_handler<F>(handler: F)
+where
+    F: Fn(&Diagnostic<'_>) + Send + Sync + 'static,
+{
+    *HANDLER.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(handler));
+    ENABLED.store(true, Ordering::Release);
+}
+
+/// Write diagnostics to `output` as JSON lines with `time`, `diagnostic` (the
+/// [kind](Diagnostic::kind)) and `message`.
+pub fn set_output(output: Box<dyn Output>) {
+    set_handler(move |diagnostic| {
+        let mut line = Map::new();
+        line.insert("time".to_string(), Value::from(timestamp::rfc3339(&Timestamp::now())));
+        line.insert("diagnostic".to_string(), Value::from(diagnostic.kind()));
+        line.insert("message".to_string(), Value::from(diagnostic.to_string()));
+        output.write(&Value::Object(line).to_string());
+    });
+}
+
+/// Stop reporting diagnostics (the default).
+pub fn disable() {
+    ENABLED.store(false, Ordering::Release);
+    *HANDLER.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Failures of one output (or other component) since the process started, counted
+/// whether or not diagnostics are enabled.  See [`output_health`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputHealth {
+    name: &'static str,
+    write_failures: u64,
+    dropped: u64,
+    panics: u64,
+    last_error: Option<String>,
+    last_failure: Option<Timestamp>,
+}
+
+impl OutputHealth {
+    /// The output's type name, or `formatter`/`output` for panics.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn write_failures(&self) -> u64 {
+        self.write_failures
+    }
+
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    pub fn panics(&self) -> u64 {
+        self.panics
+    }
+
+    /// Message of the latest write error or panic.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    pub fn last_failure(&self) -> Option<Timestamp> {
+        self.last_failure
+    }
+}
+
+/// Every output that has failed, dropped records or panicked so far, sorted by name.
+/// Outputs that never had a problem are not listed.
+///
+/// ```
+/// let unhealthy = cappie::diagnostics::output_health();
+/// for output in &unhealthy {
+///     eprintln!("{}: {} failed writes", output.name(), output.write_failures());
+/// }
+/// ```
+pub fn output_health() -> Vec<OutputHealth> {
+    HEALTH.lock().unwrap_or_else(|e| e.into_inner()).values().cloned().collect()
+}
+
+fn track(diagnostic: &Diagnostic<'_>) {
+    let mut health = HEALTH.lock().unwrap_or_else(|e| e.into_inner());
+    let name = match diagnostic {
+        Diagnostic::WriteFailed { output, .. } | Diagnostic::Dropped { output, .. } => output,
+        Diagnostic::Panicked { component, .. } => component,
+    };
+    let entry = health.entry(name).or_insert_with(|| OutputHealth {
+        name,
+        write_failures: 0,
+        dropped: 0,
+        panics: 0,
+        last_error: None,
+        last_failure: None,
+    });
+    entry.last_failure = Some(Timestamp::now());
+    match diagnostic {
+        Diagnostic::WriteFailed { error, .. } => {
+            entry.write_failures += 1;
+            entry.last_error = Some(error.to_string());
+        }
+        Diagnostic::Dropped { total, .. } => entry.dropped = entry.dropped.max(*total),
+        Diagnostic::Panicked { message, .. } => {
+            entry.panics += 1;
+            entry.last_error = Some(message.to_string());
+        }
+    }
+}
+
+/// Hand `diagnostic` to the configured handler, if any.
+pub(crate) fn report(diagnostic: Diagnostic<'_>) {
+    track(&diagnostic);
+    if !ENABLED.load(Ordering::Acquire) || REPORTING.with(|r| r.replace(true)) {
+        return;
+    }
+    let _reset = Reset;
+    let handler = HANDLER.read().unwrap_or_else(|e| e.into_inner()).clone();
+    if let Some(handler) = handler {
+        handler(&diagnostic);
+    }
+}
+
+/// Clears the re‑entrancy flag, also if the handler panics.
+struct Reset;
+
+impl Drop for Reset {
+    fn drop(&mut self) {
+        REPORTING.with(|r| r.set(false));
+    }
+}
+
+/// Report `result` as a failed write of `output` if it is an error.
+pub(crate) fn check_write(output: &'static str, result: io::Result<()>) {
+    if let Err(error) = result {
+        report(Diagnostic::WriteFailed { output, error: &error });
+    }
+}
+
+/// Report a dropped record of `output`, `total` being the count including this one.
+pub(crate) fn dropped(output: &'static str, reason: &'static str, total: u64) {
+    let diagnostic = Diagnostic::Dropped { output, reason, total };
+    if total.is_power_of_two() {
+        report(diagnostic);
+    } else {
+        track(&diagnostic);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::level::Level;
+use crate::level_floor::LevelFloor;
+use serde_json::{Map, Value};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use std::thread;
+use std::time::Duration;
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// `floor` while writes are paused; above every level.
+const PAUSED: u8 = u8::MAX;
+
+/// Degrades a logger while the volume it writes to runs out of space, instead of letting
+/// it fill the disk or fail every write.  Attach it with
+/// [`Logger::with_disk_space_guard`](crate::Logger::with_disk_space_guard).
+///
+/// Every [interval](Self::with_interval) the free space of the volume holding `path` is
+/// sampled:
+///
+/// * below `min_free` bytes, records below the [raised level](Self::with_raised_level)
+///   (`Info` by default, dropping `Debug` and `Trace`) are discarded, or all records
+///   once [paused](Self::with_pause);
+/// * once more than `min_free` plus a tenth is free again, the configured level applies
+///   again.
+///
+/// Each transition is reported as a `Warn` record with the fields `disk_space` (`low` or
+/// `restored`), `path`, `free_bytes`, `min_free_bytes` and `min_level` (`paused` while
+/// paused), written ahead of the next record the logger writes – the last one before a
+/// pause.
+///
+/// ```no_run
+/// use cappie::{DiskSpaceGuard, FileOutput, Level, Logger};
+///
+/// let log = Logger::new("ingest")
+///     .with_output(Box::new(FileOutput::new("/var/log/ingest.log")))
+///     .with_disk_space_guard(DiskSpaceGuard::new("/var/log", 512 * 1024 * 1024).with_raised_level(Level::Warn));
+/// log.info("dropped while less than 512 MiB are free");
+/// ```
+///
+/// Free space is read with `statvfs` on Unix and `GetDiskFreeSpaceExW` on Windows; where
+/// neither is available the guard never degrades.
+pub struct DiskSpaceGuard {
+    path: PathBuf,
+    min_free: u64,
+    raised: Option<Level>,
+    interval: Duration,
+}
+
+impl DiskSpaceGuard {
+    pub fn new<P: AsRef<Path>>(path: P, min_free: u64) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            min_free,
+            raised: Some(Level::Info),
+            interval: DEFAULT_INTERVAL,
+        }
+    }
+
+    /// Minimum level while space is low (default `Info`).
+    pub fn with_raised_level(mut self, level: Level) -> Self {
+        self.raised = Some(level);
+        self
+    }
+
+    /// Write nothing at all while space is low.
+    pub fn with_pause(mut self) -> Self {
+        self.raised = None;
+        self
+    }
+
+    /// Time between two samples (default 5 seconds).
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Take a first sample, then keep sampling on a background thread, which ends once
+    /// the returned state is dropped.
+    pub(crate) fn start(self) -> Arc<DiskSpaceState> {
+        let state = Arc::new(DiskSpaceState {
+            floor: LevelFloor::new(),
+            path: self.path.to_string_lossy().into_owned(),
+            min_free: self.min_free,
+        });
+        // The first records may already find the disk full.
+        self.sample(&state);
+        let weak = Arc::downgrade(&state);
+        let _ = thread::Builder::new()
+            .name("cappie-disk-space".to_string())
+            .spawn(move || self.run(weak));
+        state
+    }
+
+    fn run(self, state: Weak<DiskSpaceState>) {
+        loop {
+            thread::sleep(self.interval);
+            let Some(state) = state.upgrade() else {
+                return;
+            };
+            self.sample(&state);
+        }
+    }
+
+    fn sample(&self, state: &DiskSpaceState) {
+        let Ok(free) = free_space(&self.path) else {
+            return;
+        };
+        let low = state.floor.get() != 0;
+        if !low && free < self.min_free {
+            let floor = self.raised.map_or(PAUSED, |level| level.value());
+            state.floor.set(floor, Transition { raised: Some(self.raised), free });
+        } else if low && free > self.min_free.saturating_add(self.min_free / 10) {
+            state.floor.set(0, Transition { raised: None, free });
+        }
+    }
+}
+
+/// What a running guard shares with the loggers it is attached to.
+pub(crate) struct DiskSpaceState {
+    /// Raised, or [`PAUSED`], while space is low.
+    floor: LevelFloor<Transition>,
+    path: String,
+    min_free: u64,
+}
+
+struct Transition {
+    /// `Some` when space ran low, holding the raised level or `None` for a pause.
+    raised: Option<Option<Level>>,
+    free: u64,
+}
+
+impl DiskSpaceState {
+    /// Raised minimum level; `Fatal` while paused, see [`paused`](Self::paused).
+    pub(crate) fn floor(&self) -> Option<Level> {
+        match self.floor.get() {
+            PAUSED => Some(Level::Fatal),
+            floor => Level::from_value(floor),
+        }
+    }
+
+    pub(crate) fn paused(&self) -> bool {
+        self.floor.get() == PAUSED
+    }
+
+    /// Whether a transition waits to be reported.
+    pub(crate) fn pending(&self) -> bool {
+        self.floor.pending()
+    }
+
+    /// Message and fields of the transition to report, if one happened since the last
+    /// call.  `configured` is the logger's own level, reported once space is back.
+    pub(crate) fn take_report(&self, configured: Level) -> Option<(&'static str, Map<String, Value>)> {
+        let transition = self.floor.take()?;
+        let (msg, state, min_level) = match transition.raised {
+            Some(Some(level)) => ("disk space low, log level raised", "low", level.max(configured).as_str()),
+            Some(None) => ("disk space low, logging paused", "low", "paused"),
+            None => ("disk space restored", "restored", configured.as_str()),
+        };
+        let mut fields = Map::new();
+        fields.insert("disk_space".to_string(), Value::from(state));
+        fields.insert("path".to_string(), Value::from(self.path.as_str()));
+        fields.insert("free_bytes".to_string(), Value::from(transition.free));
+        fields.insert("min_free_bytes".to_string(), Value::from(self.min_free));
+        fields.insert("min_level".to_string(), Value::from(min_level));
+        Some((msg, fields))
+    }
+}
+
+/// Bytes available to unprivileged users on the volume holding `path`.
+#[cfg(unix)]
+fn free_space(path: &Path) -> io::Result<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    #[allow(clippy::unnecessary_cast)]
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(windows)]
+fn free_space(path: &Path) -> io::Result<u64> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut available = 0u64;
+    if unsafe { GetDiskFreeSpaceExW(wide.as_ptr(), &mut available, std::ptr::null_mut(), std::ptr::null_mut()) } == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(available)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn free_space(_path: &Path) -> io::Result<u64> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "free disk space is not available on this platform"))
+}
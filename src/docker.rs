@@ -0,0 +1,81 @@
+use crate::console::{strip_ansi, Stream};
+use crate::error::BuildError;
+use crate::formatter::{Formatter, JsonFormatter};
+use crate::level::Level;
+use crate::timestamp::{self, Precision, Timestamp};
+use serde_json::{Map, Value};
+
+/// Records in the shape of Docker's `json-file` logging driver:
+///
+/// ```json
+/// {"log":"{\"level\":30,…,\"msg\":\"ready\"}\n","stream":"stdout","time":"2024-01-15T10:30:00.123456789Z"}
+/// ```
+///
+/// `log` is the record as rendered by the wrapped formatter ([`JsonFormatter`] by default,
+/// colors stripped) plus the newline Docker keeps, `stream` is `stdout` – or `stderr` from
+/// the level set with [`with_stderr_from`](Self::with_stderr_from) – and `time` has
+/// nanosecond precision.
+///
+/// ```
+/// use cappie::{DockerJsonFormatter, Level, Logger, PrettyFormatter};
+///
+/// let log = Logger::new("web").with_formatter(Box::new(
+///     DockerJsonFormatter::wrapping(PrettyFormatter::new()).with_stderr_from(Level::Error),
+/// ));
+/// ```
+pub struct DockerJsonFormatter {
+    inner: Box<dyn Formatter>,
+    stderr_from: Option<Level>,
+}
+
+impl Default for DockerJsonFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DockerJsonFormatter {
+    pub fn new() -> Self {
+        Self::wrapping(JsonFormatter)
+    }
+
+    /// Render the `log` value with `inner`.
+    pub fn wrapping<F: Formatter + 'static>(inner: F) -> Self {
+        Self { inner: Box::new(inner), stderr_from: None }
+    }
+
+    /// Report records at `level` and above as `"stream":"stderr"`.
+    pub fn with_stderr_from(mut self, level: Level) -> Self {
+        self.stderr_from = Some(level);
+        self
+    }
+
+    fn stream(&self, level: Level) -> Stream {
+        match self.stderr_from {
+            Some(from) if level >= from => Stream::Stderr,
+            _ => Stream::Stdout,
+        }
+    }
+}
+
+impl Formatter for DockerJsonFormatter {
+    fn format(&self, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) -> String {
+        let rendered = self.inner.format(level, msg, fields, timestamp, name);
+        let mut log = strip_ansi(&rendered).into_owned();
+        log.push('\n');
+
+        let mut entry = Map::new();
+        entry.insert("log".to_string(), Value::String(log));
+        let stream = match self.stream(level) {
+            Stream::Stdout => "stdout",
+            Stream::Stderr => "stderr",
+        };
+        entry.insert("stream".to_string(), Value::from(stream));
+        entry.insert("time".to_string(), Value::from(timestamp::rfc3339_z(&timestamp, Precision::Nanos)));
+        serde_json::to_string(&entry).unwrap_or_default()
+    }
+
+    fn validate(&self) -> Result<(), BuildError> {
+        self.inner.validate()
+    }
+}
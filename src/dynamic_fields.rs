@@ -0,0 +1,51 @@
+use serde_json::{Map, Value};
+use std::cell::Cell;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+type Provider = dyn Fn() -> Map<String, Value> + Send + Sync;
+type Cached = (Instant, Arc<Map<String, Value>>);
+
+thread_local! {
+    /// Set while a provider runs on this thread, so records it logs itself use the
+    /// cached fields instead of calling it again.
+    static REFRESHING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Fields computed by a callback when records are written, cached for a TTL, see
+/// [`Logger::with_dynamic_fields`](crate::Logger::with_dynamic_fields).
+pub(crate) struct DynamicFields {
+    provider: Box<Provider>,
+    ttl: Duration,
+    cache: Mutex<Option<Cached>>,
+}
+
+impl DynamicFields {
+    pub(crate) fn new(ttl: Duration, provider: Box<Provider>) -> Self {
+        Self { provider, ttl, cache: Mutex::new(None) }
+    }
+
+    /// The cached fields, refreshed first if they are older than the TTL.  The lock is not
+    /// held while the provider runs; concurrent refreshes may both call it.
+    pub(crate) fn get(&self) -> Arc<Map<String, Value>> {
+        let cached = self.cache.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        match cached {
+            Some((at, fields)) if at.elapsed() < self.ttl => return fields,
+            Some((_, fields)) if REFRESHING.with(Cell::get) => return fields,
+            None if REFRESHING.with(Cell::get) => return Arc::default(),
+            _ => {}
+        }
+
+        struct Reset;
+        impl Drop for Reset {
+            fn drop(&mut self) {
+                REFRESHING.with(|r| r.set(false));
+            }
+        }
+        REFRESHING.with(|r| r.set(true));
+        let _reset = Reset;
+        let fields = Arc::new((self.provider)());
+        *self.cache.lock().unwrap_or_else(|e| e.into_inner()) = Some((Instant::now(), fields.clone()));
+        fields
+    }
+}
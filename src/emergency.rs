@@ -0,0 +1,99 @@
+/// Write `msg` and a newline straight to the standard error file descriptor, bypassing
+/// loggers, formatters, locks and the allocator.
+///
+/// Unlike the regular pipeline this is async‑signal‑safe, so it can be used from signal
+/// handlers, panic hooks and `atexit` handlers, where taking a lock that the interrupted
+/// code holds (or allocating) could deadlock.  The message is written as is, so build it
+/// beforehand (e.g. a `static` string).  Interrupted and partial writes are retried;
+/// other errors are ignored.
+///
+/// ```
+/// cappie::emergency_log("worker pool exhausted, shutting down");
+/// ```
+pub fn emergency_log(msg: &str) {
+    imp::write_line(msg.as_bytes());
+}
+
+#[cfg(unix)]
+mod imp {
+    use std::io;
+
+    const STDERR: libc::c_int = 2;
+
+    pub(super) fn write_line(msg: &[u8]) {
+        // One `writev` keeps message and newline together in the common case.
+        let iov = [
+            libc::iovec { iov_base: msg.as_ptr() as *mut libc::c_void, iov_len: msg.len() },
+            libc::iovec { iov_base: b"\n".as_ptr() as *mut libc::c_void, iov_len: 1 },
+        ];
+        let written = loop {
+            // SAFETY: both buffers are valid for reads of the given lengths.
+            let n = unsafe { libc::writev(STDERR, iov.as_ptr(), 2) };
+            if n >= 0 {
+                break n as usize;
+            }
+            if io::Error::last_os_error().kind() != io::ErrorKind::Interrupted {
+                return;
+            }
+        };
+        if written < msg.len() {
+            write_all(&msg[written..]);
+            write_all(b"\n");
+        } else if written == msg.len() {
+            write_all(b"\n");
+        }
+    }
+
+    fn write_all(mut bytes: &[u8]) {
+        while !bytes.is_empty() {
+            // SAFETY: `bytes` is valid for reads of `bytes.len()` bytes.
+            let n = unsafe { libc::write(STDERR, bytes.as_ptr() as *const libc::c_void, bytes.len()) };
+            if n > 0 {
+                bytes = &bytes[n as usize..];
+            } else if n == 0 || io::Error::last_os_error().kind() != io::ErrorKind::Interrupted {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE;
+    use windows_sys::Win32::Storage::FileSystem::WriteFile;
+    use windows_sys::Win32::System::Console::{GetStdHandle, STD_ERROR_HANDLE};
+
+    pub(super) fn write_line(msg: &[u8]) {
+        write_all(msg);
+        write_all(b"\r\n");
+    }
+
+    fn write_all(mut bytes: &[u8]) {
+        // SAFETY: GetStdHandle has no preconditions.
+        let handle = unsafe { GetStdHandle(STD_ERROR_HANDLE) };
+        if handle.is_null() || handle == INVALID_HANDLE_VALUE {
+            return;
+        }
+        while !bytes.is_empty() {
+            let chunk = bytes.len().min(u32::MAX as usize) as u32;
+            let mut written = 0u32;
+            // SAFETY: `bytes` is valid for reads of `chunk` bytes and `written` for a write.
+            let ok = unsafe { WriteFile(handle, bytes.as_ptr(), chunk, &mut written, std::ptr::null_mut()) };
+            if ok == 0 || written == 0 {
+                return;
+            }
+            bytes = &bytes[written as usize..];
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod imp {
+    use std::io::Write;
+
+    pub(super) fn write_line(msg: &[u8]) {
+        let mut err = std::io::stderr();
+        let _ = err.write_all(msg);
+        let _ = err.write_all(b"\n");
+    }
+}
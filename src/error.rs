@@ -0,0 +1,45 @@
+use std::fmt;
+use std::io;
+
+/// Why a [`LoggerBuilder`](crate::LoggerBuilder) refused to build a logger.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// A [`FlexibleFormatter`](crate::FlexibleFormatter) without components would print
+    /// empty lines.
+    NoComponents,
+    /// A strftime pattern chrono cannot interpret.
+    InvalidTimeFormat { format: String },
+    /// A [`FlexibleFormatter::from_template`](crate::FlexibleFormatter::from_template)
+    /// string that could not be parsed.
+    InvalidTemplate { template: String, reason: String },
+    /// A [`RouterOutput`](crate::output::RouterOutput) configuration line that could not
+    /// be parsed.
+    InvalidRoute { line: usize, reason: String },
+    /// A file output whose path cannot be opened for appending.
+    UnwritablePath { path: String, source: io::Error },
+    /// Reported by a third‑party formatter or output.
+    Custom(String),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::NoComponents => write!(f, "formatter has no components"),
+            BuildError::InvalidTimeFormat { format } => write!(f, "invalid time format {:?}", format),
+            BuildError::InvalidTemplate { template, reason } => write!(f, "invalid template {:?}: {}", template, reason),
+            BuildError::InvalidRoute { line, reason } => write!(f, "invalid route on line {}: {}", line, reason),
+            BuildError::UnwritablePath { path, source } => write!(f, "cannot write to {}: {}", path, source),
+            BuildError::Custom(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BuildError::UnwritablePath { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
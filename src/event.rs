@@ -0,0 +1,102 @@
+use crate::error::BuildError;
+use crate::level::Level;
+use serde_json::{json, Value};
+
+/// A stable identifier for one kind of event, so alerts and dashboards can match on
+/// `event_code` instead of message text.  Declare codes as constants and log them with
+/// [`Logger::event`](crate::Logger::event); the record is written at the code's level.
+///
+/// ```
+/// use cappie::{CaptureOutput, EventCode, Level, Logger};
+///
+/// const AUTH_001: EventCode = EventCode::new("AUTH-001", Level::Warn, "A login attempt was rejected");
+///
+/// let capture = CaptureOutput::new();
+/// let log = Logger::new("auth").with_output(Box::new(capture.clone()));
+/// log.event(AUTH_001, "login failed", |b| {
+///     b.string("user", "alice");
+/// });
+/// assert!(capture.contents().contains(r#""event_code":"AUTH-001""#));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventCode {
+    code: &'static str,
+    level: Level,
+    description: &'static str,
+}
+
+impl EventCode {
+    pub const fn new(code: &'static str, level: Level, description: &'static str) -> Self {
+        Self { code, level, description }
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+}
+
+/// The set of [`EventCode`]s an application emits, exported as JSON for documentation
+/// and alert rules.
+///
+/// ```
+/// use cappie::{EventCatalog, EventCode, Level};
+///
+/// const AUTH_001: EventCode = EventCode::new("AUTH-001", Level::Warn, "A login attempt was rejected");
+/// const AUTH_002: EventCode = EventCode::new("AUTH-002", Level::Error, "The identity provider is unreachable");
+///
+/// let catalog = EventCatalog::new().with(AUTH_001).with(AUTH_002);
+/// catalog.validate().unwrap();
+/// println!("{}", catalog.to_json());
+/// // [{"code":"AUTH-001","level":"WARN","description":"A login attempt was rejected"}, …]
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EventCatalog {
+    codes: Vec<EventCode>,
+}
+
+impl EventCatalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, code: EventCode) -> Self {
+        self.codes.push(code);
+        self
+    }
+
+    /// Codes sorted by code.
+    pub fn codes(&self) -> Vec<EventCode> {
+        let mut codes = self.codes.clone();
+        codes.sort_by_key(|c| c.code);
+        codes.dedup();
+        codes
+    }
+
+    /// Fail if one code string was registered with two different definitions.
+    pub fn validate(&self) -> Result<(), BuildError> {
+        let codes = self.codes();
+        match codes.windows(2).find(|pair| pair[0].code == pair[1].code) {
+            Some(pair) => Err(BuildError::Custom(format!("event code `{}` is defined twice", pair[0].code))),
+            None => Ok(()),
+        }
+    }
+
+    /// The catalog as a pretty‑printed JSON array of `code`, `level` and `description`
+    /// objects, sorted by code.
+    pub fn to_json(&self) -> String {
+        let entries: Vec<Value> = self
+            .codes()
+            .iter()
+            .map(|c| json!({ "code": c.code, "level": c.level.as_str(), "description": c.description }))
+            .collect();
+        serde_json::to_string_pretty(&entries).unwrap_or_default()
+    }
+}
@@ -0,0 +1,135 @@
+//! Hand‑rolled NDJSON writer behind the `fast-json` feature.  It streams the record straight
+//! into the output buffer instead of building a `serde_json::Map` per record, using `itoa` /
+//! `ryu` for numbers.
+
+use crate::fields::Fields;
+use crate::level::Level;
+use crate::timestamp::{self, Timestamp};
+use serde_json::{Number, Value};
+use std::io::Write;
+
+const RESERVED: [&str; 4] = ["level", "time", "name", "msg"];
+
+/// Writes `{"level":…,"time":…,"name":…,"msg":…,<fields>}`.  Unlike the map based path the
+/// fixed keys come first; the fields follow sorted by key, as in the default path, and a field
+/// named like one of the fixed keys replaces its value, exactly like the default path.
+pub(crate) fn write_record(buf: &mut Vec<u8>, level: Level, msg: &str, fields: Fields<'_>, timestamp: Timestamp, name: &str) {
+    buf.extend_from_slice(b"{\"level\":");
+    match fields.get("level") {
+        Some(v) => write_value(buf, v),
+        None => write_u64(buf, level.value() as u64),
+    }
+
+    buf.extend_from_slice(b",\"time\":");
+    match fields.get("time") {
+        Some(v) => write_value(buf, v),
+        None => {
+            buf.push(b'"');
+            let _ = write!(buf, "{}", timestamp::format(&timestamp, "%+"));
+            buf.push(b'"');
+        }
+    }
+
+    buf.extend_from_slice(b",\"name\":");
+    match fields.get("name") {
+        Some(v) => write_value(buf, v),
+        None => write_str(buf, name),
+    }
+
+    buf.extend_from_slice(b",\"msg\":");
+    match fields.get("msg") {
+        Some(v) => write_value(buf, v),
+        None => write_str(buf, msg),
+    }
+
+    for (k, v) in fields.sorted() {
+        if RESERVED.contains(&k) {
+            continue;
+        }
+        buf.push(b',');
+        write_str(buf, k);
+        buf.push(b':');
+        write_value(buf, v);
+    }
+    buf.push(b'}');
+}
+
+fn write_value(buf: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Null => buf.extend_from_slice(b"null"),
+        Value::Bool(true) => buf.extend_from_slice(b"true"),
+        Value::Bool(false) => buf.extend_from_slice(b"false"),
+        Value::Number(n) => write_number(buf, n),
+        Value::String(s) => write_str(buf, s),
+        Value::Array(items) => {
+            buf.push(b'[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    buf.push(b',');
+                }
+                write_value(buf, item);
+            }
+            buf.push(b']');
+        }
+        Value::Object(map) => {
+            buf.push(b'{');
+            for (i, (k, v)) in map.iter().enumerate() {
+                if i > 0 {
+                    buf.push(b',');
+                }
+                write_str(buf, k);
+                buf.push(b':');
+                write_value(buf, v);
+            }
+            buf.push(b'}');
+        }
+    }
+}
+
+fn write_number(buf: &mut Vec<u8>, n: &Number) {
+    if let Some(u) = n.as_u64() {
+        write_u64(buf, u);
+    } else if let Some(i) = n.as_i64() {
+        buf.extend_from_slice(itoa::Buffer::new().format(i).as_bytes());
+    } else if let Some(f) = n.as_f64().filter(|f| f.is_finite()) {
+        buf.extend_from_slice(ryu::Buffer::new().format_finite(f).as_bytes());
+    } else {
+        // Arbitrary precision numbers and anything else serde_json can hold.
+        let _ = write!(buf, "{}", n);
+    }
+}
+
+fn write_u64(buf: &mut Vec<u8>, n: u64) {
+    buf.extend_from_slice(itoa::Buffer::new().format(n).as_bytes());
+}
+
+fn write_str(buf: &mut Vec<u8>, s: &str) {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    buf.push(b'"');
+    let bytes = s.as_bytes();
+    let mut start = 0;
+    for (i, &b) in bytes.iter().enumerate() {
+        let escape: &[u8] = match b {
+            b'"' => b"\\\"",
+            b'\\' => b"\\\\",
+            b'\n' => b"\\n",
+            b'\r' => b"\\r",
+            b'\t' => b"\\t",
+            0x08 => b"\\b",
+            0x0c => b"\\f",
+            0x00..=0x1f => {
+                buf.extend_from_slice(&bytes[start..i]);
+                buf.extend_from_slice(&[b'\\', b'u', b'0', b'0', HEX[(b >> 4) as usize], HEX[(b & 0xf) as usize]]);
+                start = i + 1;
+                continue;
+            }
+            _ => continue,
+        };
+        buf.extend_from_slice(&bytes[start..i]);
+        buf.extend_from_slice(escape);
+        start = i + 1;
+    }
+    buf.extend_from_slice(&bytes[start..]);
+    buf.push(b'"');
+}
@@ -0,0 +1,195 @@
+//! The fields of a record as layers of maps, so the logger can hand base, context, scope
+//! and per‑call fields to a formatter without merging them into one map first.
+
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+use std::fmt;
+
+/// Base, dynamic, context, scope and per‑call fields.
+const MAX_LAYERS: usize = 5;
+
+/// A field key that is either borrowed for `'static` or owned.  The `*_static` methods of
+/// [`LogBuilder`](crate::LogBuilder) take one, so a key written as a string literal reaches
+/// the formatter without being copied into a `String`.
+///
+/// ```
+/// use cappie::Logger;
+///
+/// let log = Logger::new("api");
+/// log.info_with("request served", |b| {
+///     b.number_static("status", 200).string_static("route", "/users");
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Key(Cow<'static, str>);
+
+impl Key {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub(crate) fn into_string(self) -> String {
+        self.0.into_owned()
+    }
+}
+
+impl From<&'static str> for Key {
+    fn from(key: &'static str) -> Self {
+        Key(Cow::Borrowed(key))
+    }
+}
+
+impl From<String> for Key {
+    fn from(key: String) -> Self {
+        Key(Cow::Owned(key))
+    }
+}
+
+impl From<Cow<'static, str>> for Key {
+    fn from(key: Cow<'static, str>) -> Self {
+        Key(key)
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// One layer of fields: a map, or pairs with [`Key`]s and no key given twice.
+#[derive(Clone, Copy)]
+enum Layer<'a> {
+    Map(&'a Map<String, Value>),
+    Pairs(&'a [(Key, Value)]),
+}
+
+impl<'a> Layer<'a> {
+    fn get(self, key: &str) -> Option<&'a Value> {
+        match self {
+            Layer::Map(map) => map.get(key),
+            Layer::Pairs(pairs) => pairs.iter().find(|(k, _)| k.as_str() == key).map(|(_, value)| value),
+        }
+    }
+
+    fn iter(self) -> impl Iterator<Item = (&'a str, &'a Value)> {
+        let (map, pairs) = match self {
+            Layer::Map(map) => (Some(map), None),
+            Layer::Pairs(pairs) => (None, Some(pairs)),
+        };
+        let map = map.into_iter().flatten().map(|(key, value)| (key.as_str(), value));
+        map.chain(pairs.into_iter().flatten().map(|(key, value)| (key.as_str(), value)))
+    }
+}
+
+/// Read‑only view of a record's fields, kept as the layers the [`Logger`](crate::Logger)
+/// collected them from (base fields first, per‑call fields last).  A key set in several
+/// layers takes the value of the last one, exactly as if the layers had been merged.
+///
+/// Formatters get one in [`Formatter::format_fields_into`](crate::Formatter::format_fields_into);
+/// a single map converts with `Fields::from`.
+///
+/// ```
+/// use cappie::Fields;
+/// use serde_json::{json, Map};
+///
+/// let mut map = Map::new();
+/// map.insert("user".to_string(), json!("ana"));
+/// let fields = Fields::from(&map);
+/// assert_eq!(fields.get("user"), Some(&json!("ana")));
+/// assert_eq!(fields.iter().count(), 1);
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct Fields<'a> {
+    layers: [Option<Layer<'a>>; MAX_LAYERS],
+}
+
+impl<'a> Fields<'a> {
+    /// Add `layer` on top of the existing ones; empty maps are skipped, so the layers
+    /// always fill the array from the front.
+    pub(crate) fn push(&mut self, layer: &'a Map<String, Value>) {
+        if !layer.is_empty() {
+            self.push_layer(Layer::Map(layer));
+        }
+    }
+
+    /// [`push`](Self::push) for pairs with static or owned keys, each key at most once.
+    pub(crate) fn push_pairs(&mut self, layer: &'a [(Key, Value)]) {
+        if !layer.is_empty() {
+            self.push_layer(Layer::Pairs(layer));
+        }
+    }
+
+    fn push_layer(&mut self, layer: Layer<'a>) {
+        let slot = self.layers.iter_mut().find(|slot| slot.is_none()).expect("too many field layers");
+        *slot = Some(layer);
+    }
+
+    fn layers(&self) -> impl DoubleEndedIterator<Item = Layer<'a>> + '_ {
+        self.layers.iter().flatten().copied()
+    }
+
+    /// Value of `key` in the topmost layer that has it.
+    pub fn get(&self, key: &str) -> Option<&'a Value> {
+        self.layers().rev().find_map(|layer| layer.get(key))
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Every key once, with its winning value.  Keys come in the order they first appear
+    /// in, going from the bottom layer up; within a layer, in map order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, &'a Value)> + '_ {
+        let layers = &self.layers;
+        layers.iter().flatten().enumerate().flat_map(move |(i, &layer)| {
+            let (below, above) = (&layers[..i], &layers[i + 1..]);
+            layer.iter().filter_map(move |(key, value)| {
+                if below.iter().flatten().any(|layer| layer.get(key).is_some()) {
+                    return None;
+                }
+                let value = above.iter().flatten().rev().find_map(|layer| layer.get(key)).unwrap_or(value);
+                Some((key, value))
+            })
+        })
+    }
+
+    /// Like [`iter`](Self::iter), sorted by key: the order of a merged `serde_json::Map`,
+    /// which the built‑in formatters keep whether or not the layers were merged.
+    pub fn sorted(&self) -> Vec<(&'a str, &'a Value)> {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers[0].is_none()
+    }
+
+    /// The fields as one map: borrowed if there is a single map layer, merged otherwise.
+    pub fn to_map(&self) -> Cow<'a, Map<String, Value>> {
+        let mut layers = self.layers().peekable();
+        let mut map = match layers.peek() {
+            Some(&Layer::Map(bottom)) => {
+                layers.next();
+                Cow::Borrowed(bottom)
+            }
+            _ => Cow::Owned(Map::new()),
+        };
+        for layer in layers {
+            let merged = map.to_mut();
+            for (key, value) in layer.iter() {
+                merged.insert(key.to_string(), value.clone());
+            }
+        }
+        map
+    }
+}
+
+impl<'a> From<&'a Map<String, Value>> for Fields<'a> {
+    fn from(map: &'a Map<String, Value>) -> Self {
+        let mut fields = Fields::default();
+        fields.push(map);
+        fields
+    }
+}
@@ -0,0 +1,93 @@
+use std::fs::File;
+use std::io;
+
+/// Exclusive advisory lock on an open file, released when dropped.  Processes that take
+/// the same lock before writing never interleave their writes; others are not stopped.
+pub(crate) struct FileLock<'a> {
+    file: &'a File,
+}
+
+impl<'a> FileLock<'a> {
+    /// Block until the lock is held.
+    pub(crate) fn acquire(file: &'a File) -> io::Result<Self> {
+        lock(file)?;
+        Ok(Self { file })
+    }
+}
+
+impl Drop for FileLock<'_> {
+    fn drop(&mut self) {
+        unlock(self.file);
+    }
+}
+
+#[cfg(unix)]
+fn lock(file: &File) -> io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    loop {
+        // SAFETY: flock on a descriptor `file` keeps open for the duration of the call.
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } == 0 {
+            return Ok(());
+        }
+        let error = io::Error::last_os_error();
+        if error.kind() != io::ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn unlock(file: &File) {
+    use std::os::unix::io::AsRawFd;
+
+    // SAFETY: as in `lock`.  Closing the file would release the lock as well.
+    unsafe {
+        libc::flock(file.as_raw_fd(), libc::LOCK_UN);
+    }
+}
+
+/// Windows file locks are mandatory, so the lock is taken on the last byte of the largest
+/// possible file, which no record will ever be written to.
+#[cfg(windows)]
+fn lock(file: &File) -> io::Result<()> {
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::Storage::FileSystem::{LockFileEx, LOCKFILE_EXCLUSIVE_LOCK};
+
+    let mut overlapped = last_byte();
+    // SAFETY: the handle stays open for the call; `overlapped` outlives it and, as the
+    // handle is not opened for overlapped I/O, the call completes before returning.
+    if unsafe { LockFileEx(file.as_raw_handle() as _, LOCKFILE_EXCLUSIVE_LOCK, 0, 1, 0, &mut overlapped) } == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn unlock(file: &File) {
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::Storage::FileSystem::UnlockFileEx;
+
+    let mut overlapped = last_byte();
+    // SAFETY: as in `lock`.
+    unsafe {
+        UnlockFileEx(file.as_raw_handle() as _, 0, 1, 0, &mut overlapped);
+    }
+}
+
+#[cfg(windows)]
+fn last_byte() -> windows_sys::Win32::System::IO::OVERLAPPED {
+    // SAFETY: OVERLAPPED is a plain C struct for which all zeroes is valid.
+    let mut overlapped: windows_sys::Win32::System::IO::OVERLAPPED = unsafe { std::mem::zeroed() };
+    overlapped.Anonymous.Anonymous.Offset = u32::MAX;
+    overlapped.Anonymous.Anonymous.OffsetHigh = u32::MAX;
+    overlapped
+}
+
+#[cfg(not(any(unix, windows)))]
+fn lock(_file: &File) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn unlock(_file: &File) {}
@@ -0,0 +1,90 @@
+//! Process‑wide registry of outputs that hold records in memory, so they can be drained when
+//! the process is going down abnormally.
+
+use std::sync::{Arc, Mutex, MutexGuard, Once, TryLockError, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Something that keeps records in memory and can push them out on demand.
+pub(crate) trait Flush: Send + Sync {
+    fn flush_now(&self);
+}
+
+static REGISTRY: Mutex<Vec<Weak<dyn Flush>>> = Mutex::new(Vec::new());
+static INSTALL: Once = Once::new();
+
+/// How long crash‑time flushing waits for a lock held by another thread before giving up on
+/// that output.
+const LOCK_PATIENCE: Duration = Duration::from_millis(50);
+
+/// Add a buffered output to the registry.  Only a weak reference is kept; dropped outputs
+/// disappear from the registry on the next registration.
+pub(crate) fn register(target: &Arc<dyn Flush>) {
+    if let Some(mut registry) = lock_briefly(&REGISTRY) {
+        registry.retain(|weak| weak.strong_count() > 0);
+        registry.push(Arc::downgrade(target));
+    }
+}
+
+/// Lock `mutex`, but give up after a short while instead of blocking forever.  A panic can
+/// fire while the panicking thread itself holds an output's lock, and the panic hook runs on
+/// that same thread – a plain `lock()` would deadlock there.
+pub(crate) fn lock_briefly<T>(mutex: &Mutex<T>) -> Option<MutexGuard<'_, T>> {
+    let deadline = Instant::now() + LOCK_PATIENCE;
+    loop {
+        match mutex.try_lock() {
+            Ok(guard) => return Some(guard),
+            Err(TryLockError::Poisoned(e)) => return Some(e.into_inner()),
+            Err(TryLockError::WouldBlock) if Instant::now() < deadline => thread::yield_now(),
+            Err(TryLockError::WouldBlock) => return None,
+        }
+    }
+}
+
+/// Flush every live buffered output in the process (block‑buffered stdout, memory‑mapped
+/// files, …).  Outputs whose lock cannot be taken within a few milliseconds are skipped, so
+/// this is safe to call from a panic hook.
+pub fn flush_all() {
+    let targets: Vec<Arc<dyn Flush>> = match lock_briefly(&REGISTRY) {
+        Some(registry) => registry.iter().filter_map(Weak::upgrade).collect(),
+        None => return,
+    };
+    for target in targets {
+        target.flush_now();
+    }
+}
+
+/// Make sure buffered records reach their destination when the process dies early:
+///
+/// * a panic hook calls [`flush_all`] and then the previously installed hook.  The hook also
+///   runs with `panic = "abort"`, before the process aborts;
+/// * on Unix an `atexit` handler calls [`flush_all`], covering `std::process::exit` from
+///   any thread, where destructors do not run.
+///
+/// Installing more than once has no further effect.  Signals that kill the process outright
+/// (`SIGKILL`, a segfault) cannot be intercepted.
+///
+/// ```
+/// cappie::install_crash_handlers();
+/// ```
+pub fn install_crash_handlers() {
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            flush_all();
+            previous(info);
+        }));
+
+        #[cfg(unix)]
+        {
+            extern "C" fn at_exit() {
+                flush_all();
+            }
+            // SAFETY: `at_exit` is a plain `extern "C"` function with the signature `atexit`
+            // expects.  A failed registration only means the exit path is not covered.
+            unsafe {
+                libc::atexit(at_exit);
+            }
+        }
+    });
+}
@@ -0,0 +1,302 @@
+use crate::error::BuildError;
+use crate::formatter::{format_record_into, Formatter, PrettyFormatter};
+use crate::output::{Output, Record, StderrOutput};
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+use std::sync::Arc;
+
+thread_local! {
+    static BUFFER: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+    /// Formats of the records being delivered, see [`deliver_record`].
+    static CACHE: RefCell<Cache> = const { RefCell::new(Cache { current: None, next: 0, entries: Vec::new() }) };
+}
+
+struct Cache {
+    /// Sequence number of the record being delivered on this thread.
+    current: Option<u64>,
+    next: u64,
+    entries: Vec<CacheEntry>,
+}
+
+/// One shared formatter's rendering of one record, identified by the formatter's address
+/// and the record's sequence number (records logged from within an output get their own).
+struct CacheEntry {
+    formatter: usize,
+    record: u64,
+    bytes: Rc<Vec<u8>>,
+}
+
+/// Buffers larger than this are not kept for reuse.
+const MAX_RETAINED_BUFFER: usize = 64 * 1024;
+
+/// Gives an output a formatter of its own, so one logger can write different formats to
+/// different places.
+///
+/// Records arrive with their fields already merged and processed (key policy, limits,
+/// record ids) by the logger; only the final formatting step is repeated, with this
+/// output's formatter.  Text passed to [`write`](Output::write) is forwarded unchanged.
+///
+/// Outputs created with [`shared`](Self::shared) from the same formatter format each record
+/// once between them when they sit in the same [`MultiOutput`](crate::MultiOutput) or
+/// logger; each distinct formatter runs only when an output needs it.
+///
+/// ```
+/// use cappie::{CaptureOutput, Logger, MultiOutput, PrettyFormatter};
+/// use cappie::output::FormattedOutput;
+///
+/// let json = CaptureOutput::new();
+/// let pretty = CaptureOutput::new();
+/// let output = MultiOutput::new()
+///     .add_output(Box::new(json.clone()))
+///     .add_output(Box::new(FormattedOutput::new(
+///         Box::new(PrettyFormatter::new().with_no_colors()),
+///         Box::new(pretty.clone()),
+///     )));
+/// let log = Logger::new("app").with_output(Box::new(output));
+/// log.info("ready");
+///
+/// assert!(json.contents().starts_with('{'));
+/// assert!(pretty.contents().contains("(app) INFO: ready"));
+/// ```
+pub struct FormattedOutput {
+    formatter: Arc<dyn Formatter>,
+    inner: Box<dyn Output>,
+}
+
+impl FormattedOutput {
+    pub fn new(formatter: Box<dyn Formatter>, inner: Box<dyn Output>) -> Self {
+        Self::shared(Arc::from(formatter), inner)
+    }
+
+    /// Use a formatter shared with other outputs, so records are formatted once for all
+    /// of them.
+    ///
+    /// ```
+    /// use cappie::{FileOutput, Formatter, LogfmtFormatter, MultiOutput};
+    /// use cappie::output::FormattedOutput;
+    /// use std::sync::Arc;
+    ///
+    /// let logfmt: Arc<dyn Formatter> = Arc::new(LogfmtFormatter::new());
+    /// let output = MultiOutput::new()
+    ///     .add_output(Box::new(FormattedOutput::shared(logfmt.clone(), Box::new(FileOutput::new("a.log")))))
+    ///     .add_output(Box::new(FormattedOutput::shared(logfmt, Box::new(FileOutput::new("b.log")))));
+    /// ```
+    pub fn shared(formatter: Arc<dyn Formatter>, inner: Box<dyn Output>) -> Self {
+        Self { formatter, inner }
+    }
+
+    /// Reformat `record` and hand the result to `f`.
+    fn reformat<R>(&self, record: &Record<'_>, f: impl FnOnce(&Record<'_>) -> R) -> R {
+        let with = |formatted: &[u8]| {
+            f(&Record {
+                formatted,
+                binary: self.formatter.is_binary(),
+                ..*record
+            })
+        };
+        if let Some(bytes) = self.cached(record) {
+            return with(&bytes);
+        }
+        let format = |buf: &mut Vec<u8>| {
+            format_record_into(&*self.formatter, buf, record.level, record.tags, record.msg, record.fields, record.timestamp, record.name);
+            with(buf)
+        };
+        // A nested FormattedOutput finds the buffer in use and gets a fresh one.
+        BUFFER.with(|cell| match cell.try_borrow_mut() {
+            Ok(mut buf) => {
+                buf.clear();
+                let result = format(&mut buf);
+                if buf.capacity() > MAX_RETAINED_BUFFER {
+                    *buf = Vec::new();
+                }
+                result
+            }
+            Err(_) => format(&mut Vec::new()),
+        })
+    }
+
+    /// This formatter's rendering of `record` from the cache, formatting and storing it if
+    /// it is not there yet.  `None` for a formatter no other output shares, which formats
+    /// into the reused per‑thread buffer instead, and outside [`deliver_record`].
+    fn cached(&self, record: &Record<'_>) -> Option<Rc<Vec<u8>>> {
+        if Arc::strong_count(&self.formatter) == 1 {
+            return None;
+        }
+        let formatter = Arc::as_ptr(&self.formatter) as *const () as usize;
+        let (seq, hit) = CACHE.with(|cache| {
+            let cache = cache.borrow();
+            let seq = cache.current?;
+            let hit = cache.entries.iter().find(|e| e.formatter == formatter && e.record == seq);
+            Some((seq, hit.map(|e| e.bytes.clone())))
+        })?;
+        if hit.is_some() {
+            return hit;
+        }
+        let mut bytes = Vec::new();
+        format_record_into(&*self.formatter, &mut bytes, record.level, record.tags, record.msg, record.fields, record.timestamp, record.name);
+        let bytes = Rc::new(bytes);
+        CACHE.with(|cache| cache.borrow_mut().entries.push(CacheEntry { formatter, record: seq, bytes: bytes.clone() }));
+        Some(bytes)
+    }
+}
+
+/// Run `f` – the delivery of a new record – under a sequence number of its own, so
+/// [`FormattedOutput`]s sharing a formatter format it once.  A record logged from within
+/// `f` gets a new number; its formats are dropped when its delivery ends.
+pub(crate) fn deliver_record<R>(f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<u64>, u64);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            // Runs even if an output panics, so stale entries are never matched later.
+            let Restore(previous, seq) = *self;
+            CACHE.with(|cache| {
+                let mut cache = cache.borrow_mut();
+                cache.entries.retain(|e| e.record != seq);
+                cache.current = previous;
+            });
+        }
+    }
+
+    let restore = CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let seq = cache.next;
+        cache.next += 1;
+        Restore(cache.current.replace(seq), seq)
+    });
+    let result = f();
+    drop(restore);
+    result
+}
+
+/// [`deliver_record`] for an output that fans one record out, such as
+/// [`MultiOutput`](crate::MultiOutput): within a delivery the record keeps its number.
+pub(crate) fn with_format_cache<R>(f: impl FnOnce() -> R) -> R {
+    if CACHE.with(|cache| cache.borrow().current.is_some()) {
+        f()
+    } else {
+        deliver_record(f)
+    }
+}
+
+impl Output for FormattedOutput {
+    fn write(&self, message: &str) {
+        self.inner.write(message);
+    }
+
+    fn write_bytes(&self, bytes: &[u8]) {
+        self.inner.write_bytes(bytes);
+    }
+
+    fn write_record(&self, record: &Record<'_>) {
+        self.reformat(record, |record| self.inner.write_record(record));
+    }
+
+    fn try_write_record(&self, record: &Record<'_>) -> io::Result<()> {
+        self.reformat(record, |record| self.inner.try_write_record(record))
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+
+    fn try_flush(&self) -> io::Result<()> {
+        self.inner.try_flush()
+    }
+
+    fn validate(&self) -> Result<(), BuildError> {
+        self.formatter.validate()?;
+        self.inner.validate()
+    }
+
+    fn describe(&self) -> String {
+        format!("FormattedOutput({}, {})", self.formatter.describe(), self.inner.describe())
+    }
+}
+
+/// The common deployment shape in one output: every record goes to a machine‑readable
+/// sink as the logger formatted it (JSON by default) and, as a pretty line, to stderr for
+/// the human watching.
+///
+/// The record's fields are merged and processed once and the machine format is produced
+/// once by the logger; only the pretty line is formatted on top.  Change the human side
+/// with [`with_human_formatter`](Self::with_human_formatter) and
+/// [`with_human_output`](Self::with_human_output).
+///
+/// ```no_run
+/// use cappie::{FileOutput, Logger};
+/// use cappie::output::DualOutput;
+///
+/// let log = Logger::new("api")
+///     .with_output(Box::new(DualOutput::new(Box::new(FileOutput::new("api.jsonl")))));
+/// log.info("listening"); // JSON line in api.jsonl, pretty line on stderr
+/// ```
+pub struct DualOutput {
+    machine: Box<dyn Output>,
+    human: FormattedOutput,
+}
+
+impl DualOutput {
+    pub fn new(machine: Box<dyn Output>) -> Self {
+        Self {
+            machine,
+            human: FormattedOutput::new(Box::new(PrettyFormatter::new()), Box::new(StderrOutput)),
+        }
+    }
+
+    /// Defaults to [`PrettyFormatter`].
+    pub fn with_human_formatter(mut self, formatter: Box<dyn Formatter>) -> Self {
+        self.human.formatter = Arc::from(formatter);
+        self
+    }
+
+    /// Defaults to [`StderrOutput`].
+    pub fn with_human_output(mut self, output: Box<dyn Output>) -> Self {
+        self.human.inner = output;
+        self
+    }
+}
+
+impl Output for DualOutput {
+    fn write(&self, message: &str) {
+        self.machine.write(message);
+        self.human.write(message);
+    }
+
+    fn write_bytes(&self, bytes: &[u8]) {
+        self.machine.write_bytes(bytes);
+    }
+
+    fn write_record(&self, record: &Record<'_>) {
+        self.machine.write_record(record);
+        self.human.write_record(record);
+    }
+
+    /// Reports the machine side's result; the human line is best effort.
+    fn try_write_record(&self, record: &Record<'_>) -> io::Result<()> {
+        let result = self.machine.try_write_record(record);
+        self.human.write_record(record);
+        result
+    }
+
+    fn flush(&self) {
+        self.machine.flush();
+        self.human.flush();
+    }
+
+    /// Reports the machine side's result.
+    fn try_flush(&self) -> io::Result<()> {
+        let result = self.machine.try_flush();
+        self.human.flush();
+        result
+    }
+
+    fn validate(&self) -> Result<(), BuildError> {
+        self.machine.validate()?;
+        self.human.validate()
+    }
+
+    fn describe(&self) -> String {
+        format!("DualOutput({}, {})", self.machine.describe(), self.human.describe())
+    }
+}
@@ -1,7 +1,14 @@
+use crate::fields::Fields;
 use crate::level::Level;
-use chrono::{DateTime, Utc};
+use crate::theme::Theme;
+use crate::time_format::{checked_pattern, TimeFormat};
+use crate::console::{self, Stream};
+use crate::error::BuildError;
+use crate::timestamp::{self, Timestamp};
 use serde_json::{Map, Value};
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::io::Write;
 
 /// Converts a log record as emitted by [`Logger`] into its **final textual form** that gets
 /// written by an [`Output`].  The trait is intentionally minimal: implement the single
@@ -14,16 +21,102 @@ use std::collections::HashMap;
 /// * `level`     – severity of the message
 /// * `msg`       – human‑readable log message
 /// * `fields`    – structured key/value pairs attached to the event
-/// * `timestamp` – wall‑clock time of the call (UTC)
+/// * `timestamp` – wall‑clock time of the call (UTC), see [`Timestamp`]
 /// * `name`      – hierarchical logger name (`frontend.http` etc.)
 pub trait Formatter: Send + Sync {
-    fn format(&self, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: DateTime<Utc>, name: &str) -> String;
+    fn format(&self, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) -> String;
+    
+    /// Append the formatted record to `buf` instead of returning a fresh `String`.  The
+    /// [`Logger`](crate::Logger) calls this with a reused per‑thread buffer, so formatters
+    /// that override it avoid one allocation per record.  The default delegates to
+    /// [`format`](Self::format).
+    fn format_into(&self, buf: &mut Vec<u8>, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) {
+        buf.extend_from_slice(self.format(level, msg, fields, timestamp, name).as_bytes());
+    }
+    
+    /// Append a record carrying [tags](crate::Logger::log_tagged).  The default adds them to
+    /// the fields as a `tags` array and calls [`format_into`](Self::format_into); formatters
+    /// with a better place for them override it.  `tags` is never empty.
+    #[allow(clippy::too_many_arguments)]
+    fn format_tagged_into(&self, buf: &mut Vec<u8>, level: Level, tags: &[&str], msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) {
+        let mut fields = fields.clone();
+        fields.insert("tags".to_string(), Value::from(tags));
+        self.format_into(buf, level, msg, &fields, timestamp, name);
+    }
+    
+    /// Append a record whose fields are still split into the [layers](Fields) the logger
+    /// collected them from.  This is what the [`Logger`](crate::Logger) calls when no output
+    /// needs the merged fields, so formatters that read the layers directly save merging
+    /// them per record.  The default merges them and calls
+    /// [`format_tagged_into`](Self::format_tagged_into) or [`format_into`](Self::format_into);
+    /// `tags` may be empty.
+    #[allow(clippy::too_many_arguments)]
+    fn format_fields_into(&self, buf: &mut Vec<u8>, level: Level, tags: &[&str], msg: &str, fields: Fields<'_>, timestamp: Timestamp, name: &str) {
+        let fields = fields.to_map();
+        if tags.is_empty() {
+            self.format_into(buf, level, msg, &fields, timestamp, name);
+        } else {
+            self.format_tagged_into(buf, level, tags, msg, &fields, timestamp, name);
+        }
+    }
+    
+    /// Whether [`format_into`](Self::format_into) produces binary frames rather than a line
+    /// of text.  Binary records are handed to [`Output::write_bytes`](crate::Output::write_bytes)
+    /// untouched (no newline, no UTF‑8 conversion); text records go to `Output::write`.
+    fn is_binary(&self) -> bool {
+        false
+    }
+    
+    /// Check the configuration, used by [`LoggerBuilder::build`](crate::LoggerBuilder::build).
+    /// The default accepts everything.
+    fn validate(&self) -> Result<(), BuildError> {
+        Ok(())
+    }
+    
+    /// Short description for [`Logger::log_startup_banner`](crate::Logger::log_startup_banner).
+    /// The default is the type name without its module path.
+    fn describe(&self) -> String {
+        short_type_name(std::any::type_name::<Self>())
+    }
+}
+
+/// `cappie::output::FileOutput` → `FileOutput`; generic arguments are dropped.
+pub(crate) fn short_type_name(name: &str) -> String {
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name).to_string()
+}
+
+fn validate_time_format(format: &str) -> Result<(), BuildError> {
+    TimeFormat::from(format).validate()
+}
+
+/// [`Formatter::format_tagged_into`] for possibly empty `tags`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn format_record_into<F: Formatter + ?Sized>(formatter: &F, buf: &mut Vec<u8>, level: Level, tags: &[&str], msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) {
+    if tags.is_empty() {
+        formatter.format_into(buf, level, msg, fields, timestamp, name);
+    } else {
+        formatter.format_tagged_into(buf, level, tags, msg, fields, timestamp, name);
+    }
+}
+
+/// Runs `format_into` on a fresh buffer; used by the built‑in formatters to implement
+/// [`Formatter::format`] in terms of their buffer‑based code path.
+fn format_to_string<F: Formatter + ?Sized>(formatter: &F, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) -> String {
+    let mut buf = Vec::new();
+    formatter.format_into(&mut buf, level, msg, fields, timestamp, name);
+    String::from_utf8(buf).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
 }
 
 /// Serialises a record to **newline‑delimited JSON (ND‑JSON)** – perfectly suited for
 /// machine ingestion.  All user‑supplied fields are flattened into the top‑level object so
 /// they can be queried without additional object navigation.
 ///
+/// With the `fast-json` cargo feature the record is streamed straight into the output
+/// buffer (numbers via `itoa`/`ryu`) instead of going through a temporary `Map`.  The
+/// output is the same JSON, except that `level`, `time`, `name` and `msg` always come first
+/// rather than being sorted in among the fields.
+///
 /// Example output (pretty‑printed for readability):
 /// ```jsonc
 /// {
@@ -38,22 +131,55 @@ pub trait Formatter: Send + Sync {
 pub struct JsonFormatter;
 
 impl Formatter for JsonFormatter {
-    fn format(&self, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: DateTime<Utc>, name: &str) -> String {
-        let mut log_entry = Map::new();
-        
-        log_entry.insert("level".to_string(), Value::Number(level.value().into()));
-        log_entry.insert("time".to_string(), Value::String(timestamp.to_rfc3339()));
-        log_entry.insert("name".to_string(), Value::String(name.to_string()));
-        log_entry.insert("msg".to_string(), Value::String(msg.to_string()));
-        
-        for (k, v) in fields {
-            log_entry.insert(k.clone(), v.clone());
+    fn format(&self, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) -> String {
+        format_to_string(self, level, msg, fields, timestamp, name)
+    }
+    
+    fn format_into(&self, buf: &mut Vec<u8>, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) {
+        write_json(buf, level, msg, Fields::from(fields), timestamp, name);
+    }
+    
+    fn format_fields_into(&self, buf: &mut Vec<u8>, level: Level, tags: &[&str], msg: &str, fields: Fields<'_>, timestamp: Timestamp, name: &str) {
+        if tags.is_empty() {
+            write_json(buf, level, msg, fields, timestamp, name);
+        } else {
+            self.format_tagged_into(buf, level, tags, msg, &fields.to_map(), timestamp, name);
         }
-        
-        serde_json::to_string(&log_entry).unwrap_or_default()
     }
 }
 
+#[cfg(feature = "fast-json")]
+fn write_json(buf: &mut Vec<u8>, level: Level, msg: &str, fields: Fields<'_>, timestamp: Timestamp, name: &str) {
+    crate::fast_json::write_record(buf, level, msg, fields, timestamp, name);
+}
+
+#[cfg(not(feature = "fast-json"))]
+fn write_json(buf: &mut Vec<u8>, level: Level, msg: &str, fields: Fields<'_>, timestamp: Timestamp, name: &str) {
+    use serde::ser::{SerializeMap, Serializer};
+    
+    // Streamed in the key order of a merged `Map`, without building one.  A field named
+    // like one of the fixed keys replaces its value.
+    let mut keys: Vec<&str> = fields.iter().map(|(key, _)| key).collect();
+    keys.extend(["level", "time", "name", "msg"].into_iter().filter(|key| !fields.contains_key(key)));
+    keys.sort_unstable();
+    
+    let time = timestamp::rfc3339(&timestamp);
+    let mut serializer = serde_json::Serializer::new(buf);
+    let Ok(mut map) = serializer.serialize_map(Some(keys.len())) else {
+        return;
+    };
+    for key in keys {
+        let _ = match (fields.get(key), key) {
+            (Some(value), _) => map.serialize_entry(key, value),
+            (None, "level") => map.serialize_entry(key, &level.value()),
+            (None, "time") => map.serialize_entry(key, &time),
+            (None, "name") => map.serialize_entry(key, name),
+            (None, _) => map.serialize_entry(key, msg),
+        };
+    }
+    let _ = map.end();
+}
+
 /// Defines the position of different components in the log output
 #[derive(Debug, Clone, PartialEq)]
 #[derive(Hash)]
@@ -73,8 +199,37 @@ pub enum ComponentPosition {
     End,
 }
 
+impl ComponentPosition {
+    /// Place of the position in the rendering order.
+    fn rank(&self) -> u8 {
+        match self {
+            ComponentPosition::Start => 0,
+            ComponentPosition::AfterTime => 1,
+            ComponentPosition::AfterName => 2,
+            ComponentPosition::AfterLevel => 3,
+            ComponentPosition::AfterMessage => 4,
+            ComponentPosition::End => 5,
+        }
+    }
+}
+
 /// Represents a template token that can be positioned and styled
+///
+/// New settings may be added, so components are built with [`new`](Self::new) and the
+/// `with_*` methods rather than a struct literal:
+///
+/// ```
+/// use cappie::{ComponentPosition, ComponentType, FlexibleFormatter, TemplateComponent};
+///
+/// let mut formatter = FlexibleFormatter::new();
+/// formatter.components.push(
+///     TemplateComponent::new(ComponentType::CustomText("pid=42".to_string()), ComponentPosition::End)
+///         .with_prefix(" ")
+///         .with_order(-1),
+/// );
+/// ```
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct TemplateComponent {
     /// The type of component
     pub component_type: ComponentType,
@@ -86,6 +241,83 @@ pub struct TemplateComponent {
     pub prefix: Option<String>,
     /// Optional suffix (e.g., "]" for timestamp)
     pub suffix: Option<String>,
+    /// Rank among components with the same position: lower values render first, equal
+    /// values keep the order in which the components were added.  Defaults to `0`.
+    pub order: i32,
+    /// See [`with_width`](Self::with_width).
+    pub(crate) width: Option<usize>,
+    /// See [`with_align`](Self::with_align).
+    pub(crate) align: Align,
+    /// See [`with_truncate`](Self::with_truncate).
+    pub(crate) truncate: Option<usize>,
+}
+
+impl TemplateComponent {
+    /// A component without color, prefix or suffix, at order `0`.
+    pub fn new(component_type: ComponentType, position: ComponentPosition) -> Self {
+        Self {
+            component_type,
+            position,
+            color: None,
+            prefix: None,
+            suffix: None,
+            order: 0,
+            width: None,
+            align: Align::Left,
+            truncate: None,
+        }
+    }
+    
+    pub fn with_color(mut self, color: &str) -> Self {
+        self.color = Some(color.to_string());
+        self
+    }
+    
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = Some(prefix.to_string());
+        self
+    }
+    
+    pub fn with_suffix(mut self, suffix: &str) -> Self {
+        self.suffix = Some(suffix.to_string());
+        self
+    }
+    
+    /// See [`order`](Self::order).
+    pub fn with_order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+    
+    /// Pad to at least `width` columns with spaces, for fixed columns.  Prefix and suffix
+    /// are not counted.
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+    
+    /// Side content narrower than the [width](Self::with_width) is aligned to (default
+    /// [`Align::Left`]).
+    pub fn with_align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+    
+    /// Cut content to at most `max` columns, the last of them an ellipsis.
+    pub fn with_truncate(mut self, max: usize) -> Self {
+        self.truncate = Some(max);
+        self
+    }
+}
+
+/// Side a [`TemplateComponent`] narrower than its [width](TemplateComponent::with_width)
+/// is aligned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+    Center,
 }
 
 /// Types of components that can be included in log output
@@ -112,11 +344,13 @@ pub enum ComponentType {
 ///
 /// Default format: `[12:34:56] (logger) INFO: message key=value`
 /// ```rust
+/// # use cappie::FlexibleFormatter;
 /// let formatter = FlexibleFormatter::new();
 /// ```
 ///
 /// Custom format: `message INFO [12:34:56]: fields`
 /// ```rust
+/// # use cappie::{FlexibleFormatter, ComponentType, ComponentPosition};
 /// let formatter = FlexibleFormatter::new()
 ///     .clear_components()
 ///     .add_component(ComponentType::Message, ComponentPosition::Start, None, None, None)
@@ -129,65 +363,111 @@ pub struct FlexibleFormatter {
     pub time_format: String,
     pub reset_color: String,
     pub components: Vec<TemplateComponent>,
+    /// Per‑level colors for [`ComponentType::Level`] components that have no color of
+    /// their own, usually filled in by [`with_theme`](Self::with_theme).
+    pub level_colors: HashMap<Level, String>,
+    /// How the [`ComponentType::Fields`] component renders each key/value pair.
+    pub field_format: FieldFormat,
+    /// How the [`ComponentType::LoggerName`] component shortens long names.
+    pub name_abbreviation: NameAbbreviation,
+}
+
+/// Rendering of the individual pairs of a [`ComponentType::Fields`] component.
+///
+/// Each pair is rendered through [`template`](Self::template), in which `{key}` is the field
+/// name, `{value}` the plain value (strings unquoted) and `{json}` the value as JSON.
+/// Pairs are joined with [`separator`](Self::separator).
+///
+/// ```
+/// # use cappie::FlexibleFormatter;
+/// // user: "alice", attempts: 3
+/// let formatter = FlexibleFormatter::new()
+///     .with_field_template("{key}: {json}")
+///     .with_field_separator(", ");
+///
+/// // "user":"alice","attempts":3
+/// let formatter = FlexibleFormatter::new()
+///     .with_field_template("\"{key}\":{json}")
+///     .with_field_separator(",");
+/// ```
+#[derive(Debug, Clone)]
+pub struct FieldFormat {
+    pub template: String,
+    pub separator: String,
+    /// Style (ANSI escape) for the pairs of particular keys, e.g. to highlight `error`.
+    pub key_colors: HashMap<String, String>,
+}
+
+impl Default for FieldFormat {
+    fn default() -> Self {
+        Self {
+            template: "{key}={value}".to_string(),
+            separator: " ".to_string(),
+            key_colors: HashMap::new(),
+        }
+    }
+}
+
+impl FieldFormat {
+    fn render(&self, fields: Fields<'_>, reset: &str) -> String {
+        let mut out = String::new();
+        for (i, (key, value)) in fields.sorted().into_iter().enumerate() {
+            if i > 0 {
+                out.push_str(&self.separator);
+            }
+            // Without a reset sequence a color would bleed into the rest of the line.
+            let color = self.key_colors.get(key).filter(|_| !reset.is_empty());
+            if let Some(color) = color {
+                out.push_str(color);
+            }
+            
+            let mut rest = self.template.as_str();
+            while let Some(open) = rest.find('{') {
+                out.push_str(&rest[..open]);
+                rest = &rest[open..];
+                if let Some(tail) = rest.strip_prefix("{key}") {
+                    out.push_str(key);
+                    rest = tail;
+                } else if let Some(tail) = rest.strip_prefix("{value}") {
+                    out.push_str(&format_value(value));
+                    rest = tail;
+                } else if let Some(tail) = rest.strip_prefix("{json}") {
+                    out.push_str(&value.to_string());
+                    rest = tail;
+                } else {
+                    out.push('{');
+                    rest = &rest[1..];
+                }
+            }
+            out.push_str(rest);
+            
+            if color.is_some() {
+                out.push_str(reset);
+            }
+        }
+        out
+    }
 }
 
 impl Default for FlexibleFormatter {
     fn default() -> Self {
-        let mut components = Vec::new();
-        
         // Default format: [HH:mm:SS] (name) LEVEL: message fields
-        components.push(TemplateComponent {
-            component_type: ComponentType::Timestamp,
-            position: ComponentPosition::Start,
-            color: None,
-            prefix: Some("[".to_string()),
-            suffix: Some("]".to_string()),
-        });
-        
-        components.push(TemplateComponent {
-            component_type: ComponentType::LoggerName,
-            position: ComponentPosition::AfterTime,
-            color: None,
-            prefix: Some(" (".to_string()),
-            suffix: Some(")".to_string()),
-        });
-        
-        components.push(TemplateComponent {
-            component_type: ComponentType::Level,
-            position: ComponentPosition::AfterName,
-            color: None,
-            prefix: Some(" ".to_string()),
-            suffix: None,
-        });
-        
-        components.push(TemplateComponent {
-            component_type: ComponentType::CustomText(":".to_string()),
-            position: ComponentPosition::AfterLevel,
-            color: None,
-            prefix: None,
-            suffix: None,
-        });
-        
-        components.push(TemplateComponent {
-            component_type: ComponentType::Message,
-            position: ComponentPosition::AfterLevel,
-            color: None,
-            prefix: Some(" ".to_string()),
-            suffix: None,
-        });
-        
-        components.push(TemplateComponent {
-            component_type: ComponentType::Fields,
-            position: ComponentPosition::End,
-            color: None,
-            prefix: Some(" ".to_string()),
-            suffix: None,
-        });
+        let components = vec![
+            TemplateComponent::new(ComponentType::Timestamp, ComponentPosition::Start).with_prefix("[").with_suffix("]"),
+            TemplateComponent::new(ComponentType::LoggerName, ComponentPosition::AfterTime).with_prefix(" (").with_suffix(")"),
+            TemplateComponent::new(ComponentType::Level, ComponentPosition::AfterName).with_prefix(" "),
+            TemplateComponent::new(ComponentType::CustomText(":".to_string()), ComponentPosition::AfterLevel),
+            TemplateComponent::new(ComponentType::Message, ComponentPosition::AfterLevel).with_prefix(" "),
+            TemplateComponent::new(ComponentType::Fields, ComponentPosition::End).with_prefix(" "),
+        ];
         
         Self {
             time_format: "%H:%M:%S".to_string(),
             reset_color: "\x1b[0m".to_string(),
             components,
+            level_colors: HashMap::new(),
+            field_format: FieldFormat::default(),
+            name_abbreviation: NameAbbreviation::Full,
         }
     }
 }
@@ -197,12 +477,51 @@ impl FlexibleFormatter {
         Self::default()
     }
     
-    /// Set the timestamp format
-    pub fn with_time_format(mut self, format: &str) -> Self {
-        self.time_format = format.to_string();
+    /// Build the component list from a compact template string, e.g. from a config file:
+    ///
+    /// ```
+    /// # use cappie::FlexibleFormatter;
+    /// let formatter = FlexibleFormatter::from_template("[{time}] ({name}) {level|bold+red}: {msg} {fields}")
+    ///     .unwrap();
+    /// ```
+    ///
+    /// * Tokens are `{time}`, `{name}`, `{level}`, `{msg}` and `{fields}` (aliases:
+    ///   `timestamp`, `logger`, `message`), optionally followed by `|style`, where style is
+    ///   a color (`red`, `bright_blue`, `gray` …) or attribute (`bold`, `dim`, `italic`,
+    ///   `underline`, `reverse`), combined with `+`.
+    /// * After the name, `:` and a width as in `format!` makes a fixed column: `{name:12}`
+    ///   pads to 12 columns, `{level:>5}` aligns right, `{msg:.80}` cuts at 80 columns with
+    ///   an ellipsis, `{name:^12.12}` does both, centred.
+    /// * Literal text becomes the prefix of the following token, and text after the last
+    ///   token its suffix – so the decoration disappears together with an empty `{fields}`.
+    /// * `{{` and `}}` are literal braces.
+    ///
+    /// Components are rendered in template order.  The time format and reset sequence keep
+    /// their defaults.
+    pub fn from_template(template: &str) -> Result<Self, BuildError> {
+        Ok(Self {
+            components: crate::template::parse(template)?,
+            ..Self::default()
+        })
+    }
+    
+    /// Set the timestamp format.  Panics if a custom pattern contains a specifier chrono
+    /// does not know, see [`try_with_time_format`](Self::try_with_time_format).
+    #[track_caller]
+    pub fn with_time_format(mut self, format: impl Into<TimeFormat>) -> Self {
+        self.time_format = checked_pattern(format.into());
         self
     }
     
+    /// Like [`with_time_format`](Self::with_time_format), returning an error instead of
+    /// panicking.
+    pub fn try_with_time_format(mut self, format: impl Into<TimeFormat>) -> Result<Self, BuildError> {
+        let format = format.into();
+        format.validate()?;
+        self.time_format = format.pattern().to_string();
+        Ok(self)
+    }
+    
     /// Clear all components (start with empty formatter)
     pub fn clear_components(mut self) -> Self {
         self.components.clear();
@@ -218,13 +537,7 @@ impl FlexibleFormatter {
         prefix: Option<String>,
         suffix: Option<String>
     ) -> Self {
-        self.components.push(TemplateComponent {
-            component_type,
-            position,
-            color,
-            prefix,
-            suffix,
-        });
+        self.components.push(TemplateComponent { color, prefix, suffix, ..TemplateComponent::new(component_type, position) });
         self
     }
     
@@ -258,88 +571,248 @@ impl FlexibleFormatter {
         self.add_component(ComponentType::CustomText(text.to_string()), position, color, None, None)
     }
     
+    /// Template for each field pair, see [`FieldFormat`].  Default: `{key}={value}`.
+    pub fn with_field_template(mut self, template: &str) -> Self {
+        self.field_format.template = template.to_string();
+        self
+    }
+    
+    /// String between field pairs.  Default: a single space.
+    pub fn with_field_separator(mut self, separator: &str) -> Self {
+        self.field_format.separator = separator.to_string();
+        self
+    }
+    
+    /// Shorten hierarchical logger names, see [`NameAbbreviation`].
+    pub fn with_name_abbreviation(mut self, abbreviation: NameAbbreviation) -> Self {
+        self.name_abbreviation = abbreviation;
+        self
+    }
+    
+    /// Style the pair of field `key`, e.g. `"\x1b[31m"` for `error`.
+    pub fn with_field_color(mut self, key: &str, color: &str) -> Self {
+        self.field_format.key_colors.insert(key.to_string(), color.to_string());
+        self
+    }
+    
+    /// Set the [`order`](TemplateComponent::order) of the first component of type
+    /// `component` (lower renders first within its position).
+    pub fn with_order(mut self, component: ComponentType, order: i32) -> Self {
+        if let Some(c) = self.components.iter_mut().find(|c| c.component_type == component) {
+            c.order = order;
+        }
+        self
+    }
+    
+    /// Pad the first component of type `component` to at least `width` columns, e.g. the
+    /// logger name to 12 so messages line up.
+    pub fn with_width(mut self, component: ComponentType, width: usize, align: Align) -> Self {
+        if let Some(c) = self.components.iter_mut().find(|c| c.component_type == component) {
+            c.width = Some(width);
+            c.align = align;
+        }
+        self
+    }
+    
+    /// Cut the first component of type `component` to at most `max` columns, the last of
+    /// them an ellipsis.
+    pub fn with_truncate(mut self, component: ComponentType, max: usize) -> Self {
+        if let Some(c) = self.components.iter_mut().find(|c| c.component_type == component) {
+            c.truncate = Some(max);
+        }
+        self
+    }
+    
+    /// Render the first `component` directly before the first `anchor`, whatever the
+    /// positions they were added with: the component takes over the anchor's position and
+    /// order.  Does nothing if either is missing.
+    pub fn move_before(self, component: ComponentType, anchor: ComponentType) -> Self {
+        self.move_next_to(component, anchor, false)
+    }
+    
+    /// Render the first `component` directly after the first `anchor`, see
+    /// [`move_before`](Self::move_before).
+    pub fn move_after(self, component: ComponentType, anchor: ComponentType) -> Self {
+        self.move_next_to(component, anchor, true)
+    }
+    
+    fn move_next_to(mut self, component: ComponentType, anchor: ComponentType, after: bool) -> Self {
+        let from = self.components.iter().position(|c| c.component_type == component);
+        let to = self.components.iter().position(|c| c.component_type == anchor);
+        let (Some(from), Some(to)) = (from, to) else {
+            return self;
+        };
+        if from == to {
+            return self;
+        }
+        
+        let mut moved = self.components.remove(from);
+        let to = if from < to { to - 1 } else { to };
+        moved.position = self.components[to].position.clone();
+        moved.order = self.components[to].order;
+        // Same position and order as the anchor, so the stable sort keeps the two adjacent.
+        self.components.insert(if after { to + 1 } else { to }, moved);
+        self
+    }
+    
+    /// Color the level by severity and mute the timestamp and logger name according to
+    /// `theme`.  Components that were given an explicit color keep it, so a theme can be
+    /// combined with individual overrides.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.level_colors = theme_colors(theme);
+        for component in &mut self.components {
+            if component.color.is_none()
+                && matches!(component.component_type, ComponentType::Timestamp | ComponentType::LoggerName)
+            {
+                component.color = Some(theme.muted().to_string());
+            }
+        }
+        if self.reset_color.is_empty() {
+            self.reset_color = "\x1b[0m".to_string();
+        }
+        self
+    }
+    
     /// Disable all colors
     pub fn with_no_colors(mut self) -> Self {
         for component in &mut self.components {
             component.color = None;
         }
+        self.level_colors.clear();
+        self.field_format.key_colors.clear();
         self.reset_color.clear();
         self
     }
 }
 
+/// `content` cut to `max` columns, the last one `…`.  Escape sequences count for nothing
+/// and are all kept, so colors opened before the cut are still reset after it.
+fn truncate_to(content: &str, max: usize) -> Cow<'_, str> {
+    if console::visible_width(content) <= max {
+        return Cow::Borrowed(content);
+    }
+    let mut out = String::with_capacity(content.len().min(max * 4 + 16));
+    let mut kept = 0;
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            out.push(c);
+            if let Some(next) = chars.next() {
+                out.push(next);
+                if next == '[' {
+                    for c in chars.by_ref() {
+                        out.push(c);
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+            }
+        } else if kept + 1 < max {
+            out.push(c);
+            kept += 1;
+        } else if kept + 1 == max {
+            out.push('…');
+            kept += 1;
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// The non‑empty level colors of `theme`.
+fn theme_colors(theme: Theme) -> HashMap<Level, String> {
+    Level::ALL
+        .into_iter()
+        .map(|level| (level, theme.level_color(level)))
+        .filter(|(_, color)| !color.is_empty())
+        .map(|(level, color)| (level, color.to_string()))
+        .collect()
+}
+
 impl Formatter for FlexibleFormatter {
-    fn format(&self, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: DateTime<Utc>, name: &str) -> String {
-        let time_str = timestamp.format(&self.time_format).to_string();
-        let level_str = level.as_str();
-        let fields_str = if !fields.is_empty() {
-            fields.iter()
-                .map(|(k, v)| format!("{}={}", k, format_value(v)))
-                .collect::<Vec<_>>()
-                .join(" ")
-        } else {
-            String::new()
-        };
-        
-        // Group components by position
-        let mut positioned_components: HashMap<ComponentPosition, Vec<&TemplateComponent>> = HashMap::new();
-        for component in &self.components {
-            positioned_components.entry(component.position.clone()).or_insert_with(Vec::new).push(component);
+    fn format(&self, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) -> String {
+        format_to_string(self, level, msg, fields, timestamp, name)
+    }
+    
+    fn validate(&self) -> Result<(), BuildError> {
+        if self.components.is_empty() {
+            return Err(BuildError::NoComponents);
         }
+        validate_time_format(&self.time_format)
+    }
+    
+    fn format_into(&self, result: &mut Vec<u8>, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) {
+        self.format_fields_into(result, level, &[], msg, Fields::from(fields), timestamp, name);
+    }
+    
+    fn format_fields_into(&self, result: &mut Vec<u8>, level: Level, tags: &[&str], msg: &str, fields: Fields<'_>, timestamp: Timestamp, name: &str) {
+        if !tags.is_empty() {
+            return self.format_tagged_into(result, level, tags, msg, &fields.to_map(), timestamp, name);
+        }
+        let time_str = timestamp::format(&timestamp, &self.time_format).to_string();
+        let level_str = level.as_str();
+        let fields_str = self.field_format.render(fields, &self.reset_color);
+        let name = self.name_abbreviation.apply(name);
         
-        let mut result = String::new();
-        
-        // Process components in order
-        let positions = [
-            ComponentPosition::Start,
-            ComponentPosition::AfterTime,
-            ComponentPosition::AfterName,
-            ComponentPosition::AfterLevel,
-            ComponentPosition::AfterMessage,
-            ComponentPosition::End,
-        ];
+        // Render by position, then by explicit order; the sort is stable, so ties keep
+        // insertion order.
+        let mut ordered: Vec<&TemplateComponent> = self.components.iter().collect();
+        ordered.sort_by_key(|component| (component.position.rank(), component.order));
         
-        for position in &positions {
-            if let Some(components) = positioned_components.get(position) {
-                for component in components {
-                    let content = match &component.component_type {
-                        ComponentType::Timestamp => Some(time_str.as_str()),
-                        ComponentType::LoggerName => Some(name),
-                        ComponentType::Level => Some(level_str),
-                        ComponentType::Message => Some(msg),
-                        ComponentType::Fields => if !fields_str.is_empty() { Some(fields_str.as_str()) } else { None },
-                        ComponentType::CustomText(text) => Some(text.as_str()),
-                    };
-                    
-                    if let Some(content) = content {
-                        // Add prefix
-                        if let Some(ref prefix) = component.prefix {
-                            result.push_str(prefix);
-                        }
-                        
-                        // Add color
-                        if let Some(ref color) = component.color {
-                            result.push_str(color);
-                        }
-                        
-                        // Add content
-                        result.push_str(content);
-                        
-                        // Add reset color
-                        if component.color.is_some() && !self.reset_color.is_empty() {
-                            result.push_str(&self.reset_color);
-                        }
-                        
-                        // Add suffix
-                        if let Some(ref suffix) = component.suffix {
-                            result.push_str(suffix);
-                        }
-                    }
+        for component in ordered {
+            let content = match &component.component_type {
+                ComponentType::Timestamp => Some(time_str.as_str()),
+                ComponentType::LoggerName => Some(&*name),
+                ComponentType::Level => Some(level_str),
+                ComponentType::Message => Some(msg),
+                ComponentType::Fields => if !fields_str.is_empty() { Some(fields_str.as_str()) } else { None },
+                ComponentType::CustomText(text) => Some(text.as_str()),
+            };
+            
+            let color = match (&component.color, &component.component_type) {
+                (Some(color), _) => Some(color.as_str()),
+                (None, ComponentType::Level) => self.level_colors.get(&level).map(String::as_str),
+                (None, _) => None,
+            };
+            
+            if let Some(content) = content {
+                let content = match component.truncate {
+                    Some(max) => truncate_to(content, max),
+                    None => Cow::Borrowed(content),
+                };
+                let padding = component.width.map_or(0, |w| w.saturating_sub(console::visible_width(&content)));
+                let (before, after) = match component.align {
+                    Align::Left => (0, padding),
+                    Align::Right => (padding, 0),
+                    Align::Center => (padding / 2, padding - padding / 2),
+                };
+                
+                // Add prefix
+                if let Some(ref prefix) = component.prefix {
+                    result.extend_from_slice(prefix.as_bytes());
+                }
+                result.resize(result.len() + before, b' ');
+                
+                // Add color
+                if let Some(color) = color {
+                    result.extend_from_slice(color.as_bytes());
+                }
+                
+                // Add content
+                result.extend_from_slice(content.as_bytes());
+                
+                // Add reset color
+                if color.is_some() && !self.reset_color.is_empty() {
+                    result.extend_from_slice(self.reset_color.as_bytes());
+                }
+                result.resize(result.len() + after, b' ');
+                
+                // Add suffix
+                if let Some(ref suffix) = component.suffix {
+                    result.extend_from_slice(suffix.as_bytes());
                 }
             }
         }
-        
-        result
     }
 }
 
@@ -351,32 +824,55 @@ impl Formatter for FlexibleFormatter {
 /// * **Level** – colourised if the respective ANSI escape code is configured in
 ///   [`colors`](Self::colors).
 /// * **Message**.
+/// * **Tags** – in brackets after the level.
 /// * **Fields** – appended as `key=value` pairs.
 ///
 /// # Example
 /// ```text
 /// [12:34:56] (auth) INFO: login succeeded user=42
+/// [12:34:57] (billing) WARN [invoice slow-path]: retrying charge attempt=2
 /// ```
+///
+/// With [`FieldLayout::Terminal`] the fields are laid out to fit the terminal instead.
+///
+/// Messages, names, tags and fields are written as they are, so a value holding escape
+/// sequences or line breaks reaches the terminal unchanged.  For untrusted input turn on
+/// [`escaping`](Self::with_escaping).
 pub struct PrettyFormatter {
     pub time_format: String,
     pub colors: HashMap<Level, String>,
     pub reset_color: String,
+    pub layout: FieldLayout,
+    pub name_abbreviation: NameAbbreviation,
+    /// Write control characters in record content as escapes, see
+    /// [`with_escaping`](Self::with_escaping).
+    pub escaping: bool,
+}
+
+/// Where [`PrettyFormatter`] puts the `key=value` fields of a record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FieldLayout {
+    /// Directly after the message, on the same line.
+    #[default]
+    Inline,
+    /// Fit the record to the width of the terminal on stdout: fields are right‑aligned on
+    /// the message line when everything fits, and wrapped onto indented lines beneath the
+    /// message otherwise.  When stdout is not a terminal this behaves like `Inline`, so
+    /// piped output stays one record per line.
+    Terminal,
+    /// Like `Terminal`, for a fixed number of columns.
+    Width(usize),
 }
 
 impl Default for PrettyFormatter {
     fn default() -> Self {
-        let mut colors = HashMap::new();
-        colors.insert(Level::Trace, "\x1b[90m".to_string()); // Bright Black
-        colors.insert(Level::Debug, "\x1b[36m".to_string()); // Cyan
-        colors.insert(Level::Info, "\x1b[32m".to_string());  // Green
-        colors.insert(Level::Warn, "\x1b[33m".to_string());  // Yellow
-        colors.insert(Level::Error, "\x1b[31m".to_string()); // Red
-        colors.insert(Level::Fatal, "\x1b[35m".to_string()); // Magenta
-        
         Self {
             time_format: "%H:%M:%S".to_string(),
-            colors,
+            colors: theme_colors(Theme::Default),
             reset_color: "\x1b[0m".to_string(),
+            layout: FieldLayout::Inline,
+            name_abbreviation: NameAbbreviation::Full,
+            escaping: false,
         }
     }
 }
@@ -386,43 +882,252 @@ impl PrettyFormatter {
         Self::default()
     }
     
-    pub fn with_time_format(mut self, format: &str) -> Self {
-        self.time_format = format.to_string();
+    /// Panics if a custom pattern contains a specifier chrono does not know, see
+    /// [`try_with_time_format`](Self::try_with_time_format).
+    #[track_caller]
+    pub fn with_time_format(mut self, format: impl Into<TimeFormat>) -> Self {
+        self.time_format = checked_pattern(format.into());
         self
     }
     
+    /// Like [`with_time_format`](Self::with_time_format), returning an error instead of
+    /// panicking.
+    pub fn try_with_time_format(mut self, format: impl Into<TimeFormat>) -> Result<Self, BuildError> {
+        let format = format.into();
+        format.validate()?;
+        self.time_format = format.pattern().to_string();
+        Ok(self)
+    }
+    
     pub fn with_color(mut self, level: Level, color: &str) -> Self {
         self.colors.insert(level, color.to_string());
         self
     }
     
+    /// Replace the level colors with those of a named [`Theme`].
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.colors = theme_colors(theme);
+        if self.reset_color.is_empty() {
+            self.reset_color = "\x1b[0m".to_string();
+        }
+        self
+    }
+    
     pub fn with_no_colors(mut self) -> Self {
         self.colors.clear();
         self.reset_color.clear();
         self
     }
+    
+    /// Choose how fields are placed, see [`FieldLayout`].
+    pub fn with_layout(mut self, layout: FieldLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+    
+    /// Shorten hierarchical logger names, see [`NameAbbreviation`].
+    pub fn with_name_abbreviation(mut self, abbreviation: NameAbbreviation) -> Self {
+        self.name_abbreviation = abbreviation;
+        self
+    }
+    
+    /// Write control characters in the logger name, tags, message and fields as `\n`, `\r`,
+    /// `\t` or `\u{..}`, so that logged data cannot move the cursor, recolor the terminal
+    /// or forge extra lines.  The colors and the line breaks of [`FieldLayout::Terminal`]
+    /// are the formatter's own and stay.
+    ///
+    /// ```
+    /// use cappie::{Formatter, Level, PrettyFormatter};
+    ///
+    /// let formatter = PrettyFormatter::new().with_no_colors().with_escaping();
+    /// let line = formatter.format(Level::Info, "ok\n[00:00:00] (auth) INFO: forged", &Default::default(), Default::default(), "auth");
+    /// assert!(line.ends_with(r"ok\n[00:00:00] (auth) INFO: forged"));
+    /// ```
+    pub fn with_escaping(mut self) -> Self {
+        self.escaping = true;
+        self
+    }
+    
+    fn text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.escaping {
+            escape_control(text)
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+    
+    fn field_pair(&self, key: &str, value: &Value) -> String {
+        format!("{}={}", self.text(key), self.text(&format_value(value)))
+    }
+}
+
+/// `text` with control characters replaced by Rust‑style escapes.
+fn escape_control(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(char::is_control) {
+        return Cow::Borrowed(text);
+    }
+    let mut escaped = String::with_capacity(text.len() + 8);
+    for c in text.chars() {
+        match c {
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// How the text formatters shorten hierarchical logger names such as
+/// `backend.http.handlers.users`, which otherwise eat the width of the line.  Only the
+/// display changes: the JSON formatters and [`Record::name`](crate::Record::name) keep the
+/// full name.
+///
+/// ```
+/// use cappie::NameAbbreviation;
+///
+/// let name = "backend.http.handlers.users";
+/// assert_eq!(NameAbbreviation::Initials.apply(name), "b.h.h.users");
+/// assert_eq!(NameAbbreviation::Last(2).apply(name), "…handlers.users");
+/// assert_eq!(NameAbbreviation::Fit(20).apply(name), "b.h.handlers.users");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NameAbbreviation {
+    #[default]
+    Full,
+    /// Every segment but the last reduced to its first character.
+    Initials,
+    /// Only the last `n` segments, after an ellipsis.
+    Last(usize),
+    /// Segments reduced to their first character from the left, only as far as needed to
+    /// fit `n` columns; names still too long then lose their start to an ellipsis.
+    Fit(usize),
+}
+
+impl NameAbbreviation {
+    pub fn apply<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        match *self {
+            NameAbbreviation::Full => Cow::Borrowed(name),
+            NameAbbreviation::Initials => match name.rsplit_once('.') {
+                Some((parents, last)) => {
+                    let mut short: Vec<&str> = parents.split('.').map(initial).collect();
+                    short.push(last);
+                    Cow::Owned(short.join("."))
+                }
+                None => Cow::Borrowed(name),
+            },
+            NameAbbreviation::Last(n) => match name.rmatch_indices('.').nth(n.max(1) - 1) {
+                Some((dot, _)) => Cow::Owned(format!("…{}", &name[dot + 1..])),
+                None => Cow::Borrowed(name),
+            },
+            NameAbbreviation::Fit(max) => {
+                let width = |segments: &[&str]| segments.iter().map(|s| s.chars().count() + 1).sum::<usize>() - 1;
+                let mut segments: Vec<&str> = name.split('.').collect();
+                if width(&segments) <= max {
+                    return Cow::Borrowed(name);
+                }
+                for i in 0..segments.len() - 1 {
+                    segments[i] = initial(segments[i]);
+                    if width(&segments) <= max {
+                        break;
+                    }
+                }
+                let short = segments.join(".");
+                let excess = short.chars().count().saturating_sub(max);
+                if excess == 0 {
+                    return Cow::Owned(short);
+                }
+                let tail: String = short.chars().skip(excess + 1).collect();
+                Cow::Owned(if max == 0 { tail } else { format!("…{tail}") })
+            }
+        }
+    }
+}
+
+/// First character of a name segment.
+fn initial(segment: &str) -> &str {
+    segment.chars().next().map_or(segment, |c| &segment[..c.len_utf8()])
+}
+
+/// Append the `key=value` `pairs` to the record line in `buf[start..]` so that nothing
+/// exceeds `width` columns: right‑aligned on the same line if they fit, otherwise greedily
+/// wrapped onto lines indented by four spaces.
+fn write_fields_to_width(buf: &mut Vec<u8>, start: usize, pairs: &[String], width: usize) {
+    const INDENT: &str = "    ";
+    
+    let fields_width = pairs.iter().map(|p| p.chars().count()).sum::<usize>() + pairs.len() - 1;
+    let head_width = std::str::from_utf8(&buf[start..])
+        .map(console::visible_width)
+        .unwrap_or(buf.len() - start);
+    
+    if head_width + 2 + fields_width <= width {
+        buf.resize(buf.len() + width - head_width - fields_width, b' ');
+        buf.extend_from_slice(pairs.join(" ").as_bytes());
+        return;
+    }
+    
+    let mut column = width;
+    for pair in pairs {
+        let pair_width = pair.chars().count();
+        if column > INDENT.len() && column + 1 + pair_width > width {
+            buf.push(b'\n');
+            buf.extend_from_slice(INDENT.as_bytes());
+            column = INDENT.len();
+        } else {
+            buf.push(b' ');
+            column += 1;
+        }
+        buf.extend_from_slice(pair.as_bytes());
+        column += pair_width;
+    }
 }
 
 impl Formatter for PrettyFormatter {
-    fn format(&self, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: DateTime<Utc>, name: &str) -> String {
-        let time_str = timestamp.format(&self.time_format).to_string();
+    fn format(&self, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) -> String {
+        format_to_string(self, level, msg, fields, timestamp, name)
+    }
+    
+    fn validate(&self) -> Result<(), BuildError> {
+        validate_time_format(&self.time_format)
+    }
+    
+    fn format_into(&self, buf: &mut Vec<u8>, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) {
+        self.format_tagged_into(buf, level, &[], msg, fields, timestamp, name);
+    }
+    
+    fn format_tagged_into(&self, buf: &mut Vec<u8>, level: Level, tags: &[&str], msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) {
+        self.format_fields_into(buf, level, tags, msg, Fields::from(fields), timestamp, name);
+    }
+    
+    fn format_fields_into(&self, buf: &mut Vec<u8>, level: Level, tags: &[&str], msg: &str, fields: Fields<'_>, timestamp: Timestamp, name: &str) {
         let level_str = level.as_str();
         
-        let color = self.colors.get(&level).cloned().unwrap_or_default();
+        let color = self.colors.get(&level).map(String::as_str).unwrap_or_default();
         let reset = &self.reset_color;
         
-        let mut result = format!("[{}] ({}) {}{}{}: {}", 
-            time_str, name, color, level_str, reset, msg);
+        let start = buf.len();
+        let _ = write!(buf, "[{}] ({}) {}{}{}", 
+            timestamp::format(&timestamp, &self.time_format), self.text(&self.name_abbreviation.apply(name)), color, level_str, reset);
+        if !tags.is_empty() {
+            let _ = write!(buf, " [{}]", self.text(&tags.join(" ")));
+        }
+        let _ = write!(buf, ": {}", self.text(msg));
         
-        if !fields.is_empty() {
-            let fields_str = fields.iter()
-                .map(|(k, v)| format!("{}={}", k, format_value(v)))
-                .collect::<Vec<_>>()
-                .join(" ");
-            result.push_str(&format!(" {}", fields_str));
+        let width = match self.layout {
+            FieldLayout::Inline => None,
+            FieldLayout::Terminal => console::terminal_width(Stream::Stdout),
+            FieldLayout::Width(width) => Some(width),
+        };
+        if let (Some(width), false) = (width, fields.is_empty()) {
+            let pairs: Vec<String> = fields.sorted().into_iter().map(|(k, v)| self.field_pair(k, v)).collect();
+            write_fields_to_width(buf, start, &pairs, width);
+            return;
         }
         
-        result
+        for (k, v) in fields.sorted() {
+            let _ = write!(buf, " {}", self.field_pair(k, v));
+        }
     }
 }
 
@@ -0,0 +1,157 @@
+use crate::level::Level;
+use crate::level_floor::LevelFloor;
+use crate::output::AsyncStatsHandle;
+use serde_json::{Map, Value};
+use std::sync::{Arc, Weak};
+use std::thread;
+use std::time::Duration;
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_QUEUE_THRESHOLD: usize = 5_000;
+const DEFAULT_DROP_THRESHOLD: f64 = 1.0;
+
+/// Raises a logger's minimum level while its [`AsyncOutput`](crate::output::AsyncOutput)
+/// cannot keep up, and lowers it again once the pressure is gone.  Attach it with
+/// [`Logger::with_governor`](crate::Logger::with_governor).
+///
+/// Every [interval](Self::with_interval) the output's queue depth and drop rate (records
+/// dropped because the queue was full or too old, per second) are sampled:
+///
+/// * when the queue holds at least the [queue threshold](Self::with_queue_threshold)
+///   records or the drop rate reaches the [drop threshold](Self::with_drop_threshold),
+///   records below the [raised level](Self::with_raised_level) are discarded;
+/// * once the queue is at most half the queue threshold and the drop rate below half the
+///   drop threshold, the configured level applies again.
+///
+/// Each transition is reported as a `Warn` record with the fields `governor` (`raised` or
+/// `restored`), `min_level`, `queued` and `drops_per_sec`, written ahead of the next record
+/// the logger writes.
+///
+/// ```
+/// use cappie::{Governor, Level, Logger, StdoutOutput};
+/// use cappie::output::AsyncOutput;
+///
+/// let output = AsyncOutput::new(Box::new(StdoutOutput));
+/// let governor = Governor::new(output.stats_handle())
+///     .with_queue_threshold(2_000)
+///     .with_raised_level(Level::Error);
+/// let log = Logger::new("ingest")
+///     .with_output(Box::new(output))
+///     .with_governor(governor);
+/// log.info("dropped while the output is backed up");
+/// ```
+pub struct Governor {
+    stats: AsyncStatsHandle,
+    raised: Level,
+    queue_threshold: usize,
+    drop_threshold: f64,
+    interval: Duration,
+}
+
+impl Governor {
+    pub fn new(stats: AsyncStatsHandle) -> Self {
+        Self {
+            stats,
+            raised: Level::Warn,
+            queue_threshold: DEFAULT_QUEUE_THRESHOLD,
+            drop_threshold: DEFAULT_DROP_THRESHOLD,
+            interval: DEFAULT_INTERVAL,
+        }
+    }
+
+    /// Minimum level while under pressure (default `Warn`).
+    pub fn with_raised_level(mut self, level: Level) -> Self {
+        self.raised = level;
+        self
+    }
+
+    /// Queue depth at which the level is raised (default 5 000).
+    pub fn with_queue_threshold(mut self, records: usize) -> Self {
+        self.queue_threshold = records.max(1);
+        self
+    }
+
+    /// Dropped records per second at which the level is raised (default 1).
+    pub fn with_drop_threshold(mut self, per_sec: f64) -> Self {
+        self.drop_threshold = per_sec;
+        self
+    }
+
+    /// Time between two samples (default 1 second).
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Start sampling on a background thread, which ends once the returned state is
+    /// dropped.
+    pub(crate) fn start(self) -> Arc<GovernorState> {
+        let state = Arc::new(GovernorState { floor: LevelFloor::new() });
+        let weak = Arc::downgrade(&state);
+        let _ = thread::Builder::new()
+            .name("cappie-governor".to_string())
+            .spawn(move || self.run(weak));
+        state
+    }
+
+    fn run(self, state: Weak<GovernorState>) {
+        let secs = self.interval.as_secs_f64().max(f64::EPSILON);
+        let mut last_dropped = dropped(&self.stats);
+        loop {
+            thread::sleep(self.interval);
+            let Some(state) = state.upgrade() else {
+                return;
+            };
+            let stats = self.stats.get();
+            let total = stats.dropped + stats.expired;
+            let drops_per_sec = total.saturating_sub(last_dropped) as f64 / secs;
+            last_dropped = total;
+
+            let raised = state.floor().is_some();
+            if !raised && (stats.queued >= self.queue_threshold || drops_per_sec >= self.drop_threshold) {
+                state.floor.set(self.raised.value(), Transition { raised: Some(self.raised), queued: stats.queued, drops_per_sec });
+            } else if raised && stats.queued <= self.queue_threshold / 2 && drops_per_sec < self.drop_threshold / 2.0 {
+                state.floor.set(0, Transition { raised: None, queued: stats.queued, drops_per_sec });
+            }
+        }
+    }
+}
+
+fn dropped(stats: &AsyncStatsHandle) -> u64 {
+    let stats = stats.get();
+    stats.dropped + stats.expired
+}
+
+/// What a running governor shares with the loggers it is attached to.
+pub(crate) struct GovernorState {
+    /// Raised while under pressure.
+    floor: LevelFloor<Transition>,
+}
+
+struct Transition {
+    raised: Option<Level>,
+    queued: usize,
+    drops_per_sec: f64,
+}
+
+impl GovernorState {
+    pub(crate) fn floor(&self) -> Option<Level> {
+        Level::from_value(self.floor.get())
+    }
+
+    /// Message and fields of the transition to report, if one happened since the last
+    /// call.  `configured` is the logger's own level, reported once pressure is gone.
+    pub(crate) fn take_report(&self, configured: Level) -> Option<(&'static str, Map<String, Value>)> {
+        let transition = self.floor.take()?;
+        let (msg, state, min_level) = match transition.raised {
+            Some(level) => ("log level raised under load", "raised", level.max(configured)),
+            None => ("log level restored", "restored", configured),
+        };
+        let mut fields = Map::new();
+        fields.insert("governor".to_string(), Value::from(state));
+        fields.insert("min_level".to_string(), Value::from(min_level.as_str()));
+        fields.insert("queued".to_string(), Value::from(transition.queued));
+        fields.insert("drops_per_sec".to_string(), Value::from(transition.drops_per_sec));
+        Some((msg, fields))
+    }
+}
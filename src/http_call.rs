@@ -0,0 +1,257 @@
+use crate::level::Level;
+use crate::logger::{LogBuilder, Logger};
+use std::fmt::Display;
+use std::time::Instant;
+
+/// Logs one outbound HTTP call, for wrapping a client (reqwest, hyper, ureq…) so service
+/// dependencies show up in the same stream as everything else.
+///
+/// Records go to `logger`'s child `http` and carry `method` and `url`.  Query values and
+/// user info are redacted from the URL (`https://api.example.com/v1/items?token=[redacted]`),
+/// except for query keys allowed with [`with_visible_query`](Self::with_visible_query).
+///
+/// [`finish`](Self::finish) logs `http call finished` with `status`, `latency_ms` and
+/// `retries` – at `Info` for success and `Warn` for 4xx/5xx responses.
+/// [`fail`](Self::fail) logs `http call failed` at `Error` with the transport error.  A call
+/// dropped before either logs `http call aborted` at `Warn`.
+///
+/// With feature `reqwest-middleware`, `HttpCallMiddleware` does this for every call of a
+/// reqwest client.
+///
+/// ```
+/// use cappie::{CaptureOutput, HttpCallLog, Logger};
+///
+/// let capture = CaptureOutput::new();
+/// let log = Logger::new("svc").with_output(Box::new(capture.clone()));
+///
+/// let mut call = HttpCallLog::new(&log, "GET", "https://api.example.com/items?page=2&token=s3cr3t")
+///     .with_visible_query(&["page"]);
+/// call.retry(); // first attempt timed out
+/// call.finish(200);
+///
+/// let line = capture.contents();
+/// assert!(line.contains(r#""url":"https://api.example.com/items?page=2&token=[redacted]""#));
+/// assert!(line.contains(r#""retries":1"#));
+/// ```
+pub struct HttpCallLog {
+    logger: Logger,
+    method: String,
+    url: String,
+    visible_query: Vec<String>,
+    start: Instant,
+    retries: u32,
+    done: bool,
+}
+
+impl HttpCallLog {
+    pub fn new(logger: &Logger, method: &str, url: &str) -> Self {
+        Self {
+            logger: logger.child("http"),
+            method: method.to_string(),
+            url: url.to_string(),
+            visible_query: Vec::new(),
+            start: Instant::now(),
+            retries: 0,
+            done: false,
+        }
+    }
+
+    /// Query parameters whose values are logged as they are.
+    pub fn with_visible_query(mut self, keys: &[&str]) -> Self {
+        self.visible_query.extend(keys.iter().map(|k| k.to_string()));
+        self
+    }
+
+    /// Count a retry.  Logged at `Debug`; the total is reported when the call ends.
+    pub fn retry(&mut self) {
+        self.retries += 1;
+        let retries = self.retries;
+        self.log(Level::Debug, "http call retrying", |b| {
+            b.field("retries", retries);
+        });
+    }
+
+    /// Log the response status.
+    pub fn finish(mut self, status: u16) {
+        self.done = true;
+        let level = if status >= 400 { Level::Warn } else { Level::Info };
+        let (start, retries) = (self.start, self.retries);
+        self.log(level, "http call finished", |b| {
+            b.field("status", status).elapsed_since("latency_ms", start).field("retries", retries);
+        });
+    }
+
+    /// Log a call that got no response.
+    pub fn fail<E: Display>(mut self, error: E) {
+        self.done = true;
+        let (start, retries) = (self.start, self.retries);
+        self.log(Level::Error, "http call failed", |b| {
+            b.field("error", error.to_string()).elapsed_since("latency_ms", start).field("retries", retries);
+        });
+    }
+
+    fn log<F>(&self, level: Level, msg: &str, f: F)
+    where
+        F: FnOnce(&mut LogBuilder),
+    {
+        if !self.logger.enabled(level) {
+            return;
+        }
+        let url = redact_url(&self.url, &self.visible_query);
+        self.logger.log_with(level, msg, |b| {
+            b.string("method", &self.method).string("url", &url);
+            f(b);
+        });
+    }
+}
+
+impl Drop for HttpCallLog {
+    fn drop(&mut self) {
+        if !self.done {
+            let (start, retries) = (self.start, self.retries);
+            self.log(Level::Warn, "http call aborted", |b| {
+                b.elapsed_since("latency_ms", start).field("retries", retries);
+            });
+        }
+    }
+}
+
+/// `url` without user info and with query values replaced by `[redacted]`, except for the
+/// `visible` keys.  The fragment is dropped.
+fn redact_url(url: &str, visible: &[String]) -> String {
+    let url = url.split('#').next().unwrap_or_default();
+    let (base, query) = match url.split_once('?') {
+        Some((base, query)) => (base, Some(query)),
+        None => (url, None),
+    };
+
+    let mut out = String::with_capacity(url.len());
+    match base.split_once("://") {
+        Some((scheme, rest)) => {
+            let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+            out.push_str(scheme);
+            out.push_str("://");
+            out.push_str(authority.rsplit_once('@').map_or(authority, |(_, host)| host));
+            out.push_str(path);
+        }
+        None => out.push_str(base),
+    }
+
+    if let Some(query) = query {
+        out.push('?');
+        for (i, pair) in query.split('&').enumerate() {
+            if i > 0 {
+                out.push('&');
+            }
+            match pair.split_once('=') {
+                Some((key, _)) if !visible.iter().any(|v| v == key) => {
+                    out.push_str(key);
+                    out.push_str("=[redacted]");
+                }
+                _ => out.push_str(pair),
+            }
+        }
+    }
+    out
+}
+
+#[cfg(feature = "reqwest-middleware")]
+pub use middleware::HttpCallMiddleware;
+
+#[cfg(feature = "reqwest-middleware")]
+mod middleware {
+    use super::HttpCallLog;
+    use crate::logger::Logger;
+    use http::Extensions;
+    use reqwest_middleware::reqwest::{Request, Response};
+    use reqwest_middleware::{Middleware, Next};
+    use std::sync::{Arc, Mutex};
+
+    /// [`reqwest-middleware`](https://docs.rs/reqwest-middleware) middleware logging every
+    /// call a client makes with an [`HttpCallLog`].  Feature `reqwest-middleware`.
+    ///
+    /// Attach it after a retry middleware, so it sees every attempt: each call is logged
+    /// once it is over, with `method`, the redacted `url`, the last `status` (or the last
+    /// error), `latency_ms` from the first attempt and the number of `retries`.  The
+    /// call's state is kept in the request extensions, so a call made with
+    /// `execute_with_extensions` needs fresh extensions.
+    ///
+    /// ```
+    /// use cappie::{HttpCallMiddleware, Logger};
+    /// use reqwest_middleware::ClientBuilder;
+    ///
+    /// let log = Logger::new("svc");
+    /// let client = ClientBuilder::new(reqwest::Client::new())
+    ///     // .with(RetryTransientMiddleware::new_with_policy(policy))
+    ///     .with(HttpCallMiddleware::new(&log).with_visible_query(&["page"]))
+    ///     .build();
+    /// ```
+    pub struct HttpCallMiddleware {
+        logger: Logger,
+        visible_query: Vec<String>,
+    }
+
+    impl HttpCallMiddleware {
+        pub fn new(logger: &Logger) -> Self {
+            Self { logger: logger.clone(), visible_query: Vec::new() }
+        }
+
+        /// Query parameters whose values are logged as they are.
+        pub fn with_visible_query(mut self, keys: &[&str]) -> Self {
+            self.visible_query.extend(keys.iter().map(|k| k.to_string()));
+            self
+        }
+    }
+
+    /// A call in progress, shared by its attempts through the request extensions.  Logged
+    /// once the last copy is dropped, which is when the client is done with the call.
+    #[derive(Clone)]
+    struct Call(Arc<Mutex<CallState>>);
+
+    struct CallState {
+        log: Option<HttpCallLog>,
+        outcome: Option<Result<u16, String>>,
+    }
+
+    impl Drop for CallState {
+        fn drop(&mut self) {
+            let Some(log) = self.log.take() else {
+                return;
+            };
+            match self.outcome.take() {
+                Some(Ok(status)) => log.finish(status),
+                Some(Err(error)) => log.fail(error),
+                // Dropped before any attempt ended: logged as aborted.
+                None => drop(log),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Middleware for HttpCallMiddleware {
+        async fn handle(&self, req: Request, extensions: &mut Extensions, next: Next<'_>) -> reqwest_middleware::Result<Response> {
+            let call = match extensions.get::<Call>() {
+                Some(call) => {
+                    let call = call.clone();
+                    if let Some(log) = &mut call.0.lock().unwrap_or_else(|e| e.into_inner()).log {
+                        log.retry();
+                    }
+                    call
+                }
+                None => {
+                    let mut log = HttpCallLog::new(&self.logger, req.method().as_str(), req.url().as_str());
+                    log.visible_query.clone_from(&self.visible_query);
+                    let call = Call(Arc::new(Mutex::new(CallState { log: Some(log), outcome: None })));
+                    extensions.insert(call.clone());
+                    call
+                }
+            };
+            let result = next.run(req, extensions).await;
+            call.0.lock().unwrap_or_else(|e| e.into_inner()).outcome = Some(match &result {
+                Ok(response) => Ok(response.status().as_u16()),
+                Err(e) => Err(e.to_string()),
+            });
+            result
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Reading the head of an HTTP/1.1 request for the small built‑in servers (the admin
+//! endpoint and [`SseOutput`](crate::output::SseOutput)), within fixed bounds so a client
+//! cannot make them allocate without limit.
+
+use std::io::{self, BufRead, Read};
+use std::net::{Shutdown, TcpStream};
+
+/// Bytes of request line and headers read before answering 431.
+pub(crate) const MAX_HEAD_BYTES: u64 = 8 * 1024;
+/// Header lines read before answering 431.
+pub(crate) const MAX_HEADERS: usize = 64;
+
+/// Request line and the headers the servers care about.
+pub(crate) struct RequestHead {
+    pub method: String,
+    /// Path and query, as sent.
+    pub target: String,
+    /// Only the admin endpoint takes a body.
+    #[cfg_attr(not(feature = "admin"), allow(dead_code))]
+    pub content_length: usize,
+}
+
+/// Read the request line and headers, or `None` when they exceed [`MAX_HEAD_BYTES`] or
+/// [`MAX_HEADERS`]; answer those with 431 and [`close_unread`].
+pub(crate) fn read_head<R: BufRead>(reader: &mut R) -> io::Result<Option<RequestHead>> {
+    let mut head = reader.take(MAX_HEAD_BYTES);
+    let mut request_line = String::new();
+    head.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0usize;
+    let mut headers = 0;
+    loop {
+        let mut header = String::new();
+        let read = head.read_line(&mut header)?;
+        let cut_off = !header.ends_with('\n') && head.limit() == 0;
+        if !cut_off && (read == 0 || header.trim().is_empty()) {
+            break;
+        }
+        headers += 1;
+        if cut_off || headers > MAX_HEADERS {
+            return Ok(None);
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    Ok(Some(RequestHead { method, target, content_length }))
+}
+
+/// Finish a connection whose request was not read to the end.  Closing with unread input
+/// would reset the connection under the response just written, so the rest is drained,
+/// up to a bound, first.
+pub(crate) fn close_unread<R: Read>(stream: &TcpStream, reader: R) -> io::Result<()> {
+    stream.shutdown(Shutdown::Write)?;
+    let _ = io::copy(&mut reader.take(8 * MAX_HEAD_BYTES), &mut io::sink());
+    Ok(())
+}
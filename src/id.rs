@@ -0,0 +1,58 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const ENCODING: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const RANDOM_BITS: u32 = 80;
+const RANDOM_MASK: u128 = (1 << RANDOM_BITS) - 1;
+
+/// Last issued `(millis, random)` pair, used to keep IDs strictly increasing when several
+/// records are stamped within the same millisecond.
+static LAST: Mutex<(u64, u128)> = Mutex::new((0, 0));
+
+/// Generates a monotonic [ULID](https://github.com/ulid/spec): 48 bits of Unix milliseconds
+/// followed by 80 random bits, Crockford base32 encoded (26 chars).  IDs sort
+/// lexicographically in creation order, also within one millisecond.
+///
+/// The random part comes from std's per-process hash keys; that is plenty for dedup keys but
+/// **not** suitable for anything security related.
+pub(crate) fn next_ulid() -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default();
+
+    let mut last = LAST.lock().unwrap_or_else(|e| e.into_inner());
+    let (millis, random) = if now <= last.0 {
+        // Same (or a skewed, earlier) millisecond: bump the previous value instead of
+        // rolling new randomness so ordering is preserved.
+        let random = (last.1 + 1) & RANDOM_MASK;
+        let millis = if random == 0 { last.0 + 1 } else { last.0 };
+        (millis, random)
+    } else {
+        (now, random_bits())
+    };
+    *last = (millis, random);
+    drop(last);
+
+    encode(((millis as u128) << RANDOM_BITS) | random)
+}
+
+fn random_bits() -> u128 {
+    let state = RandomState::new();
+    let mut hi = state.build_hasher();
+    hi.write_u8(0);
+    let mut lo = state.build_hasher();
+    lo.write_u8(1);
+    (((hi.finish() as u128) << 64) | lo.finish() as u128) & RANDOM_MASK
+}
+
+fn encode(mut value: u128) -> String {
+    let mut out = [0u8; 26];
+    for slot in out.iter_mut().rev() {
+        *slot = ENCODING[(value & 0x1f) as usize];
+        value >>= 5;
+    }
+    out.iter().map(|&b| b as char).collect()
+}
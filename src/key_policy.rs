@@ -0,0 +1,221 @@
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+/// Upper bound on remembered offending keys in [`OnViolation::Warn`] mode; beyond it new
+/// offenders are no longer reported.
+const MAX_WARNED_KEYS: usize = 1024;
+
+/// Casing convention for field keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyCase {
+    /// `request_id`
+    Snake,
+    /// `requestId`
+    Camel,
+}
+
+/// What a [`KeyPolicy`] does with a key that breaks it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnViolation {
+    /// Rewrite the key to the convention and truncate it to the maximum length.
+    Rename,
+    /// Leave the record alone and log a `Warn` record the first time each key is seen.
+    Warn,
+}
+
+/// Naming rules for field keys, applied to every record of a logger (nested object keys
+/// included) with [`Logger::with_key_policy`](crate::Logger::with_key_policy).
+///
+/// Keys are split into words at `_`, `-`, `.`, spaces and case changes, so `userID`,
+/// `user-id` and `User.Id` all become `user_id` under [`KeyCase::Snake`].  When two keys
+/// of one object end up with the same name, the later one wins.
+///
+/// ```
+/// use cappie::{KeyPolicy, Logger};
+///
+/// let log = Logger::new("api").with_key_policy(KeyPolicy::snake_case().with_max_len(32));
+/// log.info_with("request", |b| {
+///     b.field("requestId", 7); // written as `request_id`
+/// });
+/// ```
+pub struct KeyPolicy {
+    case: Option<KeyCase>,
+    max_len: Option<usize>,
+    on_violation: OnViolation,
+    warned: Mutex<HashSet<String>>,
+}
+
+impl KeyPolicy {
+    /// A policy that only enforces what is configured with the `with_*` methods.
+    pub fn new() -> Self {
+        Self {
+            case: None,
+            max_len: None,
+            on_violation: OnViolation::Rename,
+            warned: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub fn snake_case() -> Self {
+        Self::new().with_case(KeyCase::Snake)
+    }
+
+    pub fn camel_case() -> Self {
+        Self::new().with_case(KeyCase::Camel)
+    }
+
+    pub fn with_case(mut self, case: KeyCase) -> Self {
+        self.case = Some(case);
+        self
+    }
+
+    /// Longest allowed key, in characters.
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len.max(1));
+        self
+    }
+
+    /// Rename offending keys (the default) or only warn about them.
+    pub fn on_violation(mut self, action: OnViolation) -> Self {
+        self.on_violation = action;
+        self
+    }
+
+    /// The key `key` should have been, or `None` if it already complies.
+    pub fn check(&self, key: &str) -> Option<String> {
+        let mut wanted = match self.case {
+            Some(case) => convert(key, case),
+            None => key.to_string(),
+        };
+        if let Some(max_len) = self.max_len {
+            if let Some((end, _)) = wanted.char_indices().nth(max_len) {
+                wanted.truncate(end);
+                // Don't leave a dangling separator (`http_server_`).
+                while wanted.len() > 1 && wanted.ends_with('_') {
+                    wanted.pop();
+                }
+            }
+        }
+        (wanted != key).then_some(wanted)
+    }
+
+    pub(crate) fn action(&self) -> OnViolation {
+        self.on_violation
+    }
+
+    /// Rename the offending keys of `fields`.  Returns `None` if every key complies.
+    pub(crate) fn renamed(&self, fields: &Map<String, Value>) -> Option<Map<String, Value>> {
+        if !self.violates(fields) {
+            return None;
+        }
+        Some(self.rename_map(fields.clone()))
+    }
+
+    /// Offending keys of `fields` (nested ones included) not reported before, each with
+    /// the name it should have.
+    pub(crate) fn new_violations(&self, fields: &Map<String, Value>) -> Vec<(String, String)> {
+        let mut found = Vec::new();
+        self.collect(fields, &mut found);
+        if found.is_empty() {
+            return found;
+        }
+        let mut warned = self.warned.lock().unwrap_or_else(|e| e.into_inner());
+        found.retain(|(key, _)| {
+            if warned.contains(key) || warned.len() >= MAX_WARNED_KEYS {
+                return false;
+            }
+            warned.insert(key.clone());
+            true
+        });
+        found
+    }
+
+    fn violates(&self, fields: &Map<String, Value>) -> bool {
+        fields.iter().any(|(key, value)| {
+            self.check(key).is_some() || value.as_object().is_some_and(|nested| self.violates(nested))
+        })
+    }
+
+    fn rename_map(&self, fields: Map<String, Value>) -> Map<String, Value> {
+        let mut renamed = Map::new();
+        for (key, value) in fields {
+            let value = match value {
+                Value::Object(nested) => Value::Object(self.rename_map(nested)),
+                other => other,
+            };
+            let key = self.check(&key).unwrap_or(key);
+            renamed.insert(key, value);
+        }
+        renamed
+    }
+
+    fn collect(&self, fields: &Map<String, Value>, found: &mut Vec<(String, String)>) {
+        for (key, value) in fields {
+            if let Some(wanted) = self.check(key) {
+                found.push((key.clone(), wanted));
+            }
+            if let Value::Object(nested) = value {
+                self.collect(nested, found);
+            }
+        }
+    }
+}
+
+impl Default for KeyPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn convert(key: &str, case: KeyCase) -> String {
+    let mut out = String::with_capacity(key.len());
+    for (i, word) in words(key).iter().enumerate() {
+        match case {
+            KeyCase::Snake => {
+                if i > 0 {
+                    out.push('_');
+                }
+                out.extend(word.chars().flat_map(char::to_lowercase));
+            }
+            KeyCase::Camel => {
+                let mut chars = word.chars();
+                if i > 0 {
+                    out.extend(chars.next().into_iter().flat_map(char::to_uppercase));
+                }
+                out.extend(chars.flat_map(char::to_lowercase));
+            }
+        }
+    }
+    out
+}
+
+/// `HTTPServerError2` → `HTTP`, `Server`, `Error2`; separators are dropped.
+fn words(key: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let chars: Vec<(usize, char)> = key.char_indices().collect();
+    let mut start: Option<usize> = None;
+    for (i, &(pos, c)) in chars.iter().enumerate() {
+        if matches!(c, '_' | '-' | '.' | ' ') {
+            if let Some(s) = start.take() {
+                words.push(&key[s..pos]);
+            }
+            continue;
+        }
+        if let Some(s) = start {
+            let prev = chars[i - 1].1;
+            let next_lower = chars.get(i + 1).is_some_and(|&(_, n)| n.is_lowercase());
+            // `aB` starts a word, and so does the `S` in `HTTPServer`.
+            if c.is_uppercase() && (prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower)) {
+                words.push(&key[s..pos]);
+                start = Some(pos);
+            }
+        } else {
+            start = Some(pos);
+        }
+    }
+    if let Some(s) = start {
+        words.push(&key[s..]);
+    }
+    words
+}
@@ -1,4 +1,30 @@
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
+/// Least severe level the [logging macros](crate::log) are compiled in for; calls below it
+/// compile to nothing.  Chosen at build time with the `max_level_info`/`max_level_debug`
+/// features, or `release_max_level_info`/`release_max_level_debug` to strip only release
+/// builds (without `debug_assertions`).  When several are enabled the strictest wins.
+/// The `Logger` methods are not affected.
+pub const STATIC_MIN_LEVEL: Level = static_min_level();
+
+const fn static_min_level() -> Level {
+    if cfg!(feature = "max_level_info") || (cfg!(feature = "release_max_level_info") && !cfg!(debug_assertions)) {
+        Level::Info
+    } else if cfg!(feature = "max_level_debug") || (cfg!(feature = "release_max_level_debug") && !cfg!(debug_assertions)) {
+        Level::Debug
+    } else {
+        Level::Trace
+    }
+}
+
+/// Process‑wide level override; `0` means "no override".
+static GLOBAL_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// Lowest level any logger has been configured with so far.  It only ever decreases, so it
+/// is a conservative pre‑filter for the logging macros, not an exact answer.
+static LOWEST_LEVEL: AtomicU8 = AtomicU8::new(u8::MAX);
 
 /// Logging severities roughly modelled after the [RFC 5424](https://datatracker.ietf.org/doc/html/rfc5424)
 /// syslog levels.  The numeric values (10 … 60) match the typical `TRACE ≤ DEBUG ≤ INFO`…
@@ -17,6 +43,9 @@ pub enum Level {
 }
 
 impl Level {
+    /// Every level, from least to most severe.
+    pub const ALL: [Level; 6] = [Level::Trace, Level::Debug, Level::Info, Level::Warn, Level::Error, Level::Fatal];
+    
     pub fn as_str(&self) -> &'static str {
         match self {
             Level::Trace => "TRACE",
@@ -28,6 +57,7 @@ impl Level {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Level> {
         match s.to_uppercase().as_str() {
             "TRACE" => Some(Level::Trace),
@@ -43,4 +73,83 @@ impl Level {
     pub fn value(&self) -> u8 {
         *self as u8
     }
-}
\ No newline at end of file
+    
+    /// Inverse of [`value`](Self::value).
+    pub fn from_value(value: u8) -> Option<Level> {
+        match value {
+            10 => Some(Level::Trace),
+            20 => Some(Level::Debug),
+            30 => Some(Level::Info),
+            40 => Some(Level::Warn),
+            50 => Some(Level::Error),
+            60 => Some(Level::Fatal),
+            _ => None,
+        }
+    }
+}
+
+/// Lower the minimum level of **every** logger in the process to at most `level`, or
+/// restore the per‑logger levels with `None`.  The more verbose of the two levels wins: with
+/// a global `Debug`, a logger configured at `Error` writes `Debug` records while one at
+/// `Trace` keeps writing `Trace` records.  The override only ever adds records, which makes
+/// it safe for turning on verbose output in a live process, see also
+/// [`signal::install_level_toggle`](crate::signal) (feature `signals`).
+///
+/// ```
+/// use cappie::{set_global_level, Level, Logger};
+///
+/// let quiet = Logger::new("quiet").with_level(Level::Error);
+/// let chatty = Logger::new("chatty").with_level(Level::Trace);
+/// set_global_level(Some(Level::Debug));
+/// assert!(quiet.enabled(Level::Debug) && !quiet.enabled(Level::Trace));
+/// assert!(chatty.enabled(Level::Trace));
+/// # set_global_level(None);
+/// ```
+pub fn set_global_level(level: Option<Level>) {
+    if let Some(level) = level {
+        lower_bound(level);
+    }
+    GLOBAL_LEVEL.store(level.map(|l| l.value()).unwrap_or(0), Ordering::Relaxed);
+}
+
+/// The active process‑wide override set by [`set_global_level`], if any.
+pub fn global_level() -> Option<Level> {
+    Level::from_value(GLOBAL_LEVEL.load(Ordering::Relaxed))
+}
+
+/// `level` lowered by the [global override](set_global_level), if it is more verbose.
+pub(crate) fn with_global(level: Level) -> Level {
+    global_level().map_or(level, |global| global.min(level))
+}
+
+/// Shared, runtime‑adjustable minimum level of a [`Logger`](crate::Logger).  Cloning the
+/// handle gives another view of the **same** level, so admin tooling can change verbosity
+/// of a live logger without rebuilding it.
+#[derive(Debug, Clone)]
+pub struct LevelHandle(Arc<AtomicU8>);
+
+impl LevelHandle {
+    pub fn new(level: Level) -> Self {
+        lower_bound(level);
+        Self(Arc::new(AtomicU8::new(level.value())))
+    }
+
+    pub fn get(&self) -> Level {
+        Level::from_value(self.0.load(Ordering::Relaxed)).unwrap_or(Level::Info)
+    }
+
+    pub fn set(&self, level: Level) {
+        lower_bound(level);
+        self.0.store(level.value(), Ordering::Relaxed);
+    }
+}
+
+pub(crate) fn lower_bound(level: Level) {
+    LOWEST_LEVEL.fetch_min(level.value(), Ordering::Relaxed);
+}
+
+/// Whether some logger might accept records at `level`; `false` means none will.
+#[inline]
+pub(crate) fn may_be_enabled(level: Level) -> bool {
+    level.value() >= LOWEST_LEVEL.load(Ordering::Relaxed)
+}
@@ -0,0 +1,46 @@
+//! The raised minimum level that a [`Governor`](crate::Governor) or a
+//! [`DiskSpaceGuard`](crate::DiskSpaceGuard) shares with its loggers, together with the
+//! last change of it that still has to be reported.
+
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Mutex;
+
+pub(crate) struct LevelFloor<T> {
+    /// Raised minimum level as a [`Level`](crate::Level) value, or `0` while not raised.
+    floor: AtomicU8,
+    /// Whether `transition` holds a report, so loggers can skip the lock.
+    pending: AtomicBool,
+    transition: Mutex<Option<T>>,
+}
+
+impl<T> LevelFloor<T> {
+    pub(crate) fn new() -> Self {
+        Self { floor: AtomicU8::new(0), pending: AtomicBool::new(false), transition: Mutex::new(None) }
+    }
+
+    pub(crate) fn get(&self) -> u8 {
+        self.floor.load(Ordering::Relaxed)
+    }
+
+    /// Move the floor to `floor` (`0` to lower it) and keep `transition` for the next
+    /// report.
+    pub(crate) fn set(&self, floor: u8, transition: T) {
+        self.floor.store(floor, Ordering::Relaxed);
+        // An unreported earlier transition is superseded.
+        *self.transition.lock().unwrap_or_else(|e| e.into_inner()) = Some(transition);
+        self.pending.store(true, Ordering::Release);
+    }
+
+    /// Whether a transition waits to be reported.
+    pub(crate) fn pending(&self) -> bool {
+        self.pending.load(Ordering::Acquire)
+    }
+
+    /// The transition to report, if one happened since the last call.
+    pub(crate) fn take(&self) -> Option<T> {
+        if !self.pending.swap(false, Ordering::Acquire) {
+            return None;
+        }
+        self.transition.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+}
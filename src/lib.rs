@@ -1,21 +1,158 @@
+#[macro_use]
+mod macros;
+
 pub mod logger;
 pub mod level;
 pub mod formatter;
+pub mod theme;
 pub mod output;
+pub mod config;
+pub mod console;
+pub mod context;
+#[cfg(all(unix, feature = "signals"))]
+pub mod signal;
+#[cfg(feature = "admin")]
+pub mod admin;
+mod fields;
+#[cfg(feature = "binary")]
+pub mod binary;
+pub mod diagnostics;
+pub mod replay;
+pub mod testkit;
+#[cfg(feature = "tui")]
+pub mod tui;
+mod append_only;
+mod async_output;
+mod audit;
+mod build_info;
+mod builder;
+mod bytes;
+mod call_site;
+mod checksum;
+#[cfg(feature = "clap")]
+mod cli_args;
+mod cloud_logging;
+mod dead_letter;
+mod docker;
+mod dynamic_fields;
+mod emergency;
+mod error;
+mod disk_space;
+mod event;
+mod file_lock;
+mod flush;
+mod formatted;
+mod governor;
+mod http_call;
+#[cfg(any(feature = "admin", feature = "sse"))]
+mod http_head;
+mod id;
+mod sampling;
+mod key_policy;
+mod level_floor;
+mod limits;
+mod log_store;
+mod messages;
+mod logfmt;
+mod ndjson;
+mod partition;
+mod preview;
+mod progress;
+mod query_log;
+mod request;
+#[cfg(feature = "tower")]
+mod request_layer;
+mod router;
+mod snapshot;
+mod syslog;
+mod template;
+mod time_format;
+mod timestamp;
+mod tree;
+mod timings;
+#[cfg(unix)]
+mod writev;
+#[cfg(feature = "log")]
+mod log_bridge;
+#[cfg(feature = "log4rs")]
+mod log4rs_output;
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "notifications")]
+mod notification;
+#[cfg(feature = "slog")]
+mod slog_drain;
+#[cfg(feature = "sse")]
+mod sse;
+#[cfg(feature = "tokio")]
+mod supervisor;
+#[cfg(feature = "fast-json")]
+mod fast_json;
 
-pub use logger::Logger;
-pub use level::Level;
+pub use audit::AuditLogger;
+pub use build_info::BuildInfo;
+pub use builder::LoggerBuilder;
+pub use bytes::BytesEncoding;
+#[cfg(feature = "clap")]
+pub use cli_args::CappieArgs;
+pub use cloud_logging::CloudLoggingFormatter;
+pub use docker::DockerJsonFormatter;
+pub use emergency::emergency_log;
+pub use error::BuildError;
+pub use fields::{Fields, Key};
+pub use logger::{ChildOverrides, FieldPair, Logger, LoggerFactory, LoggerHandle, LogBuilder, Timer, TimedGuard};
+pub use event::{EventCatalog, EventCode};
+pub use level::{Level, LevelHandle, set_global_level, global_level, STATIC_MIN_LEVEL};
+pub use sampling::SamplingHandle;
 pub use formatter::{
     Formatter, 
     PrettyFormatter, 
     JsonFormatter, 
     FlexibleFormatter,
+    Align,
+    FieldLayout,
+    NameAbbreviation,
+    FieldFormat,
     ComponentType,
     ComponentPosition,
     TemplateComponent
 };
-pub use output::Output;
+pub use disk_space::DiskSpaceGuard;
+pub use flush::{flush_all, install_crash_handlers};
+pub use governor::Governor;
+pub use http_call::HttpCallLog;
+#[cfg(feature = "reqwest-middleware")]
+pub use http_call::HttpCallMiddleware;
+pub use key_policy::{KeyCase, KeyPolicy, OnViolation};
+pub use limits::{LimitExceeded, RecordLimits, Rejected};
+pub use logfmt::LogfmtFormatter;
+pub use messages::MessageCatalog;
+pub use preview::preview;
+pub use progress::Progress;
+pub use query_log::QueryLog;
+#[cfg(feature = "diesel")]
+pub use query_log::DieselInstrumentation;
+#[cfg(feature = "log")]
+pub use query_log::SqlxLogBridge;
+pub use request::RequestLog;
+#[cfg(feature = "tower")]
+pub use request_layer::{RequestLogFuture, RequestLogLayer, RequestLogService};
+pub use snapshot::SnapshotFormatter;
+#[cfg(feature = "tokio")]
+pub use supervisor::{supervised_spawn, supervised_spawn_restarting, RestartPolicy};
+pub use syslog::{Facility, SyslogFormatter};
+pub use theme::Theme;
+pub use time_format::TimeFormat;
+pub use timestamp::Timestamp;
+pub use tree::LoggerInfo;
+pub use output::{Output, Record, StdoutOutput, StderrOutput, FileOutput, RotatingFileOutput, MultiOutput, CaptureOutput};
 
 pub fn create_logger(name: &str) -> Logger {
     Logger::new(name)
-}
\ No newline at end of file
+}
+
+/// Items the macros expand to; not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::call_site::Callsite;
+}
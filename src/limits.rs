@@ -0,0 +1,171 @@
+use crate::level::Level;
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+type RejectHook = Arc<dyn Fn(&Rejected<'_>) + Send + Sync>;
+
+/// Caps on the size of records, applied with
+/// [`Logger::with_limits`](crate::Logger::with_limits), so that a caller attaching a huge
+/// array or thousands of fields cannot flood the outputs.
+///
+/// By default an oversized record is cut down and still written:
+///
+/// * beyond [`with_max_fields`](Self::with_max_fields) the remaining fields are left out
+///   and `fields_dropped` says how many;
+/// * when the formatted record is longer than [`with_max_bytes`](Self::with_max_bytes)
+///   the largest field values are replaced by `"[truncated: N bytes]"`, then, if that is not
+///   enough, the message is shortened, and `record_truncated: true` is added.  The envelope
+///   itself is never cut, so a record can still end up somewhat over the limit.
+///
+/// With [`reject_with`](Self::reject_with) such records are dropped instead and handed to
+/// the hook.
+///
+/// ```
+/// use cappie::{Logger, RecordLimits};
+///
+/// let log = Logger::new("api").with_limits(
+///     RecordLimits::new()
+///         .with_max_fields(64)
+///         .with_max_bytes(16 * 1024)
+///         .reject_with(|rejected| eprintln!("dropped {:?}: {}", rejected.msg, rejected.reason)),
+/// );
+/// log.info_with("upload", |b| {
+///     b.field("chunk", vec![0u8; 100_000]); // rejected
+/// });
+/// ```
+#[derive(Clone, Default)]
+pub struct RecordLimits {
+    max_fields: Option<usize>,
+    max_bytes: Option<usize>,
+    reject: Option<RejectHook>,
+}
+
+/// Which limit a record broke.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitExceeded {
+    Fields { count: usize, max: usize },
+    Bytes { size: usize, max: usize },
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitExceeded::Fields { count, max } => write!(f, "{} fields exceed the limit of {}", count, max),
+            LimitExceeded::Bytes { size, max } => write!(f, "{} bytes exceed the limit of {}", size, max),
+        }
+    }
+}
+
+/// A record dropped by [`RecordLimits::reject_with`].
+#[derive(Debug)]
+pub struct Rejected<'a> {
+    pub level: Level,
+    pub name: &'a str,
+    pub msg: &'a str,
+    pub reason: LimitExceeded,
+}
+
+impl RecordLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Most fields per record (after base and scope fields are merged in).
+    pub fn with_max_fields(mut self, max: usize) -> Self {
+        self.max_fields = Some(max);
+        self
+    }
+
+    /// Largest formatted record, in bytes.
+    pub fn with_max_bytes(mut self, max: usize) -> Self {
+        self.max_bytes = Some(max);
+        self
+    }
+
+    /// Drop records over a limit instead of truncating them, calling `hook` for each.
+    pub fn reject_with<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&Rejected<'_>) + Send + Sync + 'static,
+    {
+        self.reject = Some(Arc::new(hook));
+        self
+    }
+
+    /// Enforce the field count on `fields`.  Returns `false` if the record was rejected.
+    pub(crate) fn limit_fields(&self, level: Level, name: &str, msg: &str, fields: &mut Cow<'_, Map<String, Value>>) -> bool {
+        let Some(max) = self.max_fields.filter(|max| fields.len() > *max) else {
+            return true;
+        };
+        let count = fields.len();
+        if let Some(hook) = &self.reject {
+            hook(&Rejected { level, name, msg, reason: LimitExceeded::Fields { count, max } });
+            return false;
+        }
+        let mut kept: Map<String, Value> = fields.iter().take(max).map(|(k, v)| (k.clone(), v.clone())).collect();
+        kept.insert("fields_dropped".to_string(), Value::from(count - max));
+        *fields = Cow::Owned(kept);
+        true
+    }
+
+    /// Enforce the size limit on the record formatted into `buf`, re‑formatting it with
+    /// `format` after shrinking `fields` and `msg`.  Returns `false` if the record was
+    /// rejected.
+    pub(crate) fn fit<F>(
+        &self,
+        level: Level,
+        name: &str,
+        buf: &mut Vec<u8>,
+        msg: &mut Cow<'_, str>,
+        fields: &mut Cow<'_, Map<String, Value>>,
+        format: F,
+    ) -> bool
+    where
+        F: Fn(&mut Vec<u8>, &str, &Map<String, Value>),
+    {
+        let Some(max) = self.max_bytes.filter(|max| buf.len() > *max) else {
+            return true;
+        };
+        if let Some(hook) = &self.reject {
+            hook(&Rejected { level, name, msg, reason: LimitExceeded::Bytes { size: buf.len(), max } });
+            return false;
+        }
+
+        // Replace the largest values first until the excess is (roughly) made up.
+        let excess = buf.len() - max;
+        let mut sizes: Vec<(usize, &String)> = fields
+            .iter()
+            .map(|(k, v)| (serde_json::to_vec(v).map_or(0, |b| b.len()), k))
+            .collect();
+        sizes.sort_by_key(|(size, _)| std::cmp::Reverse(*size));
+        let mut shrunk = fields.as_ref().clone();
+        let mut saved = 0;
+        for (size, key) in sizes {
+            if saved >= excess {
+                break;
+            }
+            let placeholder = format!("[truncated: {} bytes]", size);
+            if size > placeholder.len() + 2 {
+                saved += size - placeholder.len() - 2;
+                shrunk.insert(key.clone(), Value::String(placeholder));
+            }
+        }
+        shrunk.insert("record_truncated".to_string(), Value::Bool(true));
+        buf.clear();
+        format(buf, msg, &shrunk);
+
+        if buf.len() > max {
+            let keep = msg.len().saturating_sub(buf.len() - max + 3);
+            let mut end = keep;
+            while !msg.is_char_boundary(end) {
+                end -= 1;
+            }
+            *msg = Cow::Owned(format!("{}...", &msg[..end]));
+            buf.clear();
+            format(buf, msg, &shrunk);
+        }
+        *fields = Cow::Owned(shrunk);
+        true
+    }
+}
@@ -0,0 +1,74 @@
+use crate::diagnostics::{self, Diagnostic};
+use crate::level::Level;
+use crate::log_bridge::{log_level, Fields};
+use crate::output::{Output, Record};
+use log4rs::append::Append;
+use serde_json::{Map, Value};
+use std::io;
+
+/// Writes records through a [log4rs](https://docs.rs/log4rs) appender, so the appenders a
+/// codebase already configures keep receiving its records while call sites move to
+/// Cappie.  Feature `log4rs`.
+///
+/// Records are handed to the appender directly, without going through log4rs's loggers
+/// or filters: the message becomes the `log` message, the logger name the target and
+/// fields are passed as key‑values, as [`LogCrateOutput`](crate::output::LogCrateOutput)
+/// does.  The appender's encoder does the formatting.  Appender errors are reported as
+/// [`Diagnostic::WriteFailed`].
+///
+/// ```no_run
+/// use cappie::Logger;
+/// use cappie::output::Log4rsOutput;
+/// use log4rs::append::file::FileAppender;
+///
+/// let appender = FileAppender::builder().build("log/legacy.log").unwrap();
+/// let log = Logger::new("legacy").with_output(Box::new(Log4rsOutput::new(Box::new(appender))));
+/// log.info("still lands in log/legacy.log");
+/// ```
+#[derive(Debug)]
+pub struct Log4rsOutput {
+    appender: Box<dyn Append>,
+    target: Option<String>,
+}
+
+impl Log4rsOutput {
+    pub fn new(appender: Box<dyn Append>) -> Self {
+        Self { appender, target: None }
+    }
+
+    /// Use `target` for every record instead of the logger name.
+    pub fn with_target(mut self, target: &str) -> Self {
+        self.target = Some(target.to_string());
+        self
+    }
+
+    fn append(&self, level: Level, target: &str, msg: &str, fields: &Map<String, Value>) {
+        let result = self.appender.append(
+            &::log::Record::builder()
+                .level(log_level(level))
+                .target(self.target.as_deref().unwrap_or(target))
+                .args(format_args!("{}", msg))
+                .key_values(&Fields(fields))
+                .build(),
+        );
+        if let Err(e) = result {
+            let error = io::Error::other(e);
+            diagnostics::report(Diagnostic::WriteFailed { output: "Log4rsOutput", error: &error });
+        }
+    }
+}
+
+impl Output for Log4rsOutput {
+    /// Text without metadata is appended at `Info` with target `cappie`.
+    fn write(&self, message: &str) {
+        self.append(Level::Info, "cappie", message, &Map::new());
+    }
+
+    fn write_record(&self, record: &Record<'_>) {
+        self.append(record.level, record.name, record.msg, record.fields);
+    }
+
+    fn flush(&self) {
+        self.appender.flush();
+    }
+}
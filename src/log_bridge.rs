@@ -0,0 +1,103 @@
+use crate::level::Level;
+use crate::output::{Output, Record};
+use ::log::kv::{self, Key, Source, VisitSource};
+use serde_json::{Map, Value};
+
+/// Forwards records to the [`log`](https://docs.rs/log) crate's global logger, so a library
+/// that logs through Cappie fits into an application standardized on `env_logger`, `fern`
+/// or another `log` backend.  Feature `log`.
+///
+/// The record's message becomes the `log` message and its logger name the target; fields
+/// are passed as key‑values (`log`'s `kv` API), which backends may or may not print.
+/// `Fatal` maps to `Error`.  The Cappie formatter is not used, as the backend does its own
+/// formatting.
+///
+/// ```no_run
+/// use cappie::Logger;
+/// use cappie::output::LogCrateOutput;
+///
+/// // env_logger::init();
+/// let log = Logger::new("db").with_output(Box::new(LogCrateOutput::new()));
+/// log.info("connected");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LogCrateOutput {
+    target: Option<String>,
+}
+
+impl LogCrateOutput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `target` for every record instead of the logger name.
+    pub fn with_target(mut self, target: &str) -> Self {
+        self.target = Some(target.to_string());
+        self
+    }
+
+    fn forward(&self, level: Level, target: &str, msg: &str, fields: &Map<String, Value>) {
+        let target = self.target.as_deref().unwrap_or(target);
+        let logger = ::log::logger();
+        let metadata = ::log::Metadata::builder().level(log_level(level)).target(target).build();
+        if !logger.enabled(&metadata) {
+            return;
+        }
+        logger.log(
+            &::log::Record::builder()
+                .metadata(metadata)
+                .args(format_args!("{}", msg))
+                .key_values(&Fields(fields))
+                .build(),
+        );
+    }
+}
+
+impl Output for LogCrateOutput {
+    /// Text without metadata is forwarded at `Info` with target `cappie`.
+    fn write(&self, message: &str) {
+        self.forward(Level::Info, "cappie", message, &Map::new());
+    }
+
+    fn write_record(&self, record: &Record<'_>) {
+        self.forward(record.level, record.name, record.msg, record.fields);
+    }
+
+    fn flush(&self) {
+        ::log::logger().flush();
+    }
+}
+
+pub(crate) fn log_level(level: Level) -> ::log::Level {
+    match level {
+        Level::Trace => ::log::Level::Trace,
+        Level::Debug => ::log::Level::Debug,
+        Level::Info => ::log::Level::Info,
+        Level::Warn => ::log::Level::Warn,
+        Level::Error | Level::Fatal => ::log::Level::Error,
+    }
+}
+
+/// Record fields as `log` key‑values.  Scalars keep their type; arrays and objects are
+/// passed as JSON text.
+pub(crate) struct Fields<'a>(pub(crate) &'a Map<String, Value>);
+
+impl Source for Fields<'_> {
+    fn visit<'kvs>(&'kvs self, visitor: &mut dyn VisitSource<'kvs>) -> Result<(), kv::Error> {
+        for (key, value) in self.0 {
+            let value = match value {
+                Value::Null => kv::Value::null(),
+                Value::Bool(b) => kv::Value::from(*b),
+                Value::Number(n) => match (n.as_u64(), n.as_i64()) {
+                    (Some(u), _) => kv::Value::from(u),
+                    (_, Some(i)) => kv::Value::from(i),
+                    _ => kv::Value::from(n.as_f64().unwrap_or(f64::NAN)),
+                },
+                Value::String(s) => kv::Value::from(s.as_str()),
+                other => kv::Value::from_display(other),
+            };
+            visitor.visit_pair(Key::from_str(key), value)?;
+        }
+        Ok(())
+    }
+}
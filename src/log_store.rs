@@ -0,0 +1,234 @@
+use crate::level::Level;
+use crate::output::{Output, Record};
+use crate::timestamp::Timestamp;
+use serde_json::{Map, Value};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// Output that keeps the most recent records in memory, structured, for a log panel in a
+/// GUI (egui, iced, …) or TUI.  Clones share the store: give one to the logger and query
+/// another from the UI.
+///
+/// Once the store holds [`capacity`](Self::with_capacity) records (default 10 000) the
+/// oldest is dropped for every new one.  Every record gets a sequence number, so a UI can
+/// fetch only what arrived since its last frame with [`LogQuery::after`].
+///
+/// ```
+/// use cappie::{Level, Logger};
+/// use cappie::output::{LogQuery, LogStore};
+///
+/// let store = LogStore::new().with_capacity(1000);
+/// let log = Logger::new("ui").with_output(Box::new(store.clone()));
+/// log.info("started");
+/// log.warn_with("slow frame", |b| { b.field("frame_ms", 41); });
+///
+/// let warnings = store.query(&LogQuery::new().min_level(Level::Warn));
+/// assert_eq!(warnings.len(), 1);
+/// assert_eq!(warnings[0].fields["frame_ms"], 41);
+///
+/// let matching = store.query(&LogQuery::new().text("START"));
+/// assert_eq!(matching[0].msg, "started");
+/// ```
+#[derive(Clone)]
+pub struct LogStore {
+    inner: Arc<Mutex<Inner>>,
+}
+
+struct Inner {
+    records: VecDeque<StoredRecord>,
+    capacity: usize,
+    next_seq: u64,
+}
+
+/// A record kept by a [`LogStore`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredRecord {
+    /// Position in the store's stream, starting at 1 and never reused.
+    pub seq: u64,
+    pub level: Level,
+    pub timestamp: Timestamp,
+    pub name: String,
+    pub msg: String,
+    pub fields: Map<String, Value>,
+}
+
+/// Filter for [`LogStore::query`].  All conditions must hold; an empty query matches
+/// every record.
+#[derive(Debug, Clone, Default)]
+pub struct LogQuery {
+    min_level: Option<Level>,
+    since: Option<Timestamp>,
+    until: Option<Timestamp>,
+    after: Option<u64>,
+    text: Option<String>,
+    fields: Vec<(String, Value)>,
+    limit: Option<usize>,
+}
+
+impl LogStore {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                records: VecDeque::new(),
+                capacity: DEFAULT_CAPACITY,
+                next_seq: 1,
+            })),
+        }
+    }
+
+    /// Number of records kept (at least 1).  Shrinking drops the oldest records.
+    pub fn with_capacity(self, capacity: usize) -> Self {
+        {
+            let mut inner = self.lock();
+            inner.capacity = capacity.max(1);
+            let excess = inner.records.len().saturating_sub(inner.capacity);
+            inner.records.drain(..excess);
+        }
+        self
+    }
+
+    /// Records matching `query`, oldest first.
+    pub fn query(&self, query: &LogQuery) -> Vec<StoredRecord> {
+        let inner = self.lock();
+        let mut matching: Vec<StoredRecord> = inner
+            .records
+            .iter()
+            .rev()
+            .filter(|record| query.matches(record))
+            .take(query.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect();
+        matching.reverse();
+        matching
+    }
+
+    /// All records kept, oldest first.
+    pub fn records(&self) -> Vec<StoredRecord> {
+        self.lock().records.iter().cloned().collect()
+    }
+
+    /// Sequence number of the newest record, 0 before the first.  A UI can compare it with
+    /// the value from its last frame to decide whether to redraw.
+    pub fn last_seq(&self) -> u64 {
+        self.lock().next_seq - 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().records.is_empty()
+    }
+
+    /// Remove all records.  Sequence numbers keep counting.
+    pub fn clear(&self) {
+        self.lock().records.clear();
+    }
+
+    fn push(&self, level: Level, timestamp: Timestamp, name: &str, msg: &str, fields: Map<String, Value>) {
+        let mut inner = self.lock();
+        if inner.records.len() >= inner.capacity {
+            inner.records.pop_front();
+        }
+        let seq = inner.next_seq;
+        inner.next_seq += 1;
+        inner.records.push_back(StoredRecord {
+            seq,
+            level,
+            timestamp,
+            name: name.to_string(),
+            msg: msg.to_string(),
+            fields,
+        });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for LogStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Output for LogStore {
+    /// Text without metadata is kept as an `Info` record with an empty name.
+    fn write(&self, message: &str) {
+        self.push(Level::Info, Timestamp::now(), "", message, Map::new());
+    }
+
+    fn write_record(&self, record: &Record<'_>) {
+        self.push(record.level, record.timestamp, record.name, record.msg, record.fields.clone());
+    }
+}
+
+impl LogQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only records at `level` or above.
+    pub fn min_level(mut self, level: Level) -> Self {
+        self.min_level = Some(level);
+        self
+    }
+
+    /// Only records at or after `time`.
+    pub fn since(mut self, time: Timestamp) -> Self {
+        self.since = Some(time);
+        self
+    }
+
+    /// Only records before `time`.
+    pub fn until(mut self, time: Timestamp) -> Self {
+        self.until = Some(time);
+        self
+    }
+
+    /// Only records with a sequence number greater than `seq`.
+    pub fn after(mut self, seq: u64) -> Self {
+        self.after = Some(seq);
+        self
+    }
+
+    /// Only records whose message, logger name or a string field contains `text`,
+    /// ignoring case.
+    pub fn text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_lowercase());
+        self
+    }
+
+    /// Only records with a top‑level field `key` equal to `value`.  May be given several
+    /// times.
+    pub fn field<T: Into<Value>>(mut self, key: &str, value: T) -> Self {
+        self.fields.push((key.to_string(), value.into()));
+        self
+    }
+
+    /// At most the `n` newest matching records.
+    pub fn limit(mut self, n: usize) -> Self {
+        self.limit = Some(n);
+        self
+    }
+
+    pub fn matches(&self, record: &StoredRecord) -> bool {
+        self.min_level.is_none_or(|level| record.level >= level)
+            && self.since.is_none_or(|time| record.timestamp >= time)
+            && self.until.is_none_or(|time| record.timestamp < time)
+            && self.after.is_none_or(|seq| record.seq > seq)
+            && self.fields.iter().all(|(key, value)| record.fields.get(key) == Some(value))
+            && self.text.as_deref().is_none_or(|text| contains_text(record, text))
+    }
+}
+
+fn contains_text(record: &StoredRecord, text: &str) -> bool {
+    let contains = |s: &str| s.to_lowercase().contains(text);
+    contains(&record.msg)
+        || contains(&record.name)
+        || record.fields.values().any(|value| value.as_str().is_some_and(contains))
+}
@@ -0,0 +1,143 @@
+use crate::fields::Fields;
+use crate::formatter::Formatter;
+use crate::level::Level;
+use crate::timestamp::{self, Precision, Timestamp};
+use serde_json::{Map, Value};
+use std::fmt::Write;
+
+/// [logfmt](https://brandur.org/logfmt) lines: `time=… level=info name=api msg="user created" id=42`.
+///
+/// Values containing spaces, `=`, quotes or control characters are quoted with JSON string
+/// escapes, so every record stays on one line; objects and arrays are written as quoted
+/// JSON.  Key names for the envelope can be changed, and the timestamp left out for
+/// platforms that stamp lines themselves – [`heroku`](Self::heroku) does both.
+///
+/// ```
+/// use cappie::{LogfmtFormatter, Logger};
+///
+/// let log = Logger::new("api").with_formatter(Box::new(LogfmtFormatter::new()));
+/// log.info_with("user created", |b| { b.number("id", 42); });
+/// ```
+#[derive(Debug, Clone)]
+pub struct LogfmtFormatter {
+    time_key: Option<String>,
+    level_key: String,
+    name_key: String,
+    msg_key: String,
+}
+
+impl Default for LogfmtFormatter {
+    fn default() -> Self {
+        Self {
+            time_key: Some("time".to_string()),
+            level_key: "level".to_string(),
+            name_key: "name".to_string(),
+            msg_key: "msg".to_string(),
+        }
+    }
+}
+
+impl LogfmtFormatter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Heroku / Logplex conventions: `at=<level>` and `source=<logger name>`, no timestamp
+    /// (Logplex adds its own).  See also [`Logger::heroku`](crate::Logger::heroku).
+    pub fn heroku() -> Self {
+        Self::new().without_time().with_level_key("at").with_name_key("source")
+    }
+
+    /// Leave out the timestamp.
+    pub fn without_time(mut self) -> Self {
+        self.time_key = None;
+        self
+    }
+
+    pub fn with_time_key(mut self, key: &str) -> Self {
+        self.time_key = Some(key.to_string());
+        self
+    }
+
+    pub fn with_level_key(mut self, key: &str) -> Self {
+        self.level_key = key.to_string();
+        self
+    }
+
+    pub fn with_name_key(mut self, key: &str) -> Self {
+        self.name_key = key.to_string();
+        self
+    }
+
+    pub fn with_msg_key(mut self, key: &str) -> Self {
+        self.msg_key = key.to_string();
+        self
+    }
+
+    fn line(&self, level: Level, tags: &[&str], msg: &str, fields: Fields<'_>, timestamp: Timestamp, name: &str) -> String {
+        let mut line = String::new();
+        if let Some(key) = &self.time_key {
+            pair(&mut line, key, &timestamp::rfc3339_z(&timestamp, Precision::Millis));
+        }
+        pair(&mut line, &self.level_key, &level.as_str().to_lowercase());
+        if !name.is_empty() {
+            pair(&mut line, &self.name_key, name);
+        }
+        pair(&mut line, &self.msg_key, msg);
+        if !tags.is_empty() {
+            pair(&mut line, "tags", &tags.join(","));
+        }
+        for (key, value) in fields.sorted() {
+            match value {
+                Value::String(s) => pair(&mut line, key, s),
+                other => pair(&mut line, key, &other.to_string()),
+            }
+        }
+        line
+    }
+}
+
+impl Formatter for LogfmtFormatter {
+    fn format(&self, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) -> String {
+        self.line(level, &[], msg, Fields::from(fields), timestamp, name)
+    }
+
+    /// Tags are written as `tags=a,b` after the message.
+    fn format_tagged_into(&self, buf: &mut Vec<u8>, level: Level, tags: &[&str], msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) {
+        buf.extend_from_slice(self.line(level, tags, msg, Fields::from(fields), timestamp, name).as_bytes());
+    }
+
+    fn format_fields_into(&self, buf: &mut Vec<u8>, level: Level, tags: &[&str], msg: &str, fields: Fields<'_>, timestamp: Timestamp, name: &str) {
+        buf.extend_from_slice(self.line(level, tags, msg, fields, timestamp, name).as_bytes());
+    }
+}
+
+/// Append ` key=value`, quoting the value where logfmt parsers need it.
+fn pair(line: &mut String, key: &str, value: &str) {
+    if !line.is_empty() {
+        line.push(' ');
+    }
+    for c in key.chars() {
+        line.push(if c == '=' || c == '"' || c.is_whitespace() || c.is_control() { '_' } else { c });
+    }
+    line.push('=');
+    if !value.is_empty() && !value.chars().any(|c| c == '=' || c == '"' || c.is_whitespace() || c.is_control()) {
+        line.push_str(value);
+        return;
+    }
+    line.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => line.push_str("\\\""),
+            '\\' => line.push_str("\\\\"),
+            '\n' => line.push_str("\\n"),
+            '\r' => line.push_str("\\r"),
+            '\t' => line.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(line, "\\u{:04x}", c as u32);
+            }
+            c => line.push(c),
+        }
+    }
+    line.push('"');
+}
@@ -1,104 +1,1089 @@
-use crate::level::Level;
-use crate::formatter::{Formatter, JsonFormatter, PrettyFormatter};
-use crate::output::{Output, StdoutOutput};
-use chrono::Utc;
+use crate::level::{self, global_level, Level, LevelHandle};
+use crate::fields::{Fields, Key};
+use crate::formatter::{format_record_into, Formatter, JsonFormatter, PrettyFormatter};
+use crate::formatted;
+use crate::logfmt::LogfmtFormatter;
+use crate::output::{Output, Record, StderrOutput, StdoutOutput};
+use crate::build_info::BuildInfo;
+use crate::builder::LoggerBuilder;
+use crate::bytes::BytesEncoding;
+use crate::call_site::{self, Callsite, SourcePaths};
+use crate::context;
+use crate::diagnostics::{self, Diagnostic};
+use crate::disk_space::{DiskSpaceGuard, DiskSpaceState};
+use crate::dynamic_fields::DynamicFields;
+use crate::event::EventCode;
+use crate::governor::{Governor, GovernorState};
+use crate::progress::Progress;
+use crate::syslog;
+use crate::timings::{self, Timings};
+use crate::tree::{LoggerInfo, Tree};
+use crate::id::next_ulid;
+use crate::sampling::SamplingHandle;
+use crate::key_policy::{KeyPolicy, OnViolation};
+use crate::limits::RecordLimits;
+use crate::messages::MessageCatalog;
+use crate::timestamp::{self, Precision, Timestamp};
 use serde_json::{Map, Value};
+use smallvec::SmallVec;
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
+use std::any::Any;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::panic::{self, AssertUnwindSafe, Location, UnwindSafe};
+use std::thread;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, Weak};
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime};
 
 /// Main façade that **users interact with**.  A logger is cheap to clone because it only
-/// contains a couple of `Arc`s/`Box`es, so feel free to pass it around.
+/// contains a couple of `Arc`s, so feel free to pass it around.
 ///
 /// The struct is designed for **builder‑style configuration**:
 ///
 /// ```
-/// use simple_logger::{Logger, Level, PrettyFormatter};
+/// use cappie::{Logger, Level, PrettyFormatter};
 ///
 /// let log = Logger::new("backend")
 ///     .with_level(Level::Debug)
 ///     .with_formatter(Box::new(PrettyFormatter::new()))
-///     .with_output(Box::new(simple_logger::output::StderrOutput))
+///     .with_output(Box::new(cappie::output::StderrOutput))
 ///     .with_field("version", env!("CARGO_PKG_VERSION"));
 /// ```
 ///
 /// **Thread‑safety:** all methods take `&self`; shared state is protected by `Arc`.  You can
 /// therefore use the same instance from multiple threads without additional locking.
+#[derive(Clone)]
 pub struct Logger {
-    name: String,
-    level: Level,
-    formatter: Box<dyn Formatter>,
-    output: Box<dyn Output>,
-    base_fields: Map<String, Value>,
+    name: Arc<str>,
+    level: LevelHandle,
+    pipeline: Arc<Pipeline>,
+    base_fields: Arc<Map<String, Value>>,
+    scope_fields: Arc<Map<String, Value>>,
+    timings: Arc<Timings>,
+    /// Entry in the tree of loggers, shared with clones.
+    member: Arc<Member>,
+}
+
+// Shared state is immutable or behind locks that recover from poisoning, so a logger is
+// still usable after a formatter or output panicked – which is what lets it sit behind
+// `catch_unwind`, or be an `slog` drain.
+impl std::panic::UnwindSafe for Logger {}
+impl std::panic::RefUnwindSafe for Logger {}
+
+/// Everything a logger shares with its children and clones.  Builder methods detach the
+/// logger from the shared copy (`Arc::make_mut`) before changing it.
+#[derive(Clone)]
+struct Pipeline {
+    formatter: Arc<dyn Formatter>,
+    output: Arc<dyn Output>,
+    routes: Vec<Route>,
+    record_ids: bool,
+    source_location: bool,
+    source_paths: SourcePaths,
+    sampling: SamplingHandle,
+    governor: Option<Arc<GovernorState>>,
+    disk_space: Option<Arc<DiskSpaceState>>,
+    key_policy: Option<Arc<KeyPolicy>>,
+    limits: Option<RecordLimits>,
+    bytes_encoding: BytesEncoding,
+    echo_errors: bool,
+    isolate_panics: bool,
+    dynamic_fields: Vec<Arc<DynamicFields>>,
+    /// Minimum levels per target, longest target first.
+    target_levels: Vec<(String, Level)>,
+    messages: Option<Arc<MessageCatalog>>,
+    locale: Option<Arc<str>>,
+}
+
+impl Pipeline {
+    /// Minimum level raised by the governor or the disk space guard, the higher of both.
+    fn floor(&self) -> Option<Level> {
+        let governor = self.governor.as_ref().and_then(|g| g.floor());
+        let disk_space = self.disk_space.as_ref().and_then(|d| d.floor());
+        governor.max(disk_space)
+    }
+}
+
+/// A logger as seen by [`Logger::tree`].  Clones and factory loggers share their
+/// original's entry; it goes away with the last of them.
+struct Member {
+    tree: Arc<Tree<Member>>,
+    name: Arc<str>,
+    level: LevelHandle,
+    pipeline: Mutex<Weak<Pipeline>>,
+}
+
+impl Member {
+    fn join(tree: Arc<Tree<Member>>, name: &Arc<str>, level: &LevelHandle, pipeline: &Arc<Pipeline>) -> Arc<Self> {
+        let member = Arc::new(Self {
+            tree: tree.clone(),
+            name: name.clone(),
+            level: level.clone(),
+            pipeline: Mutex::new(Arc::downgrade(pipeline)),
+        });
+        tree.add(Arc::downgrade(&member));
+        member
+    }
+}
+
+/// A range of levels as captured from any `RangeBounds<Level>`.
+pub(crate) type LevelRange = (Bound<Level>, Bound<Level>);
+
+/// Output for a range of levels, see [`Logger::route`].
+#[derive(Clone)]
+struct Route {
+    levels: LevelRange,
+    output: Arc<dyn Output>,
 }
 
 impl Logger {
     pub fn new(name: &str) -> Self {
+        let name = intern(name);
+        let level = LevelHandle::new(Level::Info);
+        let pipeline = Arc::new(Pipeline {
+                formatter: Arc::new(JsonFormatter),
+                output: Arc::new(StdoutOutput),
+                routes: Vec::new(),
+                record_ids: false,
+                source_location: false,
+                source_paths: SourcePaths::default(),
+                sampling: SamplingHandle::default(),
+                governor: None,
+                disk_space: None,
+                key_policy: None,
+                limits: None,
+                bytes_encoding: BytesEncoding::default(),
+                echo_errors: false,
+                isolate_panics: false,
+                dynamic_fields: Vec::new(),
+                target_levels: Vec::new(),
+                messages: None,
+                locale: None,
+        });
         Self {
-            name: name.to_string(),
-            level: Level::Info,
-            formatter: Box::new(JsonFormatter),
-            output: Box::new(StdoutOutput),
-            base_fields: Map::new(),
+            member: Member::join(Arc::new(Tree::new()), &name, &level, &pipeline),
+            name,
+            level,
+            pipeline,
+            base_fields: Arc::new(Map::new()),
+            scope_fields: Arc::new(Map::new()),
+            timings: Arc::new(Timings::new(timings::DEFAULT_INTERVAL)),
+        }
+    }
+    
+    /// Change the pipeline, detaching it from other loggers first.  A detached logger gets
+    /// a [tree](Self::tree) entry of its own, like [`with_level`](Self::with_level) gives
+    /// it; otherwise its entry is pointed at the result.
+    fn configure(&mut self, f: impl FnOnce(&mut Pipeline)) {
+        let shared = Arc::strong_count(&self.pipeline) > 1;
+        f(Arc::make_mut(&mut self.pipeline));
+        if shared {
+            self.member = Member::join(self.member.tree.clone(), &self.name, &self.level, &self.pipeline);
+        } else {
+            *self.member.pipeline.lock().unwrap_or_else(|e| e.into_inner()) = Arc::downgrade(&self.pipeline);
         }
     }
     
+    /// Start a [`LoggerBuilder`], which validates the configuration before handing out a
+    /// logger.
+    pub fn builder(name: &str) -> LoggerBuilder {
+        LoggerBuilder::new(name)
+    }
+    
+    /// Set the minimum level.  Like the other builder methods this detaches the logger, so
+    /// clones it was made from keep their level; share a level that changes at runtime
+    /// through [`level_handle`](Self::level_handle) instead.
+    ///
+    /// ```
+    /// use cappie::{Level, Logger};
+    ///
+    /// let api = Logger::new("api").with_level(Level::Warn);
+    /// let verbose = api.clone().with_level(Level::Trace);
+    /// assert_eq!(api.level(), Level::Warn);
+    /// assert_eq!(verbose.level(), Level::Trace);
+    /// ```
     pub fn with_level(mut self, level: Level) -> Self {
-        self.level = level;
+        self.level = LevelHandle::new(level);
+        self.member = Member::join(self.member.tree.clone(), &self.name, &self.level, &self.pipeline);
+        self
+    }
+    
+    /// Minimum level for records logged with `target` (see [`log_target`](Self::log_target))
+    /// or a target below it: `db` covers `db`, `db::pool` and `db.pool`.  The most specific
+    /// target wins; records without a matching target use the logger's level.  The
+    /// process‑wide [override](crate::set_global_level) still lowers it.
+    ///
+    /// ```
+    /// use cappie::{Level, Logger};
+    ///
+    /// let log = Logger::new("api").with_target_level("db", Level::Debug);
+    /// assert!(log.target_enabled(Level::Debug, "db::pool"));
+    /// assert!(!log.enabled(Level::Debug));
+    /// ```
+    pub fn with_target_level(mut self, target: &str, level: Level) -> Self {
+        level::lower_bound(level);
+        self.configure(|p| {
+            p.target_levels.retain(|(t, _)| t != target);
+            p.target_levels.push((target.to_string(), level));
+            p.target_levels.sort_by_key(|(t, _)| std::cmp::Reverse(t.len()));
+        });
         self
     }
     
+    /// Handle to this logger's level that can be changed at runtime, e.g. from an admin
+    /// endpoint.  Children start from the parent's current level but own their handle.
+    pub fn level_handle(&self) -> LevelHandle {
+        self.level.clone()
+    }
+    
+    /// Write only a `rate` fraction of the records below `Warn`, see [`SamplingHandle`].
+    /// Children share the rate unless they are given their own.
+    pub fn with_sampling(mut self, rate: f64) -> Self {
+        self.configure(|p| p.sampling = SamplingHandle::new(rate));
+        self
+    }
+    
+    /// Handle to this logger's sampling rate that can be changed at runtime.
+    pub fn sampling_handle(&self) -> SamplingHandle {
+        self.pipeline.sampling.clone()
+    }
+    
     pub fn with_formatter(mut self, formatter: Box<dyn Formatter>) -> Self {
-        self.formatter = formatter;
+        self.configure(|p| p.formatter = Arc::from(formatter));
         self
     }
     
     pub fn with_output(mut self, output: Box<dyn Output>) -> Self {
-        self.output = output;
+        self.configure(|p| p.output = Arc::from(output));
+        self
+    }
+    
+    /// Send records whose level falls into `levels` to `output`.  A record goes to every
+    /// route that matches it; records no route matches go to the regular
+    /// [output](Self::with_output).
+    ///
+    /// ```
+    /// use cappie::{Level, Logger, StderrOutput, StdoutOutput};
+    ///
+    /// let log = Logger::new("svc")
+    ///     .route(Level::Error.., Box::new(StderrOutput))
+    ///     .route(..Level::Error, Box::new(StdoutOutput));
+    /// ```
+    pub fn route<R: RangeBounds<Level>>(mut self, levels: R, output: Box<dyn Output>) -> Self {
+        let levels = (levels.start_bound().cloned(), levels.end_bound().cloned());
+        self.configure(|p| p.routes.push(Route { levels, output: Arc::from(output) }));
         self
     }
     
     pub fn with_field<T: Into<Value>>(mut self, key: &str, value: T) -> Self {
-        self.base_fields.insert(key.to_string(), value.into());
+        Arc::make_mut(&mut self.base_fields).insert(key.to_string(), value.into());
+        self
+    }
+    
+    /// Attach several base fields at once.  Accepts anything that yields key/value pairs
+    /// or references to them (see [`FieldPair`]), e.g. a `serde_json::Map`, a `Vec`, an
+    /// array of tuples or a slice of one:
+    ///
+    /// ```
+    /// use cappie::Logger;
+    /// use serde_json::json;
+    ///
+    /// let log = Logger::new("api").with_fields([
+    ///     ("service", json!("billing")),
+    ///     ("replicas", json!(3)),
+    /// ]);
+    ///
+    /// let region = "eu-west-1";
+    /// let log = Logger::new("api").with_fields(&[("region", region)]);
+    /// ```
+    pub fn with_fields<I>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: FieldPair,
+    {
+        let base_fields = Arc::make_mut(&mut self.base_fields);
+        for pair in fields {
+            let (k, v) = pair.into_field();
+            base_fields.insert(k, v);
+        }
+        self
+    }
+    
+    /// Attach the given environment variables as base fields.  Keys are the lower‑cased
+    /// variable names (`DEPLOY_ID` becomes `deploy_id`); unset or non‑UTF‑8 variables are
+    /// skipped.
+    pub fn with_fields_from_env(mut self, vars: &[&str]) -> Self {
+        let base_fields = Arc::make_mut(&mut self.base_fields);
+        for var in vars {
+            if let Ok(value) = std::env::var(var) {
+                base_fields.insert(var.to_lowercase(), Value::String(value));
+            }
+        }
+        self
+    }
+    
+    /// Attach `git_sha`, `build_time` and `profile` from [`build_info!`](crate::build_info)
+    /// as base fields; missing values are skipped.
+    pub fn with_build_info(mut self, info: BuildInfo) -> Self {
+        let base_fields = Arc::make_mut(&mut self.base_fields);
+        for (key, value) in info.fields() {
+            base_fields.insert(key.to_string(), Value::from(value));
+        }
+        self
+    }
+    
+    /// Templates for records logged by key with [`log_key`](Self::log_key).
+    pub fn with_messages(mut self, messages: MessageCatalog) -> Self {
+        self.configure(|p| p.messages = Some(Arc::new(messages)));
+        self
+    }
+    
+    /// Locale [`log_key`](Self::log_key) renders messages in; the catalog's default
+    /// locale if unset.  Give a child its own locale to write one output per language.
+    pub fn with_locale(mut self, locale: &str) -> Self {
+        self.configure(|p| p.locale = Some(Arc::from(locale)));
+        self
+    }
+    
+    /// Attach fields computed by `provider` when a record is written, for state that
+    /// changes while the process runs (enabled feature flags, the current config
+    /// generation…).  The result is cached for `ttl`, so the provider runs at most once per
+    /// `ttl` however many records are logged; a zero `ttl` evaluates it for every record.
+    ///
+    /// Dynamic fields override base fields and are overridden by scope and per‑call
+    /// fields.  Records the provider logs itself get the previous result.  Children and
+    /// clones share the provider and its cache.
+    ///
+    /// ```
+    /// use cappie::Logger;
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// let generation = Arc::new(AtomicU64::new(1));
+    /// let current = generation.clone();
+    /// let log = Logger::new("api").with_dynamic_fields(Duration::from_secs(5), move || {
+    ///     [("config_generation", current.load(Ordering::Relaxed))]
+    /// });
+    /// log.info("reloaded"); // {…,"config_generation":1}
+    /// ```
+    pub fn with_dynamic_fields<F, I, K, V>(mut self, ttl: Duration, provider: F) -> Self
+    where
+        F: Fn() -> I + Send + Sync + 'static,
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<Value>,
+    {
+        let provider = move || provider().into_iter().map(|(k, v)| (k.into(), v.into())).collect();
+        let fields = DynamicFields::new(ttl, Box::new(provider));
+        self.configure(|p| p.dynamic_fields.push(Arc::new(fields)));
+        self
+    }
+    
+    /// Stamp every record with a unique, time‑sortable `id` field (a
+    /// [ULID](https://github.com/ulid/spec)) so downstream consumers can deduplicate or key
+    /// exactly‑once processing on it.  IDs are monotonic within the process.
+    pub fn with_record_ids(mut self) -> Self {
+        self.configure(|p| p.record_ids = true);
+        self
+    }
+    
+    /// Add `module`, `file` and `line` of the call site to records logged through the
+    /// [macros](crate::log).
+    pub fn with_source_location(mut self) -> Self {
+        self.configure(|p| p.source_location = true);
+        self
+    }
+    
+    /// Strip `prefix`, such as the build directory of the CI machine, from the `file` of
+    /// [source locations](Self::with_source_location): with `/home/ci/build`,
+    /// `/home/ci/build/src/a.rs` is logged as `src/a.rs`.  May be given several times;
+    /// the longest matching prefix is removed.
+    ///
+    /// ```
+    /// let log = cappie::Logger::new("api")
+    ///     .with_source_location()
+    ///     .with_source_path_prefix(env!("CARGO_MANIFEST_DIR"));
+    /// ```
+    pub fn with_source_path_prefix(mut self, prefix: &str) -> Self {
+        self.configure(|p| p.source_paths.add_prefix(prefix));
+        self
+    }
+    
+    /// Log the `file` of [source locations](Self::with_source_location) from the crate's
+    /// `src` directory on, whatever the directories above it: paths of dependencies in the
+    /// cargo registry and of workspace members become `src/…` too.  The `module` field
+    /// still tells the crates apart.
+    pub fn with_source_crate_root(mut self) -> Self {
+        self.configure(|p| p.source_paths.set_crate_root());
+        self
+    }
+    
+    /// Enforce naming rules on the field keys of every record, see [`KeyPolicy`].
+    pub fn with_key_policy(mut self, policy: KeyPolicy) -> Self {
+        self.configure(|p| p.key_policy = Some(Arc::new(policy)));
+        self
+    }
+    
+    /// Cap the number of fields and the formatted size of records, see [`RecordLimits`].
+    pub fn with_limits(mut self, limits: RecordLimits) -> Self {
+        self.configure(|p| p.limits = Some(limits));
+        self
+    }
+    
+    /// How [`LogBuilder::bytes_raw`] renders binary fields of this logger (default
+    /// [`BytesEncoding::Preview`] of 32 bytes).
+    pub fn with_bytes_encoding(mut self, encoding: BytesEncoding) -> Self {
+        self.configure(|p| p.bytes_encoding = encoding);
+        self
+    }
+    
+    /// Also write `Error` and `Fatal` records to stderr, whatever the configured output, so
+    /// failures are seen in local runs and init scripts even when records go to files or
+    /// the network.  Records of a binary formatter are echoed as JSON.  Leave it off when
+    /// the output already is stderr, or errors show up twice.
+    pub fn with_error_echo(mut self, enabled: bool) -> Self {
+        self.configure(|p| p.echo_errors = enabled);
+        self
+    }
+    
+    /// Catch panics of the formatter and outputs instead of letting them unwind into the
+    /// calling code.  The record is lost and the panic is reported as a
+    /// [diagnostic](crate::diagnostics); the panic hook still runs, so the usual message is
+    /// printed.  Off by default, as some outputs panic on purpose (e.g. an
+    /// [`AppendOnlyFileOutput`](crate::output::AppendOnlyFileOutput) set to
+    /// [`panic_on_tamper`](crate::output::AppendOnlyFileOutput::panic_on_tamper)).
+    pub fn with_panic_isolation(mut self, enabled: bool) -> Self {
+        self.configure(|p| p.isolate_panics = enabled);
+        self
+    }
+    
+    /// Let `governor` raise this logger's minimum level while its output is backed up.
+    /// Children and clones share the governor.
+    pub fn with_governor(mut self, governor: Governor) -> Self {
+        self.configure(|p| p.governor = Some(governor.start()));
+        self
+    }
+    
+    /// Let `guard` raise this logger's minimum level, or pause it, while the disk it
+    /// writes to is nearly full.  Children and clones share the guard.
+    pub fn with_disk_space_guard(mut self, guard: DiskSpaceGuard) -> Self {
+        self.configure(|p| p.disk_space = Some(guard.start()));
         self
     }
     
+    /// Logger that suits where the program runs: colored [pretty](PrettyFormatter) output on
+    /// stderr when stderr is a terminal, ND‑JSON on stdout otherwise (containers, pipes,
+    /// log collectors).  Set `CAPPIE_FORMAT` to `pretty` or `json` to override the guess.
+    ///
+    /// ```
+    /// use cappie::Logger;
+    ///
+    /// let log = Logger::auto("api");
+    /// log.info("listening");
+    /// ```
+    pub fn auto(name: &str) -> Self {
+        use std::io::IsTerminal;
+        
+        let pretty = match std::env::var("CAPPIE_FORMAT").map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+            Ok("pretty") => true,
+            Ok("json") => false,
+            _ => std::io::stderr().is_terminal(),
+        };
+        if pretty {
+            Self::new(name)
+                .with_formatter(Box::new(PrettyFormatter::new()))
+                .with_output(Box::new(StderrOutput))
+        } else {
+            Self::new(name)
+        }
+    }
+    
+    /// Logger for apps on Heroku: [logfmt](LogfmtFormatter::heroku) lines with `at=` and
+    /// `source=` on stdout, without colors or timestamps, as Logplex expects.
+    ///
+    /// ```
+    /// use cappie::Logger;
+    ///
+    /// let log = Logger::heroku("web");
+    /// log.info_with("request", |b| { b.string("path", "/").number("status", 200); });
+    /// // at=info source=web msg=request path=/ status=200
+    /// ```
+    pub fn heroku(name: &str) -> Self {
+        Self::new(name).with_formatter(Box::new(LogfmtFormatter::heroku()))
+    }
+    
     pub fn pretty() -> Self {
         Self::new("app").with_formatter(Box::new(PrettyFormatter::new()))
     }
     
+    /// Create a named child that shares this logger's formatter, output and fields.  The
+    /// pipeline and field maps are reference counted, so only the name is allocated; the
+    /// child copies a map only if it is given fields of its own.
     pub fn child(&self, name: &str) -> Self {
         let child_name = if self.name.is_empty() {
-            name.to_string()
+            intern(name)
         } else {
-            format!("{}.{}", self.name, name)
+            intern(&format!("{}.{}", self.name, name))
         };
         
+        let level = LevelHandle::new(self.level.get());
         Self {
+            member: Member::join(self.member.tree.clone(), &child_name, &level, &self.pipeline),
             name: child_name,
-            level: self.level,
-            formatter: Box::new(JsonFormatter), // Reset to default for simplicity
-            output: Box::new(StdoutOutput), // Reset to default for simplicity
+            level,
+            pipeline: self.pipeline.clone(),
+            base_fields: self.base_fields.clone(),
+            scope_fields: self.scope_fields.clone(),
+            timings: Arc::new(Timings::new(self.timings.interval())),
+        }
+    }
+    
+    /// Create a named child that diverges from this logger in the parts set in `overrides`
+    /// and inherits everything else – level routes, record processing, fields – like
+    /// [`child`](Self::child).
+    ///
+    /// ```
+    /// use cappie::{Logger, LogfmtFormatter, StderrOutput};
+    ///
+    /// let log = Logger::new("web");
+    /// let access = log.child_with("access", |b| {
+    ///     b.formatter(Box::new(LogfmtFormatter::new()))
+    ///         .output(Box::new(StderrOutput))
+    /// });
+    /// let db = log.child("db"); // still JSON on stdout
+    /// ```
+    pub fn child_with<F>(&self, name: &str, overrides: F) -> Self
+    where
+        F: FnOnce(ChildOverrides) -> ChildOverrides,
+    {
+        let overrides = overrides(ChildOverrides::default());
+        let mut child = self.child(name);
+        if let Some(level) = overrides.level {
+            child.level.set(level);
+        }
+        if let Some(formatter) = overrides.formatter {
+            child = child.with_formatter(formatter);
+        }
+        if let Some(output) = overrides.output {
+            child = child.with_output(output);
+        }
+        if overrides.without_routes {
+            child.configure(|p| p.routes.clear());
+        }
+        if !overrides.fields.is_empty() {
+            child = child.with_fields(overrides.fields);
+        }
+        child
+    }
+    
+    /// A [`LoggerHandle`] that does not keep this logger alive.
+    pub fn handle(&self) -> LoggerHandle {
+        LoggerHandle {
+            member: Arc::downgrade(&self.member),
             base_fields: self.base_fields.clone(),
+            scope_fields: self.scope_fields.clone(),
+            timings: self.timings.clone(),
+        }
+    }
+    
+    /// All live loggers created from this logger's root – the root, its children and
+    /// theirs – sorted by name, for admin endpoints and debug dumps.  Loggers sharing a
+    /// name (e.g. one child per request) are listed once.
+    ///
+    /// ```
+    /// use cappie::{Level, Logger};
+    ///
+    /// let root = Logger::new("app");
+    /// let db = root.child("db").with_level(Level::Debug);
+    /// let names: Vec<String> = db.tree().into_iter().map(|info| info.name).collect();
+    /// assert_eq!(names, ["app", "app.db"]);
+    /// ```
+    pub fn tree(&self) -> Vec<LoggerInfo> {
+        let mut loggers: Vec<LoggerInfo> = Vec::new();
+        for member in self.member.tree.members() {
+            if loggers.iter().any(|info| *info.name == *member.name) {
+                continue;
+            }
+            let pipeline = member.pipeline.lock().unwrap_or_else(|e| e.into_inner()).upgrade();
+            let Some(pipeline) = pipeline else { continue };
+            let level = member.level.get();
+            let min = level::with_global(level);
+            loggers.push(LoggerInfo {
+                name: member.name.to_string(),
+                level,
+                effective_level: pipeline.floor().map_or(min, |floor| min.max(floor)),
+                formatter: pipeline.formatter.describe(),
+                output: pipeline.output.describe(),
+                routes: pipeline.routes
+                    .iter()
+                    .map(|route| (describe_levels(&route.levels), route.output.describe()))
+                    .collect(),
+            });
+        }
+        loggers.sort_by(|a, b| a.name.cmp(&b.name));
+        loggers
+    }
+    
+    /// Flush the output and all routes, writing out any records they still buffer.
+    pub fn flush(&self) {
+        self.pipeline.output.flush();
+        for route in &self.pipeline.routes {
+            route.output.flush();
+        }
+    }
+    
+    /// Hierarchical name of this logger (`app.auth` for a child named `auth`).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    
+    /// Level this logger is configured with.  The process‑wide
+    /// [override](crate::set_global_level) lowers it when filtering if it is more verbose,
+    /// and a [governor](Self::with_governor) or
+    /// [disk space guard](Self::with_disk_space_guard) may raise it; use
+    /// [`enabled`](Self::enabled) to ask whether a record would actually be written.
+    pub fn level(&self) -> Level {
+        self.level.get()
+    }
+    
+    /// Whether a record at `level` would be written.  Use it to guard work that is only
+    /// needed for logging:
+    ///
+    /// ```
+    /// use cappie::{Level, Logger};
+    ///
+    /// let log = Logger::new("cache");
+    /// if log.enabled(Level::Trace) {
+    ///     let dump = format!("{:?}", vec![1, 2, 3]);
+    ///     log.trace(&dump);
+    /// }
+    /// ```
+    pub fn enabled(&self, level: Level) -> bool {
+        self.enabled_from(level, self.configured_level())
+    }
+    
+    /// Whether a record at `level` for `target` would be written, see
+    /// [`with_target_level`](Self::with_target_level).
+    pub fn target_enabled(&self, level: Level, target: &str) -> bool {
+        let min = level::with_global(self.target_level(target).unwrap_or_else(|| self.level.get()));
+        self.enabled_from(level, min)
+    }
+    
+    fn target_level(&self, target: &str) -> Option<Level> {
+        self.pipeline.target_levels.iter().find_map(|(prefix, level)| {
+            let rest = target.strip_prefix(prefix.as_str())?;
+            (rest.is_empty() || rest.starts_with("::") || rest.starts_with('.')).then_some(*level)
+        })
+    }
+    
+    /// `enabled` for a configured minimum of `min`, taking the governor and disk space
+    /// guard into account.
+    fn enabled_from(&self, level: Level, min: Level) -> bool {
+        if let Some(disk_space) = self.pipeline.disk_space.as_ref().filter(|d| d.paused()) {
+            // One record still gets through, to carry the report of the pause.
+            return level >= min && disk_space.pending();
+        }
+        match self.pipeline.floor() {
+            Some(floor) => level >= min.max(floor),
+            None => level >= min,
+        }
+    }
+    
+    /// Minimum level before any governor adjustment.
+    fn configured_level(&self) -> Level {
+        level::with_global(self.level.get())
+    }
+    
+    fn log(&self, level: Level, msg: &str, fields: Option<CallFields>) {
+        // Check before reading the clock: filtered‑out calls should cost next to nothing.
+        if self.enabled(level) {
+            self.log_at(level, Timestamp::now(), &[], msg, fields);
+        }
+    }
+    
+    /// Shared body of the `*_with` methods; the field closure only runs if `level` is
+    /// enabled.
+    pub(crate) fn log_with<F>(&self, level: Level, msg: &str, f: F)
+    where
+        F: FnOnce(&mut LogBuilder),
+    {
+        if !self.enabled(level) {
+            return;
         }
+        let mut builder = LogBuilder::new();
+        builder.bytes_encoding = self.pipeline.bytes_encoding;
+        f(&mut builder);
+        self.log(level, msg, Some(builder.into_call_fields()));
     }
     
-    fn should_log(&self, level: Level) -> bool {
-        level >= self.level
+    /// Deliver a record whose level the caller has already checked, after any pending
+    /// governor or disk space report.
+    fn log_at(&self, level: Level, timestamp: Timestamp, tags: &[&str], msg: &str, fields: Option<CallFields>) {
+        let configured = self.configured_level();
+        let reports = [
+            self.pipeline.governor.as_ref().and_then(|g| g.take_report(configured)),
+            self.pipeline.disk_space.as_ref().and_then(|d| d.take_report(configured)),
+        ];
+        for (report, report_fields) in reports.into_iter().flatten() {
+            if Level::Warn >= configured {
+                self.write(Level::Warn, timestamp, &[], report, Some(report_fields.into()));
+            }
+        }
+        if self.pipeline.disk_space.as_ref().is_some_and(|d| d.paused()) || !self.pipeline.sampling.keep(level) {
+            return;
+        }
+        self.write(level, timestamp, tags, msg, fields);
     }
     
-    fn log(&self, level: Level, msg: &str, fields: Option<Map<String, Value>>) {
-        if !self.should_log(level) {
+    /// Format and deliver a record that already passed filtering.  Base, dynamic,
+    /// [context](crate::context), scope and per‑call fields are layered in that order (later
+    /// layers win) and only merged into one map when the pipeline needs one.
+    fn write(&self, level: Level, timestamp: Timestamp, tags: &[&str], msg: &str, fields: Option<CallFields>) {
+        let dynamic = self.dynamic_fields();
+        let context = context::current_fields();
+        let fields = fields.unwrap_or_default();
+        let mut layers = Fields::default();
+        layers.push(&self.base_fields);
+        if let Some(dynamic) = &dynamic {
+            layers.push(dynamic);
+        }
+        if let Some(context) = &context {
+            layers.push(context);
+        }
+        layers.push(&self.scope_fields);
+        layers.push(&fields.map);
+        layers.push_pairs(&fields.pairs);
+        
+        let pipeline = &self.pipeline;
+        let merge = pipeline.key_policy.is_some()
+            || pipeline.limits.is_some()
+            || pipeline.record_ids
+            || pipeline.echo_errors
+            || pipeline.output.needs_fields()
+            || pipeline.routes.iter().any(|route| route.output.needs_fields());
+        if merge {
+            let only_call_fields = self.base_fields.is_empty() && dynamic.is_none() && context.is_none() && self.scope_fields.is_empty();
+            let combined_fields = if only_call_fields {
+                Cow::Owned(fields.into_map())
+            } else {
+                layers.to_map()
+            };
+            self.write_merged(level, timestamp, tags, msg, combined_fields);
             return;
         }
         
-        let mut combined_fields = self.base_fields.clone();
-        if let Some(fields) = fields {
-            for (k, v) in fields {
-                combined_fields.insert(k, v);
+        with_record_buffer(|buf| {
+            let formatted = self.contained("formatter", || {
+                pipeline.formatter.format_fields_into(buf, level, tags, msg, layers, timestamp, &self.name);
+            });
+            if formatted.is_none() {
+                return;
+            }
+            let no_fields = Map::new();
+            self.deliver(&Record {
+                level,
+                timestamp,
+                name: &self.name,
+                msg,
+                tags,
+                fields: &no_fields,
+                formatted: buf,
+                binary: pipeline.formatter.is_binary(),
+            });
+        });
+    }
+    
+    /// [`write`](Self::write) for pipelines whose key policy, limits, record ids or outputs
+    /// need the fields merged into one map.
+    fn write_merged(&self, level: Level, timestamp: Timestamp, tags: &[&str], msg: &str, mut combined_fields: Cow<'_, Map<String, Value>>) {
+        let mut violations = Vec::new();
+        if let Some(policy) = &self.pipeline.key_policy {
+            match policy.action() {
+                OnViolation::Rename => {
+                    if let Some(renamed) = policy.renamed(&combined_fields) {
+                        combined_fields = Cow::Owned(renamed);
+                    }
+                }
+                OnViolation::Warn => violations = policy.new_violations(&combined_fields),
+            }
+        }
+        if let Some(limits) = &self.pipeline.limits {
+            if !limits.limit_fields(level, &self.name, msg, &mut combined_fields) {
+                return;
             }
         }
+        if self.pipeline.record_ids {
+            combined_fields.to_mut().insert("id".to_string(), Value::String(next_ulid()));
+        }
+        
+        let mut msg = Cow::Borrowed(msg);
+        with_record_buffer(|buf| {
+            let pipeline = &self.pipeline;
+            let format = |buf: &mut Vec<u8>, msg: &str, fields: &Map<String, Value>| {
+                format_record_into(&*pipeline.formatter, buf, level, tags, msg, fields, timestamp, &self.name);
+            };
+            let formatted = self.contained("formatter", || {
+                format(buf, &msg, &combined_fields);
+                match &pipeline.limits {
+                    Some(limits) => limits.fit(level, &self.name, buf, &mut msg, &mut combined_fields, format),
+                    None => true,
+                }
+            });
+            if formatted != Some(true) {
+                return;
+            }
+            self.deliver(&Record {
+                level,
+                timestamp,
+                name: &self.name,
+                msg: &msg,
+                tags,
+                fields: &combined_fields,
+                formatted: buf,
+                binary: pipeline.formatter.is_binary(),
+            });
+        });
         
-        let timestamp = Utc::now();
-        let formatted = self.formatter.format(level, msg, &combined_fields, timestamp, &self.name);
-        self.output.write(&formatted);
+        if !violations.is_empty() && self.enabled(Level::Warn) {
+            for (key, expected) in violations {
+                let mut fields = Map::new();
+                fields.insert("key".to_string(), Value::String(key));
+                fields.insert("expected".to_string(), Value::String(expected));
+                self.write(Level::Warn, timestamp, &[], "field key violates naming policy", Some(fields.into()));
+            }
+        }
+    }
+    
+    /// Hand a formatted record to the routes for its level, or the main output, and echo
+    /// errors to stderr if asked to.
+    fn deliver(&self, record: &Record<'_>) {
+        let pipeline = &self.pipeline;
+        let level = record.level;
+        self.contained("output", || formatted::deliver_record(|| {
+            let mut routed = false;
+            for route in pipeline.routes.iter().filter(|r| r.levels.contains(&level)) {
+                route.output.write_record(record);
+                routed = true;
+            }
+            if !routed {
+                pipeline.output.write_record(record);
+            }
+            if pipeline.echo_errors && level >= Level::Error {
+                if record.binary {
+                    let mut json = Vec::new();
+                    format_record_into(&JsonFormatter, &mut json, level, record.tags, record.msg, record.fields, record.timestamp, record.name);
+                    StderrOutput.write(&String::from_utf8_lossy(&json));
+                } else {
+                    StderrOutput.write_record(record);
+                }
+            }
+        }));
+    }
+    
+    /// Run `f`, turning a panic into a [diagnostic](crate::diagnostics) when
+    /// [panic isolation](Self::with_panic_isolation) is on.  `None` if `f` panicked.
+    fn contained<R>(&self, component: &'static str, f: impl FnOnce() -> R) -> Option<R> {
+        if !self.pipeline.isolate_panics {
+            return Some(f());
+        }
+        match panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(result) => Some(result),
+            Err(payload) => {
+                diagnostics::report(Diagnostic::Panicked { component, message: panic_message(payload.as_ref()) });
+                None
+            }
+        }
+    }
+    
+    /// The values of every [dynamic field](crate::dynamic_fields) provider, merged only if
+    /// more than one of them has any.
+    fn dynamic_fields(&self) -> Option<Arc<Map<String, Value>>> {
+        let mut merged: Option<Arc<Map<String, Value>>> = None;
+        for dynamic in &self.pipeline.dynamic_fields {
+            let Some(values) = self.contained("dynamic fields", || dynamic.get()) else {
+                continue;
+            };
+            if values.is_empty() {
+                continue;
+            }
+            match &mut merged {
+                None => merged = Some(values),
+                Some(map) => {
+                    let map = Arc::make_mut(map);
+                    for (k, v) in values.iter() {
+                        map.insert(k.clone(), v.clone());
+                    }
+                }
+            }
+        }
+        merged
+    }
+    
+    /// Log a record with a caller‑supplied timestamp instead of the current time.  Useful
+    /// when replaying historical events, ingesting external data or testing formatters
+    /// deterministically.  Level filtering and base fields apply as usual.  `timestamp` is
+    /// a [`Timestamp`], a `SystemTime`, a chrono `DateTime<Utc>` (feature `chrono`) or a
+    /// `time::OffsetDateTime` (feature `time`).
+    ///
+    /// ```
+    /// use cappie::{Logger, Level, Timestamp};
+    /// use serde_json::Map;
+    ///
+    /// let log = Logger::new("import");
+    /// // 2024-01-15T10:30:00Z
+    /// let ts = Timestamp::from_unix_nanos(1_705_314_600_000_000_000).unwrap();
+    /// log.log_with_time(Level::Info, ts, "replayed event", Map::new());
+    /// ```
+    pub fn log_with_time(&self, level: Level, timestamp: impl Into<Timestamp>, msg: &str, fields: Map<String, Value>) {
+        if self.enabled(level) {
+            self.log_at(level, timestamp.into(), &[], msg, Some(fields.into()));
+        }
+    }
+    
+    /// Log `msg` under `target`, a category finer than the logger name (a module, a
+    /// subsystem…), so one logger can serve many targets without creating a child for each.
+    /// The record carries a `target` field and is filtered by the level set with
+    /// [`with_target_level`](Self::with_target_level).  The macros take a target as
+    /// `info!(target: "db::pool", log, …)`.
+    ///
+    /// ```
+    /// use cappie::{CaptureOutput, Level, Logger};
+    ///
+    /// let capture = CaptureOutput::new();
+    /// let log = Logger::new("api")
+    ///     .with_target_level("db", Level::Debug)
+    ///     .with_output(Box::new(capture.clone()));
+    /// log.log_target(Level::Debug, "db::pool", "connection acquired");
+    /// log.log_target(Level::Debug, "http", "request parsed"); // below the logger's level
+    ///
+    /// assert!(capture.contents().contains(r#""target":"db::pool""#));
+    /// assert_eq!(capture.lines().len(), 1);
+    /// ```
+    pub fn log_target(&self, level: Level, target: &str, msg: &str) {
+        self.log_target_with(level, target, msg, |_| {});
+    }
+    
+    /// [`log_target`](Self::log_target) with fields; the closure only runs if the record
+    /// is written.
+    pub fn log_target_with<F>(&self, level: Level, target: &str, msg: &str, f: F)
+    where
+        F: FnOnce(&mut LogBuilder),
+    {
+        if !self.target_enabled(level, target) {
+            return;
+        }
+        let mut builder = LogBuilder::new();
+        builder.bytes_encoding = self.pipeline.bytes_encoding;
+        builder.string("target", target);
+        f(&mut builder);
+        self.log_at(level, Timestamp::now(), &[], msg, Some(builder.into_call_fields()));
+    }
+    
+    /// Log a `logging started` record describing the configuration this logger actually
+    /// runs with, so there is no guessing later:
+    ///
+    /// * `cappie_version`, `min_level` (the logger's level) and `global_level` if set;
+    /// * `formatter`, `output` and `routes`, as [described](Output::describe) by them;
+    /// * `hostname`, `pid`, `exe` and `profile` (`debug` or `release`).
+    ///
+    /// Base fields are included as usual, which is where build information such as a git
+    /// revision belongs.  The record is written at `Info` regardless of the level.
+    ///
+    /// ```
+    /// use cappie::Logger;
+    ///
+    /// let log = Logger::new("api");
+    /// log.log_startup_banner();
+    /// // {"level":30,…,"msg":"logging started","cappie_version":"0.2.0","min_level":"INFO",…}
+    /// ```
+    pub fn log_startup_banner(&self) {
+        let mut fields = Map::new();
+        fields.insert("cappie_version".to_string(), Value::from(env!("CARGO_PKG_VERSION")));
+        fields.insert("min_level".to_string(), Value::from(self.level().as_str()));
+        if let Some(level) = global_level() {
+            fields.insert("global_level".to_string(), Value::from(level.as_str()));
+        }
+        fields.insert("formatter".to_string(), Value::from(self.pipeline.formatter.describe()));
+        fields.insert("output".to_string(), Value::from(self.pipeline.output.describe()));
+        if !self.pipeline.routes.is_empty() {
+            let routes: Map<String, Value> = self.pipeline.routes
+                .iter()
+                .map(|route| (describe_levels(&route.levels), Value::from(route.output.describe())))
+                .collect();
+            fields.insert("routes".to_string(), Value::Object(routes));
+        }
+        if let Some(hostname) = syslog::hostname() {
+            fields.insert("hostname".to_string(), Value::from(hostname));
+        }
+        fields.insert("pid".to_string(), Value::from(std::process::id()));
+        if let Some(exe) = std::env::current_exe().ok().and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned())) {
+            fields.insert("exe".to_string(), Value::from(exe));
+        }
+        let profile = if cfg!(debug_assertions) { "debug" } else { "release" };
+        fields.insert("profile".to_string(), Value::from(profile));
+        self.write(Level::Info, Timestamp::now(), &[], "logging started", Some(fields.into()));
+    }
+    
+    /// Target of the [logging macros](crate::log).
+    #[doc(hidden)]
+    pub fn log_callsite(&self, callsite: &'static Callsite, args: fmt::Arguments<'_>) {
+        self.log_callsite_target(callsite, None, args);
+    }
+    
+    /// Target of the [logging macros](crate::log) given a `target:`.
+    #[doc(hidden)]
+    pub fn log_callsite_target(&self, callsite: &'static Callsite, target: Option<&str>, args: fmt::Arguments<'_>) {
+        let level = callsite.level();
+        let enabled = match target {
+            Some(target) => self.target_enabled(level, target),
+            None => self.enabled(level),
+        };
+        if enabled {
+            self.write_callsite(callsite, target, args, None);
+        }
+    }
+    
+    /// Target of [`log_once!`](crate::log_once) (`every` is `None`) and
+    /// [`log_every!`](crate::log_every).
+    #[doc(hidden)]
+    pub fn log_callsite_every(&self, callsite: &'static Callsite, every: Option<u64>, args: fmt::Arguments<'_>) {
+        if !self.enabled(callsite.level()) {
+            return;
+        }
+        let count = callsite.hit();
+        let occurrences = match every {
+            None if count == 1 => None,
+            Some(n) if (count - 1).is_multiple_of(n.max(1)) => Some(count),
+            _ => return,
+        };
+        self.write_callsite(callsite, None, args, occurrences);
+    }
+    
+    fn write_callsite(&self, callsite: &'static Callsite, target: Option<&str>, args: fmt::Arguments<'_>, occurrences: Option<u64>) {
+        let level = callsite.level();
+        let msg = match args.as_str() {
+            Some(msg) => Cow::Borrowed(msg),
+            None => Cow::Owned(args.to_string()),
+        };
+        let mut fields = Map::new();
+        if let Some(target) = target {
+            fields.insert("target".to_string(), Value::from(target));
+        }
+        if self.pipeline.source_location {
+            fields.insert("module".to_string(), Value::from(callsite.module_path()));
+            fields.insert("file".to_string(), Value::from(self.pipeline.source_paths.trim(callsite.file())));
+            fields.insert("line".to_string(), Value::from(callsite.line()));
+        }
+        if let Some(occurrences) = occurrences {
+            fields.insert("occurrences".to_string(), Value::from(occurrences));
+        }
+        self.log_at(level, Timestamp::now(), &[], &msg, Some(fields.into()));
     }
     
     pub fn trace(&self, msg: &str) {
@@ -109,9 +1094,7 @@ impl Logger {
     where
         F: FnOnce(&mut LogBuilder),
     {
-        let mut builder = LogBuilder::new();
-        f(&mut builder);
-        self.log(Level::Trace, msg, Some(builder.fields));
+        self.log_with(Level::Trace, msg, f);
     }
     
     pub fn debug(&self, msg: &str) {
@@ -122,9 +1105,7 @@ impl Logger {
     where
         F: FnOnce(&mut LogBuilder),
     {
-        let mut builder = LogBuilder::new();
-        f(&mut builder);
-        self.log(Level::Debug, msg, Some(builder.fields));
+        self.log_with(Level::Debug, msg, f);
     }
     
     pub fn info(&self, msg: &str) {
@@ -135,9 +1116,7 @@ impl Logger {
     where
         F: FnOnce(&mut LogBuilder),
     {
-        let mut builder = LogBuilder::new();
-        f(&mut builder);
-        self.log(Level::Info, msg, Some(builder.fields));
+        self.log_with(Level::Info, msg, f);
     }
     
     pub fn warn(&self, msg: &str) {
@@ -148,9 +1127,7 @@ impl Logger {
     where
         F: FnOnce(&mut LogBuilder),
     {
-        let mut builder = LogBuilder::new();
-        f(&mut builder);
-        self.log(Level::Warn, msg, Some(builder.fields));
+        self.log_with(Level::Warn, msg, f);
     }
     
     pub fn error(&self, msg: &str) {
@@ -161,53 +1138,864 @@ impl Logger {
     where
         F: FnOnce(&mut LogBuilder),
     {
-        let mut builder = LogBuilder::new();
-        f(&mut builder);
-        self.log(Level::Error, msg, Some(builder.fields));
-    }
-    
-    pub fn fatal(&self, msg: &str) {
-        self.log(Level::Fatal, msg, None);
+        self.log_with(Level::Error, msg, f);
     }
     
-    pub fn fatal_with<F>(&self, msg: &str, f: F) 
-    where
-        F: FnOnce(&mut LogBuilder),
-    {
-        let mut builder = LogBuilder::new();
-        f(&mut builder);
-        self.log(Level::Fatal, msg, Some(builder.fields));
-    }
-}
-
-pub struct LogBuilder {
-    fields: Map<String, Value>,
-}
-
-impl LogBuilder {
-    pub fn new() -> Self {
-        Self {
-            fields: Map::new(),
+    /// Log `msg` with string tags, kept apart from the fields: formatters render them on
+    /// their own (`INFO [billing slow-path]: …` in [`PrettyFormatter`], a `tags` array in
+    /// JSON) and [`RouteRule::tag`](crate::output::RouteRule::tag) routes on them.
+    ///
+    /// ```
+    /// use cappie::{CaptureOutput, Logger};
+    ///
+    /// let capture = CaptureOutput::new();
+    /// let log = Logger::new("checkout").with_output(Box::new(capture.clone()));
+    /// log.info_tagged(&["billing", "slow-path"], "charge retried");
+    /// assert!(capture.contents().contains(r#""tags":["billing","slow-path"]"#));
+    /// ```
+    pub fn log_tagged(&self, level: Level, tags: &[&str], msg: &str) {
+        if self.enabled(level) {
+            self.log_at(level, Timestamp::now(), tags, msg, None);
         }
     }
     
-    pub fn field<T: Into<Value>>(&mut self, key: &str, value: T) -> &mut Self {
-        self.fields.insert(key.to_string(), value.into());
-        self
+    pub fn info_tagged(&self, tags: &[&str], msg: &str) {
+        self.log_tagged(Level::Info, tags, msg);
     }
     
-    pub fn string(&mut self, key: &str, value: &str) -> &mut Self {
-        self.fields.insert(key.to_string(), Value::String(value.to_string()));
-        self
+    pub fn warn_tagged(&self, tags: &[&str], msg: &str) {
+        self.log_tagged(Level::Warn, tags, msg);
     }
     
-    pub fn number<T: Into<serde_json::Number>>(&mut self, key: &str, value: T) -> &mut Self {
-        self.fields.insert(key.to_string(), Value::Number(value.into()));
-        self
+    pub fn error_tagged(&self, tags: &[&str], msg: &str) {
+        self.log_tagged(Level::Error, tags, msg);
     }
     
-    pub fn bool(&mut self, key: &str, value: bool) -> &mut Self {
-        self.fields.insert(key.to_string(), Value::Bool(value));
+    /// Log `msg` at `code`'s level with a stable `event_code` field, see [`EventCode`].
+    pub fn event<F>(&self, code: EventCode, msg: &str, f: F)
+    where
+        F: FnOnce(&mut LogBuilder),
+    {
+        self.log_with(code.level(), msg, |b| {
+            b.string("event_code", code.code());
+            f(b);
+        });
+    }
+    
+    /// Log the [message](MessageCatalog) registered under `key`, in this logger's locale,
+    /// with a `message_key` field holding `key`.  The template's placeholders are filled
+    /// from the fields `f` adds.  Without a catalog the message is the key.
+    pub fn log_key<F>(&self, level: Level, key: &str, f: F)
+    where
+        F: FnOnce(&mut LogBuilder),
+    {
+        if !self.enabled(level) {
+            return;
+        }
+        let mut builder = LogBuilder::new();
+        builder.bytes_encoding = self.pipeline.bytes_encoding;
+        f(&mut builder);
+        let mut fields = builder.into_fields();
+        let msg = match &self.pipeline.messages {
+            Some(messages) => messages.render(self.pipeline.locale.as_deref(), key, &fields),
+            None => Cow::Borrowed(key),
+        };
+        fields.insert("message_key".to_string(), Value::String(key.to_string()));
+        self.log_at(level, Timestamp::now(), &[], &msg, Some(fields.into()));
+    }
+    
+    pub fn info_key<F>(&self, key: &str, f: F)
+    where
+        F: FnOnce(&mut LogBuilder),
+    {
+        self.log_key(Level::Info, key, f);
+    }
+    
+    pub fn warn_key<F>(&self, key: &str, f: F)
+    where
+        F: FnOnce(&mut LogBuilder),
+    {
+        self.log_key(Level::Warn, key, f);
+    }
+    
+    pub fn error_key<F>(&self, key: &str, f: F)
+    where
+        F: FnOnce(&mut LogBuilder),
+    {
+        self.log_key(Level::Error, key, f);
+    }
+    
+    /// Start a [`TimedGuard`] that logs `msg` at `level` when dropped, with the elapsed
+    /// monotonic time attached as `duration_ms`/`duration_us`.
+    ///
+    /// ```
+    /// use cappie::{Logger, Level};
+    ///
+    /// let log = Logger::new("db");
+    /// {
+    ///     let _t = log.timed(Level::Debug, "query finished");
+    ///     // ... run the query ...
+    /// } // record emitted here
+    /// ```
+    pub fn timed(&self, level: Level, msg: &str) -> TimedGuard<'_> {
+        TimedGuard {
+            logger: self,
+            level,
+            msg: msg.to_string(),
+            timer: Timer::start(),
+        }
+    }
+    
+    /// Log `msg` the first time this call site is reached and stay silent afterwards, e.g.
+    /// for deprecation notices inside hot paths.  Call sites are tracked process‑wide;
+    /// calls made while `level` is disabled do not count.
+    ///
+    /// Each enabled call looks its call site up in a shared map under a read lock.  In
+    /// loops run by many threads, [`log_once!`](crate::log_once) avoids that: it keeps
+    /// the count in a static at the call site and costs a single atomic add.
+    ///
+    /// ```
+    /// use cappie::Logger;
+    ///
+    /// let log = Logger::new("config");
+    /// for _ in 0..3 {
+    ///     log.warn_once("`timeout` is deprecated, use `timeout_ms`"); // logged once
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn log_once(&self, level: Level, msg: &str) {
+        if self.enabled(level) && call_site::hit(Location::caller()) == 1 {
+            self.log(level, msg, None);
+        }
+    }
+    
+    /// Log `msg` on the first and then every `n`th call from this call site, with the
+    /// number of calls so far attached as `occurrences`.  Useful for progress notes in
+    /// loops; calls made while `level` is disabled do not count.  As with
+    /// [`log_once`](Self::log_once), [`log_every!`](crate::log_every) is the cheaper
+    /// form for loops shared by many threads.
+    ///
+    /// ```
+    /// use cappie::Logger;
+    ///
+    /// let log = Logger::new("import");
+    /// for _ in 0..5000 {
+    ///     log.info_every(1000, "processed batch"); // logged at calls 1, 1001, 2001, …
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn log_every(&self, level: Level, n: u64, msg: &str) {
+        if !self.enabled(level) {
+            return;
+        }
+        let count = call_site::hit(Location::caller());
+        if (count - 1).is_multiple_of(n.max(1)) {
+            self.log_with(level, msg, |b| {
+                b.field("occurrences", count);
+            });
+        }
+    }
+    
+    #[track_caller]
+    pub fn info_once(&self, msg: &str) {
+        self.log_once(Level::Info, msg);
+    }
+    
+    #[track_caller]
+    pub fn warn_once(&self, msg: &str) {
+        self.log_once(Level::Warn, msg);
+    }
+    
+    #[track_caller]
+    pub fn error_once(&self, msg: &str) {
+        self.log_once(Level::Error, msg);
+    }
+    
+    #[track_caller]
+    pub fn debug_every(&self, n: u64, msg: &str) {
+        self.log_every(Level::Debug, n, msg);
+    }
+    
+    #[track_caller]
+    pub fn info_every(&self, n: u64, msg: &str) {
+        self.log_every(Level::Info, n, msg);
+    }
+    
+    #[track_caller]
+    pub fn warn_every(&self, n: u64, msg: &str) {
+        self.log_every(Level::Warn, n, msg);
+    }
+    
+    /// Record a named duration for the periodic timing summary instead of logging it.
+    ///
+    /// Once per [summary interval](Self::with_timing_interval) (default one minute) the
+    /// call that crosses it logs a single `Info` record `"timings"` with one field per name,
+    /// holding `count`, `p50_ms`, `p95_ms` and `max_ms`, and starts over.  Clones and
+    /// [factory](LoggerFactory) loggers share the summary; children keep their own.
+    ///
+    /// ```
+    /// use cappie::Logger;
+    /// use std::time::Instant;
+    ///
+    /// let log = Logger::new("db");
+    /// let start = Instant::now();
+    /// // ... run the query ...
+    /// log.observe("db_query", start.elapsed());
+    /// ```
+    pub fn observe(&self, name: &str, duration: Duration) {
+        if !self.enabled(Level::Info) {
+            return;
+        }
+        if let Some(summary) = self.timings.observe(name, duration) {
+            self.log(Level::Info, "timings", Some(summary.into()));
+        }
+    }
+    
+    /// Log the timing summary now, e.g. before shutting down, rather than waiting for the
+    /// interval.  Does nothing if no durations were observed.
+    pub fn flush_timings(&self) {
+        if let Some(summary) = self.timings.summary() {
+            self.log(Level::Info, "timings", Some(summary.into()));
+        }
+    }
+    
+    /// How often [`observe`](Self::observe) emits its summary.  Starts a fresh aggregator.
+    pub fn with_timing_interval(mut self, interval: Duration) -> Self {
+        self.timings = Arc::new(Timings::new(interval));
         self
     }
-}
\ No newline at end of file
+    
+    /// Log a security‑relevant event at `Warn`, tagged so SIEM rules can match on one
+    /// shape regardless of the source: `security: true` and an `event` object with the
+    /// `action` and a numeric `severity` (0–10, derived from the level).  The fields map
+    /// directly onto the common schemas:
+    ///
+    /// | field            | ECS              | CEF          | LEEF      |
+    /// |------------------|------------------|--------------|-----------|
+    /// | `event.action`   | `event.action`   | Signature ID | `EventID` |
+    /// | `event.severity` | `event.severity` | Severity     | `sev`     |
+    ///
+    /// Further `event` keys set by `f` (e.g. `outcome`) are kept.
+    ///
+    /// ```
+    /// use cappie::Logger;
+    ///
+    /// let log = Logger::new("auth");
+    /// log.security_event("login_failed", |b| {
+    ///     b.string("user", "alice").string("source_ip", "203.0.113.7");
+    /// });
+    /// ```
+    pub fn security_event<F>(&self, action: &str, f: F)
+    where
+        F: FnOnce(&mut LogBuilder),
+    {
+        self.security_event_at(Level::Warn, action, f);
+    }
+    
+    /// [`security_event`](Self::security_event) at an explicit level, e.g. `Info` for a
+    /// successful login or `Error` for a detected intrusion.
+    pub fn security_event_at<F>(&self, level: Level, action: &str, f: F)
+    where
+        F: FnOnce(&mut LogBuilder),
+    {
+        self.log_with(level, action, |b| {
+            f(b);
+            let mut event = match b.take("event") {
+                Some(Value::Object(event)) => event,
+                _ => Map::new(),
+            };
+            event.insert("action".to_string(), Value::from(action));
+            event.insert("severity".to_string(), Value::from(security_severity(level)));
+            b.field_static("event", event).field_static("security", true);
+        });
+    }
+    
+    /// Log a panic caught with [`catch_unwind`](std::panic::catch_unwind) as an `Error`
+    /// record, e.g. at an FFI boundary where unwinding must stop.  Fields:
+    ///
+    /// * `panic` – the payload if it is a string (`panic!("…")`), otherwise a placeholder;
+    /// * `thread` – the current thread's name, or its id if it has none;
+    /// * `backtrace` – captured here (not where the panic happened) when enabled through
+    ///   `RUST_BACKTRACE` / `RUST_LIB_BACKTRACE`.
+    ///
+    /// ```
+    /// use cappie::Logger;
+    ///
+    /// let log = Logger::new("ffi");
+    /// let result = std::panic::catch_unwind(|| -> i32 { panic!("bad input") });
+    /// if let Err(payload) = result {
+    ///     log.log_panic("callback panicked", payload.as_ref());
+    /// }
+    /// ```
+    pub fn log_panic(&self, msg: &str, payload: &(dyn Any + Send)) {
+        let backtrace = Backtrace::capture();
+        let backtrace = (backtrace.status() == BacktraceStatus::Captured).then(|| backtrace.to_string());
+        self.log_panic_with_backtrace(msg, payload, backtrace.as_deref());
+    }
+    
+    /// [`log_panic`](Self::log_panic) with a backtrace taken elsewhere, e.g. in a panic hook.
+    pub(crate) fn log_panic_with_backtrace(&self, msg: &str, payload: &(dyn Any + Send), backtrace: Option<&str>) {
+        self.log_with(Level::Error, msg, |b| {
+            b.string("panic", panic_message(payload));
+            let thread = thread::current();
+            match thread.name() {
+                Some(name) => b.string("thread", name),
+                None => b.string("thread", &format!("{:?}", thread.id())),
+            };
+            if let Some(backtrace) = backtrace {
+                b.string("backtrace", backtrace);
+            }
+        });
+    }
+    
+    /// Run `f`, logging a panic with [`log_panic`](Self::log_panic) and returning `None`
+    /// instead of unwinding further.
+    ///
+    /// ```
+    /// use cappie::Logger;
+    ///
+    /// let log = Logger::new("plugin");
+    /// let value = log.catch_panic("plugin crashed", || 40 + 2);
+    /// assert_eq!(value, Some(42));
+    /// ```
+    pub fn catch_panic<F, R>(&self, msg: &str, f: F) -> Option<R>
+    where
+        F: FnOnce() -> R + UnwindSafe,
+    {
+        match panic::catch_unwind(f) {
+            Ok(result) => Some(result),
+            Err(payload) => {
+                self.log_panic(msg, payload.as_ref());
+                None
+            }
+        }
+    }
+    
+    /// Start a [`Progress`] helper that logs `msg` with throughput and ETA fields while
+    /// items complete.  `total` is the expected number of items, if known.
+    pub fn progress(&self, msg: &str, total: Option<u64>) -> Progress {
+        Progress::new(self, msg, total)
+    }
+    
+    pub fn fatal(&self, msg: &str) {
+        self.log(Level::Fatal, msg, None);
+    }
+    
+    pub fn fatal_with<F>(&self, msg: &str, f: F) 
+    where
+        F: FnOnce(&mut LogBuilder),
+    {
+        self.log_with(Level::Fatal, msg, f);
+    }
+}
+
+/// Upper bound on distinct interned logger names; beyond it names are allocated per logger
+/// so that e.g. one child per request id cannot grow the table without limit.
+const MAX_INTERNED_NAMES: usize = 1024;
+
+static NAMES: Mutex<Option<HashSet<Arc<str>>>> = Mutex::new(None);
+
+/// Returns a shared `Arc<str>` for `name`, so the many loggers created with the same name
+/// (children, factory loggers, clones) point at a single allocation.
+fn intern(name: &str) -> Arc<str> {
+    let mut names = NAMES.lock().unwrap_or_else(|e| e.into_inner());
+    let names = names.get_or_insert_with(HashSet::new);
+    if let Some(existing) = names.get(name) {
+        return existing.clone();
+    }
+    let name: Arc<str> = Arc::from(name);
+    if names.len() < MAX_INTERNED_NAMES {
+        names.insert(name.clone());
+    }
+    name
+}
+
+/// The message of a `panic!` payload.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("non-string panic payload")
+}
+
+/// CEF‑style 0–10 severity for `level`.
+fn security_severity(level: Level) -> u8 {
+    match level {
+        Level::Trace => 0,
+        Level::Debug => 1,
+        Level::Info => 3,
+        Level::Warn => 5,
+        Level::Error => 8,
+        Level::Fatal => 10,
+    }
+}
+
+/// Buffers larger than this are released after use instead of being kept for the next
+/// record, so one huge record doesn't pin its memory for the life of the thread.
+const MAX_RETAINED_BUFFER: usize = 64 * 1024;
+
+thread_local! {
+    static RECORD_BUFFER: RefCell<Vec<u8>> = RefCell::new(Vec::with_capacity(512));
+}
+
+/// A route's levels in Rust range syntax, e.g. `WARN..` or `DEBUG..=INFO`.
+fn describe_levels((start, end): &LevelRange) -> String {
+    let start = match start {
+        Bound::Included(level) | Bound::Excluded(level) => level.as_str(),
+        Bound::Unbounded => "",
+    };
+    match end {
+        Bound::Included(level) => format!("{}..={}", start, level.as_str()),
+        Bound::Excluded(level) => format!("{}..{}", start, level.as_str()),
+        Bound::Unbounded => format!("{}..", start),
+    }
+}
+
+/// Run `f` with this thread's reusable formatting buffer (cleared).  Falls back to a fresh
+/// buffer if the thread‑local one is already in use, e.g. when an output logs itself.
+fn with_record_buffer<F: FnOnce(&mut Vec<u8>)>(f: F) {
+    RECORD_BUFFER.with(|cell| match cell.try_borrow_mut() {
+        Ok(mut buf) => {
+            buf.clear();
+            f(&mut buf);
+            if buf.capacity() > MAX_RETAINED_BUFFER {
+                *buf = Vec::with_capacity(512);
+            }
+        }
+        Err(_) => f(&mut Vec::new()),
+    });
+}
+
+/// What a [`Logger::child_with`] child changes; anything not set is inherited from the
+/// parent.
+#[derive(Default)]
+pub struct ChildOverrides {
+    level: Option<Level>,
+    formatter: Option<Box<dyn Formatter>>,
+    output: Option<Box<dyn Output>>,
+    without_routes: bool,
+    fields: Map<String, Value>,
+}
+
+impl ChildOverrides {
+    pub fn level(mut self, level: Level) -> Self {
+        self.level = Some(level);
+        self
+    }
+    
+    pub fn formatter(mut self, formatter: Box<dyn Formatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+    
+    /// Replaces the parent's main output.  Level [routes](Logger::route) are still
+    /// inherited and take precedence; drop them with [`without_routes`](Self::without_routes).
+    pub fn output(mut self, output: Box<dyn Output>) -> Self {
+        self.output = Some(output);
+        self
+    }
+    
+    /// Send every record to the child's output, ignoring the parent's level routes.
+    pub fn without_routes(mut self) -> Self {
+        self.without_routes = true;
+        self
+    }
+    
+    /// Base field of the child, on top of the inherited ones.
+    pub fn field<T: Into<Value>>(mut self, key: &str, value: T) -> Self {
+        self.fields.insert(key.to_string(), value.into());
+        self
+    }
+}
+
+/// Stamps out short‑lived loggers (one per request, job, connection …) from a template.
+///
+/// Every logger produced shares the template's pipeline, level and base fields; the per‑request
+/// fields live in a separate small map layered on top, so creating one never copies the
+/// template's base fields.
+///
+/// ```
+/// use cappie::{Logger, LoggerFactory};
+/// use serde_json::json;
+///
+/// let factory = LoggerFactory::new(&Logger::new("http").with_field("service", "api"));
+/// let log = factory.logger([("request_id", json!("r-42")), ("method", json!("GET"))]);
+/// log.info("request started");
+/// ```
+#[derive(Clone)]
+pub struct LoggerFactory {
+    template: Logger,
+}
+
+impl LoggerFactory {
+    pub fn new(template: &Logger) -> Self {
+        Self {
+            template: template.clone(),
+        }
+    }
+    
+    /// Create a logger with the template's configuration plus `fields`, which take
+    /// precedence over base fields but not over per‑call fields.
+    pub fn logger<I, K, V>(&self, fields: I) -> Logger
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<Value>,
+    {
+        let mut scope_fields = (*self.template.scope_fields).clone();
+        for (k, v) in fields {
+            scope_fields.insert(k.into(), v.into());
+        }
+        
+        Logger {
+            name: self.template.name.clone(),
+            level: self.template.level.clone(),
+            member: self.template.member.clone(),
+            pipeline: self.template.pipeline.clone(),
+            base_fields: self.template.base_fields.clone(),
+            scope_fields: Arc::new(scope_fields),
+            timings: self.template.timings.clone(),
+        }
+    }
+}
+
+/// Weak reference to a [`Logger`], for plugins and other code that must not keep the
+/// host's outputs alive.  Once the host has dropped the logger (every clone of it),
+/// [`upgrade`](Self::upgrade) returns `None` and the logging methods do nothing.
+///
+/// ```
+/// use cappie::Logger;
+///
+/// let host = Logger::new("host");
+/// let handle = host.handle();
+/// handle.info("plugin loaded");
+///
+/// drop(host);
+/// assert!(handle.upgrade().is_none());
+/// handle.info("ignored");
+/// ```
+#[derive(Clone)]
+pub struct LoggerHandle {
+    member: Weak<Member>,
+    base_fields: Arc<Map<String, Value>>,
+    scope_fields: Arc<Map<String, Value>>,
+    timings: Arc<Timings>,
+}
+
+impl LoggerHandle {
+    /// The logger, with its current pipeline, if the host still has it.
+    pub fn upgrade(&self) -> Option<Logger> {
+        let member = self.member.upgrade()?;
+        let pipeline = member.pipeline.lock().unwrap_or_else(|e| e.into_inner()).upgrade()?;
+        Some(Logger {
+            name: member.name.clone(),
+            level: member.level.clone(),
+            pipeline,
+            base_fields: self.base_fields.clone(),
+            scope_fields: self.scope_fields.clone(),
+            timings: self.timings.clone(),
+            member,
+        })
+    }
+    
+    /// Whether [`upgrade`](Self::upgrade) would still reach the logger.
+    pub fn is_alive(&self) -> bool {
+        self.upgrade().is_some()
+    }
+    
+    pub fn trace(&self, msg: &str) {
+        self.log(Level::Trace, msg);
+    }
+    
+    pub fn debug(&self, msg: &str) {
+        self.log(Level::Debug, msg);
+    }
+    
+    pub fn info(&self, msg: &str) {
+        self.log(Level::Info, msg);
+    }
+    
+    pub fn warn(&self, msg: &str) {
+        self.log(Level::Warn, msg);
+    }
+    
+    pub fn error(&self, msg: &str) {
+        self.log(Level::Error, msg);
+    }
+    
+    pub fn fatal(&self, msg: &str) {
+        self.log(Level::Fatal, msg);
+    }
+    
+    fn log(&self, level: Level, msg: &str) {
+        if let Some(logger) = self.upgrade() {
+            logger.log(level, msg, None);
+        }
+    }
+}
+
+/// Fields a [`LogBuilder`] keeps inline before it allocates; most records have fewer.
+const INLINE_FIELDS: usize = 8;
+
+/// The per‑call fields of a record: a map handed over by the logger itself, or the pairs
+/// of a [`LogBuilder`], no key given twice.
+#[derive(Default)]
+struct CallFields {
+    map: Map<String, Value>,
+    pairs: SmallVec<[(Key, Value); INLINE_FIELDS]>,
+}
+
+impl CallFields {
+    fn into_map(mut self) -> Map<String, Value> {
+        for (key, value) in self.pairs {
+            self.map.insert(key.into_string(), value);
+        }
+        self.map
+    }
+}
+
+impl From<Map<String, Value>> for CallFields {
+    fn from(map: Map<String, Value>) -> Self {
+        Self { map, pairs: SmallVec::new() }
+    }
+}
+
+/// Collects the fields of one record.  Keys given as `&str` are copied; the `*_static`
+/// methods take a [`Key`] instead, which a string literal becomes without allocating.
+/// Setting a key again, either way, replaces its value.
+///
+/// Up to eight fields are kept inline as key/value pairs, so a typical record needs no
+/// map and, with static keys, no allocation for its fields beyond their values.  A ninth
+/// key moves them all into a map, so large records do not pay a scan per field.
+#[derive(Default)]
+pub struct LogBuilder {
+    fields: CallFields,
+    bytes_encoding: BytesEncoding,
+}
+
+impl LogBuilder {
+    pub fn new() -> Self {
+        Self {
+            fields: CallFields::default(),
+            bytes_encoding: BytesEncoding::default(),
+        }
+    }
+    
+    pub(crate) fn into_fields(self) -> Map<String, Value> {
+        self.fields.into_map()
+    }
+    
+    fn into_call_fields(self) -> CallFields {
+        self.fields
+    }
+    
+    fn pair(&mut self, key: &str) -> Option<&mut (Key, Value)> {
+        self.fields.pairs.iter_mut().find(|(k, _)| k.as_str() == key)
+    }
+    
+    /// Whether new keys go to the map, moving the pairs there once they are full.
+    fn spilled(&mut self) -> bool {
+        if self.fields.pairs.len() == INLINE_FIELDS {
+            for (key, value) in self.fields.pairs.drain(..) {
+                self.fields.map.insert(key.into_string(), value);
+            }
+        }
+        !self.fields.map.is_empty()
+    }
+    
+    fn insert(&mut self, key: &str, value: Value) -> &mut Self {
+        if let Some(pair) = self.pair(key) {
+            pair.1 = value;
+        } else if self.spilled() {
+            self.fields.map.insert(key.to_string(), value);
+        } else {
+            self.fields.pairs.push((Key::from(key.to_string()), value));
+        }
+        self
+    }
+    
+    fn insert_static(&mut self, key: Key, value: Value) -> &mut Self {
+        if let Some(pair) = self.pair(key.as_str()) {
+            pair.1 = value;
+        } else if self.spilled() {
+            self.fields.map.insert(key.into_string(), value);
+        } else {
+            self.fields.pairs.push((key, value));
+        }
+        self
+    }
+    
+    /// Remove `key`, returning its value if it was set.
+    fn take(&mut self, key: &str) -> Option<Value> {
+        if let Some(value) = self.fields.map.remove(key) {
+            return Some(value);
+        }
+        let i = self.fields.pairs.iter().position(|(k, _)| k.as_str() == key)?;
+        Some(self.fields.pairs.remove(i).1)
+    }
+    
+    pub fn field<T: Into<Value>>(&mut self, key: &str, value: T) -> &mut Self {
+        self.insert(key, value.into())
+    }
+    
+    pub fn string(&mut self, key: &str, value: &str) -> &mut Self {
+        self.insert(key, Value::String(value.to_string()))
+    }
+    
+    pub fn number<T: Into<serde_json::Number>>(&mut self, key: &str, value: T) -> &mut Self {
+        self.insert(key, Value::Number(value.into()))
+    }
+    
+    pub fn bool(&mut self, key: &str, value: bool) -> &mut Self {
+        self.insert(key, Value::Bool(value))
+    }
+    
+    /// [`field`](Self::field) with a [`Key`], e.g. a string literal, that is not copied.
+    pub fn field_static<T: Into<Value>>(&mut self, key: impl Into<Key>, value: T) -> &mut Self {
+        self.insert_static(key.into(), value.into())
+    }
+    
+    pub fn string_static(&mut self, key: impl Into<Key>, value: &str) -> &mut Self {
+        self.insert_static(key.into(), Value::String(value.to_string()))
+    }
+    
+    pub fn number_static<T: Into<serde_json::Number>>(&mut self, key: impl Into<Key>, value: T) -> &mut Self {
+        self.insert_static(key.into(), Value::Number(value.into()))
+    }
+    
+    pub fn bool_static(&mut self, key: impl Into<Key>, value: bool) -> &mut Self {
+        self.insert_static(key.into(), Value::Bool(value))
+    }
+    
+    /// Attach a duration as fractional milliseconds (`12.5`).
+    pub fn duration(&mut self, key: &str, duration: Duration) -> &mut Self {
+        self.insert(key, Value::from(duration.as_secs_f64() * 1000.0))
+    }
+    
+    /// Attach the time elapsed since `start` as fractional milliseconds, like
+    /// [`duration`](Self::duration).
+    pub fn elapsed_since(&mut self, key: &str, start: Instant) -> &mut Self {
+        self.duration(key, start.elapsed())
+    }
+    
+    /// Attach a wall‑clock time as an RFC 3339 UTC timestamp
+    /// (`2024-01-15T10:30:00.250Z`).
+    pub fn system_time(&mut self, key: &str, time: SystemTime) -> &mut Self {
+        let time: Timestamp = time.into();
+        self.insert(key, Value::String(timestamp::rfc3339_z(&time, Precision::Auto)))
+    }
+    
+    /// Attach an IP address in its usual notation (`10.0.0.1`, `::1`).
+    pub fn ip<A: Into<IpAddr>>(&mut self, key: &str, addr: A) -> &mut Self {
+        self.insert(key, Value::String(addr.into().to_string()))
+    }
+    
+    /// Attach a socket address as `host:port` (`[::1]:8080` for IPv6).
+    pub fn socket_addr<A: Into<SocketAddr>>(&mut self, key: &str, addr: A) -> &mut Self {
+        self.insert(key, Value::String(addr.into().to_string()))
+    }
+    
+    /// Attach a file system path.  Non‑UTF‑8 parts are replaced with `U+FFFD`.
+    pub fn path<P: AsRef<Path>>(&mut self, key: &str, path: P) -> &mut Self {
+        self.insert(key, Value::String(path.as_ref().to_string_lossy().into_owned()))
+    }
+    
+    /// Attach binary data rendered with the logger's
+    /// [bytes encoding](Logger::with_bytes_encoding) – by default a short hex preview
+    /// rather than a `{:?}` dump that can be many times the size of the data.
+    ///
+    /// ```
+    /// use cappie::{BytesEncoding, Logger};
+    ///
+    /// let log = Logger::new("proto").with_bytes_encoding(BytesEncoding::Base64);
+    /// log.debug_with("frame received", |b| {
+    ///     b.bytes_raw("payload", &[0xde, 0xad, 0xbe, 0xef]); // "3q2+7w=="
+    /// });
+    /// ```
+    pub fn bytes_raw(&mut self, key: &str, bytes: &[u8]) -> &mut Self {
+        let encoding = self.bytes_encoding;
+        self.bytes_raw_as(key, bytes, encoding)
+    }
+    
+    /// [`bytes_raw`](Self::bytes_raw) with an explicit encoding.
+    pub fn bytes_raw_as(&mut self, key: &str, bytes: &[u8], encoding: BytesEncoding) -> &mut Self {
+        self.insert(key, encoding.render(bytes))
+    }
+    
+    /// Start a monotonic [`Timer`].  Pair with [`elapsed`](Self::elapsed) once the work is
+    /// done so durations never go negative under wall‑clock adjustments.
+    pub fn timer() -> Timer {
+        Timer::start()
+    }
+    
+    /// Attach the time elapsed since `timer` was started as `duration_ms` and `duration_us`.
+    pub fn elapsed(&mut self, timer: &Timer) -> &mut Self {
+        self.duration_fields(timer.elapsed())
+    }
+    
+    /// Attach an already measured duration as `duration_ms` and `duration_us`.
+    pub fn duration_fields(&mut self, duration: Duration) -> &mut Self {
+        self.field_static("duration_ms", duration.as_millis() as u64).field_static("duration_us", duration.as_micros() as u64)
+    }
+}
+
+/// Monotonic stopwatch backed by [`Instant`], used for the `duration_ms`/`duration_us`
+/// fields.
+///
+/// ```
+/// use cappie::{Logger, LogBuilder};
+///
+/// let log = Logger::new("jobs");
+/// let timer = LogBuilder::timer();
+/// // ... do the work ...
+/// log.info_with("job done", |b| { b.elapsed(&timer); });
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Timer {
+    start: Instant,
+}
+
+impl Timer {
+    pub fn start() -> Self {
+        Self { start: Instant::now() }
+    }
+    
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// Guard returned by [`Logger::timed`]; emits its record with duration fields on drop.
+pub struct TimedGuard<'a> {
+    logger: &'a Logger,
+    level: Level,
+    msg: String,
+    timer: Timer,
+}
+
+impl Drop for TimedGuard<'_> {
+    fn drop(&mut self) {
+        let mut builder = LogBuilder::new();
+        builder.elapsed(&self.timer);
+        self.logger.log(self.level, &self.msg, Some(builder.into_call_fields()));
+    }
+}
+
+/// A key/value pair accepted by [`Logger::with_fields`]: a tuple, or a reference to one,
+/// whose value is then cloned.
+pub trait FieldPair {
+    fn into_field(self) -> (String, Value);
+}
+
+impl<K: Into<String>, V: Into<Value>> FieldPair for (K, V) {
+    fn into_field(self) -> (String, Value) {
+        (self.0.into(), self.1.into())
+    }
+}
+
+impl<K: AsRef<str>, V: Clone + Into<Value>> FieldPair for &(K, V) {
+    fn into_field(self) -> (String, Value) {
+        (self.0.as_ref().to_string(), self.1.clone().into())
+    }
+}
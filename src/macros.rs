@@ -0,0 +1,167 @@
+/// Log a formatted message through a logger at a fixed level.
+///
+/// The level‑specific macros ([`info!`](crate::info), [`warn!`](crate::warn), …) are
+/// usually more convenient.  The message is only formatted if the logger accepts the
+/// level, and calls below [`STATIC_MIN_LEVEL`](crate::STATIC_MIN_LEVEL) are compiled out;
+/// with [`Logger::with_source_location`](crate::Logger::with_source_location) the
+/// record also carries `module`, `file` and `line`.  A leading `target: …` logs under that
+/// target, see [`Logger::log_target`](crate::Logger::log_target).
+///
+/// ```
+/// use cappie::{log, Level, Logger};
+///
+/// let log = Logger::new("server");
+/// log!(log, Level::Info, "listening on port {}", 8080);
+/// log!(target: "tls", log, Level::Info, "certificate reloaded");
+/// ```
+#[macro_export]
+macro_rules! log {
+    (target: $target:expr, $logger:expr, $level:expr, $($arg:tt)+) => {{
+        static CALLSITE: $crate::__private::Callsite =
+            $crate::__private::Callsite::new($level, ::core::module_path!(), ::core::file!(), ::core::line!());
+        if ($level as u8) >= ($crate::STATIC_MIN_LEVEL as u8) && CALLSITE.interested() {
+            $crate::Logger::log_callsite_target(&$logger, &CALLSITE, ::core::option::Option::Some($target), ::core::format_args!($($arg)+));
+        }
+    }};
+    ($logger:expr, $level:expr, $($arg:tt)+) => {{
+        static CALLSITE: $crate::__private::Callsite =
+            $crate::__private::Callsite::new($level, ::core::module_path!(), ::core::file!(), ::core::line!());
+        // Constant, so calls below `STATIC_MIN_LEVEL` are removed entirely.
+        if ($level as u8) >= ($crate::STATIC_MIN_LEVEL as u8) && CALLSITE.interested() {
+            $crate::Logger::log_callsite(&$logger, &CALLSITE, ::core::format_args!($($arg)+));
+        }
+    }};
+}
+
+/// Log a formatted message the first time this call site is reached, like
+/// [`Logger::log_once`](crate::Logger::log_once).  The count lives in a static next to
+/// the call, so an enabled call costs one atomic add and no lock.
+///
+/// ```
+/// use cappie::{log_once, Level, Logger};
+///
+/// let log = Logger::new("config");
+/// for _ in 0..3 {
+///     log_once!(log, Level::Warn, "`{}` is deprecated", "timeout"); // logged once
+/// }
+/// ```
+#[macro_export]
+macro_rules! log_once {
+    ($logger:expr, $level:expr, $($arg:tt)+) => {{
+        static CALLSITE: $crate::__private::Callsite =
+            $crate::__private::Callsite::new($level, ::core::module_path!(), ::core::file!(), ::core::line!());
+        if ($level as u8) >= ($crate::STATIC_MIN_LEVEL as u8) && CALLSITE.interested() {
+            $crate::Logger::log_callsite_every(&$logger, &CALLSITE, ::core::option::Option::None, ::core::format_args!($($arg)+));
+        }
+    }};
+}
+
+/// Log a formatted message on the first and then every `n`th call from this call site,
+/// with `occurrences` attached, like [`Logger::log_every`](crate::Logger::log_every) but
+/// with the count in a static next to the call: one atomic add per enabled call.
+///
+/// ```
+/// use cappie::{log_every, CaptureOutput, Level, Logger};
+///
+/// let capture = CaptureOutput::new();
+/// let log = Logger::new("import").with_output(Box::new(capture.clone()));
+/// for batch in 0..5000 {
+///     log_every!(log, Level::Info, 1000, "processed batch {}", batch); // at calls 1, 1001, …
+/// }
+/// assert_eq!(capture.lines().len(), 5);
+/// assert!(capture.lines()[4].contains(r#""occurrences":4001"#));
+/// ```
+#[macro_export]
+macro_rules! log_every {
+    ($logger:expr, $level:expr, $n:expr, $($arg:tt)+) => {{
+        static CALLSITE: $crate::__private::Callsite =
+            $crate::__private::Callsite::new($level, ::core::module_path!(), ::core::file!(), ::core::line!());
+        if ($level as u8) >= ($crate::STATIC_MIN_LEVEL as u8) && CALLSITE.interested() {
+            $crate::Logger::log_callsite_every(&$logger, &CALLSITE, ::core::option::Option::Some($n), ::core::format_args!($($arg)+));
+        }
+    }};
+}
+
+/// [`log!`](crate::log) at `Trace`.
+#[macro_export]
+macro_rules! trace {
+    (target: $target:expr, $logger:expr, $($arg:tt)+) => {
+        $crate::log!(target: $target, $logger, $crate::Level::Trace, $($arg)+)
+    };
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::log!($logger, $crate::Level::Trace, $($arg)+)
+    };
+}
+
+/// [`log!`](crate::log) at `Debug`.
+#[macro_export]
+macro_rules! debug {
+    (target: $target:expr, $logger:expr, $($arg:tt)+) => {
+        $crate::log!(target: $target, $logger, $crate::Level::Debug, $($arg)+)
+    };
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::log!($logger, $crate::Level::Debug, $($arg)+)
+    };
+}
+
+/// [`log!`](crate::log) at `Info`.
+///
+/// ```
+/// let log = cappie::Logger::new("jobs");
+/// cappie::info!(log, "{} jobs queued", 3);
+/// ```
+#[macro_export]
+macro_rules! info {
+    (target: $target:expr, $logger:expr, $($arg:tt)+) => {
+        $crate::log!(target: $target, $logger, $crate::Level::Info, $($arg)+)
+    };
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::log!($logger, $crate::Level::Info, $($arg)+)
+    };
+}
+
+/// [`log!`](crate::log) at `Warn`.
+#[macro_export]
+macro_rules! warn {
+    (target: $target:expr, $logger:expr, $($arg:tt)+) => {
+        $crate::log!(target: $target, $logger, $crate::Level::Warn, $($arg)+)
+    };
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::log!($logger, $crate::Level::Warn, $($arg)+)
+    };
+}
+
+/// [`log!`](crate::log) at `Error`.
+#[macro_export]
+macro_rules! error {
+    (target: $target:expr, $logger:expr, $($arg:tt)+) => {
+        $crate::log!(target: $target, $logger, $crate::Level::Error, $($arg)+)
+    };
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::log!($logger, $crate::Level::Error, $($arg)+)
+    };
+}
+
+/// The [`BuildInfo`](crate::BuildInfo) a build script passed to this crate with
+/// [`BuildInfo::emit`](crate::BuildInfo::emit).  Without one, `profile` falls back to
+/// `debug` or `release` from `debug_assertions` and the other values are `None`.
+///
+/// ```
+/// let info = cappie::build_info!();
+/// assert!(info.profile.is_some());
+/// ```
+#[macro_export]
+macro_rules! build_info {
+    () => {
+        $crate::BuildInfo {
+            git_sha: ::core::option_env!("CAPPIE_GIT_SHA").map(::std::string::String::from),
+            build_time: ::core::option_env!("CAPPIE_BUILD_TIME").map(::std::string::String::from),
+            profile: ::std::string::String::from(match ::core::option_env!("CAPPIE_PROFILE") {
+                ::core::option::Option::Some(profile) => profile,
+                ::core::option::Option::None if ::core::cfg!(debug_assertions) => "debug",
+                ::core::option::Option::None => "release",
+            })
+            .into(),
+        }
+    };
+}
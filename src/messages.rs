@@ -0,0 +1,101 @@
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Per‑locale message templates looked up by key, for records logged with
+/// [`Logger::log_key`](crate::Logger::log_key).  The record's message is the template
+/// for the logger's [locale](crate::Logger::with_locale) with its `{field}` placeholders
+/// filled in, and the key is kept as a `message_key` field for machines.
+///
+/// ```
+/// use cappie::{CaptureOutput, Logger, MessageCatalog};
+///
+/// let messages = MessageCatalog::new("en")
+///     .with("en", "user.login.success", "{user} signed in")
+///     .with("fr", "user.login.success", "{user} s'est connecté");
+///
+/// let capture = CaptureOutput::new();
+/// let log = Logger::new("auth")
+///     .with_output(Box::new(capture.clone()))
+///     .with_messages(messages)
+///     .with_locale("fr");
+/// log.info_key("user.login.success", |b| {
+///     b.string("user", "alice");
+/// });
+///
+/// let line = capture.contents();
+/// assert!(line.contains(r#""msg":"alice s'est connecté""#));
+/// assert!(line.contains(r#""message_key":"user.login.success""#));
+/// ```
+///
+/// A key missing from the locale falls back to the default locale, then to the key
+/// itself.  `{{` and `}}` write literal braces; placeholders naming no field of the call
+/// are left as they are.
+#[derive(Debug, Clone)]
+pub struct MessageCatalog {
+    default_locale: String,
+    /// Templates by locale, then key.
+    templates: HashMap<String, HashMap<String, String>>,
+}
+
+impl MessageCatalog {
+    pub fn new(default_locale: &str) -> Self {
+        Self { default_locale: default_locale.to_string(), templates: HashMap::new() }
+    }
+
+    pub fn with(mut self, locale: &str, key: &str, template: &str) -> Self {
+        self.templates
+            .entry(locale.to_string())
+            .or_default()
+            .insert(key.to_string(), template.to_string());
+        self
+    }
+
+    pub fn default_locale(&self) -> &str {
+        &self.default_locale
+    }
+
+    /// The template for `key` in `locale`, or in the default locale.
+    pub fn template(&self, locale: Option<&str>, key: &str) -> Option<&str> {
+        let lookup = |locale: &str| self.templates.get(locale)?.get(key).map(String::as_str);
+        locale.and_then(lookup).or_else(|| lookup(&self.default_locale))
+    }
+
+    /// The message for `key` in `locale`, with placeholders taken from `fields`.
+    pub fn render<'a>(&self, locale: Option<&str>, key: &'a str, fields: &Map<String, Value>) -> Cow<'a, str> {
+        match self.template(locale, key) {
+            Some(template) => Cow::Owned(fill(template, fields)),
+            None => Cow::Borrowed(key),
+        }
+    }
+}
+
+fn fill(template: &str, fields: &Map<String, Value>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(i) = rest.find(['{', '}']) {
+        out.push_str(&rest[..i]);
+        let tail = &rest[i..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        let placeholder = tail.strip_prefix('{').and_then(|t| Some((t, t.find('}')?)));
+        match placeholder.and_then(|(t, end)| Some((fields.get(&t[..end])?, end))) {
+            Some((value, end)) => {
+                match value {
+                    Value::String(s) => out.push_str(s),
+                    other => out.push_str(&other.to_string()),
+                }
+                rest = &tail[end + 2..];
+            }
+            None => {
+                out.push_str(&tail[..1]);
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
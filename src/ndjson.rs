@@ -0,0 +1,372 @@
+use crate::diagnostics;
+use crate::output::{line, FilePermissions, Output, Record};
+use serde_json::Value;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const DEFAULT_SEGMENT_SIZE: u64 = 64 * 1024 * 1024;
+const DEFAULT_MAX_SEGMENTS: usize = 10;
+
+/// Local structured‑log store: ND‑JSON records in size‑capped `.jsonl` segments.
+///
+/// For the base path `logs/app` records are appended to `logs/app.jsonl`.  When that file
+/// would grow beyond the [segment size](Self::with_segment_size) it is closed and renamed to
+/// `logs/app.000001.jsonl` (then `000002`, …), optionally gzip‑compressed to
+/// `.jsonl.gz` (feature `compression`), and segments beyond
+/// [`with_max_segments`](Self::with_max_segments) are deleted, oldest first.  A
+/// [byte budget](Self::with_max_total_size) bounds the disk space of the whole store.
+///
+/// [`tail`](Self::tail) reads the most recent records back.  Use it with the default
+/// [`JsonFormatter`](crate::JsonFormatter).
+///
+/// ```no_run
+/// use cappie::Logger;
+/// use cappie::output::NdjsonFileOutput;
+///
+/// let store = NdjsonFileOutput::open("logs/app").unwrap()
+///     .with_segment_size(16 * 1024 * 1024)
+///     .with_max_segments(5);
+/// let log = Logger::new("app").with_output(Box::new(store));
+/// log.info("stored");
+///
+/// for record in NdjsonFileOutput::tail("logs/app", 20).unwrap() {
+///     println!("{}", record["msg"]);
+/// }
+/// ```
+pub struct NdjsonFileOutput {
+    base: PathBuf,
+    segment_size: u64,
+    max_segments: usize,
+    max_total_size: Option<u64>,
+    compress: bool,
+    permissions: FilePermissions,
+    active: Mutex<Active>,
+}
+
+struct Active {
+    file: File,
+    size: u64,
+    next_index: u64,
+    /// Whether [`open`](NdjsonFileOutput::open) created the file, so that permissions set
+    /// afterwards still apply to it.
+    #[cfg_attr(not(unix), allow(dead_code))]
+    created: bool,
+}
+
+impl NdjsonFileOutput {
+    /// Open the store at `base` (a path without extension), creating the directory if
+    /// necessary and continuing the numbering of existing segments.
+    pub fn open<P: AsRef<Path>>(base: P) -> io::Result<Self> {
+        let base = base.as_ref().to_path_buf();
+        if let Some(dir) = base.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let (file, created) = match OpenOptions::new().append(true).create_new(true).open(active_path(&base)) {
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => (open_active(&base, &FilePermissions::default())?, false),
+            result => (result?, true),
+        };
+        let size = file.metadata()?.len();
+        let next_index = segments(&base)?.last().map_or(1, |(index, _)| index + 1);
+        Ok(Self {
+            base,
+            segment_size: DEFAULT_SEGMENT_SIZE,
+            max_segments: DEFAULT_MAX_SEGMENTS,
+            max_total_size: None,
+            compress: false,
+            permissions: FilePermissions::default(),
+            active: Mutex::new(Active { file, size, next_index, created }),
+        })
+    }
+
+    /// Size in bytes at which the active file is rotated (default 64 MiB).
+    pub fn with_segment_size(mut self, bytes: u64) -> Self {
+        self.segment_size = bytes.max(1);
+        self
+    }
+
+    /// Number of closed segments to keep (default 10).  The active file is not counted.
+    pub fn with_max_segments(mut self, count: usize) -> Self {
+        self.max_segments = count;
+        self
+    }
+
+    /// Bytes the store may take on disk, active file and segments together, e.g.
+    /// `1 << 30` for 1 GiB.  On rotation the oldest segments are deleted, whatever
+    /// [`with_max_segments`](Self::with_max_segments) allows, until the rest leaves room
+    /// for a full active file; a budget below the segment size shrinks the segments.  Only
+    /// a single record larger than a segment can overshoot it.
+    pub fn with_max_total_size(mut self, bytes: u64) -> Self {
+        self.max_total_size = Some(bytes);
+        self
+    }
+
+    /// Permission bits for the files of the store, see
+    /// [`FileOutput::with_mode`](crate::FileOutput::with_mode).  Applies to an active file
+    /// that [`open`](Self::open) created; segments keep the mode of the active file they
+    /// were, and compressed ones are given it too.
+    #[cfg(unix)]
+    pub fn with_mode(mut self, mode: u32) -> Self {
+        self.permissions.mode = Some(mode);
+        self.apply_to_created();
+        self
+    }
+
+    /// Owner and/or group for the files of the store, see
+    /// [`FileOutput::with_owner`](crate::FileOutput::with_owner) and
+    /// [`with_mode`](Self::with_mode).
+    #[cfg(unix)]
+    pub fn with_owner(mut self, uid: Option<u32>, gid: Option<u32>) -> Self {
+        self.permissions.owner = Some((uid, gid));
+        self.apply_to_created();
+        self
+    }
+
+    #[cfg(unix)]
+    fn apply_to_created(&self) {
+        let active = self.active.lock().unwrap_or_else(|e| e.into_inner());
+        if active.created {
+            diagnostics::check_write("NdjsonFileOutput", self.permissions.apply(&active.file));
+        }
+    }
+
+    /// Size at which the active file is rotated.
+    fn rotate_at(&self) -> u64 {
+        self.max_total_size.map_or(self.segment_size, |budget| self.segment_size.min(budget).max(1))
+    }
+
+    /// Gzip closed segments (on a background thread, so logging is not held up).
+    #[cfg(feature = "compression")]
+    pub fn with_compression(mut self) -> Self {
+        self.compress = true;
+        self
+    }
+
+    /// The last `n` records of the store at `base`, oldest first, read across segments.
+    /// Lines that are not valid JSON are skipped.
+    pub fn tail<P: AsRef<Path>>(base: P, n: usize) -> io::Result<Vec<Value>> {
+        let base = base.as_ref();
+        let mut files = vec![active_path(base)];
+        files.extend(segments(base)?.into_iter().rev().map(|(_, path)| path));
+
+        let mut newest_first: Vec<Value> = Vec::new();
+        for path in files {
+            if newest_first.len() >= n {
+                break;
+            }
+            let records = match read_records(&path) {
+                Ok(records) => records,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+            let wanted = n - newest_first.len();
+            newest_first.extend(records.into_iter().rev().take(wanted));
+        }
+        newest_first.reverse();
+        Ok(newest_first)
+    }
+
+    fn append(&self, bytes: &[u8]) -> io::Result<()> {
+        let mut active = self.active.lock().unwrap_or_else(|e| e.into_inner());
+        if active.size > 0 && active.size + bytes.len() as u64 > self.rotate_at() {
+            // A failed rotation keeps writing to the current file rather than losing records.
+            if let Err(error) = self.rotate(&mut active) {
+                diagnostics::report(diagnostics::Diagnostic::WriteFailed { output: "NdjsonFileOutput", error: &error });
+            }
+        }
+        active.file.write_all(bytes)?;
+        active.size += bytes.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&self, active: &mut Active) -> io::Result<()> {
+        let segment = segment_path(&self.base, active.next_index);
+        fs::rename(active_path(&self.base), &segment)?;
+        active.file = open_active(&self.base, &self.permissions)?;
+        active.size = 0;
+        active.next_index += 1;
+
+        self.prune()?;
+        if self.compress {
+            compress_in_background(segment, self.permissions);
+        }
+        Ok(())
+    }
+
+    fn prune(&self) -> io::Result<()> {
+        let segments = segments(&self.base)?;
+        let mut excess = segments.len().saturating_sub(self.max_segments);
+        if let Some(budget) = self.max_total_size {
+            // Leave room for the active file to fill up.
+            let room = budget.saturating_sub(self.rotate_at());
+            let sizes: Vec<u64> = segments.iter().map(|(index, _)| self.segment_bytes(*index)).collect();
+            let mut total: u64 = sizes.iter().sum();
+            let mut over = 0;
+            while total > room {
+                total -= sizes[over];
+                over += 1;
+            }
+            excess = excess.max(over);
+        }
+        for (index, _) in &segments[..excess] {
+            // Either file may exist while a segment is being compressed.
+            let plain = segment_path(&self.base, *index);
+            let _ = fs::remove_file(with_suffix(&plain, ".gz"));
+            let _ = fs::remove_file(plain);
+        }
+        Ok(())
+    }
+
+    /// Disk space of segment `index`, counting both files while it is being compressed.
+    fn segment_bytes(&self, index: u64) -> u64 {
+        let plain = segment_path(&self.base, index);
+        [with_suffix(&plain, ".gz"), plain]
+            .iter()
+            .filter_map(|path| fs::metadata(path).ok())
+            .map(|metadata| metadata.len())
+            .sum()
+    }
+}
+
+impl Output for NdjsonFileOutput {
+    fn write(&self, message: &str) {
+        diagnostics::check_write("NdjsonFileOutput", self.append(&line(message)));
+    }
+
+    fn write_bytes(&self, bytes: &[u8]) {
+        diagnostics::check_write("NdjsonFileOutput", self.append(bytes));
+    }
+
+    fn try_write_record(&self, record: &Record<'_>) -> io::Result<()> {
+        self.append(&record.bytes())
+    }
+
+    fn needs_fields(&self) -> bool {
+        false
+    }
+}
+
+fn active_path(base: &Path) -> PathBuf {
+    with_suffix(base, ".jsonl")
+}
+
+fn segment_path(base: &Path, index: u64) -> PathBuf {
+    with_suffix(base, &format!(".{:06}.jsonl", index))
+}
+
+fn with_suffix(base: &Path, suffix: &str) -> PathBuf {
+    let mut path = base.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+fn open_active(base: &Path, permissions: &FilePermissions) -> io::Result<File> {
+    permissions.open_append(&active_path(base))
+}
+
+/// Closed segments of `base`, ordered by index.  While a segment is being compressed both
+/// files exist; the plain one is listed.
+fn segments(base: &Path) -> io::Result<Vec<(u64, PathBuf)>> {
+    let dir = match base.parent().filter(|d| !d.as_os_str().is_empty()) {
+        Some(dir) => dir.to_path_buf(),
+        None => PathBuf::from("."),
+    };
+    let stem = match base.file_name().and_then(|n| n.to_str()) {
+        Some(stem) => format!("{}.", stem),
+        None => return Ok(Vec::new()),
+    };
+
+    let mut found: Vec<(u64, PathBuf)> = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let Some(rest) = name.to_str().and_then(|n| n.strip_prefix(&stem)) else {
+            continue;
+        };
+        let (index, compressed) = match (rest.strip_suffix(".jsonl"), rest.strip_suffix(".jsonl.gz")) {
+            (Some(index), _) => (index, false),
+            (_, Some(index)) => (index, true),
+            _ => continue,
+        };
+        // Only names `segment_path` produces: a neighbouring store such as `app.2024`
+        // has its active file at `app.2024.jsonl`, which must not be taken for segment 2024.
+        let Some(index) = index.parse::<u64>().ok().filter(|i| format!("{:06}", i) == index) else {
+            continue;
+        };
+        match found.iter_mut().find(|(i, _)| *i == index) {
+            Some(_) if compressed => {}
+            Some(existing) => existing.1 = entry.path(),
+            None => found.push((index, entry.path())),
+        }
+    }
+    found.sort_by_key(|(index, _)| *index);
+    Ok(found)
+}
+
+fn read_records(path: &Path) -> io::Result<Vec<Value>> {
+    let file = File::open(path)?;
+    let reader: Box<dyn Read> = if path.extension().is_some_and(|ext| ext == "gz") {
+        gzip_reader(file)?
+    } else {
+        Box::new(file)
+    };
+    let mut records = Vec::new();
+    for line in BufReader::new(reader).lines() {
+        if let Ok(record) = serde_json::from_str(&line?) {
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+
+#[cfg(feature = "compression")]
+pub(crate) fn gzip_reader(file: File) -> io::Result<Box<dyn Read>> {
+    Ok(Box::new(flate2::read::MultiGzDecoder::new(file)))
+}
+
+#[cfg(not(feature = "compression"))]
+pub(crate) fn gzip_reader(_file: File) -> io::Result<Box<dyn Read>> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "reading .gz segments requires the `compression` feature"))
+}
+
+#[cfg(feature = "compression")]
+fn compress_in_background(segment: PathBuf, permissions: FilePermissions) {
+    let _ = std::thread::Builder::new()
+        .name("cappie-compress".to_string())
+        .spawn(move || {
+            let _ = compress(&segment, &permissions);
+        });
+}
+
+#[cfg(not(feature = "compression"))]
+fn compress_in_background(_segment: PathBuf, _permissions: FilePermissions) {}
+
+/// Write `segment.gz` next to the plain segment via a temporary file, then remove the
+/// plain one.  Readers see either a complete plain or a complete compressed segment.
+#[cfg(feature = "compression")]
+fn compress(segment: &Path, permissions: &FilePermissions) -> io::Result<()> {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    let gz = with_suffix(segment, ".gz");
+    let tmp = with_suffix(segment, ".gz.tmp");
+    let mut source = File::open(segment)?;
+    let result = (|| {
+        let file = File::create(&tmp)?;
+        permissions.apply(&file)?;
+        let mut encoder = GzEncoder::new(file, Compression::default());
+        io::copy(&mut source, &mut encoder)?;
+        encoder.finish()?.sync_all()?;
+        fs::rename(&tmp, &gz)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+        return result;
+    }
+    match fs::remove_file(segment) {
+        // Pruned while we were compressing it: the compressed copy must go as well.
+        Err(e) if e.kind() == io::ErrorKind::NotFound => fs::remove_file(&gz),
+        result => result,
+    }
+}
@@ -0,0 +1,140 @@
+use crate::diagnostics;
+use crate::level::Level;
+use crate::output::{Output, Record};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Shows records as desktop notifications – handy for developer tools and long local
+/// builds, where a failure would otherwise scroll by unnoticed.  Feature `notifications`.
+///
+/// Records below the [minimum level](Self::with_min_level) (default `Warn`) are ignored.
+/// The title is the level and logger name, the body the message.  To avoid a storm of
+/// pop‑ups at most one notification is shown per [interval](Self::with_interval)
+/// (default 2 seconds); the rest are dropped.  Usually combined with a regular output
+/// through [`MultiOutput`](crate::MultiOutput).
+///
+/// Notifications are shown by the platform's own tool, run in the background:
+/// `notify-send` on Linux and the BSDs, `osascript` on macOS and PowerShell toasts on
+/// Windows.  Where the tool is missing nothing is shown.
+///
+/// ```no_run
+/// use cappie::{Logger, MultiOutput, StdoutOutput};
+/// use cappie::output::NotificationOutput;
+///
+/// let output = MultiOutput::new()
+///     .add_output(Box::new(StdoutOutput))
+///     .add_output(Box::new(NotificationOutput::new("build")));
+/// let log = Logger::new("build").with_output(Box::new(output));
+/// log.error("tests failed");
+/// ```
+pub struct NotificationOutput {
+    app_name: String,
+    min_level: Level,
+    interval: Duration,
+    last_shown: Mutex<Option<Instant>>,
+    dropped: AtomicU64,
+}
+
+impl NotificationOutput {
+    /// `app_name` is shown as the sender where the platform supports it.
+    pub fn new(app_name: &str) -> Self {
+        Self {
+            app_name: app_name.to_string(),
+            min_level: Level::Warn,
+            interval: DEFAULT_INTERVAL,
+            last_shown: Mutex::new(None),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    pub fn with_min_level(mut self, level: Level) -> Self {
+        self.min_level = level;
+        self
+    }
+
+    /// Minimum time between two notifications.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    fn notify(&self, level: Level, title: String, body: String) {
+        {
+            let mut last = self.last_shown.lock().unwrap_or_else(|e| e.into_inner());
+            if last.is_some_and(|at| at.elapsed() < self.interval) {
+                drop(last);
+                let total = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+                diagnostics::dropped("NotificationOutput", "rate limited", total);
+                return;
+            }
+            *last = Some(Instant::now());
+        }
+        let mut command = command(&self.app_name, level, &title, &body);
+        command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+        // Wait on a separate thread so logging is not held up and no zombie is left.
+        let _ = thread::Builder::new()
+            .name("cappie-notify".to_string())
+            .spawn(move || {
+                let _ = command.status();
+            });
+    }
+}
+
+impl Output for NotificationOutput {
+    /// Text without a level is shown as is, titled with the app name.
+    fn write(&self, message: &str) {
+        self.notify(self.min_level, self.app_name.clone(), message.to_string());
+    }
+
+    fn write_record(&self, record: &Record<'_>) {
+        if record.level >= self.min_level {
+            self.notify(record.level, format!("{} {}", record.level.as_str(), record.name), record.msg.to_string());
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn command(_app_name: &str, _level: Level, title: &str, body: &str) -> Command {
+    // Passed as arguments rather than spliced into the script, so no quoting is needed.
+    let mut command = Command::new("osascript");
+    command
+        .args(["-e", "on run argv", "-e", "display notification (item 2 of argv) with title (item 1 of argv)", "-e", "end run"])
+        .args([title, body]);
+    command
+}
+
+#[cfg(windows)]
+fn command(_app_name: &str, _level: Level, title: &str, body: &str) -> Command {
+    const SCRIPT: &str = "\
+        [Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null; \
+        $xml = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02); \
+        $text = $xml.GetElementsByTagName('text'); \
+        $text.Item(0).AppendChild($xml.CreateTextNode($env:CAPPIE_TITLE)) > $null; \
+        $text.Item(1).AppendChild($xml.CreateTextNode($env:CAPPIE_BODY)) > $null; \
+        $toast = [Windows.UI.Notifications.ToastNotification]::new($xml); \
+        [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('PowerShell').Show($toast)";
+    // Title and body travel in environment variables so they are never parsed as script.
+    let mut command = Command::new("powershell");
+    command
+        .args(["-NoProfile", "-NonInteractive", "-Command", SCRIPT])
+        .env("CAPPIE_TITLE", title)
+        .env("CAPPIE_BODY", body);
+    command
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn command(app_name: &str, level: Level, title: &str, body: &str) -> Command {
+    let urgency = if level >= Level::Error { "critical" } else { "normal" };
+    let mut command = Command::new("notify-send");
+    command
+        .arg(format!("--app-name={}", app_name))
+        .arg(format!("--urgency={}", urgency))
+        .arg("--")
+        .args([title, body]);
+    command
+}
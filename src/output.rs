@@ -1,16 +1,332 @@
-use std::io::Write;
-use std::fs::OpenOptions;
-use std::path::Path;
+use std::borrow::Cow;
+use std::io::{self, Write};
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
+use crate::console::{self, Stream};
+use crate::diagnostics;
+use crate::error::BuildError;
+use crate::file_lock::FileLock;
+use crate::flush::{self, Flush};
+use crate::formatted;
+use crate::formatter::short_type_name;
+use crate::level::Level;
+use crate::timestamp::{self, Timestamp};
+use serde_json::{Map, Value};
+
+pub use crate::append_only::AppendOnlyFileOutput;
+pub use crate::async_output::{AsyncOutput, AsyncStats, AsyncStatsHandle};
+pub use crate::dead_letter::DeadLetterOutput;
+pub use crate::formatted::{DualOutput, FormattedOutput};
+pub use crate::log_store::{LogQuery, LogStore, StoredRecord};
+pub use crate::ndjson::NdjsonFileOutput;
+pub use crate::partition::PartitionedOutput;
+pub use crate::router::{RouteRule, RouterOutput};
+pub use crate::snapshot::CaptureOutput;
+#[cfg(feature = "log")]
+pub use crate::log_bridge::LogCrateOutput;
+#[cfg(feature = "log4rs")]
+pub use crate::log4rs_output::Log4rsOutput;
+#[cfg(feature = "mmap")]
+pub use crate::mmap::MmapFileOutput;
+#[cfg(feature = "notifications")]
+pub use crate::notification::NotificationOutput;
+#[cfg(feature = "sse")]
+pub use crate::sse::SseOutput;
+#[cfg(unix)]
+pub use crate::writev::WritevOutput;
+
+/// Destination for formatted records.
+///
+/// **Line atomicity:** the built‑in outputs write every record together with its trailing
+/// newline in a single `write` call while holding the stream lock, so records from
+/// concurrently logging threads never interleave mid‑line.  Files are opened in append mode,
+/// which extends that guarantee to other processes for records below the platform's atomic
+/// append size (`PIPE_BUF`, usually 4 KiB, for pipes; typically much larger for local files).
 pub trait Output: Send + Sync {
     fn write(&self, message: &str);
+    
+    /// Write a binary record exactly as given – no newline is appended and nothing is
+    /// re‑encoded.  Used for records produced by a [binary formatter](crate::Formatter::is_binary)
+    /// (MessagePack, protobuf, compressed frames …).
+    ///
+    /// The default falls back to [`write`](Self::write) with a lossy UTF‑8 conversion, so
+    /// text‑only outputs keep working, but binary data will not survive that route.
+    fn write_bytes(&self, bytes: &[u8]) {
+        self.write(&String::from_utf8_lossy(bytes));
+    }
+    
+    /// Push out anything the output buffers internally.  Called by
+    /// [`Logger::flush`](crate::Logger::flush); unbuffered outputs can ignore it.
+    fn flush(&self) {}
+    
+    /// Like [`flush`](Self::flush), but report whether buffered records reached their
+    /// destination, for callers that must know, such as [`AuditLogger`](crate::AuditLogger).
+    /// The default flushes and returns `Ok`; outputs that sync to disk return the I/O error.
+    fn try_flush(&self) -> io::Result<()> {
+        self.flush();
+        Ok(())
+    }
+    
+    /// Check that the output can work, used by
+    /// [`LoggerBuilder::build`](crate::LoggerBuilder::build).  The default accepts
+    /// everything.
+    fn validate(&self) -> Result<(), BuildError> {
+        Ok(())
+    }
+    
+    /// Write a formatted record, with the level, name and fields it was made from at hand.
+    /// This is what the [`Logger`](crate::Logger) calls; outputs that route or filter by
+    /// record contents override it.  The default passes the formatted bytes to
+    /// [`write_bytes`](Self::write_bytes) for binary records and to [`write`](Self::write)
+    /// otherwise.
+    fn write_record(&self, record: &Record<'_>) {
+        if record.binary {
+            self.write_bytes(record.formatted);
+        } else {
+            match std::str::from_utf8(record.formatted) {
+                Ok(formatted) => self.write(formatted),
+                Err(_) => self.write(&String::from_utf8_lossy(record.formatted)),
+            }
+        }
+    }
+    
+    /// Like [`write_record`](Self::write_record), but report whether the record was
+    /// delivered, so wrappers such as [`DeadLetterOutput`] can act on failures.  The
+    /// default cannot tell and always returns `Ok`; the built‑in stream and file outputs
+    /// return the I/O error.
+    fn try_write_record(&self, record: &Record<'_>) -> io::Result<()> {
+        self.write_record(record);
+        Ok(())
+    }
+    
+    /// Whether [`write_record`](Self::write_record) reads [`Record::fields`].  When no
+    /// output of a logger does, the logger formats straight from its field layers and
+    /// leaves `fields` empty instead of merging them per record.  The default says yes;
+    /// outputs that only write the formatted record return `false`.
+    fn needs_fields(&self) -> bool {
+        true
+    }
+    
+    /// Short description for [`Logger::log_startup_banner`](crate::Logger::log_startup_banner).
+    /// The default is the type name without its module path; outputs that wrap others
+    /// list them.
+    fn describe(&self) -> String {
+        short_type_name(std::any::type_name::<Self>())
+    }
+}
+
+/// A record on its way to an [`Output`]: the formatter's result plus what it was made from.
+#[derive(Debug, Clone, Copy)]
+pub struct Record<'a> {
+    pub level: Level,
+    pub timestamp: Timestamp,
+    pub name: &'a str,
+    pub msg: &'a str,
+    /// Labels given with [`Logger::log_tagged`](crate::Logger::log_tagged), kept apart from
+    /// the fields.
+    pub tags: &'a [&'a str],
+    /// Base, context, scope and per‑call fields merged, or empty if none of the logger's
+    /// outputs [needs them](Output::needs_fields).
+    pub fields: &'a Map<String, Value>,
+    /// The formatted record, without trailing newline for text formatters.
+    pub formatted: &'a [u8],
+    /// Whether `formatted` comes from a [binary formatter](crate::Formatter::is_binary).
+    pub binary: bool,
+}
+
+impl Record<'_> {
+    /// The text of a text record, lossily converted if the formatter produced invalid
+    /// UTF‑8.
+    pub fn text(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(self.formatted)
+    }
+    
+    /// What a byte‑oriented output writes: binary records as they are, text records with a
+    /// trailing newline.
+    pub(crate) fn bytes(&self) -> Cow<'_, [u8]> {
+        if self.binary {
+            Cow::Borrowed(self.formatted)
+        } else {
+            let mut line = Vec::with_capacity(self.formatted.len() + 1);
+            line.extend_from_slice(self.formatted);
+            line.push(b'\n');
+            Cow::Owned(line)
+        }
+    }
+}
+
+/// Write `record` to a standard stream, with ANSI escapes stripped where the stream does
+/// not understand them.
+fn write_to_stream(stream: Stream, out: &mut dyn Write, record: &Record<'_>) -> io::Result<()> {
+    if record.binary {
+        out.write_all(record.formatted)
+    } else {
+        out.write_all(&line(&console::for_stream(stream, &record.text())))
+    }
+}
+
+/// `message` plus a trailing newline in one buffer, ready for a single write call.
+pub(crate) fn line(message: &str) -> Vec<u8> {
+    let mut line = Vec::with_capacity(message.len() + 1);
+    line.extend_from_slice(message.as_bytes());
+    line.push(b'\n');
+    line
 }
 
 pub struct StdoutOutput;
 
 impl Output for StdoutOutput {
     fn write(&self, message: &str) {
-        println!("{}", message);
+        let message = console::for_stream(Stream::Stdout, message);
+        diagnostics::check_write("StdoutOutput", io::stdout().lock().write_all(&line(&message)));
+    }
+    
+    fn write_bytes(&self, bytes: &[u8]) {
+        diagnostics::check_write("StdoutOutput", io::stdout().lock().write_all(bytes));
+    }
+    
+    fn try_write_record(&self, record: &Record<'_>) -> io::Result<()> {
+        write_to_stream(Stream::Stdout, &mut io::stdout().lock(), record)
+    }
+    
+    fn needs_fields(&self) -> bool {
+        false
+    }
+}
+
+impl StdoutOutput {
+    /// A stdout output that batches records in its own buffer, see [`BufferMode`].
+    pub fn buffered(mode: BufferMode) -> BufferedStdoutOutput {
+        BufferedStdoutOutput::new(mode)
+    }
+}
+
+/// How [`BufferedStdoutOutput`] batches records before handing them to stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferMode {
+    /// Every record is written to the locked stdout handle right away and flushed – the
+    /// right choice for interactive use.
+    Line,
+    /// Records are collected until roughly this many bytes are pending and then written in
+    /// one go.  Much higher throughput when stdout is piped to a collector, at the cost of
+    /// records sitting in memory until the next flush.
+    Block(usize),
+}
+
+/// Stdout output with explicit buffering control.  Writes go straight to the locked stdout
+/// handle as raw bytes – no `println!` formatting machinery involved.
+///
+/// In [`BufferMode::Block`] pending records are written when the buffer fills up, on
+/// [`Output::flush`], when the output is dropped, and by [`flush_all`](crate::flush_all)
+/// (see [`install_crash_handlers`](crate::install_crash_handlers)).
+///
+/// ```
+/// use cappie::{Logger, StdoutOutput};
+/// use cappie::output::BufferMode;
+///
+/// let log = Logger::new("batch")
+///     .with_output(Box::new(StdoutOutput::buffered(BufferMode::Block(64 * 1024))));
+/// log.info("queued");
+/// log.flush();
+/// ```
+pub struct BufferedStdoutOutput {
+    mode: BufferMode,
+    buffer: Arc<StdoutBuffer>,
+}
+
+/// The pending bytes of a [`BufferedStdoutOutput`], shared with the
+/// [crash flush registry](crate::flush_all).
+struct StdoutBuffer {
+    pending: Mutex<Vec<u8>>,
+}
+
+impl StdoutBuffer {
+    fn drain(pending: &mut Vec<u8>) {
+        if pending.is_empty() {
+            return;
+        }
+        let mut out = io::stdout().lock();
+        diagnostics::check_write("BufferedStdoutOutput", out.write_all(pending).and_then(|()| out.flush()));
+        pending.clear();
+    }
+}
+
+impl Flush for StdoutBuffer {
+    fn flush_now(&self) {
+        if let Some(mut pending) = flush::lock_briefly(&self.pending) {
+            Self::drain(&mut pending);
+        }
+    }
+}
+
+impl BufferedStdoutOutput {
+    pub fn new(mode: BufferMode) -> Self {
+        let capacity = match mode {
+            BufferMode::Line => 0,
+            BufferMode::Block(size) => size,
+        };
+        let buffer = Arc::new(StdoutBuffer {
+            pending: Mutex::new(Vec::with_capacity(capacity)),
+        });
+        if let BufferMode::Block(_) = mode {
+            flush::register(&(buffer.clone() as Arc<dyn Flush>));
+        }
+        Self { mode, buffer }
+    }
+    
+    fn push(&self, bytes: &[u8], newline: bool) {
+        let size = match self.mode {
+            BufferMode::Line => 0,
+            BufferMode::Block(size) => size,
+        };
+        let mut pending = self.buffer.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.extend_from_slice(bytes);
+        if newline {
+            pending.push(b'\n');
+        }
+        if pending.len() >= size {
+            StdoutBuffer::drain(&mut pending);
+        }
+    }
+}
+
+impl Output for BufferedStdoutOutput {
+    fn write(&self, message: &str) {
+        let message = &*console::for_stream(Stream::Stdout, message);
+        match self.mode {
+            BufferMode::Line => {
+                let mut out = io::stdout().lock();
+                diagnostics::check_write("BufferedStdoutOutput", out.write_all(&line(message)).and_then(|()| out.flush()));
+            }
+            BufferMode::Block(_) => self.push(message.as_bytes(), true),
+        }
+    }
+    
+    fn write_bytes(&self, bytes: &[u8]) {
+        match self.mode {
+            BufferMode::Line => {
+                let mut out = io::stdout().lock();
+                diagnostics::check_write("BufferedStdoutOutput", out.write_all(bytes).and_then(|()| out.flush()));
+            }
+            BufferMode::Block(_) => self.push(bytes, false),
+        }
+    }
+    
+    fn flush(&self) {
+        let mut pending = self.buffer.pending.lock().unwrap_or_else(|e| e.into_inner());
+        StdoutBuffer::drain(&mut pending);
+    }
+    
+    fn needs_fields(&self) -> bool {
+        false
+    }
+}
+
+impl Drop for BufferedStdoutOutput {
+    fn drop(&mut self) {
+        self.flush();
     }
 }
 
@@ -18,34 +334,427 @@ pub struct StderrOutput;
 
 impl Output for StderrOutput {
     fn write(&self, message: &str) {
-        eprintln!("{}", message);
+        let message = console::for_stream(Stream::Stderr, message);
+        diagnostics::check_write("StderrOutput", io::stderr().lock().write_all(&line(&message)));
+    }
+    
+    fn write_bytes(&self, bytes: &[u8]) {
+        diagnostics::check_write("StderrOutput", io::stderr().lock().write_all(bytes));
+    }
+    
+    fn try_write_record(&self, record: &Record<'_>) -> io::Result<()> {
+        write_to_stream(Stream::Stderr, &mut io::stderr().lock(), record)
+    }
+    
+    fn needs_fields(&self) -> bool {
+        false
     }
 }
 
+/// Appends records to a file, creating it on first use.
+///
+/// By default a new file gets the process umask defaults (usually `0644`).  Logs often hold
+/// sensitive data, so on Unix the permissions and owner of files this output creates can
+/// be set explicitly:
+///
+/// ```no_run
+/// # #[cfg(unix)] {
+/// use cappie::{FileOutput, Logger};
+///
+/// let log = Logger::new("payments")
+///     .with_output(Box::new(FileOutput::new("payments.log").with_mode(0o600)));
+/// # }
+/// ```
+///
+/// Records are appended with `O_APPEND`, so several processes logging to one file never
+/// overwrite each other, and each record goes out in one `write` call, which local file
+/// systems carry out whole.  Where that is not enough – records larger than one write,
+/// network file systems – [`with_locking`](Self::with_locking) serializes the writes.
 pub struct FileOutput {
     path: String,
+    /// Set by [`per_process`](Self::per_process): `path` is a template, expanded into
+    /// this for the process that writes.
+    per_process: Option<Mutex<ProcessPath>>,
+    locking: bool,
+    permissions: FilePermissions,
 }
 
 impl FileOutput {
     pub fn new<P: AsRef<Path>>(path: P) -> Self {
         Self {
             path: path.as_ref().to_string_lossy().to_string(),
+            per_process: None,
+            locking: false,
+            permissions: FilePermissions::default(),
         }
     }
+    
+    /// A file of its own for every process, so instances of a program never contend for
+    /// one: in `template`, `{pid}` stands for the process id, `{start_time}` for the UTC
+    /// time the process first writes through the output (`20240131T120000Z`) and
+    /// `{hostname}` for the machine's name.  Missing directories are created.
+    ///
+    /// The name is worked out again when the process id changes, so workers forked after
+    /// the logger was set up still get their own files.
+    ///
+    /// ```no_run
+    /// use cappie::{FileOutput, Logger};
+    ///
+    /// let log = Logger::new("app")
+    ///     .with_output(Box::new(FileOutput::per_process("logs/app-{pid}-{start_time}.log")));
+    /// log.info("started"); // logs/app-4711-20240131T120000Z.log
+    /// ```
+    pub fn per_process(template: &str) -> Self {
+        Self {
+            per_process: Some(Mutex::new(ProcessPath { pid: 0, path: String::new() })),
+            ..Self::new(template)
+        }
+    }
+    
+    /// Hold an exclusive advisory lock (`flock`, `LockFileEx` on Windows) on the file while
+    /// a record is written, so processes that all log with locking – the workers of a
+    /// forking server, say – never interleave partial lines.  Costs two system calls per
+    /// record.
+    pub fn with_locking(mut self) -> Self {
+        self.locking = true;
+        self
+    }
+    
+    /// Permission bits for a newly created file, e.g. `0o600`.  Applied exactly, regardless
+    /// of the umask.  Existing files are left alone.
+    #[cfg(unix)]
+    pub fn with_mode(mut self, mode: u32) -> Self {
+        self.permissions.mode = Some(mode);
+        self
+    }
+    
+    /// Owner and/or group (numeric ids) for a newly created file.  Changing the owner needs
+    /// the corresponding privilege (`CAP_CHOWN`, or group membership for the group); when
+    /// it is refused the file keeps the process' ids and the record is still written.
+    #[cfg(unix)]
+    pub fn with_owner(mut self, uid: Option<u32>, gid: Option<u32>) -> Self {
+        self.permissions.owner = Some((uid, gid));
+        self
+    }
+}
+
+impl FileOutput {
+    /// A write‑once variant for audit logs that refuses to truncate and fails loudly when
+    /// the file is replaced, see [`AppendOnlyFileOutput`].
+    pub fn append_only<P: AsRef<Path>>(path: P) -> io::Result<AppendOnlyFileOutput> {
+        AppendOnlyFileOutput::open(path)
+    }
+    
+    fn append(&self, bytes: &[u8]) -> io::Result<()> {
+        let file = self.open()?;
+        let _lock = if self.locking { Some(FileLock::acquire(&file)?) } else { None };
+        (&file).write_all(bytes)
+    }
+    
+    /// The file this process writes to.
+    fn path(&self) -> Cow<'_, str> {
+        let Some(process_path) = &self.per_process else {
+            return Cow::Borrowed(&self.path);
+        };
+        let mut process_path = process_path.lock().unwrap_or_else(|e| e.into_inner());
+        let pid = std::process::id();
+        if process_path.pid != pid {
+            let start_time = timestamp::format(&Timestamp::now(), "%Y%m%dT%H%M%SZ").to_string();
+            let path = self.path
+                .replace("{pid}", &pid.to_string())
+                .replace("{start_time}", &start_time)
+                .replace("{hostname}", &crate::syslog::hostname().unwrap_or_default());
+            if let Some(dir) = Path::new(&path).parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                // A failure shows up when the file is opened.
+                let _ = std::fs::create_dir_all(dir);
+            }
+            *process_path = ProcessPath { pid, path };
+        }
+        Cow::Owned(process_path.path.clone())
+    }
+    
+    fn open(&self) -> io::Result<File> {
+        self.permissions.open_append(Path::new(&*self.path()))
+    }
+}
+
+/// Mode and owner given to the files an output creates, set through the `with_mode` and
+/// `with_owner` methods of the file outputs.  Without either, files are created with the
+/// process' umask and ids.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct FilePermissions {
+    #[cfg(unix)]
+    pub(crate) mode: Option<u32>,
+    #[cfg(unix)]
+    pub(crate) owner: Option<(Option<u32>, Option<u32>)>,
+}
+
+impl FilePermissions {
+    /// Open `path` for appending, creating it if necessary.
+    pub(crate) fn open_append(&self, path: &Path) -> io::Result<File> {
+        #[cfg(unix)]
+        if self.mode.is_some() || self.owner.is_some() {
+            return self.open_with_permissions(path);
+        }
+        OpenOptions::new().create(true).append(true).open(path)
+    }
+    
+    /// Open the file, and if it has to be created, create it with the configured mode and
+    /// owner.  `create_new` tells us whether we are the creator, so existing files are
+    /// never touched.
+    #[cfg(unix)]
+    fn open_with_permissions(&self, path: &Path) -> io::Result<File> {
+        use std::os::unix::fs::OpenOptionsExt;
+        
+        match OpenOptions::new().append(true).open(path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            result => return result,
+        }
+        
+        let mut options = OpenOptions::new();
+        options.append(true).create_new(true);
+        if let Some(mode) = self.mode {
+            // The umask can only narrow this, so the file is never more open than asked.
+            options.mode(mode);
+        }
+        let file = match options.open(path) {
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                return OpenOptions::new().append(true).open(path);
+            }
+            result => result?,
+        };
+        self.apply(&file)?;
+        Ok(file)
+    }
+    
+    /// Give a file this process just created the configured mode and owner.  A refused
+    /// change of owner is ignored.
+    pub(crate) fn apply(&self, file: &File) -> io::Result<()> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            
+            if let Some(mode) = self.mode {
+                file.set_permissions(std::fs::Permissions::from_mode(mode))?;
+            }
+            if let Some((uid, gid)) = self.owner {
+                let _ = std::os::unix::fs::fchown(file, uid, gid);
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = file;
+        Ok(())
+    }
 }
 
 impl Output for FileOutput {
     fn write(&self, message: &str) {
-        if let Ok(mut file) = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&self.path) 
-        {
-            let _ = writeln!(file, "{}", message);
+        diagnostics::check_write("FileOutput", self.append(&line(message)));
+    }
+    
+    fn write_bytes(&self, bytes: &[u8]) {
+        diagnostics::check_write("FileOutput", self.append(bytes));
+    }
+    
+    fn try_write_record(&self, record: &Record<'_>) -> io::Result<()> {
+        self.append(&record.bytes())
+    }
+    
+    /// Opens the file for appending (creating it, like the first write would).
+    fn validate(&self) -> Result<(), BuildError> {
+        self.open().map(drop).map_err(|source| BuildError::UnwritablePath {
+            path: self.path().into_owned(),
+            source,
+        })
+    }
+    
+    fn needs_fields(&self) -> bool {
+        false
+    }
+}
+
+/// Expansion of a [`FileOutput::per_process`] template for process `pid`.
+struct ProcessPath {
+    pid: u32,
+    path: String,
+}
+
+const DEFAULT_MAX_SIZE: u64 = 10 * 1024 * 1024;
+const DEFAULT_MAX_FILES: usize = 5;
+
+/// Appends records to a file that is rotated once it would grow beyond
+/// [`with_max_size`](Self::with_max_size): `app.log` is renamed to `app.log.1`, the
+/// previous `app.log.1` to `app.log.2` and so on, and files beyond
+/// [`with_max_files`](Self::with_max_files) are deleted.  A
+/// [byte budget](Self::with_max_total_size) also bounds the disk space of the whole set.
+///
+/// ```no_run
+/// use cappie::{Logger, RotatingFileOutput};
+///
+/// let log = Logger::new("app").with_output(Box::new(
+///     RotatingFileOutput::new("app.log").with_max_size(10 * 1024 * 1024).with_max_files(3),
+/// ));
+/// log.info("started");
+/// ```
+///
+/// Rotation renames files, so one process should own the set; processes sharing a log file
+/// are better served by [`FileOutput`] and an external rotator.
+pub struct RotatingFileOutput {
+    path: PathBuf,
+    max_size: u64,
+    max_files: usize,
+    max_total_size: Option<u64>,
+    permissions: FilePermissions,
+    /// The open file and its size, opened on first use.
+    active: Mutex<Option<(File, u64)>>,
+}
+
+impl RotatingFileOutput {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            max_size: DEFAULT_MAX_SIZE,
+            max_files: DEFAULT_MAX_FILES,
+            max_total_size: None,
+            permissions: FilePermissions::default(),
+            active: Mutex::new(None),
+        }
+    }
+    
+    /// Size in bytes at which the file is rotated (default 10 MiB).  A record larger than
+    /// this gets a file of its own.
+    pub fn with_max_size(mut self, bytes: u64) -> Self {
+        self.max_size = bytes.max(1);
+        self
+    }
+    
+    /// Number of rotated files kept besides the current one (default 5).
+    pub fn with_max_files(mut self, files: usize) -> Self {
+        self.max_files = files;
+        self
+    }
+    
+    /// Bytes the current and rotated files may take together.  On rotation the oldest
+    /// files are deleted, whatever [`with_max_files`](Self::with_max_files) allows, until
+    /// the rest leaves room for a full current file; a budget below the maximum size
+    /// shrinks the files.
+    pub fn with_max_total_size(mut self, bytes: u64) -> Self {
+        self.max_total_size = Some(bytes);
+        self
+    }
+    
+    /// Permission bits for the files, see [`FileOutput::with_mode`].  Rotated files keep
+    /// the mode of the file they were.
+    #[cfg(unix)]
+    pub fn with_mode(mut self, mode: u32) -> Self {
+        self.permissions.mode = Some(mode);
+        self
+    }
+    
+    /// Owner and/or group for the files, see [`FileOutput::with_owner`].
+    #[cfg(unix)]
+    pub fn with_owner(mut self, uid: Option<u32>, gid: Option<u32>) -> Self {
+        self.permissions.owner = Some((uid, gid));
+        self
+    }
+    
+    /// Size at which the file is rotated.
+    fn rotate_at(&self) -> u64 {
+        self.max_total_size.map_or(self.max_size, |budget| self.max_size.min(budget).max(1))
+    }
+    
+    fn append(&self, bytes: &[u8]) -> io::Result<()> {
+        let mut active = self.active.lock().unwrap_or_else(|e| e.into_inner());
+        let (file, size) = match &mut *active {
+            Some(open) => open,
+            closed @ None => closed.insert(self.open()?),
+        };
+        if *size > 0 && *size + bytes.len() as u64 > self.rotate_at() {
+            match self.rotate().and_then(|()| self.open()) {
+                Ok(reopened) => (*file, *size) = reopened,
+                // A failed rotation keeps writing to the current file rather than losing records.
+                Err(error) => {
+                    diagnostics::report(diagnostics::Diagnostic::WriteFailed { output: "RotatingFileOutput", error: &error });
+                }
+            }
+        }
+        file.write_all(bytes)?;
+        *size += bytes.len() as u64;
+        Ok(())
+    }
+    
+    fn open(&self) -> io::Result<(File, u64)> {
+        let file = self.permissions.open_append(&self.path)?;
+        let size = file.metadata()?.len();
+        Ok((file, size))
+    }
+    
+    /// `app.log.{n}`, or `app.log` itself for 0.
+    fn numbered(&self, n: usize) -> PathBuf {
+        if n == 0 {
+            return self.path.clone();
+        }
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{n}"));
+        PathBuf::from(path)
+    }
+    
+    /// Shift every file up one number, dropping those beyond the limits.
+    fn rotate(&self) -> io::Result<()> {
+        let _ = std::fs::remove_file(self.numbered(self.max_files));
+        for n in (0..self.max_files).rev() {
+            match std::fs::rename(self.numbered(n), self.numbered(n + 1)) {
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                result => result?,
+            }
         }
+        if let Some(budget) = self.max_total_size {
+            // Leave room for the current file to fill up.
+            let room = budget.saturating_sub(self.rotate_at());
+            let mut total = 0;
+            for n in 1..=self.max_files {
+                let path = self.numbered(n);
+                total += std::fs::metadata(&path).map_or(0, |metadata| metadata.len());
+                if total > room {
+                    let _ = std::fs::remove_file(path);
+                }
+            }
+        }
+        Ok(())
     }
 }
 
+impl Output for RotatingFileOutput {
+    fn write(&self, message: &str) {
+        diagnostics::check_write("RotatingFileOutput", self.append(&line(message)));
+    }
+    
+    fn write_bytes(&self, bytes: &[u8]) {
+        diagnostics::check_write("RotatingFileOutput", self.append(bytes));
+    }
+    
+    fn try_write_record(&self, record: &Record<'_>) -> io::Result<()> {
+        self.append(&record.bytes())
+    }
+    
+    /// Opens the file for appending (creating it, like the first write would).
+    fn validate(&self) -> Result<(), BuildError> {
+        let mut active = self.active.lock().unwrap_or_else(|e| e.into_inner());
+        if active.is_none() {
+            *active = Some(self.open().map_err(|source| BuildError::UnwritablePath {
+                path: self.path.to_string_lossy().into_owned(),
+                source,
+            })?);
+        }
+        Ok(())
+    }
+    
+    fn needs_fields(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Default)]
 pub struct MultiOutput {
     outputs: Vec<Box<dyn Output>>,
 }
@@ -69,4 +778,63 @@ impl Output for MultiOutput {
             output.write(message);
         }
     }
+    
+    fn write_bytes(&self, bytes: &[u8]) {
+        for output in &self.outputs {
+            output.write_bytes(bytes);
+        }
+    }
+    
+    fn flush(&self) {
+        for output in &self.outputs {
+            output.flush();
+        }
+    }
+    
+    /// Flushes every output and reports the first error.
+    fn try_flush(&self) -> io::Result<()> {
+        let mut result = Ok(());
+        for output in &self.outputs {
+            let flushed = output.try_flush();
+            if result.is_ok() {
+                result = flushed;
+            }
+        }
+        result
+    }
+    
+    fn validate(&self) -> Result<(), BuildError> {
+        self.outputs.iter().try_for_each(|output| output.validate())
+    }
+    
+    fn write_record(&self, record: &Record<'_>) {
+        formatted::with_format_cache(|| {
+            for output in &self.outputs {
+                output.write_record(record);
+            }
+        });
+    }
+    
+    /// Writes to every output and reports the first error.
+    fn try_write_record(&self, record: &Record<'_>) -> io::Result<()> {
+        formatted::with_format_cache(|| {
+            let mut result = Ok(());
+            for output in &self.outputs {
+                let written = output.try_write_record(record);
+                if result.is_ok() {
+                    result = written;
+                }
+            }
+            result
+        })
+    }
+    
+    fn needs_fields(&self) -> bool {
+        self.outputs.iter().any(|output| output.needs_fields())
+    }
+    
+    fn describe(&self) -> String {
+        let outputs: Vec<String> = self.outputs.iter().map(|output| output.describe()).collect();
+        format!("MultiOutput[{}]", outputs.join(", "))
+    }
 }
\ No newline at end of file
@@ -0,0 +1,227 @@
+use crate::diagnostics;
+use crate::error::BuildError;
+use crate::output::{line, Output, Record};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+const DEFAULT_MAX_OPEN: usize = 64;
+
+/// Partition used for records that lack the key field.
+const DEFAULT_PARTITION: &str = "";
+
+type Factory = dyn Fn(&str) -> io::Result<Box<dyn Output>> + Send + Sync;
+
+/// Sends each record to a sink chosen by the value of one field, e.g. one file per
+/// `tenant_id`.
+///
+/// Sinks are created on first use by a factory and kept in a least‑recently‑used cache of
+/// at most [`with_max_open`](Self::with_max_open) entries (default 64); the sink falling
+/// out is flushed and dropped, closing whatever it holds, and recreated when its partition
+/// shows up again.  Records without the field go to the [fallback](Self::with_fallback),
+/// or else to the partition of the empty value.  If a sink cannot be created the record
+/// goes to the fallback, if any.
+///
+/// ```no_run
+/// use cappie::Logger;
+/// use cappie::output::PartitionedOutput;
+///
+/// let log = Logger::new("api").with_output(Box::new(PartitionedOutput::files("tenant_id", "logs/tenants")));
+/// log.info_with("invoice sent", |b| { b.string("tenant_id", "acme"); }); // logs/tenants/acme.log
+/// ```
+pub struct PartitionedOutput {
+    key: String,
+    factory: Box<Factory>,
+    max_open: usize,
+    fallback: Option<Box<dyn Output>>,
+    sinks: Mutex<Sinks>,
+}
+
+#[derive(Default)]
+struct Sinks {
+    open: HashMap<String, (Arc<dyn Output>, u64)>,
+    clock: u64,
+}
+
+impl PartitionedOutput {
+    /// Partition by the field `key`, creating sinks with `factory(partition value)`.
+    pub fn new<F>(key: &str, factory: F) -> Self
+    where
+        F: Fn(&str) -> io::Result<Box<dyn Output>> + Send + Sync + 'static,
+    {
+        Self {
+            key: key.to_string(),
+            factory: Box::new(factory),
+            max_open: DEFAULT_MAX_OPEN,
+            fallback: None,
+            sinks: Mutex::new(Sinks::default()),
+        }
+    }
+
+    /// One append‑mode file per partition, `dir/<value>.log`, and `dir/_default.log` for
+    /// the empty value.  Characters other than letters, digits, `-` and non‑leading `_`
+    /// and `.` are percent‑encoded, so values can neither leave `dir` nor collide with
+    /// each other.
+    pub fn files<P: AsRef<Path>>(key: &str, dir: P) -> Self {
+        let dir = dir.as_ref().to_path_buf();
+        Self::new(key, move |value| {
+            fs::create_dir_all(&dir)?;
+            let path = dir.join(format!("{}.log", file_stem(value)));
+            Ok(Box::new(PartitionFile::open(path)?) as Box<dyn Output>)
+        })
+    }
+
+    /// Most sinks kept open at once (at least 1).
+    pub fn with_max_open(mut self, max_open: usize) -> Self {
+        self.max_open = max_open.max(1);
+        self
+    }
+
+    /// Output for records without the key field and for partitions whose sink cannot be
+    /// created.
+    pub fn with_fallback(mut self, output: Box<dyn Output>) -> Self {
+        self.fallback = Some(output);
+        self
+    }
+
+    fn sink(&self, partition: &str) -> io::Result<Arc<dyn Output>> {
+        let mut sinks = self.sinks.lock().unwrap_or_else(|e| e.into_inner());
+        sinks.clock += 1;
+        let now = sinks.clock;
+        if let Some((sink, used)) = sinks.open.get_mut(partition) {
+            *used = now;
+            return Ok(sink.clone());
+        }
+
+        let sink: Arc<dyn Output> = Arc::from((self.factory)(partition)?);
+        if sinks.open.len() >= self.max_open {
+            let oldest = sinks.open.iter().min_by_key(|(_, (_, used))| *used).map(|(k, _)| k.clone());
+            if let Some((evicted, _)) = oldest.and_then(|k| sinks.open.remove(&k)) {
+                evicted.flush();
+            }
+        }
+        sinks.open.insert(partition.to_string(), (sink.clone(), now));
+        Ok(sink)
+    }
+}
+
+impl Output for PartitionedOutput {
+    /// Records without metadata cannot be partitioned; they go to the fallback or the
+    /// partition of the empty value.
+    fn write(&self, message: &str) {
+        match &self.fallback {
+            Some(fallback) => fallback.write(message),
+            None => {
+                if let Ok(sink) = self.sink(DEFAULT_PARTITION) {
+                    sink.write(message);
+                }
+            }
+        }
+    }
+
+    fn write_record(&self, record: &Record<'_>) {
+        diagnostics::check_write("PartitionedOutput", self.try_write_record(record));
+    }
+
+    fn try_write_record(&self, record: &Record<'_>) -> io::Result<()> {
+        let partition = match record.fields.get(&self.key) {
+            Some(Value::String(value)) => Some(value.clone()),
+            Some(Value::Null) | None => None,
+            Some(other) => Some(other.to_string()),
+        };
+        let sink = match (&partition, &self.fallback) {
+            (None, Some(fallback)) => return fallback.try_write_record(record),
+            (None, None) => self.sink(DEFAULT_PARTITION),
+            (Some(partition), _) => self.sink(partition),
+        };
+        match (sink, &self.fallback) {
+            (Ok(sink), _) => sink.try_write_record(record),
+            (Err(_), Some(fallback)) => fallback.try_write_record(record),
+            (Err(e), None) => Err(e),
+        }
+    }
+
+    fn flush(&self) {
+        let sinks: Vec<Arc<dyn Output>> = {
+            let sinks = self.sinks.lock().unwrap_or_else(|e| e.into_inner());
+            sinks.open.values().map(|(sink, _)| sink.clone()).collect()
+        };
+        for sink in sinks {
+            sink.flush();
+        }
+        if let Some(fallback) = &self.fallback {
+            fallback.flush();
+        }
+    }
+
+    /// Flushes the open partitions and the fallback and reports the first error.
+    fn try_flush(&self) -> io::Result<()> {
+        let sinks: Vec<Arc<dyn Output>> = {
+            let sinks = self.sinks.lock().unwrap_or_else(|e| e.into_inner());
+            sinks.open.values().map(|(sink, _)| sink.clone()).collect()
+        };
+        let mut result = Ok(());
+        let flushed = sinks.iter().map(|sink| sink.try_flush()).chain(self.fallback.iter().map(|fallback| fallback.try_flush()));
+        for flushed in flushed {
+            if result.is_ok() {
+                result = flushed;
+            }
+        }
+        result
+    }
+
+    fn validate(&self) -> Result<(), BuildError> {
+        match &self.fallback {
+            Some(fallback) => fallback.validate(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// `value` as a file name: safe characters kept, everything else as `%XX` bytes.  A
+/// leading `_` is encoded as well, which keeps `_default` free for the empty value.
+fn file_stem(value: &str) -> String {
+    if value.is_empty() {
+        return "_default".to_string();
+    }
+    let mut stem = String::with_capacity(value.len());
+    for (i, byte) in value.bytes().enumerate() {
+        let keep = byte.is_ascii_alphanumeric() || byte == b'-' || ((byte == b'_' || byte == b'.') && i > 0);
+        if keep {
+            stem.push(byte as char);
+        } else {
+            stem.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    stem
+}
+
+/// Partition file kept open between records, so the LRU cap bounds open descriptors.
+struct PartitionFile {
+    file: Mutex<File>,
+}
+
+impl PartitionFile {
+    fn open(path: PathBuf) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { file: Mutex::new(file) })
+    }
+}
+
+impl Output for PartitionFile {
+    fn write(&self, message: &str) {
+        self.write_bytes(&line(message));
+    }
+
+    fn write_bytes(&self, bytes: &[u8]) {
+        let result = self.file.lock().unwrap_or_else(|e| e.into_inner()).write_all(bytes);
+        diagnostics::check_write("PartitionedOutput", result);
+    }
+
+    fn try_write_record(&self, record: &Record<'_>) -> io::Result<()> {
+        self.file.lock().unwrap_or_else(|e| e.into_inner()).write_all(&record.bytes())
+    }
+}
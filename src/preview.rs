@@ -0,0 +1,52 @@
+use crate::formatter::Formatter;
+use crate::level::Level;
+use crate::timestamp;
+use serde_json::{json, Map, Value};
+
+/// Render a handful of made‑up records with `formatter` – every level, with and without
+/// fields, a long message – one per line.  Handy while tuning a
+/// [`FlexibleFormatter`](crate::FlexibleFormatter) layout or a theme:
+///
+/// ```
+/// use cappie::{preview, FlexibleFormatter};
+///
+/// let formatter = FlexibleFormatter::from_template("{time} {level|bold} {msg} {fields}").unwrap();
+/// println!("{}", preview(&formatter));
+/// ```
+///
+/// The timestamp is fixed, so the output is the same from run to run.  Binary formatters
+/// are shown lossily converted to UTF‑8.
+pub fn preview(formatter: &dyn Formatter) -> String {
+    let timestamp = timestamp::ymd_hms(2024, 1, 15, 10, 30, 0);
+    let request = fields(json!({ "method": "GET", "path": "/api/users/42", "status": 200, "duration_ms": 12 }));
+    let failure = fields(json!({ "attempt": 3, "retry": true, "error": { "kind": "timeout", "after_ms": 5000 } }));
+    let long = "a rather long message that keeps going to show how the layout copes with text \
+                wider than a typical terminal, including wrapping and field placement after it";
+
+    let records: [(Level, &str, &str, Map<String, Value>); 8] = [
+        (Level::Trace, "app", "entering handler", Map::new()),
+        (Level::Debug, "app.db", "cache miss", fields(json!({ "key": "user:42" }))),
+        (Level::Info, "app.http", "request completed", request),
+        (Level::Info, "app", "started", Map::new()),
+        (Level::Warn, "app.db", "slow query", fields(json!({ "table": "orders", "rows": 18240 }))),
+        (Level::Error, "app.http", "upstream request failed", failure),
+        (Level::Fatal, "app", "cannot bind port 8080", fields(json!({ "port": 8080 }))),
+        (Level::Info, "app", long, fields(json!({ "note": "long message" }))),
+    ];
+
+    let mut out = String::new();
+    for (level, name, msg, fields) in &records {
+        let mut buf = Vec::new();
+        formatter.format_into(&mut buf, *level, msg, fields, timestamp, name);
+        out.push_str(&String::from_utf8_lossy(&buf));
+        out.push('\n');
+    }
+    out
+}
+
+fn fields(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
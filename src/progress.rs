@@ -0,0 +1,159 @@
+use crate::level::Level;
+use crate::logger::{LogBuilder, Logger};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Periodic progress records for long‑running batch work, created with
+/// [`Logger::progress`].
+///
+/// Call [`inc`](Self::inc) as items complete.  At most once per
+/// [interval](Self::with_interval) – however fast or slow the items are – a record is logged
+/// with `done`, `per_sec` and, when the total is known, `total`, `percent` and `eta_s`.
+/// [`finish`](Self::finish) (or dropping the helper) logs a final summary with
+/// `finished: true` and the overall `duration_ms`.
+///
+/// All methods take `&self`, so one `Progress` can be shared by worker threads.
+///
+/// ```
+/// use cappie::Logger;
+///
+/// let log = Logger::new("import");
+/// let progress = log.progress("importing rows", Some(10_000));
+/// for _row in 0..10_000 {
+///     // ... import the row ...
+///     progress.inc(1);
+/// }
+/// progress.finish();
+/// ```
+pub struct Progress {
+    logger: Logger,
+    msg: String,
+    total: Option<u64>,
+    level: Level,
+    interval: Duration,
+    started: Instant,
+    done: AtomicU64,
+    last_report: Mutex<Instant>,
+    finished: AtomicBool,
+}
+
+impl Progress {
+    pub(crate) fn new(logger: &Logger, msg: &str, total: Option<u64>) -> Self {
+        let now = Instant::now();
+        Self {
+            logger: logger.clone(),
+            msg: msg.to_string(),
+            total,
+            level: Level::Info,
+            interval: DEFAULT_INTERVAL,
+            started: now,
+            done: AtomicU64::new(0),
+            last_report: Mutex::new(now),
+            finished: AtomicBool::new(false),
+        }
+    }
+
+    /// Minimum time between two progress records (default 5 seconds).
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Level of the progress and summary records (default `Info`).
+    pub fn with_level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Record `n` more completed items, logging a progress record if the interval has
+    /// passed.
+    pub fn inc(&self, n: u64) {
+        let done = self.done.fetch_add(n, Ordering::Relaxed) + n;
+        self.maybe_report(done);
+    }
+
+    /// Set the number of completed items, for work that reports absolute positions (bytes
+    /// read, offsets).
+    pub fn set(&self, done: u64) {
+        self.done.store(done, Ordering::Relaxed);
+        self.maybe_report(done);
+    }
+
+    /// Items completed so far.
+    pub fn done(&self) -> u64 {
+        self.done.load(Ordering::Relaxed)
+    }
+
+    /// Log the summary record.  Later calls, and the drop, do nothing.
+    pub fn finish(&self) {
+        if self.finished.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        let done = self.done();
+        let elapsed = self.started.elapsed();
+        self.logger.log_with(self.level, &self.msg, |b| {
+            self.counts(b, done, elapsed);
+            b.bool("finished", true).duration_fields(elapsed);
+        });
+    }
+
+    fn maybe_report(&self, done: u64) {
+        if !self.logger.enabled(self.level) {
+            return;
+        }
+        // Workers that find another thread reporting skip the check instead of waiting.
+        let Ok(mut last) = self.last_report.try_lock() else {
+            return;
+        };
+        let now = Instant::now();
+        if now.duration_since(*last) < self.interval {
+            return;
+        }
+        *last = now;
+        drop(last);
+
+        let elapsed = now.duration_since(self.started);
+        self.logger.log_with(self.level, &self.msg, |b| {
+            self.counts(b, done, elapsed);
+            if let Some(total) = self.total {
+                let per_sec = rate(done, elapsed);
+                if per_sec > 0.0 && done < total {
+                    b.field("eta_s", round1((total - done) as f64 / per_sec));
+                }
+            }
+        });
+    }
+
+    fn counts(&self, b: &mut LogBuilder, done: u64, elapsed: Duration) {
+        b.field("done", done);
+        if let Some(total) = self.total {
+            b.field("total", total);
+            if total > 0 {
+                b.field("percent", round1(done as f64 * 100.0 / total as f64));
+            }
+        }
+        b.field("per_sec", round1(rate(done, elapsed)));
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+fn rate(done: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs > 0.0 {
+        done as f64 / secs
+    } else {
+        0.0
+    }
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
@@ -0,0 +1,343 @@
+use crate::level::Level;
+use crate::logger::{LogBuilder, Logger};
+use std::time::Duration;
+
+const DEFAULT_MAX_STATEMENT_LEN: usize = 1000;
+
+/// Structured SQL query events under a `db` child logger, fed by a database integration
+/// (`SqlxLogBridge` with feature `log`, `DieselInstrumentation` with feature `diesel`, or
+/// hand‑written calls).
+///
+/// Each [`record`](Self::record)ed query becomes a `Debug` record `query` with `statement`,
+/// `duration_ms` and, when known, `rows`.  Queries slower than the
+/// [slow threshold](Self::with_slow_threshold) are logged as `slow query` at `Warn`.
+///
+/// Statements are cleaned up before logging: whitespace is collapsed onto one line, string
+/// and numeric literals are replaced by `?` (so values inlined into SQL don't end up in
+/// the logs; bind placeholders like `$1` are kept), and the result is cut to
+/// [`with_max_statement_len`](Self::with_max_statement_len) bytes.
+///
+/// ```
+/// use cappie::{CaptureOutput, Level, Logger, QueryLog};
+/// use std::time::Duration;
+///
+/// let capture = CaptureOutput::new();
+/// let log = Logger::new("app").with_level(Level::Debug).with_output(Box::new(capture.clone()));
+/// let queries = QueryLog::new(&log);
+///
+/// queries.record("SELECT *\n  FROM users\n  WHERE email = 'a@example.com'", Duration::from_millis(3), Some(1));
+/// assert!(capture.contents().contains(r#""statement":"SELECT * FROM users WHERE email = ?""#));
+/// ```
+pub struct QueryLog {
+    logger: Logger,
+    max_statement_len: usize,
+    redact_literals: bool,
+    slow_threshold: Option<Duration>,
+}
+
+impl QueryLog {
+    /// Log under `logger`'s child `db`.
+    pub fn new(logger: &Logger) -> Self {
+        Self {
+            logger: logger.child("db"),
+            max_statement_len: DEFAULT_MAX_STATEMENT_LEN,
+            redact_literals: true,
+            slow_threshold: None,
+        }
+    }
+
+    /// Longest statement logged, in bytes (default 1000); longer ones end in `...`.
+    pub fn with_max_statement_len(mut self, bytes: usize) -> Self {
+        self.max_statement_len = bytes;
+        self
+    }
+
+    /// Keep literals in logged statements (they are replaced by `?` by default).
+    pub fn with_literals(mut self) -> Self {
+        self.redact_literals = false;
+        self
+    }
+
+    /// Log queries taking at least `threshold` as `slow query` at `Warn`.
+    pub fn with_slow_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_threshold = Some(threshold);
+        self
+    }
+
+    pub fn logger(&self) -> &Logger {
+        &self.logger
+    }
+
+    /// Log one executed query.
+    pub fn record(&self, statement: &str, duration: Duration, rows: Option<u64>) {
+        let slow = self.slow_threshold.is_some_and(|threshold| duration >= threshold);
+        let (level, msg) = if slow { (Level::Warn, "slow query") } else { (Level::Debug, "query") };
+        self.write(level, msg, statement, duration, |b| {
+            if let Some(rows) = rows {
+                b.field("rows", rows);
+            }
+        });
+    }
+
+    /// Log a query that failed, as `query failed` at `Error`.
+    #[cfg(feature = "diesel")]
+    fn record_failure(&self, statement: &str, duration: Duration, error: &str) {
+        self.write(Level::Error, "query failed", statement, duration, |b| {
+            b.string("error", error);
+        });
+    }
+
+    fn write<F>(&self, level: Level, msg: &str, statement: &str, duration: Duration, f: F)
+    where
+        F: FnOnce(&mut LogBuilder),
+    {
+        if !self.logger.enabled(level) {
+            return;
+        }
+        let statement = self.clean(statement);
+        self.logger.log_with(level, msg, |b| {
+            b.string("statement", &statement).duration("duration_ms", duration);
+            f(b);
+        });
+    }
+
+    fn clean(&self, statement: &str) -> String {
+        let mut clean = if self.redact_literals {
+            redact_literals(statement)
+        } else {
+            statement.split_whitespace().collect::<Vec<_>>().join(" ")
+        };
+        if clean.len() > self.max_statement_len {
+            let mut end = self.max_statement_len;
+            while !clean.is_char_boundary(end) {
+                end -= 1;
+            }
+            clean.truncate(end);
+            clean.push_str("...");
+        }
+        clean
+    }
+}
+
+/// Collapse whitespace and replace string and numeric literals by `?`.  Quoted identifiers
+/// (`"name"`, `` `name` ``), digits inside identifiers and `$1`‑style placeholders are kept.
+fn redact_literals(statement: &str) -> String {
+    let mut out = String::with_capacity(statement.len());
+    let mut chars = statement.chars().peekable();
+    let mut prev: Option<char> = None;
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                if !out.is_empty() && chars.peek().is_some() {
+                    out.push(' ');
+                }
+                prev = Some(' ');
+                continue;
+            }
+            '\'' => {
+                // '' inside a string is an escaped quote.
+                loop {
+                    match chars.next() {
+                        Some('\'') if chars.peek() == Some(&'\'') => {
+                            chars.next();
+                        }
+                        Some('\'') | None => break,
+                        Some(_) => {}
+                    }
+                }
+                out.push('?');
+            }
+            '"' | '`' => {
+                out.push(c);
+                for inner in chars.by_ref() {
+                    out.push(inner);
+                    if inner == c {
+                        break;
+                    }
+                }
+            }
+            c if c.is_ascii_digit() && !prev.is_some_and(|p| p.is_alphanumeric() || p == '_' || p == '$') => {
+                while chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '.').is_some() {}
+                out.push('?');
+            }
+            c => out.push(c),
+        }
+        prev = out.chars().next_back();
+    }
+    out
+}
+
+#[cfg(feature = "log")]
+pub use sqlx::SqlxLogBridge;
+
+#[cfg(feature = "log")]
+mod sqlx {
+    use super::QueryLog;
+    use std::time::Duration;
+
+    /// A [`log`](https://docs.rs/log) backend that turns sqlx's query log records (target
+    /// `sqlx::query`) into [`QueryLog`] events and hands every other record to a fallback
+    /// backend, if any.  Feature `log`.
+    ///
+    /// sqlx writes `<summary>; rows affected: N, rows returned: M, elapsed: 1.234ms`
+    /// followed by a blank line and the statement; the statement, elapsed time and row
+    /// count are taken from that.  Records that don't match are logged with the whole
+    /// message as the statement.
+    ///
+    /// ```no_run
+    /// use cappie::{Logger, QueryLog, SqlxLogBridge};
+    /// use std::time::Duration;
+    ///
+    /// let log = Logger::new("app");
+    /// let queries = QueryLog::new(&log).with_slow_threshold(Duration::from_millis(100));
+    /// SqlxLogBridge::new(queries).install().unwrap();
+    /// ```
+    pub struct SqlxLogBridge {
+        queries: QueryLog,
+        fallback: Option<Box<dyn log::Log>>,
+    }
+
+    impl SqlxLogBridge {
+        pub fn new(queries: QueryLog) -> Self {
+            Self { queries, fallback: None }
+        }
+
+        /// Backend for records that are not sqlx queries, e.g. an `env_logger::Logger`.
+        pub fn with_fallback(mut self, fallback: Box<dyn log::Log>) -> Self {
+            self.fallback = Some(fallback);
+            self
+        }
+
+        /// Install as the global `log` backend, with the max level at `Trace` so the
+        /// backends decide what to keep.
+        pub fn install(self) -> Result<(), log::SetLoggerError> {
+            log::set_boxed_logger(Box::new(self))?;
+            log::set_max_level(log::LevelFilter::Trace);
+            Ok(())
+        }
+    }
+
+    impl log::Log for SqlxLogBridge {
+        fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+            is_query(metadata.target()) || self.fallback.as_ref().is_some_and(|f| f.enabled(metadata))
+        }
+
+        fn log(&self, record: &log::Record<'_>) {
+            if !is_query(record.target()) {
+                if let Some(fallback) = &self.fallback {
+                    fallback.log(record);
+                }
+                return;
+            }
+            let message = record.args().to_string();
+            let (statement, elapsed, rows) = parse(&message);
+            self.queries.record(statement, elapsed.unwrap_or_default(), rows);
+        }
+
+        fn flush(&self) {
+            self.queries.logger().flush();
+            if let Some(fallback) = &self.fallback {
+                fallback.flush();
+            }
+        }
+    }
+
+    fn is_query(target: &str) -> bool {
+        target == "sqlx::query" || target.starts_with("sqlx::query::")
+    }
+
+    /// Statement, elapsed time and rows (returned, or affected if none were returned).
+    fn parse(message: &str) -> (&str, Option<Duration>, Option<u64>) {
+        let (summary, statement) = match message.split_once("\n\n") {
+            Some((summary, statement)) => (summary, statement),
+            None => (message, message),
+        };
+        let number = |key: &str| -> Option<u64> {
+            let rest = &summary[summary.find(key)? + key.len()..];
+            let end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+            rest[..end].parse().ok()
+        };
+        let rows = match (number("rows returned: "), number("rows affected: ")) {
+            (Some(0), affected @ Some(_)) => affected,
+            (returned, affected) => returned.or(affected),
+        };
+        let elapsed = summary
+            .find("elapsed: ")
+            .and_then(|at| parse_duration(summary[at + "elapsed: ".len()..].split([',', ' ']).next()?));
+        (statement.trim(), elapsed, rows)
+    }
+
+    /// A `Duration` in its `Debug` form, e.g. `1.234ms` or `12.000µs`.
+    fn parse_duration(text: &str) -> Option<Duration> {
+        let split = text.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+        let value: f64 = text[..split].parse().ok()?;
+        let secs = match &text[split..] {
+            "s" => value,
+            "ms" => value / 1e3,
+            "µs" | "us" => value / 1e6,
+            "ns" => value / 1e9,
+            _ => return None,
+        };
+        Some(Duration::from_secs_f64(secs))
+    }
+}
+
+#[cfg(feature = "diesel")]
+pub use diesel_instrumentation::DieselInstrumentation;
+
+#[cfg(feature = "diesel")]
+mod diesel_instrumentation {
+    use super::QueryLog;
+    use diesel::connection::{Instrumentation, InstrumentationEvent};
+    use std::time::Instant;
+
+    /// A diesel [`Instrumentation`] that records every query a connection runs as a
+    /// [`QueryLog`] event.  Feature `diesel`.
+    ///
+    /// The statement is the SQL diesel sends, without the bind values diesel appends in a
+    /// comment, so they never reach the logs.  Diesel does not report row counts, so there
+    /// is no `rows` field.  A query that fails is logged as `query failed` at `Error`, with
+    /// the diesel error as `error`.
+    ///
+    /// ```no_run
+    /// use cappie::{DieselInstrumentation, Logger, QueryLog};
+    /// use diesel::connection::{set_default_instrumentation, Instrumentation};
+    ///
+    /// // For every connection opened from now on; a single one takes
+    /// // `conn.set_instrumentation(DieselInstrumentation::new(queries))`.
+    /// fn instrumentation() -> Option<Box<dyn Instrumentation>> {
+    ///     let queries = QueryLog::new(&Logger::new("app"));
+    ///     Some(Box::new(DieselInstrumentation::new(queries)))
+    /// }
+    /// set_default_instrumentation(instrumentation).unwrap();
+    /// ```
+    pub struct DieselInstrumentation {
+        queries: QueryLog,
+        started: Option<Instant>,
+    }
+
+    impl DieselInstrumentation {
+        pub fn new(queries: QueryLog) -> Self {
+            Self { queries, started: None }
+        }
+    }
+
+    impl Instrumentation for DieselInstrumentation {
+        fn on_connection_event(&mut self, event: InstrumentationEvent<'_>) {
+            match event {
+                InstrumentationEvent::StartQuery { .. } => self.started = Some(Instant::now()),
+                InstrumentationEvent::FinishQuery { query, error, .. } => {
+                    let duration = self.started.take().map(|start| start.elapsed()).unwrap_or_default();
+                    let query = query.to_string();
+                    let statement = query.rfind(" -- binds: ").map_or(&*query, |at| &query[..at]);
+                    match error {
+                        Some(error) => self.queries.record_failure(statement, duration, &error.to_string()),
+                        None => self.queries.record(statement, duration, None),
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
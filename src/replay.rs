@@ -0,0 +1,259 @@
+//! Re‑sending stored records through an [`Output`], e.g. to backfill a collector after an
+//! outage.
+//!
+//! Reads ND‑JSON as written by [`JsonFormatter`] (plain, or `.gz` with the `compression`
+//! feature), [dead‑letter](crate::output::DeadLetterOutput) files, and – with the `binary`
+//! feature – binary logs.  Records are formatted again and handed to the output like fresh
+//! ones.
+//!
+//! ```no_run
+//! use cappie::replay::{replay_file, Replay, Timestamps};
+//! use cappie::FileOutput;
+//!
+//! // Everything from the dead-letter file, at most 500 records per second.
+//! let stats = replay_file("dead-letter.jsonl", &FileOutput::new("app.log"), Some(500)).unwrap();
+//! println!("sent {}, failed {}, skipped {}", stats.sent, stats.failed, stats.skipped);
+//!
+//! // Same, but stamped with the current time.
+//! Replay::new()
+//!     .with_timestamps(Timestamps::Now)
+//!     .run_file("app.000003.jsonl", &FileOutput::new("backfill.log"))
+//!     .unwrap();
+//! ```
+
+use crate::formatter::{Formatter, JsonFormatter};
+use crate::level::Level;
+use crate::output::{Output, Record};
+use crate::timestamp::{self, Timestamp};
+use serde_json::{Map, Value};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Which timestamp replayed records carry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Timestamps {
+    /// The original ones.
+    #[default]
+    Preserve,
+    /// The time of replay.
+    Now,
+    /// Shifted so the first record is at the given time, keeping the gaps between records.
+    StartAt(Timestamp),
+}
+
+/// Outcome of a replay.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplayStats {
+    /// Records the output accepted.
+    pub sent: u64,
+    /// Records the output reported an error for.
+    pub failed: u64,
+    /// Lines that were not a record, and corrupt binary records.
+    pub skipped: u64,
+}
+
+/// Replay settings: formatter, pacing and timestamps.
+pub struct Replay {
+    formatter: Box<dyn Formatter>,
+    rate_limit: Option<u32>,
+    timestamps: Timestamps,
+}
+
+impl Default for Replay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Replay the records in `path` to `output` as ND‑JSON, keeping their timestamps, at most
+/// `rate_limit` records per second.
+pub fn replay_file<P: AsRef<Path>>(path: P, output: &dyn Output, rate_limit: Option<u32>) -> io::Result<ReplayStats> {
+    Replay::new().with_rate_limit(rate_limit).run_file(path, output)
+}
+
+impl Replay {
+    pub fn new() -> Self {
+        Self {
+            formatter: Box::new(JsonFormatter),
+            rate_limit: None,
+            timestamps: Timestamps::Preserve,
+        }
+    }
+
+    /// Formatter the records are rendered with (default [`JsonFormatter`]).
+    pub fn with_formatter(mut self, formatter: Box<dyn Formatter>) -> Self {
+        self.formatter = formatter;
+        self
+    }
+
+    /// Records per second, or `None` for as fast as the output takes them.
+    pub fn with_rate_limit(mut self, per_second: Option<u32>) -> Self {
+        self.rate_limit = per_second.filter(|&n| n > 0);
+        self
+    }
+
+    pub fn with_timestamps(mut self, timestamps: Timestamps) -> Self {
+        self.timestamps = timestamps;
+        self
+    }
+
+    /// Replay a file, telling ND‑JSON and binary logs apart by their first byte.
+    pub fn run_file<P: AsRef<Path>>(&self, path: P, output: &dyn Output) -> io::Result<ReplayStats> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        let reader: Box<dyn Read> = if path.extension().is_some_and(|ext| ext == "gz") {
+            crate::ndjson::gzip_reader(file)?
+        } else {
+            Box::new(file)
+        };
+        let mut reader = BufReader::new(reader);
+        let is_text = match reader.fill_buf()?.first() {
+            None => return Ok(ReplayStats::default()),
+            Some(byte) => *byte == b'{' || byte.is_ascii_whitespace(),
+        };
+        if is_text {
+            self.run(reader, output)
+        } else {
+            self.run_binary(reader, output)
+        }
+    }
+
+    /// Replay ND‑JSON lines from `reader`.
+    pub fn run<R: BufRead>(&self, reader: R, output: &dyn Output) -> io::Result<ReplayStats> {
+        let mut stats = ReplayStats::default();
+        let mut pacer = Pacer::new(self.rate_limit);
+        let mut shift = None;
+        for line in reader.lines() {
+            match parse_line(&line?) {
+                Some(record) => self.send(record, output, &mut pacer, &mut shift, &mut stats),
+                None => stats.skipped += 1,
+            }
+        }
+        Ok(stats)
+    }
+
+    #[cfg(feature = "binary")]
+    fn run_binary<R: Read>(&self, reader: R, output: &dyn Output) -> io::Result<ReplayStats> {
+        let mut stats = ReplayStats::default();
+        let mut pacer = Pacer::new(self.rate_limit);
+        let mut shift = None;
+        let mut records = crate::binary::RecordReader::new(reader);
+        for record in records.by_ref() {
+            let record = record?;
+            let record = Stored {
+                level: record.level,
+                timestamp: record.timestamp,
+                name: record.name,
+                msg: record.msg,
+                fields: record.fields,
+            };
+            self.send(record, output, &mut pacer, &mut shift, &mut stats);
+        }
+        stats.skipped += records.skipped();
+        Ok(stats)
+    }
+
+    #[cfg(not(feature = "binary"))]
+    fn run_binary<R: Read>(&self, _reader: R, _output: &dyn Output) -> io::Result<ReplayStats> {
+        Err(io::Error::new(io::ErrorKind::InvalidData, "not ND-JSON; binary logs require the `binary` feature"))
+    }
+
+    fn send(&self, record: Stored, output: &dyn Output, pacer: &mut Pacer, shift: &mut Option<i128>, stats: &mut ReplayStats) {
+        let timestamp = match self.timestamps {
+            Timestamps::Preserve => record.timestamp,
+            Timestamps::Now => Timestamp::now(),
+            Timestamps::StartAt(start) => {
+                let shift = *shift.get_or_insert(start.unix_nanos() - record.timestamp.unix_nanos());
+                Timestamp::from_unix_nanos(record.timestamp.unix_nanos() + shift).unwrap_or(start)
+            }
+        };
+        pacer.wait();
+
+        let mut buf = Vec::new();
+        self.formatter.format_into(&mut buf, record.level, &record.msg, &record.fields, timestamp, &record.name);
+        let replayed = Record {
+            level: record.level,
+            timestamp,
+            name: &record.name,
+            msg: &record.msg,
+            tags: &[],
+            fields: &record.fields,
+            formatted: &buf,
+            binary: self.formatter.is_binary(),
+        };
+        match output.try_write_record(&replayed) {
+            Ok(()) => stats.sent += 1,
+            Err(_) => stats.failed += 1,
+        }
+    }
+}
+
+/// A record read back from storage.
+struct Stored {
+    level: Level,
+    timestamp: Timestamp,
+    name: String,
+    msg: String,
+    fields: Map<String, Value>,
+}
+
+/// An ND‑JSON record, or a dead‑letter entry wrapping one.
+fn parse_line(line: &str) -> Option<Stored> {
+    let Value::Object(mut map) = serde_json::from_str::<Value>(line).ok()? else {
+        return None;
+    };
+    if map.contains_key("failed_at") {
+        if let Some(Value::String(inner)) = map.get("record") {
+            return parse_line(inner);
+        }
+    }
+
+    let level = match map.remove("level")? {
+        Value::Number(n) => Level::from_value(u8::try_from(n.as_u64()?).ok()?)?,
+        Value::String(s) => Level::from_str(&s)?,
+        _ => return None,
+    };
+    let timestamp = match map.remove("time") {
+        Some(Value::String(time)) => timestamp::parse_rfc3339(&time)?,
+        _ => return None,
+    };
+    let name = match map.remove("name") {
+        Some(Value::String(name)) => name,
+        _ => String::new(),
+    };
+    let msg = match map.remove("msg") {
+        Some(Value::String(msg)) => msg,
+        _ => String::new(),
+    };
+    Some(Stored { level, timestamp, name, msg, fields: map })
+}
+
+/// Spaces out records to a fixed rate.
+struct Pacer {
+    interval: Option<Duration>,
+    next: Option<Instant>,
+}
+
+impl Pacer {
+    fn new(per_second: Option<u32>) -> Self {
+        Self {
+            interval: per_second.map(|n| Duration::from_secs(1) / n),
+            next: None,
+        }
+    }
+
+    fn wait(&mut self) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+        let now = Instant::now();
+        let due = self.next.unwrap_or(now);
+        if due > now {
+            thread::sleep(due - now);
+        }
+        self.next = Some(due.max(now) + interval);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::id::next_ulid;
+use crate::level::Level;
+use crate::logger::{Logger, LoggerFactory};
+use serde_json::{Map, Value};
+use std::net::SocketAddr;
+use std::time::Instant;
+
+/// Per‑request logging for HTTP servers, independent of the framework: the glue a
+/// middleware (tower, axum, actix, hyper services…) needs around each request.
+///
+/// [`new`](Self::new) derives a request logger carrying `request_id` (a fresh ULID unless
+/// one is [given](Self::with_request_id)), `method` and `path`, plus `remote_addr` if
+/// [set](Self::with_remote_addr).  Hand [`logger`](Self::logger) to the handler, e.g. via
+/// request extensions, so everything it logs carries the same context.
+///
+/// [`finish`](Self::finish) logs `request finished` with `status` and `latency_ms`, at
+/// `Error` for 5xx responses and `Info` otherwise.  A `RequestLog` dropped without being
+/// finished – the handler panicked or its future was cancelled – logs `request aborted` at
+/// `Warn`.
+///
+/// With feature `tower`, `RequestLogLayer` does all of this for each request of a tower
+/// service.
+///
+/// ```
+/// use cappie::{CaptureOutput, Logger, RequestLog};
+///
+/// let capture = CaptureOutput::new();
+/// let log = Logger::new("http").with_output(Box::new(capture.clone()));
+///
+/// let request = RequestLog::new(&log, "GET", "/users/7").start();
+/// request.logger().info("user loaded");
+/// request.finish(200);
+///
+/// let lines = capture.lines();
+/// assert_eq!(lines.len(), 2);
+/// assert!(lines[0].contains(r#""path":"/users/7""#));
+/// assert!(lines[1].contains(r#""status":200"#));
+/// ```
+pub struct RequestLog {
+    factory: LoggerFactory,
+    fields: Map<String, Value>,
+    logger: Logger,
+    start: Instant,
+    finished: bool,
+}
+
+impl RequestLog {
+    pub fn new(logger: &Logger, method: &str, path: &str) -> Self {
+        let mut fields = Map::new();
+        fields.insert("request_id".to_string(), Value::from(next_ulid()));
+        fields.insert("method".to_string(), Value::from(method));
+        fields.insert("path".to_string(), Value::from(path));
+        let factory = LoggerFactory::new(logger);
+        Self {
+            logger: factory.logger(fields.clone()),
+            factory,
+            fields,
+            start: Instant::now(),
+            finished: false,
+        }
+    }
+
+    /// Use the caller's request id (e.g. an incoming `X-Request-Id` header) instead of a
+    /// fresh one.
+    pub fn with_request_id(self, id: &str) -> Self {
+        self.with_context("request_id", Value::from(id))
+    }
+
+    pub fn with_remote_addr(self, addr: SocketAddr) -> Self {
+        self.with_context("remote_addr", Value::from(addr.to_string()))
+    }
+
+    /// Log `request started` at `Debug`.
+    pub fn start(self) -> Self {
+        self.logger.debug("request started");
+        self
+    }
+
+    /// The request logger, carrying the request context.
+    pub fn logger(&self) -> &Logger {
+        &self.logger
+    }
+
+    /// The request id the records carry.
+    pub fn request_id(&self) -> &str {
+        self.fields["request_id"].as_str().unwrap_or_default()
+    }
+
+    /// Log the response status and latency.
+    pub fn finish(mut self, status: u16) {
+        self.finished = true;
+        let level = if status >= 500 { Level::Error } else { Level::Info };
+        let start = self.start;
+        self.logger.log_with(level, "request finished", |b| {
+            b.field("status", status).elapsed_since("latency_ms", start);
+        });
+    }
+
+    fn with_context(mut self, key: &str, value: Value) -> Self {
+        self.fields.insert(key.to_string(), value);
+        self.logger = self.factory.logger(self.fields.clone());
+        self
+    }
+}
+
+impl Drop for RequestLog {
+    fn drop(&mut self) {
+        if !self.finished {
+            let start = self.start;
+            self.logger.warn_with("request aborted", |b| {
+                b.elapsed_since("latency_ms", start);
+            });
+        }
+    }
+}
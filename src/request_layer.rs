@@ -0,0 +1,148 @@
+use crate::logger::Logger;
+use crate::request::RequestLog;
+use http::{Request, Response};
+use pin_project_lite::pin_project;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// [`RequestLog`] as tower middleware, for axum, tonic, hyper and other tower based
+/// servers.  Feature `tower`.
+///
+/// Each request gets a request logger carrying `request_id`, `method`, `path` and, when
+/// known, `remote_addr`.  It is inserted into the request extensions, where handlers find
+/// it (`Extension<Logger>` in axum), and `request started` and `request finished` with
+/// `status` and `latency_ms` are logged around the inner service as [`RequestLog`]
+/// describes.  A request whose inner service fails, or whose future is dropped, is
+/// logged as `request aborted`.
+///
+/// The request id is taken from the `X-Request-Id` header when the client sent one (see
+/// [`with_request_id_header`](Self::with_request_id_header)).  The remote address is read
+/// from a `SocketAddr` request extension or, with feature `axum`, from axum's
+/// `ConnectInfo<SocketAddr>`.
+///
+/// ```
+/// use axum::routing::get;
+/// use axum::{Extension, Router};
+/// use cappie::{Logger, RequestLogLayer};
+///
+/// async fn show_user(Extension(log): Extension<Logger>) -> &'static str {
+///     log.info("user loaded");
+///     "ada"
+/// }
+///
+/// let log = Logger::new("http");
+/// let app: Router = Router::new()
+///     .route("/users/{id}", get(show_user))
+///     .layer(RequestLogLayer::new(&log));
+/// ```
+#[derive(Clone)]
+pub struct RequestLogLayer {
+    logger: Logger,
+    request_id_header: Option<String>,
+}
+
+impl RequestLogLayer {
+    pub fn new(logger: &Logger) -> Self {
+        Self {
+            logger: logger.clone(),
+            request_id_header: Some(REQUEST_ID_HEADER.to_string()),
+        }
+    }
+
+    /// Take the request id from `header` instead of `X-Request-Id`.
+    pub fn with_request_id_header(mut self, header: &str) -> Self {
+        self.request_id_header = Some(header.to_string());
+        self
+    }
+
+    /// Give every request a fresh id, whatever headers the client sent.
+    pub fn without_request_id_header(mut self) -> Self {
+        self.request_id_header = None;
+        self
+    }
+}
+
+impl<S> Layer<S> for RequestLogLayer {
+    type Service = RequestLogService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestLogService { inner, layer: self.clone() }
+    }
+}
+
+/// Service added by [`RequestLogLayer`].
+#[derive(Clone)]
+pub struct RequestLogService<S> {
+    inner: S,
+    layer: RequestLogLayer,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for RequestLogService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = RequestLogFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        let mut log = RequestLog::new(&self.layer.logger, request.method().as_str(), request.uri().path());
+        let id = self.layer.request_id_header.as_deref().and_then(|header| request.headers().get(header));
+        if let Some(id) = id.and_then(|id| id.to_str().ok()).filter(|id| !id.is_empty()) {
+            log = log.with_request_id(id);
+        }
+        if let Some(addr) = remote_addr(&request) {
+            log = log.with_remote_addr(addr);
+        }
+        let log = log.start();
+        request.extensions_mut().insert(log.logger().clone());
+        RequestLogFuture {
+            inner: self.inner.call(request),
+            log: Some(log),
+        }
+    }
+}
+
+fn remote_addr<B>(request: &Request<B>) -> Option<SocketAddr> {
+    #[cfg(feature = "axum")]
+    if let Some(axum::extract::ConnectInfo(addr)) = request.extensions().get::<axum::extract::ConnectInfo<SocketAddr>>() {
+        return Some(*addr);
+    }
+    request.extensions().get::<SocketAddr>().copied()
+}
+
+pin_project! {
+    /// Response future of [`RequestLogService`].
+    pub struct RequestLogFuture<F> {
+        #[pin]
+        inner: F,
+        log: Option<RequestLog>,
+    }
+}
+
+impl<F, B, E> Future for RequestLogFuture<F>
+where
+    F: Future<Output = Result<Response<B>, E>>,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let result = ready!(this.inner.poll(cx));
+        // Without a response the request log is dropped unfinished: `request aborted`.
+        if let (Some(log), Ok(response)) = (this.log.take(), &result) {
+            log.finish(response.status().as_u16());
+        }
+        Poll::Ready(result)
+    }
+}
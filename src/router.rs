@@ -0,0 +1,465 @@
+//! [`RouterOutput`] and its configuration language.
+//!
+//! A configuration has one rule per line; `#` starts a comment:
+//!
+//! ```text
+//! # name glob   conditions                 sink
+//! route "backend.db*" level>=warn           => file("db-warn.log")
+//! route "*"           level>=error          => stderr
+//! route "api.*"       tenant=="acme"        => acme
+//! default                                   => stdout
+//! ```
+//!
+//! * the glob is matched against the logger name; `*` matches any run of characters
+//!   (dots included) and `?` a single one;
+//! * conditions are `level` comparisons (`>=`, `>`, `<=`, `<`, `==` with a level name),
+//!   [tag](crate::Logger::log_tagged) tests `tag=="billing"` / `tag!="billing"` and field
+//!   comparisons `key==value` / `key!=value`, where the value is a quoted string or a JSON
+//!   literal (`42`, `true`, `null`); all conditions of a rule must hold;
+//! * sinks are `stdout`, `stderr`, `file("path")` or a name registered with
+//!   [`RouterOutput::with_sink`].
+//!
+//! Every matching rule receives the record; records no rule matches go to the `default`
+//! sink, if there is one.
+
+use crate::error::BuildError;
+use crate::level::Level;
+use crate::logger::LevelRange;
+use crate::output::{FileOutput, Output, Record, StderrOutput, StdoutOutput};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io;
+use std::ops::{Bound, RangeBounds};
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+/// Upper bound on cached name lookups; names beyond it are matched on every record.
+const MAX_CACHED_NAMES: usize = 1024;
+
+/// Conditions a record has to meet for a route of a [`RouterOutput`].
+///
+/// ```
+/// use cappie::Level;
+/// use cappie::output::RouteRule;
+///
+/// let rule = RouteRule::new("backend.db*").levels(Level::Warn..).field("region", "eu");
+/// let billing = RouteRule::new("*").tag("billing");
+/// ```
+#[derive(Debug, Clone)]
+pub struct RouteRule {
+    glob: String,
+    levels: LevelRange,
+    tags: Vec<(String, bool)>,
+    fields: Vec<(String, Value, bool)>,
+}
+
+impl RouteRule {
+    /// Rule for loggers whose name matches `glob`.
+    pub fn new(glob: &str) -> Self {
+        Self {
+            glob: glob.to_string(),
+            levels: (Bound::Unbounded, Bound::Unbounded),
+            tags: Vec::new(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Only records whose level is in `levels`.
+    pub fn levels<R: RangeBounds<Level>>(mut self, levels: R) -> Self {
+        self.levels = (levels.start_bound().cloned(), levels.end_bound().cloned());
+        self
+    }
+
+    /// Only records tagged `tag`.
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.tags.push((tag.to_string(), true));
+        self
+    }
+
+    /// Only records not tagged `tag`.
+    pub fn without_tag(mut self, tag: &str) -> Self {
+        self.tags.push((tag.to_string(), false));
+        self
+    }
+
+    /// Only records whose field `key` equals `value`.
+    pub fn field<T: Into<Value>>(mut self, key: &str, value: T) -> Self {
+        self.fields.push((key.to_string(), value.into(), true));
+        self
+    }
+
+    /// Only records whose field `key` is absent or differs from `value`.
+    pub fn field_ne<T: Into<Value>>(mut self, key: &str, value: T) -> Self {
+        self.fields.push((key.to_string(), value.into(), false));
+        self
+    }
+
+    fn matches_fields(&self, record: &Record<'_>) -> bool {
+        self.tags
+            .iter()
+            .all(|(tag, tagged)| record.tags.contains(&tag.as_str()) == *tagged)
+            && self
+                .fields
+                .iter()
+                .all(|(key, value, equal)| (record.fields.get(key) == Some(value)) == *equal)
+    }
+}
+
+/// Output that sends records to sinks according to [rules](RouteRule) on logger name,
+/// level and fields, configured in code or with the [configuration language](self).
+///
+/// ```
+/// use cappie::{Level, Logger};
+/// use cappie::output::{RouteRule, RouterOutput, StderrOutput, StdoutOutput};
+///
+/// let router = RouterOutput::new()
+///     .route(RouteRule::new("backend.db*").levels(Level::Warn..), Box::new(StderrOutput))
+///     .default_output(Box::new(StdoutOutput));
+///
+/// let same = RouterOutput::parse(r#"
+///     route "backend.db*" level>=warn => stderr
+///     default => stdout
+/// "#).unwrap();
+///
+/// let log = Logger::new("backend").with_output(Box::new(same));
+/// ```
+#[derive(Default)]
+pub struct RouterOutput {
+    rules: Vec<(RouteRule, Arc<dyn Output>)>,
+    default: Option<Arc<dyn Output>>,
+    sinks: HashMap<String, Arc<dyn Output>>,
+    /// Indices of the rules whose glob matches a logger name.
+    by_name: RwLock<HashMap<Box<str>, Arc<[usize]>>>,
+}
+
+impl RouterOutput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send records matching `rule` to `output`.
+    pub fn route(mut self, rule: RouteRule, output: Box<dyn Output>) -> Self {
+        self.rules.push((rule, Arc::from(output)));
+        self.by_name.get_mut().unwrap_or_else(|e| e.into_inner()).clear();
+        self
+    }
+
+    /// Output for records no rule matches.
+    pub fn default_output(mut self, output: Box<dyn Output>) -> Self {
+        self.default = Some(Arc::from(output));
+        self
+    }
+
+    /// Make `output` available to configurations as the sink `name`.  Register sinks
+    /// before calling [`with_config`](Self::with_config).
+    pub fn with_sink(mut self, name: &str, output: Box<dyn Output>) -> Self {
+        self.sinks.insert(name.to_string(), Arc::from(output));
+        self
+    }
+
+    /// Router configured by `config`, see the [module documentation](self).
+    pub fn parse(config: &str) -> Result<Self, BuildError> {
+        Self::new().with_config(config)
+    }
+
+    /// Router configured by the file at `path`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, BuildError> {
+        let path = path.as_ref();
+        let config = std::fs::read_to_string(path).map_err(|e| BuildError::Custom(format!("cannot read {}: {}", path.display(), e)))?;
+        Self::parse(&config)
+    }
+
+    /// Add the rules of `config` to this router.
+    pub fn with_config(mut self, config: &str) -> Result<Self, BuildError> {
+        for (index, text) in config.lines().enumerate() {
+            let invalid = |reason: String| BuildError::InvalidRoute { line: index + 1, reason };
+            let tokens = tokenize(text).map_err(invalid)?;
+            if tokens.is_empty() {
+                continue;
+            }
+            let (rule, sink) = parse_line(&tokens).map_err(invalid)?;
+            let output = self.sink(&sink).map_err(invalid)?;
+            match rule {
+                Some(rule) => self.rules.push((rule, output)),
+                None => self.default = Some(output),
+            }
+        }
+        self.by_name.get_mut().unwrap_or_else(|e| e.into_inner()).clear();
+        Ok(self)
+    }
+
+    fn sink(&self, sink: &Sink) -> Result<Arc<dyn Output>, String> {
+        Ok(match sink {
+            Sink::Stdout => Arc::new(StdoutOutput),
+            Sink::Stderr => Arc::new(StderrOutput),
+            Sink::File(path) => Arc::new(FileOutput::new(path)),
+            Sink::Named(name) => self.sinks.get(name).cloned().ok_or_else(|| format!("unknown sink `{}`", name))?,
+        })
+    }
+
+    /// Hand `record` to every sink whose rule matches, or to the default sink when none
+    /// does; the first error is returned.
+    fn deliver(&self, record: &Record<'_>, write: impl Fn(&dyn Output) -> io::Result<()>) -> io::Result<()> {
+        let mut result = Ok(());
+        let mut routed = false;
+        for &index in self.rules_for(record.name).iter() {
+            let (rule, output) = &self.rules[index];
+            if rule.levels.contains(&record.level) && rule.matches_fields(record) {
+                let written = write(output.as_ref());
+                if result.is_ok() {
+                    result = written;
+                }
+                routed = true;
+            }
+        }
+        if !routed {
+            if let Some(default) = &self.default {
+                result = write(default.as_ref());
+            }
+        }
+        result
+    }
+
+    fn rules_for(&self, name: &str) -> Arc<[usize]> {
+        if let Some(rules) = self.by_name.read().unwrap_or_else(|e| e.into_inner()).get(name) {
+            return rules.clone();
+        }
+        let rules: Arc<[usize]> = self
+            .rules
+            .iter()
+            .enumerate()
+            .filter(|(_, (rule, _))| glob_match(&rule.glob, name))
+            .map(|(index, _)| index)
+            .collect();
+        let mut cache = self.by_name.write().unwrap_or_else(|e| e.into_inner());
+        if cache.len() < MAX_CACHED_NAMES {
+            cache.insert(name.into(), rules.clone());
+        }
+        rules
+    }
+}
+
+impl Output for RouterOutput {
+    /// Text without metadata matches no rule and goes to the default sink.
+    fn write(&self, message: &str) {
+        if let Some(default) = &self.default {
+            default.write(message);
+        }
+    }
+
+    fn write_record(&self, record: &Record<'_>) {
+        let _ = self.deliver(record, |output| {
+            output.write_record(record);
+            Ok(())
+        });
+    }
+
+    /// Writes to every matching sink and reports the first error.
+    fn try_write_record(&self, record: &Record<'_>) -> io::Result<()> {
+        self.deliver(record, |output| output.try_write_record(record))
+    }
+
+    fn flush(&self) {
+        for (_, output) in &self.rules {
+            output.flush();
+        }
+        if let Some(default) = &self.default {
+            default.flush();
+        }
+    }
+
+    /// Flushes every sink and reports the first error.
+    fn try_flush(&self) -> io::Result<()> {
+        let mut result = Ok(());
+        for output in self.rules.iter().map(|(_, output)| output).chain(&self.default) {
+            let flushed = output.try_flush();
+            if result.is_ok() {
+                result = flushed;
+            }
+        }
+        result
+    }
+
+    fn validate(&self) -> Result<(), BuildError> {
+        self.rules
+            .iter()
+            .map(|(_, output)| output)
+            .chain(&self.default)
+            .try_for_each(|output| output.validate())
+    }
+}
+
+/// `*` matches any run of characters, `?` exactly one.
+fn glob_match(glob: &str, name: &str) -> bool {
+    let (glob, name): (Vec<char>, Vec<char>) = (glob.chars().collect(), name.chars().collect());
+    let (mut g, mut n) = (0, 0);
+    // Position after the last `*` and the name position it was tried at, for backtracking.
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match glob.get(g) {
+            Some('*') => {
+                star = Some((g + 1, n));
+                g += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                g += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((after, tried)) => {
+                    g = after;
+                    n = tried + 1;
+                    star = Some((after, tried + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    glob[g..].iter().all(|&c| c == '*')
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Str(String),
+    Op(&'static str),
+    Open,
+    Close,
+}
+
+enum Sink {
+    Stdout,
+    Stderr,
+    File(String),
+    Named(String),
+}
+
+fn tokenize(line: &str) -> Result<Vec<Token>, String> {
+    const OPS: [&str; 8] = ["=>", ">=", "<=", "==", "!=", ">", "<", "="];
+
+    let mut tokens = Vec::new();
+    let mut rest = line.trim_start();
+    while let Some(c) = rest.chars().next() {
+        if c == '#' {
+            break;
+        }
+        if c == '"' {
+            let (text, after) = quoted(rest)?;
+            tokens.push(Token::Str(text));
+            rest = after;
+        } else if c == '(' || c == ')' {
+            tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            rest = &rest[1..];
+        } else if let Some(op) = OPS.iter().find(|op| rest.starts_with(**op)) {
+            tokens.push(Token::Op(op));
+            rest = &rest[op.len()..];
+        } else {
+            let end = rest
+                .find(|c: char| c.is_whitespace() || "\"()<>=!#".contains(c))
+                .unwrap_or(rest.len());
+            if end == 0 {
+                return Err(format!("unexpected `{}`", c));
+            }
+            tokens.push(Token::Word(rest[..end].to_string()));
+            rest = &rest[end..];
+        }
+        rest = rest.trim_start();
+    }
+    Ok(tokens)
+}
+
+/// A `"…"` string with `\"` and `\\` escapes, and the text after it.
+fn quoted(text: &str) -> Result<(String, &str), String> {
+    let mut value = String::new();
+    let mut chars = text.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, &text[i + 1..])),
+            '\\' => match chars.next() {
+                Some((_, c)) => value.push(c),
+                None => break,
+            },
+            c => value.push(c),
+        }
+    }
+    Err("unterminated string".to_string())
+}
+
+/// A `route …` line gives a rule, a `default …` line none.
+fn parse_line(tokens: &[Token]) -> Result<(Option<RouteRule>, Sink), String> {
+    let arrow = tokens
+        .iter()
+        .position(|t| *t == Token::Op("=>"))
+        .ok_or("missing `=> sink`")?;
+    let (head, sink) = (&tokens[..arrow], parse_sink(&tokens[arrow + 1..])?);
+
+    match head.first() {
+        Some(Token::Word(w)) if w == "default" && head.len() == 1 => Ok((None, sink)),
+        Some(Token::Word(w)) if w == "route" => {
+            let glob = match head.get(1) {
+                Some(Token::Str(glob)) | Some(Token::Word(glob)) => glob,
+                _ => return Err("expected a quoted name glob after `route`".to_string()),
+            };
+            let mut rule = RouteRule::new(glob);
+            let mut conditions = head[2..].chunks(3);
+            for condition in &mut conditions {
+                rule = parse_condition(rule, condition)?;
+            }
+            Ok((Some(rule), sink))
+        }
+        _ => Err("expected `route` or `default`".to_string()),
+    }
+}
+
+fn parse_condition(mut rule: RouteRule, condition: &[Token]) -> Result<RouteRule, String> {
+    let [Token::Word(key), Token::Op(op), value] = condition else {
+        return Err("expected a condition such as `level>=warn` or `key==\"value\"`".to_string());
+    };
+    if key == "level" {
+        let level = match value {
+            Token::Word(name) | Token::Str(name) => Level::from_str(name).ok_or_else(|| format!("unknown level `{}`", name))?,
+            _ => return Err("expected a level name".to_string()),
+        };
+        let (start, end) = &mut rule.levels;
+        match *op {
+            ">=" => *start = Bound::Included(level),
+            ">" => *start = Bound::Excluded(level),
+            "<=" => *end = Bound::Included(level),
+            "<" => *end = Bound::Excluded(level),
+            "==" | "=" => (*start, *end) = (Bound::Included(level), Bound::Included(level)),
+            op => return Err(format!("`{}` cannot compare levels", op)),
+        }
+        return Ok(rule);
+    }
+    if key == "tag" {
+        let Token::Str(tag) = value else {
+            return Err("expected a quoted tag".to_string());
+        };
+        return match *op {
+            "==" | "=" => Ok(rule.tag(tag)),
+            "!=" => Ok(rule.without_tag(tag)),
+            op => Err(format!("`{}` cannot compare tags", op)),
+        };
+    }
+
+    let value = match value {
+        Token::Str(text) => Value::String(text.clone()),
+        Token::Word(text) => serde_json::from_str(text).map_err(|_| format!("`{}` is neither a quoted string nor a JSON literal", text))?,
+        _ => return Err(format!("expected a value for `{}`", key)),
+    };
+    match *op {
+        "==" | "=" => Ok(rule.field(key, value)),
+        "!=" => Ok(rule.field_ne(key, value)),
+        op => Err(format!("`{}` cannot compare fields", op)),
+    }
+}
+
+fn parse_sink(tokens: &[Token]) -> Result<Sink, String> {
+    match tokens {
+        [Token::Word(w)] if w == "stdout" => Ok(Sink::Stdout),
+        [Token::Word(w)] if w == "stderr" => Ok(Sink::Stderr),
+        [Token::Word(w), Token::Open, Token::Str(path), Token::Close] if w == "file" => Ok(Sink::File(path.clone())),
+        [Token::Word(name)] => Ok(Sink::Named(name.clone())),
+        _ => Err("expected `stdout`, `stderr`, `file(\"path\")` or a sink name after `=>`".to_string()),
+    }
+}
@@ -0,0 +1,70 @@
+use crate::level::Level;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Shared, runtime‑adjustable sampling rate of a [`Logger`](crate::Logger): the fraction
+/// of records below `Warn` that are written, from `0.0` (none) to `1.0` (all, the
+/// default).  Warnings and errors are never sampled out.  Like
+/// [`LevelHandle`](crate::LevelHandle), clones are views of the **same** rate.
+///
+/// Sampling is deterministic: at a rate of `0.25` exactly every fourth record is kept,
+/// so counts in the output can be scaled back up.
+///
+/// ```
+/// use cappie::{Logger, SamplingHandle};
+///
+/// let log = Logger::new("api").with_sampling(0.5);
+/// let sampling: SamplingHandle = log.sampling_handle();
+/// sampling.set(1.0);
+/// assert_eq!(log.sampling_handle().get(), 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SamplingHandle(Arc<State>);
+
+#[derive(Debug)]
+struct State {
+    /// Bits of the `f64` rate.
+    rate: AtomicU64,
+    /// Records seen below `Warn`, kept or not.
+    seen: AtomicU64,
+}
+
+impl SamplingHandle {
+    /// A handle with `rate`, clamped to `0.0..=1.0`; `NaN` counts as `1.0`.
+    pub fn new(rate: f64) -> Self {
+        Self(Arc::new(State { rate: AtomicU64::new(clamp(rate).to_bits()), seen: AtomicU64::new(0) }))
+    }
+
+    pub fn get(&self) -> f64 {
+        f64::from_bits(self.0.rate.load(Ordering::Relaxed))
+    }
+
+    pub fn set(&self, rate: f64) {
+        self.0.rate.store(clamp(rate).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Whether a record at `level` should be written.
+    pub(crate) fn keep(&self, level: Level) -> bool {
+        let rate = self.get();
+        if level >= Level::Warn || rate >= 1.0 {
+            return true;
+        }
+        // Keep the n‑th record when it pushes `n * rate` past the next whole number.
+        let n = self.0.seen.fetch_add(1, Ordering::Relaxed) + 1;
+        (n as f64 * rate).floor() > ((n - 1) as f64 * rate).floor()
+    }
+}
+
+impl Default for SamplingHandle {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+fn clamp(rate: f64) -> f64 {
+    if rate.is_nan() {
+        1.0
+    } else {
+        rate.clamp(0.0, 1.0)
+    }
+}
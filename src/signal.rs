@@ -0,0 +1,71 @@
+use crate::level::{global_level, set_global_level, Level};
+use crate::logger::Logger;
+use signal_hook::consts::{SIGUSR1, SIGUSR2};
+use signal_hook::iterator::{Handle, Signals};
+use std::io;
+use std::thread::{self, JoinHandle};
+
+/// Background listener installed by [`install_level_toggle`].  Dropping it leaves the
+/// handlers running for the rest of the process; call [`close`](Self::close) to remove them.
+pub struct LevelToggle {
+    handle: Handle,
+    thread: JoinHandle<()>,
+}
+
+impl LevelToggle {
+    /// Unregister the signal handlers and wait for the listener thread to exit.  The global
+    /// override stays at whatever it was last set to.
+    pub fn close(self) {
+        self.handle.close();
+        let _ = self.thread.join();
+    }
+}
+
+/// Let operators change verbosity of a live process without a restart:
+///
+/// * `SIGUSR1` lowers the [global level](crate::set_global_level) one step, first to
+///   `Debug`, then to `Trace`.
+/// * `SIGUSR2` clears the override again so every logger uses its own level.
+///
+/// Each change is reported through `logger`, which is moved onto the listener thread.
+///
+/// ```no_run
+/// use cappie::Logger;
+///
+/// let toggle = cappie::signal::install_level_toggle(Logger::new("signals")).unwrap();
+/// // kill -USR1 <pid>  -> debug logs on
+/// // kill -USR2 <pid>  -> back to normal
+/// # toggle.close();
+/// ```
+pub fn install_level_toggle(logger: Logger) -> io::Result<LevelToggle> {
+    let mut signals = Signals::new([SIGUSR1, SIGUSR2])?;
+    let handle = signals.handle();
+
+    let thread = thread::Builder::new()
+        .name("cappie-signals".to_string())
+        .spawn(move || {
+            for signal in signals.forever() {
+                match signal {
+                    SIGUSR1 => {
+                        let next = match global_level() {
+                            Some(Level::Debug) | Some(Level::Trace) => Level::Trace,
+                            _ => Level::Debug,
+                        };
+                        set_global_level(Some(next));
+                        logger.info_with("log level lowered by SIGUSR1", |b| {
+                            b.string("log_level", next.as_str());
+                        });
+                    }
+                    SIGUSR2 => {
+                        // Report first: once the override is gone the logger's own level
+                        // might filter this record out.
+                        logger.info("log level restored by SIGUSR2");
+                        set_global_level(None);
+                    }
+                    _ => {}
+                }
+            }
+        })?;
+
+    Ok(LevelToggle { handle, thread })
+}
@@ -0,0 +1,122 @@
+use crate::level::Level;
+use crate::logger::Logger;
+use crate::timestamp::Timestamp;
+use serde_json::{Map, Value};
+use slog::{Drain, FlushError, Key, Never, OwnedKVList, Record, Serializer, KV};
+use std::fmt;
+
+/// A Cappie [`Logger`] can be the drain of an [`slog`](https://docs.rs/slog) logger, so a
+/// codebase moves to Cappie one module at a time: the `slog` call sites stay, and their
+/// records reach Cappie's formatters and outputs.  Feature `slog`.
+///
+/// The record's message becomes the Cappie message; its key‑values and those of the `slog`
+/// logger become fields, the record's own winning over the logger's.  Numbers and booleans
+/// keep their type, nested `serde` values become JSON, anything else is written as text.
+/// `Critical` maps to `Fatal`.  The Cappie logger's level and filters apply as usual.
+///
+/// ```
+/// use cappie::{CaptureOutput, Logger};
+/// use slog::{info, o};
+///
+/// let capture = CaptureOutput::new();
+/// let cappie = Logger::new("billing").with_output(Box::new(capture.clone()));
+/// let log = slog::Logger::root(cappie, o!("region" => "eu"));
+/// info!(log, "invoice sent"; "invoice_id" => 42);
+///
+/// let line = capture.contents();
+/// assert!(line.contains(r#""invoice_id":42"#) && line.contains(r#""region":"eu""#));
+/// ```
+impl Drain for Logger {
+    type Ok = ();
+    type Err = Never;
+
+    fn log(&self, record: &Record<'_>, values: &OwnedKVList) -> Result<(), Never> {
+        let level = level(record.level());
+        if !self.enabled(level) {
+            return Ok(());
+        }
+        // Record values first, then the logger's from the newest child up: the first
+        // value for a key is kept.
+        let mut fields = Fields(Map::new());
+        let _ = record.kv().serialize(record, &mut fields);
+        let _ = values.serialize(record, &mut fields);
+        self.log_with_time(level, Timestamp::now(), &record.msg().to_string(), fields.0);
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<(), FlushError> {
+        Logger::flush(self);
+        Ok(())
+    }
+
+    fn is_enabled(&self, level: slog::Level) -> bool {
+        self.enabled(self::level(level))
+    }
+}
+
+fn level(level: slog::Level) -> Level {
+    match level {
+        slog::Level::Trace => Level::Trace,
+        slog::Level::Debug => Level::Debug,
+        slog::Level::Info => Level::Info,
+        slog::Level::Warning => Level::Warn,
+        slog::Level::Error => Level::Error,
+        slog::Level::Critical => Level::Fatal,
+    }
+}
+
+/// Collects `slog` key‑values as record fields.
+struct Fields(Map<String, Value>);
+
+impl Fields {
+    fn put(&mut self, key: Key, value: Value) -> slog::Result {
+        self.0.entry(key.to_string()).or_insert(value);
+        Ok(())
+    }
+}
+
+macro_rules! emit_as_value {
+    ($($method:ident: $ty:ty),* $(,)?) => {
+        $(
+            fn $method(&mut self, key: Key, value: $ty) -> slog::Result {
+                self.put(key, Value::from(value))
+            }
+        )*
+    };
+}
+
+impl Serializer for Fields {
+    emit_as_value! {
+        emit_bool: bool,
+        emit_u8: u8,
+        emit_i8: i8,
+        emit_u16: u16,
+        emit_i16: i16,
+        emit_u32: u32,
+        emit_i32: i32,
+        emit_u64: u64,
+        emit_i64: i64,
+        emit_usize: usize,
+        emit_isize: isize,
+        emit_f32: f32,
+        emit_f64: f64,
+        emit_str: &str,
+    }
+
+    fn emit_unit(&mut self, key: Key) -> slog::Result {
+        self.put(key, Value::Null)
+    }
+
+    fn emit_none(&mut self, key: Key) -> slog::Result {
+        self.put(key, Value::Null)
+    }
+
+    fn emit_arguments(&mut self, key: Key, value: &fmt::Arguments<'_>) -> slog::Result {
+        self.put(key, Value::from(value.to_string()))
+    }
+
+    fn emit_serde(&mut self, key: Key, value: &dyn slog::SerdeValue) -> slog::Result {
+        let value = serde_json::to_value(value.as_serde()).unwrap_or_else(|e| Value::from(e.to_string()));
+        self.put(key, value)
+    }
+}
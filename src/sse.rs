@@ -0,0 +1,208 @@
+use crate::console::strip_ansi;
+use crate::http_head;
+use crate::level::Level;
+use crate::output::{Output, Record};
+use std::io::{self, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+const WRITE_TIMEOUT: Duration = Duration::from_millis(200);
+/// Requests being read at once; connections beyond this are closed unanswered.
+const MAX_PENDING: usize = 16;
+
+/// Streams formatted records to browsers and `curl` over Server‑Sent Events.  Feature `sse`.
+///
+/// [`bind`](Self::bind) starts a small HTTP server on a background thread.  Every `GET`
+/// request becomes a subscription; each record is sent as one event named after its level,
+/// with the formatted record as data.  `?level=warn` in the URL subscribes to `Warn` and
+/// above only.  ANSI colors are stripped; binary records are not sent.
+///
+/// A client that does not keep up (a write blocks for more than 200 ms) or has gone away
+/// is disconnected, so a stuck browser tab cannot hold up logging.  Requests are read off
+/// the accept thread and answered with 431 beyond 8 KiB of headers or 64 header lines, so
+/// a slow or oversized request does not hold up other subscribers either.
+///
+/// ```no_run
+/// use cappie::Logger;
+/// use cappie::output::SseOutput;
+///
+/// let sse = SseOutput::bind("127.0.0.1:9092").unwrap();
+/// let log = Logger::new("worker").with_output(Box::new(sse));
+/// // curl -N 'http://127.0.0.1:9092/?level=warn'
+/// log.warn("disk almost full");
+/// ```
+pub struct SseOutput {
+    addr: SocketAddr,
+    clients: Arc<Mutex<Vec<Client>>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+struct Client {
+    stream: TcpStream,
+    min_level: Level,
+}
+
+impl SseOutput {
+    /// Listen on `addr`.  Port 0 picks a free port; see [`local_addr`](Self::local_addr).
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let clients: Arc<Mutex<Vec<Client>>> = Arc::default();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let clients = clients.clone();
+            let stop = stop.clone();
+            let pending = Arc::new(AtomicUsize::new(0));
+            thread::Builder::new()
+                .name("cappie-sse".to_string())
+                .spawn(move || {
+                    for stream in listener.incoming() {
+                        if stop.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        let Ok(stream) = stream else { continue };
+                        if pending.fetch_add(1, Ordering::SeqCst) >= MAX_PENDING {
+                            pending.fetch_sub(1, Ordering::SeqCst);
+                            continue;
+                        }
+                        let clients = clients.clone();
+                        let stop = stop.clone();
+                        let handshake_pending = pending.clone();
+                        let spawned = thread::Builder::new().name("cappie-sse-subscribe".to_string()).spawn(move || {
+                            if let Ok(client) = subscribe(stream) {
+                                if !stop.load(Ordering::SeqCst) {
+                                    clients.lock().unwrap_or_else(|e| e.into_inner()).push(client);
+                                }
+                            }
+                            handshake_pending.fetch_sub(1, Ordering::SeqCst);
+                        });
+                        if spawned.is_err() {
+                            pending.fetch_sub(1, Ordering::SeqCst);
+                        }
+                    }
+                })?
+        };
+
+        Ok(Self { addr, clients, stop, thread: Some(thread) })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Number of connected clients.
+    pub fn clients(&self) -> usize {
+        self.clients.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    fn send(&self, level: Option<Level>, text: &str) {
+        let mut clients = self.clients.lock().unwrap_or_else(|e| e.into_inner());
+        if clients.is_empty() {
+            return;
+        }
+        let event = event(level, text);
+        clients.retain_mut(|client| {
+            if level.is_some_and(|level| level < client.min_level) {
+                return true;
+            }
+            client.stream.write_all(event.as_bytes()).is_ok()
+        });
+    }
+}
+
+impl Output for SseOutput {
+    /// Text without metadata is sent to every client, as an unnamed event.
+    fn write(&self, message: &str) {
+        self.send(None, &strip_ansi(message));
+    }
+
+    fn write_record(&self, record: &Record<'_>) {
+        if !record.binary {
+            self.send(Some(record.level), &strip_ansi(&record.text()));
+        }
+    }
+}
+
+impl Drop for SseOutput {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // Unblock `accept` with a throwaway connection.
+        let _ = TcpStream::connect(self.addr);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Read the request and answer with the event‑stream headers, or with an error (and no
+/// subscription) for anything but `GET`, an unknown level or an oversized request.
+fn subscribe(stream: TcpStream) -> io::Result<Client> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    stream.set_write_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let Some(head) = http_head::read_head(&mut reader)? else {
+        let refused = reject(&stream, "431 Request Header Fields Too Large", "request headers too large");
+        http_head::close_unread(&stream, reader)?;
+        return refused;
+    };
+
+    let method = head.method.as_str();
+    let level = head.target
+        .split_once('?')
+        .map(|(_, query)| query)
+        .into_iter()
+        .flat_map(|query| query.split('&'))
+        .find_map(|pair| pair.strip_prefix("level="));
+
+    let mut stream = stream;
+    let min_level = match (method, level) {
+        ("GET", None) => Level::Trace,
+        ("GET", Some(level)) => match Level::from_str(level) {
+            Some(level) => level,
+            None => return reject(&stream, "400 Bad Request", "unknown level"),
+        },
+        _ => return reject(&stream, "405 Method Not Allowed", "only GET is supported"),
+    };
+
+    stream.write_all(
+        b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n\
+          Connection: keep-alive\r\nAccess-Control-Allow-Origin: *\r\n\r\n",
+    )?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    Ok(Client { stream, min_level })
+}
+
+fn reject(mut stream: &TcpStream, status: &str, msg: &str) -> io::Result<Client> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        msg.len(),
+        msg,
+    )?;
+    Err(io::Error::new(io::ErrorKind::InvalidInput, msg.to_string()))
+}
+
+/// One SSE event.  Each line of `text` becomes a `data:` line, so multi‑line records
+/// arrive intact.
+fn event(level: Option<Level>, text: &str) -> String {
+    let mut event = String::with_capacity(text.len() + 32);
+    if let Some(level) = level {
+        event.push_str("event: ");
+        event.push_str(&level.as_str().to_lowercase());
+        event.push('\n');
+    }
+    for line in text.lines() {
+        event.push_str("data: ");
+        event.push_str(line);
+        event.push('\n');
+    }
+    event.push('\n');
+    event
+}
@@ -0,0 +1,252 @@
+use crate::logger::{Logger, LoggerFactory};
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::cell::{Cell, RefCell};
+use std::fmt::Display;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::Once;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+static INSTALL_HOOK: Once = Once::new();
+
+thread_local! {
+    /// Set while a supervised future is polled on this thread.
+    static SUPERVISED: Cell<bool> = const { Cell::new(false) };
+    /// Backtrace of the last panic in a supervised future, taken by the panic hook.
+    static BACKTRACE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Spawn `future` on the current tokio runtime and log what happens to it, so background
+/// tasks cannot die silently.  Feature `tokio`.
+///
+/// Records carry a `task` field with `name`:
+///
+/// * `task started` at `Debug`;
+/// * `task finished` at `Info`, with `duration_ms`;
+/// * `task panicked` at `Error`, with the panic message and the backtrace of the panic
+///   site (taken by a panic hook installed on first use, which then calls the previous
+///   hook);
+/// * `task cancelled` at `Warn` if the task is aborted or its runtime shuts down first.
+///
+/// The handle resolves to `None` if the task panicked.
+///
+/// ```no_run
+/// use cappie::{supervised_spawn, Logger};
+///
+/// # async fn run() {
+/// let log = Logger::new("worker");
+/// let handle = supervised_spawn(&log, "cache-refresh", async {
+///     // ...
+/// });
+/// handle.await.unwrap();
+/// # }
+/// ```
+pub fn supervised_spawn<F>(logger: &Logger, name: &str, future: F) -> JoinHandle<Option<F::Output>>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    install_hook();
+    let logger = task_logger(logger, name);
+    tokio::spawn(async move {
+        let mut run = Run::start(&logger);
+        match (CatchPanic { inner: Box::pin(future) }).await {
+            Ok(output) => {
+                run.finished();
+                Some(output)
+            }
+            Err(panic) => {
+                run.panicked(panic);
+                None
+            }
+        }
+    })
+}
+
+/// When and how often [`supervised_spawn_restarting`] restarts a failed task.
+#[derive(Debug, Clone)]
+pub struct RestartPolicy {
+    max_restarts: Option<u32>,
+    backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl RestartPolicy {
+    /// Restart without limit, waiting 1 second before the first restart and doubling the
+    /// wait up to 60 seconds.
+    pub fn new() -> Self {
+        Self {
+            max_restarts: None,
+            backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+
+    /// Give up after `n` restarts.
+    pub fn with_max_restarts(mut self, n: u32) -> Self {
+        self.max_restarts = Some(n);
+        self
+    }
+
+    /// Wait `initial` before the first restart, doubling up to `max` for the following.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Like [`supervised_spawn`], but the task is made by `make` and restarted according to
+/// `policy` when it returns an error (logged as `task failed` at `Error`) or panics.  Each
+/// restart is logged as `task restarting` at `Warn` with `restart` (the attempt number) and
+/// `delay_ms`; running out of restarts as `task gave up` at `Error`.  An `Ok` result ends
+/// supervision.  Feature `tokio`.
+///
+/// ```no_run
+/// use cappie::{supervised_spawn_restarting, Logger, RestartPolicy};
+///
+/// # async fn consume() -> Result<(), std::io::Error> { Ok(()) }
+/// # async fn run() {
+/// let log = Logger::new("worker");
+/// supervised_spawn_restarting(&log, "queue-consumer", RestartPolicy::new().with_max_restarts(10), || consume());
+/// # }
+/// ```
+pub fn supervised_spawn_restarting<M, F, E>(logger: &Logger, name: &str, policy: RestartPolicy, mut make: M) -> JoinHandle<()>
+where
+    M: FnMut() -> F + Send + 'static,
+    F: Future<Output = Result<(), E>> + Send + 'static,
+    E: Display,
+{
+    install_hook();
+    let logger = task_logger(logger, name);
+    tokio::spawn(async move {
+        let mut restarts = 0u32;
+        let mut delay = policy.backoff;
+        loop {
+            let mut run = Run::start(&logger);
+            match (CatchPanic { inner: Box::pin(make()) }).await {
+                Ok(Ok(())) => {
+                    run.finished();
+                    return;
+                }
+                Ok(Err(error)) => run.failed(&error),
+                Err(panic) => run.panicked(panic),
+            }
+
+            if policy.max_restarts.is_some_and(|max| restarts >= max) {
+                logger.error_with("task gave up", |b| {
+                    b.field("restarts", restarts);
+                });
+                return;
+            }
+            restarts += 1;
+            logger.warn_with("task restarting", |b| {
+                b.field("restart", restarts).duration("delay_ms", delay);
+            });
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(policy.max_backoff);
+        }
+    })
+}
+
+fn task_logger(logger: &Logger, name: &str) -> Logger {
+    LoggerFactory::new(logger).logger([("task", name)])
+}
+
+/// One run of a supervised task.  Dropped without an outcome means the future was
+/// dropped before completing.
+struct Run<'a> {
+    logger: &'a Logger,
+    start: Instant,
+    done: bool,
+}
+
+impl<'a> Run<'a> {
+    fn start(logger: &'a Logger) -> Self {
+        logger.debug("task started");
+        Self { logger, start: Instant::now(), done: false }
+    }
+
+    fn finished(&mut self) {
+        self.done = true;
+        let start = self.start;
+        self.logger.info_with("task finished", |b| {
+            b.elapsed_since("duration_ms", start);
+        });
+    }
+
+    fn failed<E: Display>(&mut self, error: &E) {
+        self.done = true;
+        let start = self.start;
+        self.logger.error_with("task failed", |b| {
+            b.field("error", error.to_string()).elapsed_since("duration_ms", start);
+        });
+    }
+
+    fn panicked(&mut self, panic: Panic) {
+        self.done = true;
+        self.logger.log_panic_with_backtrace("task panicked", panic.payload.as_ref(), panic.backtrace.as_deref());
+    }
+}
+
+impl Drop for Run<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            let start = self.start;
+            self.logger.warn_with("task cancelled", |b| {
+                b.elapsed_since("duration_ms", start);
+            });
+        }
+    }
+}
+
+struct Panic {
+    payload: Box<dyn Any + Send>,
+    backtrace: Option<String>,
+}
+
+/// Polls `inner`, turning a panic into an error carrying the backtrace the hook took.
+struct CatchPanic<F> {
+    inner: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for CatchPanic<F> {
+    type Output = Result<F::Output, Panic>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let outer = SUPERVISED.with(|s| s.replace(true));
+        let result = panic::catch_unwind(AssertUnwindSafe(|| self.inner.as_mut().poll(cx)));
+        SUPERVISED.with(|s| s.set(outer));
+        match result {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => Poll::Ready(Err(Panic {
+                payload,
+                backtrace: BACKTRACE.with(|b| b.borrow_mut().take()),
+            })),
+        }
+    }
+}
+
+fn install_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if SUPERVISED.with(Cell::get) {
+                let backtrace = Backtrace::force_capture().to_string();
+                BACKTRACE.with(|b| *b.borrow_mut() = Some(backtrace));
+            }
+            previous(info);
+        }));
+    });
+}
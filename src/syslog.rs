@@ -0,0 +1,210 @@
+use crate::formatter::Formatter;
+use crate::level::Level;
+use crate::timestamp::{self, Precision, Timestamp};
+use serde_json::{Map, Value};
+
+/// Syslog facility, the "what kind of program" half of the priority value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Facility {
+    Kern = 0,
+    #[default]
+    User = 1,
+    Mail = 2,
+    Daemon = 3,
+    Auth = 4,
+    Syslog = 5,
+    Lpr = 6,
+    News = 7,
+    Uucp = 8,
+    Cron = 9,
+    AuthPriv = 10,
+    Ftp = 11,
+    Local0 = 16,
+    Local1 = 17,
+    Local2 = 18,
+    Local3 = 19,
+    Local4 = 20,
+    Local5 = 21,
+    Local6 = 22,
+    Local7 = 23,
+}
+
+/// [RFC 5424](https://datatracker.ietf.org/doc/html/rfc5424) syslog messages:
+///
+/// ```text
+/// <134>1 2024-01-15T10:30:00.123456Z web-1 api 4242 REQ [fields@32473 user="alice"] login
+/// ```
+///
+/// Facility, app‑name, hostname and msgid are configured once on the formatter, so every
+/// record of the logger carries the same header; fields go into a structured‑data element.
+/// The app‑name defaults to the logger name and the hostname to the machine's.  Header
+/// values are trimmed to the lengths and characters RFC 5424 allows.
+///
+/// ```
+/// use cappie::{Facility, Logger, SyslogFormatter};
+///
+/// let log = Logger::new("api").with_formatter(Box::new(
+///     SyslogFormatter::new()
+///         .with_facility(Facility::Local0)
+///         .with_app_name("billing-api")
+///         .with_msgid("REQ"),
+/// ));
+/// log.info("login");
+/// ```
+#[derive(Debug, Clone)]
+pub struct SyslogFormatter {
+    facility: Facility,
+    app_name: Option<String>,
+    hostname: String,
+    msgid: Option<String>,
+    sd_id: String,
+    procid: u32,
+}
+
+impl Default for SyslogFormatter {
+    fn default() -> Self {
+        Self {
+            facility: Facility::User,
+            app_name: None,
+            hostname: hostname().unwrap_or_default(),
+            msgid: None,
+            sd_id: "fields@32473".to_string(),
+            procid: std::process::id(),
+        }
+    }
+}
+
+impl SyslogFormatter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_facility(mut self, facility: Facility) -> Self {
+        self.facility = facility;
+        self
+    }
+
+    /// APP‑NAME header; defaults to the logger name.
+    pub fn with_app_name(mut self, app_name: &str) -> Self {
+        self.app_name = Some(app_name.to_string());
+        self
+    }
+
+    pub fn with_hostname(mut self, hostname: &str) -> Self {
+        self.hostname = hostname.to_string();
+        self
+    }
+
+    /// MSGID header, identifying the type of message (`-` if unset).
+    pub fn with_msgid(mut self, msgid: &str) -> Self {
+        self.msgid = Some(msgid.to_string());
+        self
+    }
+
+    /// SD‑ID of the element holding the fields (default `fields@32473`, the documentation
+    /// enterprise number).  Use your own `name@<PEN>` in production.
+    pub fn with_sd_id(mut self, sd_id: &str) -> Self {
+        self.sd_id = sd_id.to_string();
+        self
+    }
+
+    /// Syslog severity (0 = emergency … 7 = debug) for `level`.
+    pub fn severity(level: Level) -> u8 {
+        match level {
+            Level::Trace | Level::Debug => 7,
+            Level::Info => 6,
+            Level::Warn => 4,
+            Level::Error => 3,
+            Level::Fatal => 2,
+        }
+    }
+}
+
+impl Formatter for SyslogFormatter {
+    fn format(&self, level: Level, msg: &str, fields: &Map<String, Value>, timestamp: Timestamp, name: &str) -> String {
+        let pri = self.facility as u8 * 8 + Self::severity(level);
+        let mut line = format!(
+            "<{}>1 {} {} {} {} {} ",
+            pri,
+            timestamp::rfc3339_z(&timestamp, Precision::Micros),
+            header(&self.hostname, 255),
+            header(self.app_name.as_deref().unwrap_or(name), 48),
+            self.procid,
+            header(self.msgid.as_deref().unwrap_or(""), 32),
+        );
+
+        if fields.is_empty() {
+            line.push('-');
+        } else {
+            line.push('[');
+            line.push_str(&sd_name(&self.sd_id));
+            for (key, value) in fields {
+                line.push(' ');
+                line.push_str(&sd_name(key));
+                line.push_str("=\"");
+                let value = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                for c in value.chars() {
+                    if matches!(c, '"' | '\\' | ']') {
+                        line.push('\\');
+                    }
+                    line.push(c);
+                }
+                line.push('"');
+            }
+            line.push(']');
+        }
+
+        if !msg.is_empty() {
+            line.push(' ');
+            // One record per line: the transport frames messages by newline.
+            line.extend(msg.chars().map(|c| if c == '\n' || c == '\r' { ' ' } else { c }));
+        }
+        line
+    }
+}
+
+/// Header field: printable US‑ASCII without spaces, at most `max` characters, `-` if
+/// empty.
+fn header(value: &str, max: usize) -> String {
+    let value: String = value.chars().filter(|c| c.is_ascii_graphic()).take(max).collect();
+    if value.is_empty() {
+        "-".to_string()
+    } else {
+        value
+    }
+}
+
+/// SD‑NAME: printable US‑ASCII except `=`, space, `]` and `"`, at most 32 characters.
+fn sd_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_graphic() && !matches!(c, '=' | ']' | '"') { c } else { '_' })
+        .take(32)
+        .collect();
+    if name.is_empty() {
+        "_".to_string()
+    } else {
+        name
+    }
+}
+
+/// The machine's host name, if it can be determined.
+#[cfg(unix)]
+pub(crate) fn hostname() -> Option<String> {
+    let mut buf = [0u8; 256];
+    // SAFETY: gethostname writes at most `buf.len()` bytes into the live local buffer.
+    let rc = unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) };
+    if rc != 0 {
+        return None;
+    }
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    String::from_utf8(buf[..len].to_vec()).ok()
+}
+
+#[cfg(not(unix))]
+pub(crate) fn hostname() -> Option<String> {
+    std::env::var("COMPUTERNAME").ok()
+}
@@ -0,0 +1,199 @@
+//! Parser for the [`FlexibleFormatter::from_template`](crate::FlexibleFormatter::from_template)
+//! mini language.
+
+use crate::error::BuildError;
+use crate::formatter::{Align, ComponentPosition, ComponentType, TemplateComponent};
+
+/// A `{…}` token or the literal text between tokens.
+enum Piece {
+    Literal(String),
+    Token { component: ComponentType, color: Option<String>, layout: Layout },
+}
+
+/// The `:<12.40` part of a token.
+#[derive(Default)]
+struct Layout {
+    width: Option<usize>,
+    align: Align,
+    truncate: Option<usize>,
+}
+
+/// Turn `template` into components whose bucket positions render them in template order.
+pub(crate) fn parse(template: &str) -> Result<Vec<TemplateComponent>, BuildError> {
+    let pieces = tokenize(template)?;
+
+    let mut components: Vec<TemplateComponent> = Vec::new();
+    let mut pending: Option<String> = None;
+    // Positions are rendered in a fixed bucket order, so a component may never go into an
+    // earlier bucket than the one before it.
+    let mut bucket = 0;
+    let mut natural = 0;
+
+    for piece in pieces {
+        match piece {
+            Piece::Literal(text) => pending = Some(text),
+            Piece::Token { component, color, layout } => {
+                bucket = bucket.max(natural);
+                natural = match component {
+                    ComponentType::Timestamp => 1,
+                    ComponentType::LoggerName => 2,
+                    ComponentType::Level => 3,
+                    ComponentType::Message => 4,
+                    ComponentType::Fields => 5,
+                    ComponentType::CustomText(_) => natural,
+                };
+                components.push(TemplateComponent {
+                    color,
+                    prefix: pending.take(),
+                    width: layout.width,
+                    align: layout.align,
+                    truncate: layout.truncate,
+                    ..TemplateComponent::new(component, POSITIONS[bucket].clone())
+                });
+            }
+        }
+    }
+
+    // Text after the last token closes it, e.g. the `]` in `[{fields}]`.
+    if let Some(text) = pending {
+        match components.last_mut() {
+            Some(last) => last.suffix = Some(text),
+            None => components.push(TemplateComponent::new(ComponentType::CustomText(text), ComponentPosition::Start)),
+        }
+    }
+    Ok(components)
+}
+
+const POSITIONS: [ComponentPosition; 6] = [
+    ComponentPosition::Start,
+    ComponentPosition::AfterTime,
+    ComponentPosition::AfterName,
+    ComponentPosition::AfterLevel,
+    ComponentPosition::AfterMessage,
+    ComponentPosition::End,
+];
+
+fn tokenize(template: &str) -> Result<Vec<Piece>, BuildError> {
+    let mut pieces = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut token = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => token.push(c),
+                        None => return Err(invalid(template, "unclosed `{`")),
+                    }
+                }
+                if !literal.is_empty() {
+                    pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+                }
+                pieces.push(parse_token(template, &token)?);
+            }
+            '}' => return Err(invalid(template, "unmatched `}` (write `}}` for a literal brace)")),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        pieces.push(Piece::Literal(literal));
+    }
+    Ok(pieces)
+}
+
+fn parse_token(template: &str, token: &str) -> Result<Piece, BuildError> {
+    let (name, style) = match token.split_once('|') {
+        Some((name, style)) => (name.trim(), Some(style.trim())),
+        None => (token.trim(), None),
+    };
+    let (name, layout) = match name.split_once(':') {
+        Some((name, spec)) => {
+            let layout = parse_layout(spec.trim()).ok_or_else(|| invalid(template, &format!("invalid width `{}`", spec)))?;
+            (name.trim(), layout)
+        }
+        None => (name, Layout::default()),
+    };
+    let component = match name {
+        "time" | "timestamp" => ComponentType::Timestamp,
+        "name" | "logger" => ComponentType::LoggerName,
+        "level" => ComponentType::Level,
+        "msg" | "message" => ComponentType::Message,
+        "fields" => ComponentType::Fields,
+        _ => return Err(invalid(template, &format!("unknown component `{}`", name))),
+    };
+    let color = match style {
+        Some(style) => Some(parse_style(style).ok_or_else(|| invalid(template, &format!("unknown style `{}`", style)))?),
+        None => None,
+    };
+    Ok(Piece::Token { component, color, layout })
+}
+
+/// `[<>^][width][.max]`, as in `format!`: `<12`, `>5`, `.80`, `^20.40`.
+fn parse_layout(spec: &str) -> Option<Layout> {
+    let (align, rest) = match spec.chars().next() {
+        Some('<') => (Align::Left, &spec[1..]),
+        Some('>') => (Align::Right, &spec[1..]),
+        Some('^') => (Align::Center, &spec[1..]),
+        _ => (Align::Left, spec),
+    };
+    let (width, truncate) = match rest.split_once('.') {
+        Some((width, max)) => (width, Some(max.parse().ok()?)),
+        None => (rest, None),
+    };
+    let width = if width.is_empty() { None } else { Some(width.parse().ok()?) };
+    if width.is_none() && truncate.is_none() {
+        return None;
+    }
+    Some(Layout { width, align, truncate })
+}
+
+/// `red`, `bold+cyan`, `bright_blue` … into a single SGR escape sequence.
+fn parse_style(style: &str) -> Option<String> {
+    let codes = style
+        .split('+')
+        .map(|name| sgr_code(name.trim()))
+        .collect::<Option<Vec<_>>>()?;
+    Some(format!("\x1b[{}m", codes.join(";")))
+}
+
+fn sgr_code(name: &str) -> Option<&'static str> {
+    Some(match name.replace('-', "_").as_str() {
+        "bold" => "1",
+        "dim" => "2",
+        "italic" => "3",
+        "underline" => "4",
+        "reverse" => "7",
+        "black" => "30",
+        "red" => "31",
+        "green" => "32",
+        "yellow" => "33",
+        "blue" => "34",
+        "magenta" => "35",
+        "cyan" => "36",
+        "white" => "37",
+        "gray" | "grey" | "bright_black" => "90",
+        "bright_red" => "91",
+        "bright_green" => "92",
+        "bright_yellow" => "93",
+        "bright_blue" => "94",
+        "bright_magenta" => "95",
+        "bright_cyan" => "96",
+        "bright_white" => "97",
+        _ => return None,
+    })
+}
+
+fn invalid(template: &str, reason: &str) -> BuildError {
+    BuildError::InvalidTemplate { template: template.to_string(), reason: reason.to_string() }
+}
@@ -0,0 +1,211 @@
+//! Checks for [`Formatter`] implementations, run against a corpus of awkward records –
+//! control characters, emoji, right‑to‑left text, huge and deeply nested fields, keys that
+//! collide with the envelope – the same corpus Cappie's own formatters are tested with.
+//!
+//! ```
+//! use cappie::{testkit, JsonFormatter};
+//!
+//! testkit::assert_one_line_per_record(&JsonFormatter);
+//! testkit::assert_json_fields_round_trip(&JsonFormatter);
+//! ```
+//!
+//! Each assertion panics with the name of the first [`GoldenRecord`] that fails, so they
+//! can be called straight from a `#[test]`.
+
+use crate::formatter::Formatter;
+use crate::level::Level;
+use crate::timestamp::{self, Timestamp};
+use serde_json::{json, Map, Value};
+
+/// One record of the [`corpus`].
+#[derive(Debug, Clone)]
+pub struct GoldenRecord {
+    /// What the record exercises, reported when an assertion fails.
+    pub name: &'static str,
+    pub level: Level,
+    pub msg: String,
+    pub fields: Map<String, Value>,
+    pub timestamp: Timestamp,
+    pub logger: String,
+}
+
+impl GoldenRecord {
+    fn new(name: &'static str, msg: &str) -> Self {
+        Self {
+            name,
+            level: Level::Info,
+            msg: msg.to_string(),
+            fields: Map::new(),
+            timestamp: timestamp::ymd_hms(2024, 2, 29, 23, 59, 59),
+            logger: "testkit".to_string(),
+        }
+    }
+
+    fn field(mut self, key: &str, value: Value) -> Self {
+        self.fields.insert(key.to_string(), value);
+        self
+    }
+
+    /// The record through `formatter`'s [`format`](Formatter::format).
+    pub fn format(&self, formatter: &dyn Formatter) -> String {
+        formatter.format(self.level, &self.msg, &self.fields, self.timestamp, &self.logger)
+    }
+
+    /// The record through `formatter`'s [`format_into`](Formatter::format_into).
+    pub fn format_into(&self, formatter: &dyn Formatter) -> Vec<u8> {
+        let mut buf = Vec::new();
+        formatter.format_into(&mut buf, self.level, &self.msg, &self.fields, self.timestamp, &self.logger);
+        buf
+    }
+}
+
+/// Nesting depth of the `deep nesting` record, below serde_json's parse limit of 128.
+const DEPTH: usize = 100;
+
+/// The golden records, in a fixed order.
+pub fn corpus() -> Vec<GoldenRecord> {
+    let mut nested = json!("bottom");
+    for i in 0..DEPTH {
+        nested = if i % 2 == 0 { json!([nested]) } else { json!({ "level": nested }) };
+    }
+    let many: Map<String, Value> = (0..1000).map(|i| (format!("field_{i}"), Value::from(i))).collect();
+
+    let mut records = vec![
+        GoldenRecord::new("plain", "user signed in").field("user", json!("alice")),
+        GoldenRecord::new("empty message", ""),
+        GoldenRecord::new("line breaks", "first\nsecond\r\nthird\rfourth"),
+        GoldenRecord::new("control characters", "nul\0 bell\x07 tab\t escape\x1b[31mred\x1b[0m del\x7f")
+            .field("ctl", json!("\x00\x01\x1f\u{85}")),
+        GoldenRecord::new("unicode separators", "line\u{2028}paragraph\u{2029}end")
+            .field("sep", json!("\u{2028}")),
+        GoldenRecord::new("unicode", "naïve café, 日本語, ελληνικά, e\u{301}")
+            .field("städte", json!(["Zürich", "Kraków", "İstanbul"])),
+        GoldenRecord::new("right to left", "שלום עולם \u{202e}txt.exe")
+            .field("مفتاح", json!("قيمة")),
+        GoldenRecord::new("emoji", "deploy 🚀 done 👩‍👩‍👧‍👦 🏳️‍🌈 🇳🇿")
+            .field("mood", json!("😀")),
+        GoldenRecord::new("quotes and escapes", r#"she said "hi" \ back\slash {braces} key=value"#)
+            .field("path", json!(r"C:\Users\o'neil")),
+        GoldenRecord::new("awkward keys", "keys")
+            .field("", json!("empty key"))
+            .field("key with spaces", json!(1))
+            .field("a=b", json!(2))
+            .field("\"quoted\"", json!(3))
+            .field("new\nline", json!(4)),
+        GoldenRecord::new("envelope collisions", "original message")
+            .field("msg", json!("field msg"))
+            .field("level", json!("field level"))
+            .field("time", json!("field time"))
+            .field("name", json!("field name")),
+        GoldenRecord::new("numbers", "numbers")
+            .field("u64_max", json!(u64::MAX))
+            .field("i64_min", json!(i64::MIN))
+            .field("tiny", json!(f64::MIN_POSITIVE))
+            .field("huge", json!(f64::MAX))
+            .field("negative_zero", json!(-0.0))
+            .field("pi", json!(std::f64::consts::PI)),
+        GoldenRecord::new("null and bool", "scalars")
+            .field("null", Value::Null)
+            .field("yes", json!(true))
+            .field("no", json!(false)),
+        GoldenRecord::new("empty containers", "containers")
+            .field("array", json!([]))
+            .field("object", json!({}))
+            .field("string", json!("")),
+        GoldenRecord::new("deep nesting", "deep").field("nested", nested),
+        GoldenRecord::new("huge field", "huge").field("blob", json!("x".repeat(1 << 20))),
+        GoldenRecord::new("huge message", &"long message ".repeat(10_000)),
+        GoldenRecord::new("many fields", "many"),
+        GoldenRecord::new("mixed array", "array")
+            .field("items", json!([1, "two", 3.5, null, true, {"six": [7]}, "line\nbreak"])),
+    ];
+    records.iter_mut().find(|r| r.name == "many fields").unwrap().fields = many;
+
+    let mut odd_logger = GoldenRecord::new("odd logger name", "named");
+    odd_logger.logger = "backend.http\n\"handlers\" 🚀".to_string();
+    records.push(odd_logger);
+    for level in [Level::Trace, Level::Debug, Level::Warn, Level::Error] {
+        let mut record = GoldenRecord::new("levels", "every level");
+        record.level = level;
+        records.push(record);
+    }
+    records
+}
+
+/// Every record must format to a single line: no `\n` or `\r` anywhere.  Skips
+/// [binary](Formatter::is_binary) formatters.
+pub fn assert_one_line_per_record(formatter: &dyn Formatter) {
+    if formatter.is_binary() {
+        return;
+    }
+    for record in corpus() {
+        let line = record.format(formatter);
+        if let Some(at) = line.find(['\n', '\r']) {
+            panic!("`{}`: line break at byte {at} of {}", record.name, excerpt(&line, at));
+        }
+    }
+}
+
+/// [`format_into`](Formatter::format_into) must append exactly what
+/// [`format`](Formatter::format) returns, after whatever the buffer already held.
+pub fn assert_format_into_matches_format(formatter: &dyn Formatter) {
+    for record in corpus() {
+        let expected = record.format(formatter);
+        let mut buf = b"prefix".to_vec();
+        formatter.format_into(&mut buf, record.level, &record.msg, &record.fields, record.timestamp, &record.logger);
+        if !buf.starts_with(b"prefix") || buf[6..] != *expected.as_bytes() {
+            panic!("`{}`: format_into differs from format", record.name);
+        }
+    }
+}
+
+/// Every record must be one JSON object holding each field unchanged at the top level
+/// (fields named like an envelope key may replace it).  For JSON formatters that write
+/// fields flat, like [`JsonFormatter`](crate::JsonFormatter).
+pub fn assert_json_fields_round_trip(formatter: &dyn Formatter) {
+    for record in corpus() {
+        let line = record.format(formatter);
+        let parsed: Value = match serde_json::from_str(&line) {
+            Ok(parsed) => parsed,
+            Err(e) => panic!("`{}`: not valid JSON ({e}): {}", record.name, excerpt(&line, e.column())),
+        };
+        let Some(object) = parsed.as_object() else {
+            panic!("`{}`: not a JSON object", record.name);
+        };
+        for (key, value) in &record.fields {
+            match object.get(key) {
+                Some(parsed) if same(parsed, value) => {}
+                Some(parsed) => panic!("`{}`: field {key:?} came back as {}", record.name, excerpt(&parsed.to_string(), 0)),
+                None => panic!("`{}`: field {key:?} is missing", record.name),
+            }
+        }
+    }
+}
+
+/// Equality that allows for float parsing being off in the last bit.
+fn same(parsed: &Value, expected: &Value) -> bool {
+    match (parsed, expected) {
+        (Value::Number(a), Value::Number(b)) if b.is_f64() => {
+            let (a, b) = (a.as_f64().unwrap_or(f64::NAN), b.as_f64().unwrap_or(f64::NAN));
+            a == b || ((a - b) / b).abs() < 1e-15
+        }
+        (Value::Array(a), Value::Array(b)) => a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same(a, b)),
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len() && b.iter().all(|(k, v)| a.get(k).is_some_and(|a| same(a, v)))
+        }
+        _ => parsed == expected,
+    }
+}
+
+/// Up to 80 characters of `text` around byte `at`, for failure messages.
+fn excerpt(text: &str, at: usize) -> String {
+    let boundary = |mut i: usize| {
+        while !text.is_char_boundary(i) {
+            i -= 1;
+        }
+        i
+    };
+    let start = boundary(at.saturating_sub(40));
+    let end = boundary(at.saturating_add(40).min(text.len()));
+    format!("{:?}", &text[start..end])
+}
@@ -0,0 +1,125 @@
+use crate::level::Level;
+
+/// Named color schemes for the human‑readable formatters, see
+/// [`PrettyFormatter::with_theme`](crate::PrettyFormatter::with_theme) and
+/// [`FlexibleFormatter::with_theme`](crate::FlexibleFormatter::with_theme).
+///
+/// `Solarized`, `Dracula` and `ColorblindSafe` use 24‑bit colors, which the standard
+/// outputs reduce to the nearest color on terminals with fewer (see
+/// [`console::color_depth`](crate::console::color_depth)); the others stick to the basic
+/// 16 ANSI colors and attributes.
+///
+/// ```
+/// use cappie::{PrettyFormatter, Theme};
+///
+/// let formatter = PrettyFormatter::new().with_theme(Theme::Dracula);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Theme {
+    /// The built‑in colors of [`PrettyFormatter`](crate::PrettyFormatter).
+    #[default]
+    Default,
+    Solarized,
+    Dracula,
+    /// No hues at all – severity is carried by dim, bold, underline and reverse video.
+    Monochrome,
+    /// Bold bright colors; errors and fatals on a solid background.
+    HighContrast,
+    /// The Okabe–Ito palette, distinguishable with the common forms of color blindness.
+    /// Errors are bold as well, so they never rely on hue alone.
+    ColorblindSafe,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 6] = [
+        Theme::Default,
+        Theme::Solarized,
+        Theme::Dracula,
+        Theme::Monochrome,
+        Theme::HighContrast,
+        Theme::ColorblindSafe,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Theme::Default => "default",
+            Theme::Solarized => "solarized",
+            Theme::Dracula => "dracula",
+            Theme::Monochrome => "monochrome",
+            Theme::HighContrast => "high-contrast",
+            Theme::ColorblindSafe => "colorblind",
+        }
+    }
+
+    /// Parse a theme name as returned by [`as_str`](Self::as_str), e.g. from an environment
+    /// variable.  Case‑insensitive; `_` and `-` are interchangeable.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Theme> {
+        let name = s.trim().to_lowercase().replace('_', "-");
+        Theme::ALL.into_iter().find(|theme| theme.as_str() == name)
+    }
+
+    /// ANSI escape sequence used for `level`.  Empty means "print without styling".
+    pub fn level_color(&self, level: Level) -> &'static str {
+        match self {
+            Theme::Default => match level {
+                Level::Trace => "\x1b[90m",
+                Level::Debug => "\x1b[36m",
+                Level::Info => "\x1b[32m",
+                Level::Warn => "\x1b[33m",
+                Level::Error => "\x1b[31m",
+                Level::Fatal => "\x1b[35m",
+            },
+            Theme::Solarized => match level {
+                Level::Trace => "\x1b[38;2;88;110;117m",
+                Level::Debug => "\x1b[38;2;42;161;152m",
+                Level::Info => "\x1b[38;2;133;153;0m",
+                Level::Warn => "\x1b[38;2;181;137;0m",
+                Level::Error => "\x1b[38;2;220;50;47m",
+                Level::Fatal => "\x1b[1;38;2;211;54;130m",
+            },
+            Theme::Dracula => match level {
+                Level::Trace => "\x1b[38;2;98;114;164m",
+                Level::Debug => "\x1b[38;2;139;233;253m",
+                Level::Info => "\x1b[38;2;80;250;123m",
+                Level::Warn => "\x1b[38;2;241;250;140m",
+                Level::Error => "\x1b[38;2;255;85;85m",
+                Level::Fatal => "\x1b[1;38;2;255;121;198m",
+            },
+            Theme::Monochrome => match level {
+                Level::Trace | Level::Debug => "\x1b[2m",
+                Level::Info => "",
+                Level::Warn => "\x1b[1m",
+                Level::Error => "\x1b[1;4m",
+                Level::Fatal => "\x1b[1;7m",
+            },
+            Theme::HighContrast => match level {
+                Level::Trace => "\x1b[37m",
+                Level::Debug => "\x1b[1;96m",
+                Level::Info => "\x1b[1;92m",
+                Level::Warn => "\x1b[1;93m",
+                Level::Error => "\x1b[1;97;41m",
+                Level::Fatal => "\x1b[1;97;45m",
+            },
+            Theme::ColorblindSafe => match level {
+                Level::Trace => "\x1b[38;2;153;153;153m",
+                Level::Debug => "\x1b[38;2;86;180;233m",
+                Level::Info => "\x1b[38;2;0;158;115m",
+                Level::Warn => "\x1b[38;2;230;159;0m",
+                Level::Error => "\x1b[1;38;2;213;94;0m",
+                Level::Fatal => "\x1b[1;38;2;204;121;167m",
+            },
+        }
+    }
+
+    /// Subdued style for secondary parts of a line such as the timestamp and logger name.
+    pub fn muted(&self) -> &'static str {
+        match self {
+            Theme::Default | Theme::Monochrome => "\x1b[2m",
+            Theme::Solarized => "\x1b[38;2;88;110;117m",
+            Theme::Dracula => "\x1b[38;2;98;114;164m",
+            Theme::HighContrast => "\x1b[97m",
+            Theme::ColorblindSafe => "\x1b[38;2;153;153;153m",
+        }
+    }
+}
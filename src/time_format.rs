@@ -0,0 +1,75 @@
+use crate::error::BuildError;
+use crate::timestamp;
+
+/// Timestamp layout for the human‑readable formatters: a named preset or a custom
+/// strftime pattern as documented by
+/// [chrono](https://docs.rs/chrono/latest/chrono/format/strftime/index.html).
+///
+/// Anything that takes `impl Into<TimeFormat>` also accepts a plain pattern string:
+///
+/// ```
+/// use cappie::{PrettyFormatter, TimeFormat};
+///
+/// let a = PrettyFormatter::new().with_time_format(TimeFormat::Rfc3339);
+/// let b = PrettyFormatter::new().with_time_format("%d.%m. %H:%M");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TimeFormat {
+    /// `2025-06-21T12:34:56.789012345+00:00`
+    Rfc3339,
+    /// Seconds since the Unix epoch: `1750509296`
+    Unix,
+    /// Milliseconds since the Unix epoch: `1750509296789`
+    UnixMillis,
+    /// `20250621T123456Z`, sortable and free of separators that need quoting.
+    Compact,
+    /// `12:34:56`, the default of the pretty formatters.
+    Time,
+    /// Any other strftime pattern.
+    Custom(String),
+}
+
+impl TimeFormat {
+    /// The strftime pattern this format stands for.
+    pub fn pattern(&self) -> &str {
+        match self {
+            TimeFormat::Rfc3339 => "%+",
+            TimeFormat::Unix => "%s",
+            TimeFormat::UnixMillis => "%s%3f",
+            TimeFormat::Compact => "%Y%m%dT%H%M%SZ",
+            TimeFormat::Time => "%H:%M:%S",
+            TimeFormat::Custom(pattern) => pattern,
+        }
+    }
+    
+    /// Check that the timestamp backend understands every specifier in the pattern.  The
+    /// presets always pass.
+    pub fn validate(&self) -> Result<(), BuildError> {
+        if !timestamp::valid_pattern(self.pattern()) {
+            return Err(BuildError::InvalidTimeFormat { format: self.pattern().to_string() });
+        }
+        Ok(())
+    }
+}
+
+impl From<&str> for TimeFormat {
+    fn from(pattern: &str) -> Self {
+        TimeFormat::Custom(pattern.to_string())
+    }
+}
+
+impl From<String> for TimeFormat {
+    fn from(pattern: String) -> Self {
+        TimeFormat::Custom(pattern)
+    }
+}
+
+/// Validated pattern of `format`, panicking with a readable message if it is invalid.  Used
+/// by the `with_time_format` builder methods.
+#[track_caller]
+pub(crate) fn checked_pattern(format: TimeFormat) -> String {
+    if let Err(e) = format.validate() {
+        panic!("{}", e);
+    }
+    format.pattern().to_string()
+}
@@ -0,0 +1,414 @@
+//! The time type records carry, and the handful of operations the crate needs on it.
+//!
+//! [`Timestamp`] holds chrono's `DateTime<Utc>` with the default `chrono` feature, or
+//! `time::OffsetDateTime` when only the `time` feature is on.  It converts from and into
+//! the types of every enabled backend, so turning on `time` next to `chrono` only adds
+//! conversions.  Everything else in the crate goes through this module, so formatters,
+//! outputs and parsers behave the same on both.
+
+use std::fmt;
+use std::time::SystemTime;
+
+/// Point in time a record was logged at, in UTC.
+///
+/// Create one with [`Timestamp::now`], from a `SystemTime`, or from a
+/// `chrono::DateTime<Utc>` (feature `chrono`) or `time::OffsetDateTime` (feature `time`);
+/// it converts back into each of them.  Converting into the backend the crate does not
+/// store panics outside the years ‑9999 to 9999, which `time` cannot represent.
+/// [`Formatter`](crate::Formatter) methods receive this type whichever features are on,
+/// so enabling a backend anywhere in the dependency graph does not change their signature.
+///
+/// ```
+/// use cappie::Timestamp;
+///
+/// let start = Timestamp::from_unix_nanos(1_705_314_600_250_000_000).unwrap();
+/// assert_eq!(start.to_string(), "2024-01-15T10:30:00.250+00:00");
+/// assert!(Timestamp::now() > start);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(backend::Time);
+
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+compile_error!("cappie needs a timestamp backend: enable the `chrono` (default) or the `time` feature");
+
+impl Timestamp {
+    /// The current time.
+    pub fn now() -> Self {
+        backend::now()
+    }
+    
+    /// The time `nanos` nanoseconds after the Unix epoch, or `None` if the backend cannot
+    /// represent it.
+    pub fn from_unix_nanos(nanos: i128) -> Option<Self> {
+        backend::from_unix_nanos(nanos)
+    }
+    
+    /// Nanoseconds since the Unix epoch, negative before it.
+    pub fn unix_nanos(&self) -> i128 {
+        backend::unix_nanos(self)
+    }
+    
+    /// This time as chrono's `DateTime<Utc>`, e.g. for a custom
+    /// [`Formatter`](crate::Formatter) that formats it with chrono.
+    #[cfg(feature = "chrono")]
+    pub fn to_chrono(self) -> chrono::DateTime<chrono::Utc> {
+        self.into()
+    }
+    
+    /// This time as a UTC `time::OffsetDateTime`.  With the `chrono` backend this panics
+    /// outside the years ‑9999 to 9999, like the `From` conversion.
+    #[cfg(feature = "time")]
+    pub fn to_time(self) -> time::OffsetDateTime {
+        self.into()
+    }
+}
+
+/// The Unix epoch.
+impl Default for Timestamp {
+    fn default() -> Self {
+        backend::from_unix(0).unwrap()
+    }
+}
+
+/// RFC 3339 with as many fractional digits as needed: `2024-01-15T10:30:00.250+00:00`.
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&rfc3339(self))
+    }
+}
+
+impl From<SystemTime> for Timestamp {
+    fn from(time: SystemTime) -> Self {
+        Timestamp(time.into())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for Timestamp {
+    fn from(time: chrono::DateTime<chrono::Utc>) -> Self {
+        Timestamp(time)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<Timestamp> for chrono::DateTime<chrono::Utc> {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.0
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::OffsetDateTime> for Timestamp {
+    fn from(time: time::OffsetDateTime) -> Self {
+        #[cfg(feature = "chrono")]
+        return Timestamp::from_unix_nanos(time.unix_timestamp_nanos()).expect("time::OffsetDateTime out of chrono's range");
+        #[cfg(not(feature = "chrono"))]
+        Timestamp(time.to_offset(time::UtcOffset::UTC))
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<Timestamp> for time::OffsetDateTime {
+    fn from(timestamp: Timestamp) -> Self {
+        #[cfg(feature = "chrono")]
+        return time::OffsetDateTime::from_unix_timestamp_nanos(timestamp.unix_nanos()).expect("Timestamp out of the time crate's range");
+        #[cfg(not(feature = "chrono"))]
+        timestamp.0
+    }
+}
+
+/// Fractional seconds written by [`rfc3339_z`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Precision {
+    Secs,
+    Millis,
+    Micros,
+    Nanos,
+    /// 0, 3, 6 or 9 digits, as many as the value needs.
+    Auto,
+}
+
+#[cfg(feature = "chrono")]
+mod backend {
+    use super::{Precision, Timestamp};
+    use chrono::format::{Item, StrftimeItems};
+    use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+    use std::fmt;
+
+    pub(crate) type Time = DateTime<Utc>;
+
+    pub(crate) fn now() -> Timestamp {
+        Timestamp(Utc::now())
+    }
+
+    pub(crate) fn ymd_hms(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> Timestamp {
+        Timestamp(Utc.with_ymd_and_hms(year, month, day, hour, minute, second).unwrap())
+    }
+
+    pub(crate) fn from_unix(secs: i64) -> Option<Timestamp> {
+        DateTime::from_timestamp(secs, 0).map(Timestamp)
+    }
+
+    pub(crate) fn from_unix_nanos(nanos: i128) -> Option<Timestamp> {
+        let secs = i64::try_from(nanos.div_euclid(1_000_000_000)).ok()?;
+        DateTime::from_timestamp(secs, nanos.rem_euclid(1_000_000_000) as u32).map(Timestamp)
+    }
+
+    pub(crate) fn unix_nanos(timestamp: &Timestamp) -> i128 {
+        timestamp.0.timestamp() as i128 * 1_000_000_000 + timestamp.0.timestamp_subsec_nanos() as i128
+    }
+
+    pub(crate) fn parse_rfc3339(text: &str) -> Option<Timestamp> {
+        DateTime::parse_from_rfc3339(text).ok().map(|time| Timestamp(time.with_timezone(&Utc)))
+    }
+
+    /// As many fractional digits as needed and a `+00:00` offset:
+    /// `2024-01-15T10:30:00.250+00:00`.
+    pub(crate) fn rfc3339(timestamp: &Timestamp) -> String {
+        timestamp.0.to_rfc3339()
+    }
+
+    /// UTC with a `Z` suffix: `2024-01-15T10:30:00.250Z`.
+    pub(crate) fn rfc3339_z(timestamp: &Timestamp, precision: Precision) -> String {
+        let format = match precision {
+            Precision::Secs => SecondsFormat::Secs,
+            Precision::Millis => SecondsFormat::Millis,
+            Precision::Micros => SecondsFormat::Micros,
+            Precision::Nanos => SecondsFormat::Nanos,
+            Precision::Auto => SecondsFormat::AutoSi,
+        };
+        timestamp.0.to_rfc3339_opts(format, true)
+    }
+
+    /// `timestamp` laid out by a strftime `pattern`.  Writing it fails on specifiers the
+    /// backend does not know; patterns from the builders have passed `valid_pattern`.
+    pub(crate) fn format<'a>(timestamp: &Timestamp, pattern: &'a str) -> impl fmt::Display + 'a {
+        timestamp.0.format(pattern)
+    }
+
+    pub(crate) fn valid_pattern(pattern: &str) -> bool {
+        !StrftimeItems::new(pattern).any(|item| matches!(item, Item::Error))
+    }
+}
+
+#[cfg(all(feature = "time", not(feature = "chrono")))]
+mod backend {
+    use super::{Precision, Timestamp};
+    use std::fmt::{self, Write};
+    use time::format_description::well_known::Rfc3339;
+    use time::{Date, Month, OffsetDateTime, UtcOffset};
+
+    pub(crate) type Time = OffsetDateTime;
+
+    pub(crate) fn now() -> Timestamp {
+        Timestamp(OffsetDateTime::now_utc())
+    }
+
+    pub(crate) fn ymd_hms(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> Timestamp {
+        let month = Month::try_from(month as u8).unwrap();
+        let time = Date::from_calendar_date(year, month, day as u8)
+            .and_then(|date| date.with_hms(hour as u8, minute as u8, second as u8))
+            .unwrap();
+        Timestamp(time.assume_utc())
+    }
+
+    pub(crate) fn from_unix(secs: i64) -> Option<Timestamp> {
+        OffsetDateTime::from_unix_timestamp(secs).ok().map(Timestamp)
+    }
+
+    pub(crate) fn from_unix_nanos(nanos: i128) -> Option<Timestamp> {
+        OffsetDateTime::from_unix_timestamp_nanos(nanos).ok().map(Timestamp)
+    }
+
+    pub(crate) fn unix_nanos(timestamp: &Timestamp) -> i128 {
+        timestamp.0.unix_timestamp_nanos()
+    }
+
+    pub(crate) fn parse_rfc3339(text: &str) -> Option<Timestamp> {
+        OffsetDateTime::parse(text, &Rfc3339).ok().map(|time| Timestamp(time.to_offset(UtcOffset::UTC)))
+    }
+
+    pub(crate) fn rfc3339(timestamp: &Timestamp) -> String {
+        format(timestamp, "%+").to_string()
+    }
+
+    pub(crate) fn rfc3339_z(timestamp: &Timestamp, precision: Precision) -> String {
+        let pattern = match precision {
+            Precision::Secs => "%Y-%m-%dT%H:%M:%SZ",
+            Precision::Millis => "%Y-%m-%dT%H:%M:%S%.3fZ",
+            Precision::Micros => "%Y-%m-%dT%H:%M:%S%.6fZ",
+            Precision::Nanos => "%Y-%m-%dT%H:%M:%S%.9fZ",
+            Precision::Auto => "%Y-%m-%dT%H:%M:%S%.fZ",
+        };
+        format(timestamp, pattern).to_string()
+    }
+
+    pub(crate) fn format<'a>(timestamp: &Timestamp, pattern: &'a str) -> impl fmt::Display + 'a {
+        Strftime { time: timestamp.0, pattern }
+    }
+
+    pub(crate) fn valid_pattern(pattern: &str) -> bool {
+        write_pattern(&mut String::new(), &OffsetDateTime::UNIX_EPOCH, pattern).is_ok()
+    }
+
+    /// chrono's strftime syntax, less the parse‑only `%#z`.
+    struct Strftime<'a> {
+        time: OffsetDateTime,
+        pattern: &'a str,
+    }
+
+    impl fmt::Display for Strftime<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write_pattern(f, &self.time, self.pattern)
+        }
+    }
+
+    const WEEKDAYS: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+    const MONTHS: [&str; 12] = [
+        "January", "February", "March", "April", "May", "June",
+        "July", "August", "September", "October", "November", "December",
+    ];
+
+    fn write_pattern(out: &mut dyn Write, time: &OffsetDateTime, pattern: &str) -> fmt::Result {
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.write_char(c)?;
+                continue;
+            }
+            // Padding override: `-` none, `_` spaces, `0` zeros.
+            let pad = match chars.peek() {
+                Some('-') => Some(None),
+                Some('_') => Some(Some(' ')),
+                Some('0') => Some(Some('0')),
+                _ => None,
+            };
+            if pad.is_some() {
+                chars.next();
+            }
+            let number = |out: &mut dyn Write, value: i64, width: usize, fill: char| {
+                let fill = pad.unwrap_or(Some(fill));
+                match fill {
+                    Some(' ') => write!(out, "{:>width$}", value),
+                    Some(_) => write!(out, "{:0width$}", value),
+                    None => write!(out, "{}", value),
+                }
+            };
+            let nanos = time.nanosecond();
+            match chars.next().ok_or(fmt::Error)? {
+                'Y' => number(out, time.year() as i64, 4, '0')?,
+                'C' => number(out, time.year().div_euclid(100) as i64, 2, '0')?,
+                'y' => number(out, time.year().rem_euclid(100) as i64, 2, '0')?,
+                'G' => number(out, time.to_iso_week_date().0 as i64, 4, '0')?,
+                'g' => number(out, time.to_iso_week_date().0.rem_euclid(100) as i64, 2, '0')?,
+                'm' => number(out, time.month() as i64, 2, '0')?,
+                'b' | 'h' => out.write_str(&MONTHS[time.month() as usize - 1][..3])?,
+                'B' => out.write_str(MONTHS[time.month() as usize - 1])?,
+                'd' => number(out, time.day() as i64, 2, '0')?,
+                'e' => number(out, time.day() as i64, 2, ' ')?,
+                'a' => out.write_str(&WEEKDAYS[time.weekday().number_days_from_monday() as usize][..3])?,
+                'A' => out.write_str(WEEKDAYS[time.weekday().number_days_from_monday() as usize])?,
+                'w' => number(out, time.weekday().number_days_from_sunday() as i64, 1, '0')?,
+                'u' => number(out, time.weekday().number_from_monday() as i64, 1, '0')?,
+                'U' => number(out, time.sunday_based_week() as i64, 2, '0')?,
+                'W' => number(out, time.monday_based_week() as i64, 2, '0')?,
+                'V' => number(out, time.iso_week() as i64, 2, '0')?,
+                'j' => number(out, time.ordinal() as i64, 3, '0')?,
+                'D' | 'x' => write_pattern(out, time, "%m/%d/%y")?,
+                'F' => write_pattern(out, time, "%Y-%m-%d")?,
+                'v' => write_pattern(out, time, "%e-%b-%Y")?,
+                'H' => number(out, time.hour() as i64, 2, '0')?,
+                'k' => number(out, time.hour() as i64, 2, ' ')?,
+                'I' => number(out, twelve_hour(time.hour()), 2, '0')?,
+                'l' => number(out, twelve_hour(time.hour()), 2, ' ')?,
+                'P' => out.write_str(if time.hour() < 12 { "am" } else { "pm" })?,
+                'p' => out.write_str(if time.hour() < 12 { "AM" } else { "PM" })?,
+                'M' => number(out, time.minute() as i64, 2, '0')?,
+                'S' => number(out, time.second() as i64, 2, '0')?,
+                'f' => write!(out, "{:09}", nanos)?,
+                '.' => match chars.next() {
+                    Some('f') => write_auto_fraction(out, nanos)?,
+                    Some(digits @ ('3' | '6' | '9')) if chars.next() == Some('f') => {
+                        out.write_char('.')?;
+                        write_fraction(out, nanos, digits)?;
+                    }
+                    _ => return Err(fmt::Error),
+                },
+                digits @ ('3' | '6' | '9') if chars.next() == Some('f') => write_fraction(out, nanos, digits)?,
+                'R' => write_pattern(out, time, "%H:%M")?,
+                'T' | 'X' => write_pattern(out, time, "%H:%M:%S")?,
+                'r' => write_pattern(out, time, "%I:%M:%S %p")?,
+                'c' => write_pattern(out, time, "%a %b %e %H:%M:%S %Y")?,
+                '+' => {
+                    write_pattern(out, time, "%Y-%m-%dT%H:%M:%S%.f")?;
+                    write_offset(out, time.offset(), Some(":"))?;
+                }
+                'Z' if time.offset().is_utc() => out.write_str("UTC")?,
+                'Z' => write_offset(out, time.offset(), Some(":"))?,
+                'z' => write_offset(out, time.offset(), Some(""))?,
+                ':' => {
+                    let mut colons = 1;
+                    while chars.next_if_eq(&':').is_some() {
+                        colons += 1;
+                    }
+                    if chars.next() != Some('z') {
+                        return Err(fmt::Error);
+                    }
+                    match colons {
+                        1 => write_offset(out, time.offset(), Some(":"))?,
+                        2 => {
+                            write_offset(out, time.offset(), Some(":"))?;
+                            write!(out, ":{:02}", time.offset().as_hms().2.abs())?;
+                        }
+                        3 => write_offset(out, time.offset(), None)?,
+                        _ => return Err(fmt::Error),
+                    }
+                }
+                's' => write!(out, "{}", time.unix_timestamp())?,
+                't' => out.write_char('\t')?,
+                'n' => out.write_char('\n')?,
+                '%' => out.write_char('%')?,
+                _ => return Err(fmt::Error),
+            }
+        }
+        Ok(())
+    }
+
+    fn twelve_hour(hour: u8) -> i64 {
+        match hour % 12 {
+            0 => 12,
+            hour => hour as i64,
+        }
+    }
+
+    /// `.` and 3, 6 or 9 digits, as many as `nanos` needs; nothing for whole seconds.
+    fn write_auto_fraction(out: &mut dyn Write, nanos: u32) -> fmt::Result {
+        match nanos {
+            0 => Ok(()),
+            n if n % 1_000_000 == 0 => write!(out, ".{:03}", n / 1_000_000),
+            n if n % 1_000 == 0 => write!(out, ".{:06}", n / 1_000),
+            n => write!(out, ".{:09}", n),
+        }
+    }
+
+    fn write_fraction(out: &mut dyn Write, nanos: u32, digits: char) -> fmt::Result {
+        match digits {
+            '3' => write!(out, "{:03}", nanos / 1_000_000),
+            '6' => write!(out, "{:06}", nanos / 1_000),
+            _ => write!(out, "{:09}", nanos),
+        }
+    }
+
+    /// `+hh`, followed by `separator` and the minutes unless it is `None`.
+    fn write_offset(out: &mut dyn Write, offset: UtcOffset, separator: Option<&str>) -> fmt::Result {
+        let (hours, minutes, _) = offset.as_hms();
+        let sign = if offset.is_negative() { '-' } else { '+' };
+        write!(out, "{}{:02}", sign, hours.abs())?;
+        match separator {
+            Some(separator) => write!(out, "{}{:02}", separator, minutes.abs()),
+            None => Ok(()),
+        }
+    }
+}
+
+pub(crate) use backend::{format, from_unix, parse_rfc3339, rfc3339, rfc3339_z, valid_pattern, ymd_hms};
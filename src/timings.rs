@@ -0,0 +1,87 @@
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+pub(crate) const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Durations observed through [`Logger::observe`](crate::Logger::observe) since the last
+/// summary, grouped by name.
+pub(crate) struct Timings {
+    interval: Duration,
+    state: Mutex<State>,
+}
+
+struct State {
+    since: Instant,
+    /// Samples in microseconds.  Kept exactly, so memory grows with the number of
+    /// observations per interval.
+    samples: BTreeMap<String, Vec<u64>>,
+}
+
+impl Timings {
+    pub(crate) fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            state: Mutex::new(State { since: Instant::now(), samples: BTreeMap::new() }),
+        }
+    }
+
+    pub(crate) fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Record `duration` under `name`.  Returns the summary fields if the interval has
+    /// passed, resetting the aggregator.
+    pub(crate) fn observe(&self, name: &str, duration: Duration) -> Option<Map<String, Value>> {
+        let micros = duration.as_micros().min(u64::MAX as u128) as u64;
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match state.samples.get_mut(name) {
+            Some(samples) => samples.push(micros),
+            None => {
+                state.samples.insert(name.to_string(), vec![micros]);
+            }
+        }
+        if state.since.elapsed() < self.interval {
+            return None;
+        }
+        Some(take_summary(&mut state))
+    }
+
+    /// Summary of everything observed so far, or `None` if nothing was; resets the
+    /// aggregator.
+    pub(crate) fn summary(&self) -> Option<Map<String, Value>> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.samples.is_empty() {
+            return None;
+        }
+        Some(take_summary(&mut state))
+    }
+}
+
+/// One field per name: `{"count", "p50_ms", "p95_ms", "max_ms"}`.
+fn take_summary(state: &mut State) -> Map<String, Value> {
+    state.since = Instant::now();
+    std::mem::take(&mut state.samples)
+        .into_iter()
+        .map(|(name, mut samples)| {
+            samples.sort_unstable();
+            let mut stats = Map::new();
+            stats.insert("count".to_string(), Value::from(samples.len()));
+            stats.insert("p50_ms".to_string(), millis(percentile(&samples, 50)));
+            stats.insert("p95_ms".to_string(), millis(percentile(&samples, 95)));
+            stats.insert("max_ms".to_string(), millis(samples[samples.len() - 1]));
+            (name, Value::Object(stats))
+        })
+        .collect()
+}
+
+/// Nearest‑rank percentile of sorted, non‑empty `samples`.
+fn percentile(samples: &[u64], p: usize) -> u64 {
+    let rank = (samples.len() * p).div_ceil(100);
+    samples[rank.saturating_sub(1)]
+}
+
+fn millis(micros: u64) -> Value {
+    Value::from(micros as f64 / 1000.0)
+}
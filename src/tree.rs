@@ -0,0 +1,53 @@
+use crate::level::Level;
+use serde::Serialize;
+use std::sync::{Arc, Mutex, Weak};
+
+/// One logger of a tree, as listed by [`Logger::tree`](crate::Logger::tree).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LoggerInfo {
+    pub name: String,
+    /// The logger's own level.
+    pub level: Level,
+    /// Lowest level actually written, after the process‑wide override and any governor.
+    pub effective_level: Level,
+    /// The formatter and output, as [described](crate::Output::describe) by them.
+    pub formatter: String,
+    pub output: String,
+    /// Level range and output of each [route](crate::Logger::route).
+    pub routes: Vec<(String, String)>,
+}
+
+/// The loggers created from one root, held weakly so dropped loggers disappear.
+pub(crate) struct Tree<T> {
+    members: Mutex<Members<T>>,
+}
+
+struct Members<T> {
+    live: Vec<Weak<T>>,
+    /// Length at which dropped members are next cleared out.
+    prune_at: usize,
+}
+
+const MIN_PRUNE_AT: usize = 16;
+
+impl<T> Tree<T> {
+    pub(crate) fn new() -> Self {
+        Self { members: Mutex::new(Members { live: Vec::new(), prune_at: MIN_PRUNE_AT }) }
+    }
+
+    pub(crate) fn add(&self, member: Weak<T>) {
+        let mut members = self.members.lock().unwrap_or_else(|e| e.into_inner());
+        // Short‑lived children (one per request…) would otherwise pile up.
+        if members.live.len() >= members.prune_at {
+            members.live.retain(|m| m.strong_count() > 0);
+            members.prune_at = (members.live.len() * 2).max(MIN_PRUNE_AT);
+        }
+        members.live.push(member);
+    }
+
+    /// Live members, oldest first.
+    pub(crate) fn members(&self) -> Vec<Arc<T>> {
+        let members = self.members.lock().unwrap_or_else(|e| e.into_inner());
+        members.live.iter().filter_map(Weak::upgrade).collect()
+    }
+}
@@ -0,0 +1,356 @@
+//! Terminal log viewer over a [`LogStore`], built on ratatui.
+//!
+//! [`LogView`] is a widget to place in an existing ratatui UI; [`run`] takes over the
+//! terminal and shows a store full screen until `q` is pressed.
+
+use crate::level::Level;
+use crate::log_store::{LogQuery, LogStore, StoredRecord};
+use crate::timestamp;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, StatefulWidget, Widget};
+use ratatui::DefaultTerminal;
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::io;
+use std::time::Duration;
+
+/// How often [`run`] looks for new records while no key is pressed.
+const REFRESH: Duration = Duration::from_millis(100);
+
+/// Widget listing the records of a [`LogStore`], newest at the bottom, with a status line
+/// below.  What is shown – level filter, search, follow mode, selection – lives in the
+/// [`LogViewState`] it is rendered with.
+///
+/// ```
+/// use cappie::Logger;
+/// use cappie::output::LogStore;
+/// use cappie::tui::{LogView, LogViewState};
+/// use ratatui::backend::TestBackend;
+/// use ratatui::Terminal;
+///
+/// let store = LogStore::new();
+/// let log = Logger::new("ui").with_output(Box::new(store.clone()));
+/// log.info("started");
+///
+/// let mut terminal = Terminal::new(TestBackend::new(60, 8)).unwrap();
+/// let mut state = LogViewState::new();
+/// terminal.draw(|frame| frame.render_stateful_widget(LogView::new(&store), frame.area(), &mut state)).unwrap();
+/// ```
+pub struct LogView<'a> {
+    store: &'a LogStore,
+}
+
+impl<'a> LogView<'a> {
+    pub fn new(store: &'a LogStore) -> Self {
+        Self { store }
+    }
+}
+
+/// What a [`LogView`] shows, changed by [`handle_key`](Self::handle_key):
+///
+/// | Key | Action |
+/// |-----|--------|
+/// | `1` … `6` | show `Trace` … `Fatal` and above |
+/// | `0` | show every level |
+/// | `/` | edit the search; `Enter` applies it, `Esc` cancels |
+/// | `Esc` | clear the search |
+/// | `f` | toggle follow mode |
+/// | `↑` `↓` / `k` `j` | select the previous or next record |
+/// | `PgUp` `PgDn` | move by a page |
+/// | `Home` `End` / `g` `G` | select the oldest or newest record |
+///
+/// In follow mode the newest record stays selected as records arrive.  Moving the
+/// selection away from it leaves follow mode; moving back to it resumes it.
+#[derive(Debug, Clone)]
+pub struct LogViewState {
+    min_level: Option<Level>,
+    search: String,
+    /// Search being edited, shown in place of the status line.
+    input: Option<String>,
+    follow: bool,
+    /// Sequence numbers of the selected and the first visible record.
+    selected: Option<u64>,
+    top: Option<u64>,
+    /// Selection change requested since the last render, applied once the filtered
+    /// records are known.
+    motion: Option<Motion>,
+    /// Records that fit the last render, for `PgUp` and `PgDn`.
+    page: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Motion {
+    By(isize),
+    Oldest,
+    Newest,
+}
+
+impl LogViewState {
+    /// Every level, no search, following.
+    pub fn new() -> Self {
+        Self {
+            min_level: None,
+            search: String::new(),
+            input: None,
+            follow: true,
+            selected: None,
+            top: None,
+            motion: None,
+            page: 1,
+        }
+    }
+
+    pub fn with_min_level(mut self, level: Level) -> Self {
+        self.min_level = Some(level);
+        self
+    }
+
+    /// Show only records whose message, name or fields contain `text`, ignoring case.
+    pub fn with_search(mut self, text: &str) -> Self {
+        self.search = text.to_string();
+        self
+    }
+
+    pub fn with_follow(mut self, follow: bool) -> Self {
+        self.set_follow(follow);
+        self
+    }
+
+    pub fn min_level(&self) -> Option<Level> {
+        self.min_level
+    }
+
+    pub fn search(&self) -> &str {
+        &self.search
+    }
+
+    pub fn following(&self) -> bool {
+        self.follow
+    }
+
+    /// Whether the search is being edited, so keys go to it.
+    pub fn editing(&self) -> bool {
+        self.input.is_some()
+    }
+
+    /// Apply `key` as described on the type.  Returns `false` for keys the view does not
+    /// use, such as `q`, so the caller can act on them.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if key.kind == KeyEventKind::Release {
+            return false;
+        }
+        if let Some(input) = &mut self.input {
+            match key.code {
+                KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => input.push(c),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Enter => {
+                    self.search = std::mem::take(input);
+                    self.input = None;
+                }
+                KeyCode::Esc => self.input = None,
+                _ => return false,
+            }
+            return true;
+        }
+        match key.code {
+            KeyCode::Char('/') => self.input = Some(self.search.clone()),
+            KeyCode::Char('f') => self.set_follow(!self.follow),
+            KeyCode::Char('0') => self.min_level = None,
+            KeyCode::Char(c @ '1'..='6') => self.min_level = Some(Level::ALL[c as usize - '1' as usize]),
+            KeyCode::Up | KeyCode::Char('k') => self.motion = Some(Motion::By(-1)),
+            KeyCode::Down | KeyCode::Char('j') => self.motion = Some(Motion::By(1)),
+            KeyCode::PageUp => self.motion = Some(Motion::By(-(self.page as isize))),
+            KeyCode::PageDown => self.motion = Some(Motion::By(self.page as isize)),
+            KeyCode::Home | KeyCode::Char('g') => self.motion = Some(Motion::Oldest),
+            KeyCode::End | KeyCode::Char('G') => self.motion = Some(Motion::Newest),
+            KeyCode::Esc if !self.search.is_empty() => self.search.clear(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn set_follow(&mut self, follow: bool) {
+        self.follow = follow;
+        if follow {
+            self.motion = Some(Motion::Newest);
+        }
+    }
+
+    fn query(&self) -> LogQuery {
+        let mut query = LogQuery::new();
+        if let Some(level) = self.min_level {
+            query = query.min_level(level);
+        }
+        if !self.search.is_empty() {
+            query = query.text(&self.search);
+        }
+        query
+    }
+
+    /// Apply the pending motion to `records` and return the index of the first visible
+    /// and of the selected record, keeping the selection within `height` rows.
+    fn scroll(&mut self, records: &[StoredRecord], height: usize) -> (usize, Option<usize>) {
+        let motion = self.motion.take();
+        let Some(last) = records.len().checked_sub(1) else {
+            return (0, None);
+        };
+        let position = |seq: u64| records.partition_point(|record| record.seq < seq).min(last);
+        let mut selected = match self.selected {
+            Some(seq) if !self.follow => position(seq),
+            _ => last,
+        };
+        if let Some(motion) = motion {
+            selected = match motion {
+                Motion::By(n) => selected.saturating_add_signed(n).min(last),
+                Motion::Oldest => 0,
+                Motion::Newest => last,
+            };
+            self.follow = selected == last;
+        }
+
+        let height = height.max(1);
+        let mut top = self.top.map_or(0, position).min(selected);
+        if selected >= top + height {
+            top = selected + 1 - height;
+        }
+        // Keep the window full when it can be.
+        top = top.min(records.len().saturating_sub(height));
+
+        self.page = height;
+        self.selected = Some(records[selected].seq);
+        self.top = Some(records[top].seq);
+        (top, Some(selected))
+    }
+}
+
+impl Default for LogViewState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StatefulWidget for LogView<'_> {
+    type State = LogViewState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut LogViewState) {
+        let [list_area, status_area] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+
+        let records = self.store.query(&state.query());
+        let block = Block::bordered().title(title(state));
+        let inner = block.inner(list_area);
+        block.render(list_area, buf);
+
+        let height = inner.height as usize;
+        let (top, selected) = state.scroll(&records, height);
+        let items: Vec<ListItem> = records.iter().skip(top).take(height).map(|record| ListItem::new(line(record))).collect();
+        let mut list_state = ListState::default().with_selected(selected.map(|selected| selected - top));
+        StatefulWidget::render(List::new(items).highlight_style(Style::new().reversed()), inner, buf, &mut list_state);
+
+        let status = match &state.input {
+            Some(input) => Line::from(vec![Span::raw("/").bold(), Span::raw(input.as_str()), Span::raw(" ").reversed()]),
+            None => Line::from(vec![
+                Span::raw(format!("{} of {} records", records.len(), self.store.len())),
+                Span::raw("  1-6 level  0 all  / search  f follow  q quit").dark_gray(),
+            ]),
+        };
+        Paragraph::new(status).render(status_area, buf);
+    }
+}
+
+fn title(state: &LogViewState) -> Line<'static> {
+    let mut title = String::from(" logs");
+    if let Some(level) = state.min_level {
+        let _ = write!(title, " · {}+", level.as_str());
+    }
+    if !state.search.is_empty() {
+        let _ = write!(title, " · {:?}", state.search);
+    }
+    if state.follow {
+        title.push_str(" · following");
+    }
+    title.push(' ');
+    Line::from(title)
+}
+
+fn line(record: &StoredRecord) -> Line<'static> {
+    let mut text = format!(" {}: {}", record.name, record.msg);
+    for (key, value) in &record.fields {
+        match value {
+            Value::String(s) => {
+                let _ = write!(text, " {key}={s}");
+            }
+            value => {
+                let _ = write!(text, " {key}={value}");
+            }
+        }
+    }
+    // A newline or escape sequence would break the row.
+    let text = text.replace(char::is_control, " ");
+    Line::from(vec![
+        Span::raw(timestamp::format(&record.timestamp, "%H:%M:%S%.3f ").to_string()).dark_gray(),
+        Span::styled(format!("{:<5}", record.level.as_str()), level_style(record.level)),
+        Span::raw(text),
+    ])
+}
+
+fn level_style(level: Level) -> Style {
+    match level {
+        Level::Trace => Style::new().dark_gray(),
+        Level::Debug => Style::new().cyan(),
+        Level::Info => Style::new().green(),
+        Level::Warn => Style::new().yellow(),
+        Level::Error => Style::new().red(),
+        Level::Fatal => Style::new().magenta().bold(),
+    }
+}
+
+/// Show `store` full screen, following new records, until `q` or `Ctrl‑C` is pressed.
+/// The terminal is restored afterwards, and on panic.
+///
+/// ```no_run
+/// use cappie::Logger;
+/// use cappie::output::LogStore;
+///
+/// let store = LogStore::new();
+/// let log = Logger::new("worker").with_output(Box::new(store.clone()));
+/// std::thread::spawn(move || loop {
+///     log.info("tick");
+///     std::thread::sleep(std::time::Duration::from_secs(1));
+/// });
+/// cappie::tui::run(&store).unwrap();
+/// ```
+pub fn run(store: &LogStore) -> io::Result<()> {
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, store);
+    ratatui::try_restore()?;
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, store: &LogStore) -> io::Result<()> {
+    let mut state = LogViewState::new();
+    let mut drawn = None;
+    let mut dirty = true;
+    loop {
+        if dirty || drawn != Some(store.last_seq()) {
+            drawn = Some(store.last_seq());
+            terminal.draw(|frame| frame.render_stateful_widget(LogView::new(store), frame.area(), &mut state))?;
+        }
+        dirty = event::poll(REFRESH)?;
+        if !dirty {
+            continue;
+        }
+        if let Event::Key(key) = event::read()? {
+            let quit = matches!(key.code, KeyCode::Char('q'))
+                || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL));
+            if !state.handle_key(key) && quit && key.kind != KeyEventKind::Release {
+                return Ok(());
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::diagnostics;
+use crate::level::Level;
+use crate::output::{Output, Record};
+use crate::syslog::SyslogFormatter;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::path::Path;
+
+/// Output that hands every record to the kernel in exactly one `writev(2)` call – level
+/// header, record and newline together – on a file descriptor, without any buffering in
+/// between.  Syscall tracers (`strace`, eBPF) then see one write per record, and readers
+/// that rely on write boundaries, such as journald's stdout capture, get whole records.
+///
+/// With [`with_level_prefix`](Self::with_level_prefix) each record starts with the
+/// `sd-daemon` priority prefix (`<3>` for errors, `<6>` for info, …), which journald turns
+/// into the entry's priority.
+///
+/// The kernel may still accept fewer bytes than asked for, for records larger than the
+/// pipe buffer or when interrupted by a signal; the rest then follows in further writes.
+///
+/// ```
+/// use cappie::Logger;
+/// use cappie::output::WritevOutput;
+///
+/// let log = Logger::new("unit").with_output(Box::new(WritevOutput::stdout().with_level_prefix()));
+/// log.warn("disk almost full"); // one writev: "<4>", record, "\n"
+/// ```
+pub struct WritevOutput {
+    target: Target,
+    level_prefix: bool,
+}
+
+enum Target {
+    Stdout,
+    Stderr,
+    File(File),
+}
+
+impl WritevOutput {
+    pub fn stdout() -> Self {
+        Self { target: Target::Stdout, level_prefix: false }
+    }
+
+    pub fn stderr() -> Self {
+        Self { target: Target::Stderr, level_prefix: false }
+    }
+
+    /// Append to the file at `path`, creating it if necessary.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { target: Target::File(file), level_prefix: false })
+    }
+
+    /// Start every record with the `sd-daemon` priority prefix for its level.
+    pub fn with_level_prefix(mut self) -> Self {
+        self.level_prefix = true;
+        self
+    }
+
+    fn fd(&self) -> RawFd {
+        match &self.target {
+            Target::Stdout => 1,
+            Target::Stderr => 2,
+            Target::File(file) => file.as_raw_fd(),
+        }
+    }
+
+    /// Write up to three `parts` with one `writev`.
+    fn write_parts(&self, parts: &[&[u8]]) -> io::Result<()> {
+        let mut iov = [libc::iovec { iov_base: std::ptr::null_mut(), iov_len: 0 }; 3];
+        let mut count = 0;
+        for part in parts.iter().filter(|part| !part.is_empty()) {
+            iov[count] = libc::iovec { iov_base: part.as_ptr() as *mut libc::c_void, iov_len: part.len() };
+            count += 1;
+        }
+        let total: usize = iov[..count].iter().map(|v| v.iov_len).sum();
+        let mut written = loop {
+            // SAFETY: the first `count` iovecs point into slices of `parts`, valid for
+            // their lengths.
+            let n = unsafe { libc::writev(self.fd(), iov.as_ptr(), count as libc::c_int) };
+            if n >= 0 {
+                break n as usize;
+            }
+            let error = io::Error::last_os_error();
+            if error.kind() != io::ErrorKind::Interrupted {
+                return Err(error);
+            }
+        };
+        if written == total {
+            return Ok(());
+        }
+
+        // Short write: send what is left, part by part.
+        for part in parts {
+            if written >= part.len() {
+                written -= part.len();
+                continue;
+            }
+            self.write_all(&part[written..])?;
+            written = 0;
+        }
+        Ok(())
+    }
+
+    fn write_all(&self, mut bytes: &[u8]) -> io::Result<()> {
+        while !bytes.is_empty() {
+            // SAFETY: `bytes` is valid for reads of `bytes.len()` bytes.
+            let n = unsafe { libc::write(self.fd(), bytes.as_ptr() as *const libc::c_void, bytes.len()) };
+            if n > 0 {
+                bytes = &bytes[n as usize..];
+                continue;
+            }
+            let error = if n == 0 { io::Error::from(io::ErrorKind::WriteZero) } else { io::Error::last_os_error() };
+            if error.kind() != io::ErrorKind::Interrupted {
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn level_prefix(level: Level) -> &'static [u8] {
+    match SyslogFormatter::severity(level) {
+        2 => b"<2>",
+        3 => b"<3>",
+        4 => b"<4>",
+        6 => b"<6>",
+        _ => b"<7>",
+    }
+}
+
+impl Output for WritevOutput {
+    fn write(&self, message: &str) {
+        diagnostics::check_write("WritevOutput", self.write_parts(&[message.as_bytes(), b"\n"]));
+    }
+
+    fn write_bytes(&self, bytes: &[u8]) {
+        diagnostics::check_write("WritevOutput", self.write_parts(&[bytes]));
+    }
+
+    fn write_record(&self, record: &Record<'_>) {
+        diagnostics::check_write("WritevOutput", self.try_write_record(record));
+    }
+
+    fn try_write_record(&self, record: &Record<'_>) -> io::Result<()> {
+        let header: &[u8] = if self.level_prefix { level_prefix(record.level) } else { b"" };
+        let newline: &[u8] = if record.binary { b"" } else { b"\n" };
+        self.write_parts(&[header, record.formatted, newline])
+    }
+
+    fn needs_fields(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,119 @@
+#![cfg(feature = "admin")]
+
+use cappie::admin::{serve, AdminRegistry};
+use cappie::{FileOutput, Level, Logger, Output};
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Keeps every line written to it.
+#[derive(Clone, Default)]
+struct Lines(Arc<Mutex<Vec<String>>>);
+
+impl Output for Lines {
+    fn write(&self, formatted: &str) {
+        self.0.lock().unwrap().push(formatted.to_string());
+    }
+}
+
+fn call(registry: &AdminRegistry, method: &str, path: &str, body: &str) -> (u16, Value) {
+    let res = registry.handle(method, path, body);
+    (res.status, serde_json::from_str(&res.body).unwrap())
+}
+
+#[test]
+fn sampling_rates_can_be_changed_at_runtime() {
+    let capture = Lines::default();
+    let api = Logger::new("api").with_output(Box::new(capture.clone()));
+    let registry = AdminRegistry::new();
+    registry.register(&api);
+
+    let (status, logger) = call(&registry, "PUT", "/loggers/api/sampling", r#"{"rate":0.25}"#);
+    assert_eq!((status, &logger["sampling"]), (200, &Value::from(0.25)));
+    for i in 0..8 {
+        api.info(&format!("request {i}"));
+    }
+    api.warn("slow request");
+    assert_eq!(capture.0.lock().unwrap().len(), 3);
+
+    assert_eq!(call(&registry, "PUT", "/loggers/api/sampling", "1.5").0, 400);
+    assert_eq!(call(&registry, "PUT", "/loggers/api/sampling", "1").0, 200);
+    assert_eq!(api.sampling_handle().get(), 1.0);
+
+    registry.register_handle("worker", Logger::new("worker").level_handle());
+    assert_eq!(call(&registry, "GET", "/loggers/worker", "").1["sampling"], Value::Null);
+    assert_eq!(call(&registry, "PUT", "/loggers/worker/sampling", "0.5").0, 404);
+}
+
+#[test]
+fn outputs_that_fail_are_listed_with_their_last_error() {
+    // A file below a file cannot be opened.
+    let log = Logger::new("broken").with_output(Box::new(FileOutput::new("Cargo.toml/app.log")));
+    log.info("lost");
+    log.info("lost too");
+
+    let registry = AdminRegistry::new();
+    let (status, res) = call(&registry, "GET", "/outputs", "");
+    assert_eq!(status, 200);
+    let file = res["outputs"].as_array().unwrap().iter().find(|o| o["name"] == "FileOutput").unwrap();
+    assert!(file["write_failures"].as_u64().unwrap() >= 2);
+    assert!(file["last_error"].is_string());
+    assert!(file["last_failure"].is_string());
+    assert_eq!(call(&registry, "PUT", "/outputs", "").0, 405);
+}
+
+#[test]
+fn server_answers_over_http() {
+    let api = Logger::new("api");
+    let registry = AdminRegistry::new();
+    registry.register(&api);
+    let server = serve("127.0.0.1:0", registry).unwrap();
+
+    let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+    stream.write_all(b"PUT /loggers/api/level HTTP/1.1\r\nContent-Length: 5\r\n\r\ndebug").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert_eq!(api.level_handle().get(), Level::Debug);
+    server.shutdown();
+}
+
+#[test]
+fn silent_client_does_not_block_shutdown() {
+    let server = serve("127.0.0.1:0", AdminRegistry::new()).unwrap();
+    let _silent = TcpStream::connect(server.local_addr()).unwrap();
+    // Let the server thread pick up the connection and block reading from it.
+    thread::sleep(Duration::from_millis(50));
+
+    let (done, finished) = mpsc::channel();
+    thread::spawn(move || {
+        drop(server);
+        done.send(()).unwrap();
+    });
+    finished.recv_timeout(Duration::from_secs(10)).expect("server did not shut down");
+}
+
+#[test]
+fn oversized_request_heads_are_refused() {
+    let server = serve("127.0.0.1:0", AdminRegistry::new()).unwrap();
+    let send = |request: &[u8]| {
+        let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+        stream.write_all(request).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    };
+
+    let long = format!("GET /loggers HTTP/1.1\r\nX-Padding: {}\r\n\r\n", "a".repeat(16 * 1024));
+    assert!(send(long.as_bytes()).starts_with("HTTP/1.1 431 "));
+
+    let many = format!("GET /loggers HTTP/1.1\r\n{}\r\n", "X-Header: 1\r\n".repeat(100));
+    assert!(send(many.as_bytes()).starts_with("HTTP/1.1 431 "));
+
+    let within = format!("GET /loggers HTTP/1.1\r\n{}\r\n", "X-Header: 1\r\n".repeat(64));
+    assert!(send(within.as_bytes()).starts_with("HTTP/1.1 200 OK\r\n"));
+    server.shutdown();
+}
@@ -0,0 +1,66 @@
+use cappie::output::{AsyncOutput, BufferMode};
+use cappie::{FileOutput, Logger, MultiOutput, StdoutOutput};
+use std::path::PathBuf;
+use std::process::Command;
+use std::thread;
+
+/// Set in the child process to the file its `AsyncOutput` writes to.
+const CHILD_ENV: &str = "CAPPIE_CRASH_CHILD";
+const THREADS: usize = 4;
+const RECORDS: usize = 2_000;
+
+/// Log from several threads through outputs that hold records in memory, then panic
+/// with a hook that aborts, so nothing but the crash handlers gets to flush.
+fn crash(path: PathBuf) -> ! {
+    std::panic::set_hook(Box::new(|_| std::process::abort()));
+    cappie::install_crash_handlers();
+
+    let log = Logger::new("crash").with_output(Box::new(
+        MultiOutput::new()
+            .add_output(Box::new(StdoutOutput::buffered(BufferMode::Block(1 << 20))))
+            .add_output(Box::new(AsyncOutput::new(Box::new(FileOutput::new(path))).with_capacity(THREADS * RECORDS + 1))),
+    ));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|thread| {
+            let log = log.clone();
+            thread::spawn(move || {
+                for seq in 0..RECORDS {
+                    log.info(&format!("thread={thread} seq={seq}"));
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    log.error("last words");
+    panic!("crash under load");
+}
+
+#[test]
+fn crash_handlers_flush_buffered_outputs_on_panic() {
+    if let Some(path) = std::env::var_os(CHILD_ENV) {
+        crash(path.into());
+    }
+
+    let path = std::env::temp_dir().join(format!("cappie-{}-crash.log", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args(["crash_handlers_flush_buffered_outputs_on_panic", "--exact", "--nocapture", "--test-threads=1"])
+        .env(CHILD_ENV, &path)
+        .output()
+        .unwrap();
+    let file = std::fs::read_to_string(&path).unwrap_or_default();
+    let _ = std::fs::remove_file(&path);
+    assert!(!output.status.success(), "the child was expected to crash");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for (sink, contents) in [("stdout", &*stdout), ("file", &*file)] {
+        for thread in 0..THREADS {
+            let last = format!("thread={thread} seq={}", RECORDS - 1);
+            assert!(contents.contains(&last), "{sink} is missing {last:?}");
+        }
+        assert!(contents.contains("last words"), "{sink} is missing the record logged before the panic");
+        assert_eq!(contents.matches("seq=").count(), THREADS * RECORDS, "{sink} lost records");
+    }
+}
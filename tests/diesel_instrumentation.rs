@@ -0,0 +1,42 @@
+#![cfg(feature = "diesel")]
+
+use cappie::{CaptureOutput, DieselInstrumentation, Level, Logger, QueryLog};
+use diesel::connection::{Connection, SimpleConnection};
+use diesel::sql_types::Text;
+use diesel::sqlite::SqliteConnection;
+use diesel::RunQueryDsl;
+use serde_json::Value;
+
+#[test]
+fn diesel_queries_are_logged_without_their_binds() {
+    let capture = CaptureOutput::new();
+    let log = Logger::new("app").with_level(Level::Debug).with_output(Box::new(capture.clone()));
+    let mut conn = SqliteConnection::establish(":memory:").unwrap();
+    conn.set_instrumentation(DieselInstrumentation::new(QueryLog::new(&log)));
+
+    conn.batch_execute("CREATE TABLE users (email TEXT NOT NULL UNIQUE)").unwrap();
+    let insert = || diesel::sql_query("INSERT INTO users (email) VALUES (?)").bind::<Text, _>("ada@example.com");
+    insert().execute(&mut conn).unwrap();
+    assert!(insert().execute(&mut conn).is_err());
+
+    let records: Vec<Value> = capture.lines().iter().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert!(!capture.contents().contains("ada@example.com"));
+    let summary: Vec<(&str, &str)> = records
+        .iter()
+        .map(|record| (record["msg"].as_str().unwrap(), record["statement"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("query", "CREATE TABLE users (email TEXT NOT NULL UNIQUE)"),
+            ("query", "INSERT INTO users (email) VALUES (?)"),
+            ("query failed", "INSERT INTO users (email) VALUES (?)"),
+        ]
+    );
+    for record in &records {
+        assert_eq!(record["name"], "app.db");
+        assert!(record["duration_ms"].is_number());
+    }
+    assert_eq!(records[2]["level"], Level::Error.value());
+    assert!(records[2]["error"].as_str().unwrap().contains("UNIQUE constraint failed"));
+}
@@ -0,0 +1,128 @@
+//! Property tests: arbitrary messages, names and field values must never break a record
+//! out of its line, and must come back unchanged through the parse‑back APIs.
+
+use cappie::{CaptureOutput, Formatter, JsonFormatter, Level, Logger, PrettyFormatter, Timestamp};
+use proptest::prelude::*;
+use serde_json::{Map, Value};
+
+fn field_value() -> impl Strategy<Value = Value> {
+    prop_oneof![
+        any::<String>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        any::<u64>().prop_map(Value::from),
+        any::<f64>().prop_filter("finite", |f| f.is_finite()).prop_map(Value::from),
+        any::<bool>().prop_map(Value::from),
+        Just(Value::Null),
+    ]
+}
+
+fn fields() -> impl Strategy<Value = Map<String, Value>> {
+    prop::collection::btree_map(any::<String>(), field_value(), 0..6)
+        .prop_map(|map| map.into_iter().collect())
+}
+
+/// Arbitrary text, half of the time made mostly of control characters, which `any` rarely
+/// produces.
+fn hostile() -> impl Strategy<Value = String> {
+    prop_oneof![any::<String>(), "[\\x00-\\x1f\\x7f-\\x9fa-z =]{0,16}"]
+}
+
+fn hostile_fields() -> impl Strategy<Value = Map<String, Value>> {
+    (fields(), prop::collection::btree_map(hostile(), hostile().prop_map(Value::from), 0..4))
+        .prop_map(|(mut fields, hostile)| {
+            fields.extend(hostile);
+            fields
+        })
+}
+
+/// Equality that allows for serde_json's default float parsing, which may be off in the
+/// last bit.
+fn same(parsed: &Value, expected: &Value) -> bool {
+    match (parsed, expected) {
+        (Value::Number(a), Value::Number(b)) if b.is_f64() => {
+            let (a, b) = (a.as_f64().unwrap(), b.as_f64().unwrap());
+            // Subnormals carry fewer significant bits, so compare those absolutely.
+            a == b || ((a - b) / b).abs() < 1e-15 || (a - b).abs() < f64::MIN_POSITIVE
+        }
+        _ => parsed == expected,
+    }
+}
+
+proptest! {
+    #[test]
+    fn json_record_is_one_valid_line(msg in any::<String>(), name in any::<String>(), fields in fields()) {
+        let timestamp = Timestamp::from_unix_nanos(1_704_067_200_000_000_000).unwrap();
+        let line = JsonFormatter.format(Level::Info, &msg, &fields, timestamp, &name);
+
+        prop_assert!(!line.contains('\n') && !line.contains('\r'));
+        let parsed: Value = serde_json::from_str(&line).unwrap();
+        // Fields named like an envelope key replace it.
+        prop_assert!(same(&parsed["msg"], fields.get("msg").unwrap_or(&Value::from(msg))));
+        prop_assert!(same(&parsed["name"], fields.get("name").unwrap_or(&Value::from(name))));
+        for (key, value) in &fields {
+            prop_assert!(same(&parsed[key], value), "{} != {}", parsed[key], value);
+        }
+    }
+
+    #[test]
+    fn logger_writes_one_line_per_record(msgs in prop::collection::vec(any::<String>(), 1..8)) {
+        let capture = CaptureOutput::new();
+        let log = Logger::new("prop").with_output(Box::new(capture.clone()));
+        for msg in &msgs {
+            log.info(msg);
+        }
+
+        let lines = capture.lines();
+        prop_assert_eq!(lines.len(), msgs.len());
+        for (line, msg) in lines.iter().zip(&msgs) {
+            let parsed: Value = serde_json::from_str(line).unwrap();
+            prop_assert_eq!(parsed["msg"].as_str(), Some(msg.as_str()));
+        }
+    }
+
+    #[test]
+    fn escaped_pretty_record_is_one_line(
+        msg in hostile(),
+        name in hostile(),
+        tags in prop::collection::vec(hostile(), 1..4),
+        fields in hostile_fields(),
+    ) {
+        let capture = CaptureOutput::new();
+        let log = Logger::new(&name)
+            .with_formatter(Box::new(PrettyFormatter::new().with_no_colors().with_escaping()))
+            .with_output(Box::new(capture.clone()));
+        let timestamp = Timestamp::from_unix_nanos(1_704_067_200_000_000_000).unwrap();
+        log.log_with_time(Level::Info, timestamp, &msg, fields);
+        let tags: Vec<&str> = tags.iter().map(String::as_str).collect();
+        log.log_tagged(Level::Warn, &tags, &msg);
+
+        let contents = capture.contents();
+        let records: Vec<&str> = contents.split_inclusive('\n').collect();
+        prop_assert_eq!(records.len(), 2);
+        for record in records {
+            let line = record.strip_suffix('\n').unwrap();
+            prop_assert!(!line.contains(['\x1b', '\r', '\n']), "{:?}", record);
+        }
+    }
+}
+
+#[cfg(feature = "binary")]
+proptest! {
+    #[test]
+    fn binary_record_round_trips(msg in any::<String>(), name in any::<String>(), fields in fields()) {
+        use cappie::binary::{BinaryFormatter, RecordReader};
+
+        let timestamp = Timestamp::from_unix_nanos(1_704_067_200_000_000_000).unwrap();
+        let mut frame = Vec::new();
+        BinaryFormatter.format_into(&mut frame, Level::Warn, &msg, &fields, timestamp, &name);
+
+        let records: Vec<_> = RecordReader::new(&frame[..]).collect::<Result<_, _>>().unwrap();
+        prop_assert_eq!(records.len(), 1);
+        let record = &records[0];
+        prop_assert_eq!(record.level, Level::Warn);
+        prop_assert_eq!(record.timestamp, timestamp);
+        prop_assert_eq!(&record.msg, &msg);
+        prop_assert_eq!(&record.name, &name);
+        prop_assert_eq!(&record.fields, &fields);
+    }
+}
@@ -0,0 +1,202 @@
+use cappie::{context, CaptureOutput, Fields, Formatter, Level, Logger, LoggerFactory, MultiOutput, Output, Record, Timestamp};
+use serde_json::{json, Map, Value};
+use std::sync::{Arc, Mutex};
+
+fn last_record(capture: &CaptureOutput) -> Value {
+    serde_json::from_str(capture.lines().last().unwrap()).unwrap()
+}
+
+#[test]
+fn builder_keys_may_be_borrowed_from_local_strings() {
+    let capture = CaptureOutput::new();
+    let log = Logger::new("keys").with_output(Box::new(capture.clone()));
+
+    let names: Vec<String> = (0..3).map(|i| format!("key_{i}")).collect();
+    log.info_with("dynamic keys", |b| {
+        for name in &names {
+            b.string(name.as_str(), "v").number(&format!("{name}_n"), 1).bool(name, true);
+        }
+    });
+    let record = last_record(&capture);
+    assert_eq!(record["key_0"], true);
+    assert_eq!(record["key_2_n"], 1);
+}
+
+#[test]
+fn static_and_borrowed_keys_replace_each_other() {
+    let layered = CaptureOutput::new();
+    let merged = CaptureOutput::new();
+    let outputs: [Box<dyn Output>; 2] = [
+        Box::new(layered.clone()),
+        Box::new(MultiOutput::new().add_output(Box::new(merged.clone())).add_output(Box::new(FieldsSeen::default()))),
+    ];
+    let owned = String::from("owned");
+    for output in outputs {
+        let log = Logger::new("keys").with_field("n", 0).with_output(output);
+        log.info_with("mixed keys", |b| {
+            b.number_static("n", 1).number("n", 2);
+            b.string("s", "borrowed").string_static("s", "static");
+            b.bool_static(owned.clone(), true).field_static("n", 3);
+        });
+    }
+    for capture in [&layered, &merged] {
+        let record = last_record(capture);
+        assert_eq!((&record["n"], &record["s"], &record["owned"]), (&json!(3), &json!("static"), &json!(true)));
+    }
+}
+
+#[test]
+fn builder_fields_past_the_inline_eight_keep_their_last_value() {
+    let capture = CaptureOutput::new();
+    let log = Logger::new("many").with_output(Box::new(capture.clone()));
+
+    log.info_with("many fields", |b| {
+        for i in 0..12u64 {
+            b.number(&format!("f{i}"), i);
+        }
+        b.number("f3", 30).number_static("f10", 100);
+    });
+    let record = last_record(&capture);
+    assert_eq!(record.as_object().unwrap().len(), 12 + 4);
+    assert_eq!((&record["f3"], &record["f10"], &record["f11"]), (&json!(30), &json!(100), &json!(11)));
+}
+
+#[test]
+fn builder_fields_moved_out_of_line_replace_like_inline_ones() {
+    let layered = CaptureOutput::new();
+    let merged = CaptureOutput::new();
+    let outputs: [Box<dyn Output>; 2] = [
+        Box::new(layered.clone()),
+        Box::new(MultiOutput::new().add_output(Box::new(merged.clone())).add_output(Box::new(FieldsSeen::default()))),
+    ];
+    for output in outputs {
+        let log = Logger::new("many").with_field("f0", "base").with_output(output);
+        log.security_event("export", |b| {
+            b.field("event", json!({ "outcome": "success" }));
+            for i in 0..10u64 {
+                b.number(&format!("f{i}"), i);
+            }
+            // One key that was inline before the ninth, one that never was.
+            b.string_static("f1", "inline").string("f9", "late").number_static("f9", 99);
+        });
+    }
+    for capture in [&layered, &merged] {
+        let record = last_record(capture);
+        assert_eq!((&record["f0"], &record["f1"], &record["f9"]), (&json!(0), &json!("inline"), &json!(99)));
+        assert_eq!(record["event"]["outcome"], "success");
+        assert_eq!(record["event"]["action"], "export");
+    }
+}
+
+/// Keeps the merged fields of every record it gets.
+#[derive(Clone, Default)]
+struct FieldsSeen(Arc<Mutex<Vec<Map<String, Value>>>>);
+
+impl Output for FieldsSeen {
+    fn write(&self, _: &str) {}
+
+    fn write_record(&self, record: &Record<'_>) {
+        self.0.lock().unwrap().push(record.fields.clone());
+    }
+}
+
+#[test]
+fn later_field_layers_win_with_and_without_merging() {
+    let seen = FieldsSeen::default();
+    let layered = CaptureOutput::new();
+    let merged = CaptureOutput::new();
+    let template = Logger::new("layers").with_field("layer", "base").with_field("base", 1);
+    let outputs: [Box<dyn Output>; 2] = [
+        Box::new(layered.clone()),
+        Box::new(MultiOutput::new().add_output(Box::new(merged.clone())).add_output(Box::new(seen.clone()))),
+    ];
+    for output in outputs {
+        let factory = LoggerFactory::new(&template.clone().with_output(output));
+        let log = factory.logger([("layer", json!("scope")), ("scope", json!(2))]);
+        let _context = context::push("context", 3);
+        log.info("scope wins over base");
+        log.info_with("call wins over scope", |b| {
+            b.string("layer", "call");
+        });
+    }
+
+    for capture in [&layered, &merged] {
+        let lines: Vec<Value> = capture.lines().iter().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines[0]["layer"], "scope");
+        assert_eq!(lines[1]["layer"], "call");
+        for line in &lines {
+            assert_eq!((&line["base"], &line["context"], &line["scope"]), (&json!(1), &json!(3), &json!(2)));
+        }
+    }
+    let seen = seen.0.lock().unwrap();
+    assert_eq!(seen[1]["layer"], "call");
+    assert_eq!(seen[1].len(), 4);
+}
+
+/// Writes the keys of the field layers in iteration order, with their values.
+struct Pairs;
+
+impl Formatter for Pairs {
+    fn format(&self, _: Level, _: &str, _: &Map<String, Value>, _: Timestamp, _: &str) -> String {
+        unreachable!("the logger passes layered fields")
+    }
+
+    fn format_fields_into(&self, buf: &mut Vec<u8>, _: Level, _: &[&str], _: &str, fields: Fields<'_>, _: Timestamp, _: &str) {
+        let pairs: Vec<String> = fields.iter().map(|(key, value)| format!("{key}={value}")).collect();
+        buf.extend_from_slice(pairs.join(" ").as_bytes());
+    }
+}
+
+#[test]
+fn layered_fields_list_each_key_once_with_its_winning_value() {
+    let capture = CaptureOutput::new();
+    let log = Logger::new("pairs")
+        .with_field("a", 1)
+        .with_field("b", 1)
+        .with_formatter(Box::new(Pairs))
+        .with_output(Box::new(capture.clone()));
+    log.info_with("", |b| {
+        b.number("b", 2).number("c", 2);
+    });
+    assert_eq!(capture.lines(), ["a=1 b=2 c=2"]);
+}
+
+#[test]
+fn built_in_formatters_write_the_same_line_with_and_without_merging() {
+    use cappie::{FlexibleFormatter, JsonFormatter, LogfmtFormatter, PrettyFormatter};
+
+    let formatters: [fn() -> Box<dyn Formatter>; 4] = [
+        || Box::new(JsonFormatter),
+        || Box::new(PrettyFormatter::new().with_no_colors()),
+        || Box::new(FlexibleFormatter::new().with_no_colors()),
+        || Box::new(LogfmtFormatter::new()),
+    ];
+    let timestamp = Timestamp::from_unix_nanos(1_705_314_600_000_000_000).unwrap();
+    for formatter in formatters {
+        let layered = CaptureOutput::new();
+        let merged = CaptureOutput::new();
+        let outputs: [Box<dyn Output>; 2] = [
+            Box::new(layered.clone()),
+            Box::new(MultiOutput::new().add_output(Box::new(merged.clone())).add_output(Box::new(FieldsSeen::default()))),
+        ];
+        for output in outputs {
+            let log = Logger::new("same").with_field("z", 1).with_field("b", 1).with_formatter(formatter()).with_output(output);
+            let mut fields = Map::new();
+            fields.insert("b".to_string(), json!(2));
+            fields.insert("a".to_string(), json!(2));
+            log.log_with_time(Level::Info, timestamp, "hello", fields);
+        }
+        assert_eq!(layered.lines(), merged.lines());
+    }
+}
+
+#[cfg(feature = "fast-json")]
+#[test]
+fn fast_json_writes_layered_fields_in_key_order() {
+    let capture = CaptureOutput::new();
+    let log = Logger::new("sorted").with_field("z", 1).with_field("b", 1).with_output(Box::new(capture.clone()));
+    log.info_with("hello", |b| {
+        b.number("b", 2).number("a", 2);
+    });
+    assert!(capture.lines()[0].ends_with(r#""msg":"hello","a":2,"b":2,"z":1}"#));
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "reqwest-middleware")]
+
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::Router;
+use cappie::{CaptureOutput, HttpCallMiddleware, Level, Logger};
+use http::Extensions;
+use reqwest::{Request, Response};
+use reqwest_middleware::{ClientBuilder, ClientWithMiddleware, Middleware, Next};
+use serde_json::Value;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+/// Retries server errors up to twice, as `reqwest-retry` would.
+struct RetryServerErrors;
+
+#[async_trait::async_trait]
+impl Middleware for RetryServerErrors {
+    async fn handle(&self, req: Request, extensions: &mut Extensions, next: Next<'_>) -> reqwest_middleware::Result<Response> {
+        let mut attempt = 0;
+        loop {
+            let response = next.clone().run(req.try_clone().unwrap(), extensions).await?;
+            attempt += 1;
+            if !response.status().is_server_error() || attempt == 3 {
+                return Ok(response);
+            }
+        }
+    }
+}
+
+/// Serve `/flaky`, failing with 503 `failures` times before answering, and `/missing`.
+async fn serve(failures: u32) -> String {
+    let calls = Arc::new(AtomicU32::new(0));
+    let app = Router::new()
+        .route(
+            "/flaky",
+            get(move || async move {
+                if calls.fetch_add(1, Ordering::SeqCst) < failures {
+                    StatusCode::SERVICE_UNAVAILABLE
+                } else {
+                    StatusCode::OK
+                }
+            }),
+        )
+        .route("/missing", get(|| async { StatusCode::NOT_FOUND }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{addr}")
+}
+
+fn client(log: &Logger) -> ClientWithMiddleware {
+    ClientBuilder::new(reqwest::Client::new())
+        .with(RetryServerErrors)
+        .with(HttpCallMiddleware::new(log).with_visible_query(&["page"]))
+        .build()
+}
+
+fn records(capture: &CaptureOutput) -> Vec<Value> {
+    capture.lines().iter().map(|line| serde_json::from_str(line).unwrap()).collect()
+}
+
+#[tokio::test]
+async fn calls_are_logged_once_with_their_retries() {
+    let base = serve(2).await;
+    let capture = CaptureOutput::new();
+    let log = Logger::new("svc").with_output(Box::new(capture.clone()));
+
+    let response = client(&log).get(format!("{base}/flaky?page=2&token=s3cr3t")).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    let records = records(&capture);
+    assert_eq!(records.len(), 1);
+    let call = &records[0];
+    assert_eq!((&call["name"], &call["msg"]), (&Value::from("svc.http"), &Value::from("http call finished")));
+    assert_eq!(call["method"], "GET");
+    assert_eq!(call["url"], format!("{base}/flaky?page=2&token=[redacted]"));
+    assert_eq!((&call["status"], &call["retries"]), (&Value::from(200), &Value::from(2)));
+    assert!(call["latency_ms"].is_number());
+}
+
+#[tokio::test]
+async fn error_responses_and_failed_calls_are_logged() {
+    let base = serve(0).await;
+    let capture = CaptureOutput::new();
+    let log = Logger::new("svc").with_output(Box::new(capture.clone()));
+    let client = client(&log);
+
+    assert_eq!(client.get(format!("{base}/missing")).send().await.unwrap().status(), 404);
+    // Nothing listens on port 9 (discard) of this host.
+    assert!(client.post("http://127.0.0.1:9/").send().await.is_err());
+
+    let records = records(&capture);
+    assert_eq!(records.len(), 2);
+    assert_eq!((&records[0]["level"], &records[0]["status"]), (&Value::from(Level::Warn.value()), &Value::from(404)));
+    assert_eq!((&records[1]["level"], &records[1]["msg"]), (&Value::from(Level::Error.value()), &Value::from("http call failed")));
+    assert_eq!((&records[1]["method"], &records[1]["retries"]), (&Value::from("POST"), &Value::from(0)));
+    assert!(records[1]["error"].is_string());
+}
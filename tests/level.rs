@@ -0,0 +1,26 @@
+//! The global override is process‑wide, so it gets a test binary of its own.
+
+use cappie::{set_global_level, CaptureOutput, Level, Logger};
+
+#[test]
+fn global_level_lowers_loggers_but_never_raises_them() {
+    let quiet_capture = CaptureOutput::new();
+    let chatty_capture = CaptureOutput::new();
+    let quiet = Logger::new("quiet").with_level(Level::Error).with_output(Box::new(quiet_capture.clone()));
+    let chatty = Logger::new("chatty").with_level(Level::Trace).with_output(Box::new(chatty_capture.clone()));
+
+    set_global_level(Some(Level::Debug));
+    for log in [&quiet, &chatty] {
+        log.trace("trace");
+        log.debug("debug");
+    }
+    assert_eq!(quiet_capture.lines().len(), 1);
+    assert_eq!(chatty_capture.lines().len(), 2);
+    let effective: Vec<Level> = quiet.tree().iter().chain(&chatty.tree()).map(|info| info.effective_level).collect();
+    assert_eq!(effective, [Level::Debug, Level::Trace]);
+
+    set_global_level(None);
+    quiet.debug("debug");
+    assert_eq!(quiet_capture.lines().len(), 1);
+    assert!(!quiet.enabled(Level::Warn) && chatty.enabled(Level::Trace));
+}
@@ -0,0 +1,64 @@
+#![cfg(any(feature = "slog", feature = "log4rs"))]
+
+use cappie::Logger;
+
+#[cfg(feature = "slog")]
+#[test]
+fn slog_records_reach_cappie_outputs_with_their_fields() {
+    use cappie::{CaptureOutput, Level};
+    use serde_json::Value;
+    use slog::{crit, debug, o, warn};
+
+    let capture = CaptureOutput::new();
+    let cappie = Logger::new("legacy").with_level(Level::Info).with_output(Box::new(capture.clone()));
+    let root = slog::Logger::root(cappie, o!("service" => "billing", "shard" => 1));
+    let child = root.new(o!("shard" => 7));
+
+    debug!(child, "below the cappie level");
+    warn!(child, "retrying {}", "upload"; "attempt" => 3u8, "ratio" => 0.5, "ok" => false, "shard" => 9);
+    crit!(root, "giving up");
+
+    let records: Vec<Value> = capture.lines().iter().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["msg"], "retrying upload");
+    assert_eq!(records[0]["level"], Level::Warn.value());
+    assert_eq!((&records[0]["attempt"], &records[0]["ratio"], &records[0]["ok"]), (&Value::from(3), &Value::from(0.5), &Value::from(false)));
+    // The record's own value wins over the child's, which wins over the root's.
+    assert_eq!(records[0]["shard"], 9);
+    assert_eq!(records[0]["service"], "billing");
+    assert_eq!(records[1]["level"], Level::Fatal.value());
+    assert_eq!(records[1]["shard"], 1);
+}
+
+#[cfg(feature = "log4rs")]
+#[test]
+fn log4rs_appenders_receive_cappie_records() {
+    use cappie::output::Log4rsOutput;
+    use log::kv::Key;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Default)]
+    struct Appended(Arc<Mutex<Vec<String>>>);
+
+    impl log4rs::append::Append for Appended {
+        fn append(&self, record: &log::Record) -> anyhow::Result<()> {
+            let user = record.key_values().get(Key::from_str("user")).map(|value| value.to_string());
+            let entry = format!("{} {} {} user={:?}", record.level(), record.target(), record.args(), user);
+            self.0.lock().unwrap().push(entry);
+            Ok(())
+        }
+
+        fn flush(&self) {}
+    }
+
+    let appended = Appended::default();
+    let entries = appended.0.clone();
+    let log = Logger::new("auth").with_output(Box::new(Log4rsOutput::new(Box::new(appended))));
+    log.info_with("signed in", |b| {
+        b.string("user", "ada");
+    });
+    log.fatal("keystore unreadable");
+
+    let entries = entries.lock().unwrap();
+    assert_eq!(*entries, ["INFO auth signed in user=Some(\"ada\")", "ERROR auth keystore unreadable user=None"]);
+}
@@ -0,0 +1,329 @@
+use cappie::{FileOutput, Logger};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::thread;
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("cappie-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+/// Remove `path` and the files numbered or suffixed after it.
+fn remove_all(path: &std::path::Path) {
+    for entry in std::fs::read_dir(std::env::temp_dir()).unwrap() {
+        let entry = entry.unwrap().path();
+        if entry.to_string_lossy().starts_with(&*path.to_string_lossy()) {
+            let _ = std::fs::remove_file(entry);
+        }
+    }
+}
+
+#[test]
+fn concurrent_file_writes_keep_records_whole() {
+    const THREADS: usize = 8;
+    const RECORDS: usize = 200;
+
+    let path = temp_path("atomicity.log");
+    let log = Logger::new("atomicity").with_output(Box::new(FileOutput::new(&path)));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|thread| {
+            let log = log.clone();
+            thread::spawn(move || {
+                for seq in 0..RECORDS {
+                    // Records well above PIPE_BUF, with a payload that identifies the writer.
+                    let payload = format!("{thread}").repeat(8 * 1024 + seq);
+                    log.info_with("record", |b| {
+                        b.number("thread", thread as i64).number("seq", seq as i64).string("payload", &payload);
+                    });
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let mut seen = vec![0usize; THREADS];
+    for (n, line) in contents.lines().enumerate() {
+        let record: Value = serde_json::from_str(line).unwrap_or_else(|e| panic!("line {n} is not one whole record: {e}"));
+        let thread = record["thread"].as_u64().unwrap() as usize;
+        let seq = record["seq"].as_u64().unwrap() as usize;
+        assert_eq!(record["payload"].as_str().unwrap(), format!("{thread}").repeat(8 * 1024 + seq), "line {n}");
+        seen[thread] += 1;
+    }
+    assert_eq!(seen, vec![RECORDS; THREADS]);
+}
+
+#[test]
+fn ndjson_segments_ignore_files_of_neighbouring_stores() {
+    use cappie::output::NdjsonFileOutput;
+
+    let dir = temp_path("ndjson-neighbours");
+    std::fs::create_dir_all(&dir).unwrap();
+    // The active file of a store at `app.2024`, not segment 2024 of `app`.
+    let neighbour = dir.join("app.2024.jsonl");
+    std::fs::write(&neighbour, "{\"msg\":\"neighbour\"}\n").unwrap();
+
+    let base = dir.join("app");
+    let store = NdjsonFileOutput::open(&base).unwrap().with_segment_size(64).with_max_segments(2);
+    let log = Logger::new("app").with_output(Box::new(store));
+    for seq in 0..4 {
+        log.info_with("rotate often", |b| {
+            b.number("seq", seq);
+        });
+    }
+    assert!(neighbour.exists());
+    assert!(dir.join("app.000003.jsonl").exists());
+    let records = NdjsonFileOutput::tail(&base, 10).unwrap();
+    assert!(records.iter().all(|r| r["msg"] == "rotate often"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn ndjson_stores_create_files_with_the_configured_mode() {
+    use cappie::output::NdjsonFileOutput;
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = |path: &PathBuf| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+    let base = temp_path("mode-store");
+    let store = NdjsonFileOutput::open(&base).unwrap().with_segment_size(64).with_mode(0o640);
+    let log = Logger::new("mode").with_output(Box::new(store));
+    for seq in 0..4 {
+        log.info_with("rotate often", |b| {
+            b.number("seq", seq);
+        });
+    }
+    let active = PathBuf::from(format!("{}.jsonl", base.display()));
+    let segment = PathBuf::from(format!("{}.000001.jsonl", base.display()));
+    assert_eq!((mode(&active), mode(&segment)), (0o640, 0o640));
+    remove_all(&base);
+}
+
+/// `path` numbered like a rotated file, or `path` itself for 0.
+fn rotated(path: &std::path::Path, n: usize) -> PathBuf {
+    match n {
+        0 => path.to_path_buf(),
+        n => PathBuf::from(format!("{}.{n}", path.display())),
+    }
+}
+
+/// Contents of the current file and each rotated one, stopping at the first missing number.
+fn rotation_set(path: &std::path::Path) -> Vec<String> {
+    (0..).map_while(|n| std::fs::read_to_string(rotated(path, n)).ok()).collect()
+}
+
+#[test]
+fn rotating_outputs_shift_older_files_up_one_number() {
+    use cappie::{Output, RotatingFileOutput};
+
+    let path = temp_path("shift.log");
+    let output = RotatingFileOutput::new(&path).with_max_size(4);
+    output.write("one");
+    output.write("two");
+    assert_eq!(rotation_set(&path), ["two\n", "one\n"]);
+    output.write("six");
+    assert_eq!(rotation_set(&path), ["six\n", "two\n", "one\n"]);
+    remove_all(&path);
+}
+
+#[test]
+fn rotating_outputs_delete_files_beyond_the_maximum_count() {
+    use cappie::{Output, RotatingFileOutput};
+
+    let path = temp_path("count.log");
+    let output = RotatingFileOutput::new(&path).with_max_size(4).with_max_files(2);
+    for record in ["one", "two", "six", "ten"] {
+        output.write(record);
+    }
+    assert_eq!(rotation_set(&path), ["ten\n", "six\n", "two\n"]);
+    assert!(!rotated(&path, 3).exists());
+    remove_all(&path);
+}
+
+#[test]
+fn rotating_outputs_delete_the_oldest_files_beyond_the_byte_budget() {
+    use cappie::{Output, RotatingFileOutput};
+
+    let path = temp_path("budget.log");
+    // Room for the current file and two full rotated ones.
+    let output = RotatingFileOutput::new(&path).with_max_size(6).with_max_files(10).with_max_total_size(18);
+    for seq in 1..=5 {
+        output.write(&format!("rec-{seq}"));
+    }
+    assert_eq!(rotation_set(&path), ["rec-5\n", "rec-4\n", "rec-3\n"]);
+    assert!(!rotated(&path, 3).exists() && !rotated(&path, 4).exists());
+    remove_all(&path);
+}
+
+#[cfg(unix)]
+#[test]
+fn rotating_outputs_create_files_with_the_configured_mode() {
+    use cappie::RotatingFileOutput;
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = |path: &PathBuf| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+    let path = temp_path("mode.log");
+    let log = Logger::new("mode").with_output(Box::new(RotatingFileOutput::new(&path).with_max_size(64).with_mode(0o600)));
+    for seq in 0..4 {
+        log.info_with("rotate often", |b| {
+            b.number("seq", seq);
+        });
+    }
+    assert_eq!((mode(&path), mode(&rotated(&path, 1))), (0o600, 0o600));
+    remove_all(&path);
+}
+
+#[test]
+fn composite_outputs_report_failures_to_dead_letter_and_audit() {
+    use cappie::output::{DeadLetterOutput, RouterOutput};
+    use cappie::{AuditLogger, CaptureOutput, MultiOutput};
+
+    // A file below a file cannot be opened.
+    let broken = || Box::new(FileOutput::new("Cargo.toml/app.log"));
+
+    let kept = CaptureOutput::new();
+    let dead_letters = CaptureOutput::new();
+    let multi = MultiOutput::new().add_output(Box::new(kept.clone())).add_output(broken());
+    let output = DeadLetterOutput::new(Box::new(multi)).with_dead_letter(Box::new(dead_letters.clone()));
+    let log = Logger::new("multi").with_output(Box::new(output));
+    log.info("half delivered");
+    assert_eq!(kept.lines().len(), 1);
+    assert_eq!(dead_letters.lines().len(), 1);
+
+    let router = RouterOutput::new().with_sink("broken", broken()).with_config("default => broken").unwrap();
+    let audit = AuditLogger::new("audit", Box::new(router));
+    assert!(audit.record("alice", "login").is_err());
+}
+
+struct Panicking;
+
+impl cappie::Output for Panicking {
+    fn write(&self, _message: &str) {
+        panic!("sink exploded");
+    }
+}
+
+#[test]
+fn flush_returns_after_the_async_worker_panicked() {
+    use cappie::output::AsyncOutput;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let output = AsyncOutput::new(Box::new(Panicking));
+    let stats = output.stats_handle();
+    let log = Logger::new("async").with_output(Box::new(output));
+
+    let (done, finished) = mpsc::channel();
+    thread::spawn(move || {
+        log.info("first");
+        log.flush();
+        log.info("second");
+        log.flush();
+        done.send(()).unwrap();
+    });
+    finished.recv_timeout(Duration::from_secs(10)).expect("flush waited on a dead worker");
+    assert!(stats.get().dropped >= 1);
+}
+
+/// Renders the message alone and counts how often it ran.
+#[derive(Default)]
+struct Counting(std::sync::atomic::AtomicUsize);
+
+impl cappie::Formatter for Counting {
+    fn format(&self, _: cappie::Level, msg: &str, _: &serde_json::Map<String, Value>, _: cappie::Timestamp, _: &str) -> String {
+        self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        msg.to_string()
+    }
+}
+
+/// Logs `inner` through its logger while delivering `outer`.
+struct Nesting(Logger);
+
+impl cappie::Output for Nesting {
+    fn write(&self, message: &str) {
+        if message == "outer" {
+            self.0.info("inner");
+        }
+    }
+}
+
+#[test]
+fn shared_formatters_format_each_record_once() {
+    use cappie::output::FormattedOutput;
+    use cappie::{CaptureOutput, Formatter, MultiOutput};
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    let counting = Arc::new(Counting::default());
+    let formatter: Arc<dyn Formatter> = counting.clone();
+    let (outer, inner) = (CaptureOutput::new(), CaptureOutput::new());
+    let inner_log = Logger::new("inner").with_output(Box::new(FormattedOutput::shared(formatter.clone(), Box::new(inner.clone()))));
+    let output = MultiOutput::new()
+        .add_output(Box::new(FormattedOutput::shared(formatter.clone(), Box::new(Nesting(inner_log)))))
+        .add_output(Box::new(FormattedOutput::shared(formatter, Box::new(outer.clone()))));
+    let log = Logger::new("outer").with_output(Box::new(output));
+
+    log.info("outer");
+    assert_eq!(outer.lines(), ["outer"]);
+    assert_eq!(inner.lines(), ["inner"]);
+    assert_eq!(counting.0.load(Ordering::Relaxed), 2);
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn mmap_files_reopened_after_a_crash_append_after_the_last_record() {
+    use cappie::output::MmapFileOutput;
+    use cappie::Output;
+
+    let path = temp_path("mmap-crash.log");
+    let crashed = MmapFileOutput::open(&path).unwrap().with_chunk_size(4096);
+    crashed.write("before");
+    crashed.flush();
+    // No `Drop`, so the pre-allocated padding stays in the file.
+    std::mem::forget(crashed);
+    assert!(std::fs::metadata(&path).unwrap().len() > "before\n".len() as u64);
+
+    MmapFileOutput::open(&path).unwrap().write("after");
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let records: Vec<&str> = contents.split('\0').next().unwrap().lines().collect();
+    assert_eq!(records, ["before", "after"]);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[cfg(all(feature = "mmap", feature = "binary"))]
+#[test]
+fn mmap_files_reopened_keep_binary_records_that_end_in_zero_bytes() {
+    use cappie::binary::{BinaryFormatter, RecordReader};
+    use cappie::output::MmapFileOutput;
+
+    let path = temp_path("mmap-binary.log");
+    let log = |output: MmapFileOutput, n: u64| {
+        let log = Logger::new("mmap").with_formatter(Box::new(BinaryFormatter)).with_output(Box::new(output));
+        log.info_with("frame", |b| {
+            b.number("n", n);
+        });
+        log
+    };
+    let read = || {
+        let file = std::fs::File::open(&path).unwrap();
+        RecordReader::new(file).map(|record| record.unwrap().fields["n"].clone()).collect::<Vec<_>>()
+    };
+
+    // Closed cleanly: the file ends in the record's own zero byte, which must stay.
+    drop(log(MmapFileOutput::open(&path).unwrap(), 0));
+    assert_eq!(std::fs::read(&path).unwrap().last(), Some(&0));
+    drop(log(MmapFileOutput::open(&path).unwrap(), 0));
+    assert_eq!(read(), [0, 0]);
+
+    // Crashed: no `Drop`, so padding and trailer stay behind the records.
+    std::mem::forget(log(MmapFileOutput::open(&path).unwrap(), 0));
+    drop(log(MmapFileOutput::open(&path).unwrap(), 7));
+    assert_eq!(read(), [0, 0, 0, 7]);
+    let _ = std::fs::remove_file(&path);
+}
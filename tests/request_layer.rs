@@ -0,0 +1,65 @@
+#![cfg(feature = "tower")]
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use axum::{Extension, Router};
+use cappie::{CaptureOutput, Level, Logger, RequestLogLayer};
+use serde_json::Value;
+use std::net::SocketAddr;
+use tower::ServiceExt;
+
+fn app(log: &Logger) -> Router {
+    Router::new()
+        .route(
+            "/users/{id}",
+            get(|Extension(log): Extension<Logger>| async move {
+                log.info("user loaded");
+                "ada"
+            }),
+        )
+        .route("/broken", get(|| async { StatusCode::INTERNAL_SERVER_ERROR }))
+        .layer(RequestLogLayer::new(log))
+}
+
+fn records(capture: &CaptureOutput) -> Vec<Value> {
+    capture.lines().iter().map(|line| serde_json::from_str(line).unwrap()).collect()
+}
+
+#[tokio::test]
+async fn requests_get_a_logger_and_are_logged_around_the_handler() {
+    let capture = CaptureOutput::new();
+    let log = Logger::new("http").with_level(Level::Debug).with_output(Box::new(capture.clone()));
+    let addr: SocketAddr = "192.0.2.7:51000".parse().unwrap();
+    let mut request = Request::get("/users/7?tab=orders").header("X-Request-Id", "req-42").body(Body::empty()).unwrap();
+    request.extensions_mut().insert(addr);
+
+    let response = app(&log).oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let records = records(&capture);
+    let msgs: Vec<&str> = records.iter().map(|record| record["msg"].as_str().unwrap()).collect();
+    assert_eq!(msgs, ["request started", "user loaded", "request finished"]);
+    for record in &records {
+        assert_eq!(record["request_id"], "req-42");
+        assert_eq!((&record["method"], &record["path"]), (&Value::from("GET"), &Value::from("/users/7")));
+        assert_eq!(record["remote_addr"], "192.0.2.7:51000");
+    }
+    assert_eq!(records[2]["status"], 200);
+    assert!(records[2]["latency_ms"].is_number());
+}
+
+#[tokio::test]
+async fn server_errors_are_logged_at_error_with_a_fresh_request_id() {
+    let capture = CaptureOutput::new();
+    let log = Logger::new("http").with_output(Box::new(capture.clone()));
+
+    let response = app(&log).oneshot(Request::get("/broken").body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+    let records = records(&capture);
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0]["level"], Level::Error.value());
+    assert_eq!(records[0]["status"], 500);
+    assert_eq!(records[0]["request_id"].as_str().unwrap().len(), 26);
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "sse")]
+
+use cappie::output::SseOutput;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+fn request(sse: &SseOutput, request: &[u8]) -> TcpStream {
+    let mut stream = TcpStream::connect(sse.local_addr()).unwrap();
+    stream.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
+    stream.write_all(request).unwrap();
+    stream
+}
+
+#[test]
+fn silent_clients_do_not_hold_up_subscribers() {
+    let sse = SseOutput::bind("127.0.0.1:0").unwrap();
+    let _silent = TcpStream::connect(sse.local_addr()).unwrap();
+
+    let mut subscriber = request(&sse, b"GET /?level=warn HTTP/1.1\r\n\r\n");
+    let mut status = [0u8; 15];
+    subscriber.read_exact(&mut status).unwrap();
+    assert_eq!(&status, b"HTTP/1.1 200 OK");
+}
+
+#[test]
+fn oversized_requests_are_refused() {
+    let sse = SseOutput::bind("127.0.0.1:0").unwrap();
+    let read = |mut stream: TcpStream| {
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    };
+
+    let long = format!("GET / HTTP/1.1\r\nX-Padding: {}\r\n\r\n", "a".repeat(16 * 1024));
+    assert!(read(request(&sse, long.as_bytes())).starts_with("HTTP/1.1 431 "));
+    let many = format!("GET / HTTP/1.1\r\n{}\r\n", "X-Header: 1\r\n".repeat(100));
+    assert!(read(request(&sse, many.as_bytes())).starts_with("HTTP/1.1 431 "));
+    assert_eq!(sse.clients(), 0);
+}
@@ -0,0 +1,84 @@
+//! The built‑in formatters against the `testkit` corpus.
+
+use cappie::testkit::{assert_format_into_matches_format, assert_json_fields_round_trip, assert_one_line_per_record};
+use cappie::{
+    CloudLoggingFormatter, DockerJsonFormatter, FlexibleFormatter, Formatter, JsonFormatter, LogfmtFormatter,
+    PrettyFormatter, SyslogFormatter,
+};
+
+#[test]
+fn json_formatters_keep_fields() {
+    assert_json_fields_round_trip(&JsonFormatter);
+    assert_json_fields_round_trip(&CloudLoggingFormatter::new());
+}
+
+#[test]
+fn machine_formatters_write_one_line_per_record() {
+    let formatters: [&dyn Formatter; 5] = [
+        &JsonFormatter,
+        &CloudLoggingFormatter::new(),
+        &DockerJsonFormatter::new(),
+        &LogfmtFormatter::new(),
+        &SyslogFormatter::new(),
+    ];
+    for formatter in formatters {
+        assert_one_line_per_record(formatter);
+    }
+}
+
+#[test]
+fn format_into_matches_format() {
+    let formatters: [&dyn Formatter; 7] = [
+        &JsonFormatter,
+        &CloudLoggingFormatter::new(),
+        &DockerJsonFormatter::new(),
+        &LogfmtFormatter::new(),
+        &SyslogFormatter::new(),
+        &PrettyFormatter::new(),
+        &FlexibleFormatter::default(),
+    ];
+    for formatter in formatters {
+        assert_format_into_matches_format(formatter);
+    }
+}
+
+#[cfg(feature = "binary")]
+#[test]
+fn binary_records_read_back() {
+    use cappie::binary::{BinaryFormatter, RecordReader};
+    use cappie::testkit::corpus;
+
+    for record in corpus() {
+        let frame = record.format_into(&BinaryFormatter);
+        let read = RecordReader::new(&frame[..]).next().unwrap().unwrap();
+        assert_eq!(read.msg, record.msg, "`{}`", record.name);
+        assert_eq!(read.fields, record.fields, "`{}`", record.name);
+    }
+}
+
+#[cfg(feature = "binary")]
+#[test]
+fn checksummed_binary_reader_skips_corrupt_records() {
+    use cappie::binary::{ChecksummedBinaryFormatter, RecordReader};
+    use cappie::testkit::corpus;
+
+    let records = corpus();
+    let mut file = Vec::new();
+    let mut starts = Vec::new();
+    for record in &records {
+        starts.push(file.len());
+        file.extend(record.format_into(&ChecksummedBinaryFormatter));
+    }
+    // Flip a byte inside the second record and tear the last one.
+    file[starts[1] + 10] ^= 0xFF;
+    file.truncate(file.len() - 3);
+
+    let mut reader = RecordReader::new(&file[..]);
+    let read: Vec<_> = reader.by_ref().collect::<Result<_, _>>().unwrap();
+    assert_eq!(reader.skipped(), 2);
+    let expected: Vec<_> = records.iter().enumerate().filter(|(i, _)| *i != 1 && *i != records.len() - 1).collect();
+    assert_eq!(read.len(), expected.len());
+    for (read, (_, record)) in read.iter().zip(expected) {
+        assert_eq!(read.msg, record.msg, "`{}`", record.name);
+    }
+}
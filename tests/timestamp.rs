@@ -0,0 +1,147 @@
+//! Every strftime specifier the formatters accept, checked against chrono's output.  Run
+//! with `--no-default-features --features time` to check the `time` backend against the
+//! same table.
+
+use cappie::{FlexibleFormatter, Formatter, Level, TimeFormat, Timestamp};
+use serde_json::Map;
+
+fn render(timestamp: Timestamp, pattern: &str) -> String {
+    let formatter = FlexibleFormatter::from_template("{time}").unwrap().with_no_colors().with_time_format(pattern);
+    formatter.format(Level::Info, "", &Map::new(), timestamp, "")
+}
+
+fn check(unix_nanos: i128, table: &[(&str, &str)]) {
+    let timestamp = Timestamp::from_unix_nanos(unix_nanos).unwrap();
+    for (pattern, expected) in table {
+        assert_eq!(render(timestamp, pattern), *expected, "{pattern} of {timestamp}");
+    }
+}
+
+/// 2024-03-05T07:08:09.012345678Z: a Tuesday morning, with nanoseconds.
+#[test]
+fn strftime_a_tuesday_morning() {
+    check(
+        1_709_622_489_012_345_678,
+        &[
+            ("%Y", "2024"), ("%C", "20"), ("%y", "24"), ("%G", "2024"), ("%g", "24"), ("%m", "03"),
+            ("%b", "Mar"), ("%h", "Mar"), ("%B", "March"), ("%d", "05"), ("%e", " 5"), ("%a", "Tue"),
+            ("%A", "Tuesday"), ("%w", "2"), ("%u", "2"), ("%U", "09"), ("%W", "10"), ("%V", "10"),
+            ("%j", "065"), ("%D", "03/05/24"), ("%x", "03/05/24"), ("%F", "2024-03-05"),
+            ("%v", " 5-Mar-2024"), ("%H", "07"), ("%k", " 7"), ("%I", "07"), ("%l", " 7"), ("%P", "am"),
+            ("%p", "AM"), ("%M", "08"), ("%S", "09"), ("%f", "012345678"), ("%.f", ".012345678"),
+            ("%.3f", ".012"), ("%.6f", ".012345"), ("%.9f", ".012345678"), ("%3f", "012"), ("%6f", "012345"),
+            ("%9f", "012345678"), ("%R", "07:08"), ("%T", "07:08:09"), ("%X", "07:08:09"),
+            ("%r", "07:08:09 AM"), ("%c", "Tue Mar  5 07:08:09 2024"),
+            ("%+", "2024-03-05T07:08:09.012345678+00:00"), ("%Z", "UTC"), ("%z", "+0000"), ("%:z", "+00:00"),
+            ("%::z", "+00:00:00"), ("%:::z", "+00"), ("%s", "1709622489"), ("%t", "\t"), ("%n", "\n"),
+            ("%%", "%"), ("%-d", "5"), ("%_m", " 3"), ("%0e", "05"), ("%-H", "7"), ("%_H", " 7"),
+        ],
+    );
+}
+
+/// 2023-01-01T00:00:00Z: a Sunday in ISO week 52 of 2022, at midnight.
+#[test]
+fn strftime_new_year_2023() {
+    check(
+        1_672_531_200_000_000_000,
+        &[
+            ("%Y", "2023"), ("%C", "20"), ("%y", "23"), ("%G", "2022"), ("%g", "22"), ("%m", "01"),
+            ("%b", "Jan"), ("%h", "Jan"), ("%B", "January"), ("%d", "01"), ("%e", " 1"), ("%a", "Sun"),
+            ("%A", "Sunday"), ("%w", "0"), ("%u", "7"), ("%U", "01"), ("%W", "00"), ("%V", "52"),
+            ("%j", "001"), ("%D", "01/01/23"), ("%x", "01/01/23"), ("%F", "2023-01-01"),
+            ("%v", " 1-Jan-2023"), ("%H", "00"), ("%k", " 0"), ("%I", "12"), ("%l", "12"), ("%P", "am"),
+            ("%p", "AM"), ("%M", "00"), ("%S", "00"), ("%f", "000000000"), ("%.f", ""), ("%.3f", ".000"),
+            ("%.6f", ".000000"), ("%.9f", ".000000000"), ("%3f", "000"), ("%6f", "000000"),
+            ("%9f", "000000000"), ("%R", "00:00"), ("%T", "00:00:00"), ("%X", "00:00:00"),
+            ("%r", "12:00:00 AM"), ("%c", "Sun Jan  1 00:00:00 2023"), ("%+", "2023-01-01T00:00:00+00:00"),
+            ("%Z", "UTC"), ("%z", "+0000"), ("%:z", "+00:00"), ("%::z", "+00:00:00"), ("%:::z", "+00"),
+            ("%s", "1672531200"), ("%t", "\t"), ("%n", "\n"), ("%%", "%"), ("%-d", "1"), ("%_m", " 1"),
+            ("%0e", "01"), ("%-H", "0"), ("%_H", " 0"),
+        ],
+    );
+}
+
+/// 2024-12-31T23:59:59.5Z: day 366, in ISO week 1 of 2025, just before midnight.
+#[test]
+fn strftime_new_years_eve_2024() {
+    check(
+        1_735_689_599_500_000_000,
+        &[
+            ("%Y", "2024"), ("%C", "20"), ("%y", "24"), ("%G", "2025"), ("%g", "25"), ("%m", "12"),
+            ("%b", "Dec"), ("%h", "Dec"), ("%B", "December"), ("%d", "31"), ("%e", "31"), ("%a", "Tue"),
+            ("%A", "Tuesday"), ("%w", "2"), ("%u", "2"), ("%U", "52"), ("%W", "53"), ("%V", "01"),
+            ("%j", "366"), ("%D", "12/31/24"), ("%x", "12/31/24"), ("%F", "2024-12-31"),
+            ("%v", "31-Dec-2024"), ("%H", "23"), ("%k", "23"), ("%I", "11"), ("%l", "11"), ("%P", "pm"),
+            ("%p", "PM"), ("%M", "59"), ("%S", "59"), ("%f", "500000000"), ("%.f", ".500"), ("%.3f", ".500"),
+            ("%.6f", ".500000"), ("%.9f", ".500000000"), ("%3f", "500"), ("%6f", "500000"),
+            ("%9f", "500000000"), ("%R", "23:59"), ("%T", "23:59:59"), ("%X", "23:59:59"),
+            ("%r", "11:59:59 PM"), ("%c", "Tue Dec 31 23:59:59 2024"),
+            ("%+", "2024-12-31T23:59:59.500+00:00"), ("%Z", "UTC"), ("%z", "+0000"), ("%:z", "+00:00"),
+            ("%::z", "+00:00:00"), ("%:::z", "+00"), ("%s", "1735689599"), ("%t", "\t"), ("%n", "\n"),
+            ("%%", "%"), ("%-d", "31"), ("%_m", "12"), ("%0e", "31"), ("%-H", "23"), ("%_H", "23"),
+        ],
+    );
+}
+
+#[test]
+fn unknown_specifiers_are_rejected() {
+    for pattern in ["%Q", "%.2f", "%::::z", "%"] {
+        assert!(TimeFormat::from(pattern).validate().is_err(), "{pattern}");
+    }
+    assert!(FlexibleFormatter::new().try_with_time_format("%H:%M:%S%.3f").is_ok());
+}
+
+#[test]
+fn timestamps_convert_through_unix_nanos_and_display_as_rfc3339() {
+    let timestamp = Timestamp::from_unix_nanos(-1_500_000_000).unwrap();
+    assert_eq!(timestamp.unix_nanos(), -1_500_000_000);
+    assert_eq!(timestamp.to_string(), "1969-12-31T23:59:58.500+00:00");
+    assert_eq!(Timestamp::default().to_string(), "1970-01-01T00:00:00+00:00");
+    assert_eq!(Timestamp::from(std::time::SystemTime::UNIX_EPOCH), Timestamp::default());
+    assert!(Timestamp::from_unix_nanos(i128::MAX).is_none());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn timestamps_convert_from_and_into_chrono() {
+    use chrono::{TimeZone, Utc};
+
+    let time = Utc.with_ymd_and_hms(2024, 3, 5, 7, 8, 9).unwrap();
+    let timestamp = Timestamp::from(time);
+    assert_eq!(timestamp.unix_nanos(), 1_709_622_489_000_000_000);
+    assert_eq!(chrono::DateTime::<Utc>::from(timestamp), time);
+    assert_eq!(timestamp.to_chrono(), time);
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn timestamps_convert_from_and_into_time() {
+    let time = time::OffsetDateTime::from_unix_timestamp_nanos(1_709_622_489_012_345_678).unwrap();
+    let timestamp = Timestamp::from(time);
+    assert_eq!(timestamp.to_string(), "2024-03-05T07:08:09.012345678+00:00");
+    assert_eq!(time::OffsetDateTime::from(timestamp), time);
+    assert_eq!(timestamp.to_time(), time);
+
+    let east = time.to_offset(time::UtcOffset::from_hms(2, 0, 0).unwrap());
+    assert_eq!(Timestamp::from(east), timestamp);
+}
+
+/// Formatters take a `Timestamp` whatever the features, and can hand it to chrono.
+#[cfg(feature = "chrono")]
+#[test]
+fn formatters_can_format_with_chrono() {
+    use chrono::{TimeZone, Utc};
+
+    struct Year;
+
+    impl Formatter for Year {
+        fn format(&self, _: Level, _: &str, _: &Map<String, serde_json::Value>, timestamp: Timestamp, _: &str) -> String {
+            timestamp.to_chrono().format("%Y").to_string()
+        }
+    }
+
+    let capture = cappie::CaptureOutput::new();
+    let log = cappie::Logger::new("year").with_formatter(Box::new(Year)).with_output(Box::new(capture.clone()));
+    log.log_with_time(Level::Info, Utc.with_ymd_and_hms(2024, 3, 5, 7, 8, 9).unwrap(), "", Map::new());
+    assert_eq!(capture.lines(), ["2024"]);
+}
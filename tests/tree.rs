@@ -0,0 +1,56 @@
+use cappie::{CaptureOutput, Level, Logger};
+
+#[test]
+fn sampled_loggers_stay_listed_and_reachable_through_handles() {
+    let capture = CaptureOutput::new();
+    let log = Logger::new("sampled").with_output(Box::new(capture.clone())).with_sampling(1.0);
+
+    let names: Vec<String> = log.tree().into_iter().map(|info| info.name).collect();
+    assert_eq!(names, ["sampled"]);
+
+    let handle = log.handle();
+    assert!(handle.upgrade().is_some());
+    handle.info("through the handle");
+    assert_eq!(capture.lines().len(), 1);
+}
+
+#[test]
+fn reconfigured_clones_leave_the_original_entry_alone() {
+    let host_capture = CaptureOutput::new();
+    let derived_capture = CaptureOutput::new();
+    let host = Logger::new("host").with_output(Box::new(host_capture.clone()));
+    let handle = host.handle();
+
+    let derived = host.clone().with_output(Box::new(derived_capture.clone()));
+    handle.info("to the host");
+    assert_eq!((host_capture.lines().len(), derived_capture.lines().len()), (1, 0));
+
+    drop(derived);
+    let names: Vec<String> = host.tree().into_iter().map(|info| info.name).collect();
+    assert_eq!(names, ["host"]);
+    assert!(handle.upgrade().is_some());
+    handle.info("still to the host");
+    assert_eq!(host_capture.lines().len(), 2);
+}
+
+#[test]
+fn handles_log_every_level_and_report_alive_as_long_as_they_upgrade() {
+    let capture = CaptureOutput::new();
+    let log = Logger::new("host").with_level(Level::Trace).with_output(Box::new(capture.clone()));
+    let handle = log.handle();
+
+    handle.trace("trace");
+    handle.debug("debug");
+    handle.info("info");
+    handle.warn("warn");
+    handle.error("error");
+    handle.fatal("fatal");
+    assert_eq!(capture.lines().len(), 6);
+    assert!(capture.lines()[5].contains("fatal"));
+    assert!(handle.is_alive());
+
+    drop(log);
+    assert!(!handle.is_alive() && handle.upgrade().is_none());
+    handle.fatal("dropped");
+    assert_eq!(capture.lines().len(), 6);
+}
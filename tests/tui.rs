@@ -0,0 +1,90 @@
+#![cfg(feature = "tui")]
+
+use cappie::output::LogStore;
+use cappie::tui::{LogView, LogViewState};
+use cappie::{Level, Logger};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+
+fn draw(store: &LogStore, state: &mut LogViewState, height: u16) -> Vec<String> {
+    let mut terminal = Terminal::new(TestBackend::new(80, height)).unwrap();
+    terminal.draw(|frame| frame.render_stateful_widget(LogView::new(store), frame.area(), state)).unwrap();
+    let buffer = terminal.backend().buffer();
+    (0..height)
+        .map(|y| (0..80).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+        .collect()
+}
+
+fn press(state: &mut LogViewState, keys: &str) {
+    for c in keys.chars() {
+        let code = match c {
+            '\n' => KeyCode::Enter,
+            '\x1b' => KeyCode::Esc,
+            '↑' => KeyCode::Up,
+            c => KeyCode::Char(c),
+        };
+        state.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+}
+
+fn shown(screen: &[String], text: &str) -> bool {
+    screen.iter().any(|row| row.contains(text))
+}
+
+#[test]
+fn level_filter_and_search_narrow_the_list() {
+    let store = LogStore::new();
+    let log = Logger::new("api").with_level(Level::Debug).with_output(Box::new(store.clone()));
+    log.debug("cache miss");
+    log.info("request served");
+    log.warn_with("slow request", |b| {
+        b.number("ms", 900);
+    });
+    log.error("upstream refused");
+
+    let mut state = LogViewState::new();
+    let screen = draw(&store, &mut state, 10);
+    assert!(shown(&screen, "cache miss") && shown(&screen, "upstream refused"));
+    assert!(shown(&screen, "WARN  api: slow request ms=900"));
+
+    press(&mut state, "4");
+    let screen = draw(&store, &mut state, 10);
+    assert!(!shown(&screen, "cache miss") && !shown(&screen, "request served"));
+    assert!(shown(&screen, "2 of 4 records"));
+
+    press(&mut state, "0/REQUEST\n");
+    assert_eq!(state.search(), "REQUEST");
+    let screen = draw(&store, &mut state, 10);
+    assert!(shown(&screen, "request served") && shown(&screen, "slow request"));
+    assert!(!shown(&screen, "upstream refused"));
+
+    press(&mut state, "\x1b");
+    let screen = draw(&store, &mut state, 10);
+    assert!(shown(&screen, "4 of 4 records"));
+}
+
+#[test]
+fn follow_mode_keeps_the_newest_record_in_view() {
+    let store = LogStore::new();
+    let log = Logger::new("job").with_output(Box::new(store.clone()));
+    for n in 0..20 {
+        log.info(&format!("step {n:02}"));
+    }
+
+    // Eight rows leave six for records between the borders and the status line.
+    let mut state = LogViewState::new();
+    let screen = draw(&store, &mut state, 8);
+    assert!(shown(&screen, "step 19") && !shown(&screen, "step 13"));
+
+    press(&mut state, "↑↑");
+    draw(&store, &mut state, 8);
+    assert!(!state.following());
+    log.info("step 20");
+    let screen = draw(&store, &mut state, 8);
+    assert!(shown(&screen, "step 17") && !shown(&screen, "step 20"));
+
+    press(&mut state, "f");
+    let screen = draw(&store, &mut state, 8);
+    assert!(state.following() && shown(&screen, "step 20"));
+}